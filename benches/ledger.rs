@@ -0,0 +1,79 @@
+//! Hot-path benchmarks against a large ledger. `ICL_BENCH_EVENTS` sets the number of events
+//! (default 1,000,000), spread evenly over `ICL_BENCH_ASSETS` assets (default 10,000).
+//!
+//! ```bash
+//! cargo bench --bench ledger
+//! ICL_BENCH_EVENTS=100000 cargo bench --bench ledger
+//! cargo bench --bench ledger --features parallel
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use icl::*;
+
+fn env_or(name: &str, default: usize) -> usize {
+    std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+/// Ledger with `events` events over `assets` assets, each asset carrying two proofs
+fn populated_ledger(events: usize, assets: usize) -> (IntelligenceCapitalLedger, Vec<AssetId>) {
+    let mut ledger = IntelligenceCapitalLedger::new();
+    let asset_ids: Vec<AssetId> = (0..assets).map(|_| AssetId::random()).collect();
+    let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+    for (i, asset_id) in asset_ids.iter().enumerate() {
+        lifecycle.capitalize(*asset_id, format!("team-{}", i % 50), 100_000.0, DepreciationMethod::Linear, 36)
+            .expect("capitalize");
+    }
+    for i in 0..events.saturating_sub(assets) {
+        lifecycle.utilize(asset_ids[i % assets], 1.0).expect("utilize");
+    }
+    for asset_id in &asset_ids {
+        ledger.generate_proof(*asset_id, None).expect("proof");
+        ledger.generate_proof(*asset_id, None).expect("proof");
+    }
+    (ledger, asset_ids)
+}
+
+fn large_ledger(c: &mut Criterion) {
+    let events = env_or("ICL_BENCH_EVENTS", 1_000_000);
+    let assets = env_or("ICL_BENCH_ASSETS", 10_000).clamp(1, events.max(1));
+    let (mut ledger, asset_ids) = populated_ledger(events, assets);
+    let asset_id = asset_ids[asset_ids.len() / 2];
+
+    let mut group = c.benchmark_group(format!("ledger_{}_events", events));
+    group.bench_function("asset_summary", |b| {
+        b.iter(|| IntelligenceCapitalLifecycle::new(&mut ledger).get_asset_summary(black_box(asset_id)).unwrap())
+    });
+    group.bench_function("asset_totals", |b| {
+        b.iter(|| ledger.asset_totals(black_box(asset_id)).map(|t| t.total_depreciation()))
+    });
+    group.bench_function("get_journal_entries_for_asset", |b| {
+        b.iter(|| ledger.get_journal_entries_for_asset(black_box(asset_id)).len())
+    });
+    group.bench_function("get_events_for_asset", |b| {
+        b.iter(|| ledger.get_events_for_asset(black_box(asset_id)).len())
+    });
+    group.bench_function("latest_proof_for_asset", |b| {
+        b.iter(|| ledger.latest_proof_for_asset(black_box(asset_id)).map(|p| p.proof_id))
+    });
+    group.bench_function("verify_proof_chain", |b| {
+        b.iter(|| IntegrityChecker::new(&ledger).verify_proof_chain().len())
+    });
+    group.bench_function("check_all_integrity", |b| {
+        b.iter(|| IntegrityChecker::new(&ledger).check_all_integrity().len())
+    });
+    #[cfg(feature = "parallel")]
+    group.bench_function("check_all_integrity_parallel", |b| {
+        b.iter(|| IntegrityChecker::new(&ledger).check_all_integrity_parallel().len())
+    });
+    group.bench_function("record_event", |b| {
+        b.iter(|| IntelligenceCapitalLifecycle::new(&mut ledger).utilize(black_box(asset_id), 1.0).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = large_ledger
+}
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+#![no_main]
+
+use chrono::{DateTime, Utc};
+use icl::{depreciate, months_between, DepreciationInput, DepreciationMethod, UsageUnit};
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    method: u8,
+    expected_units: u64,
+    initial_value: f64,
+    current_value: f64,
+    salvage_value: f64,
+    useful_life_months: i32,
+    start_secs: i64,
+    end_secs: i64,
+    rate_multiplier: f64,
+    units: f64,
+}
+
+fuzz_target!(|input: Input| {
+    let (Some(start_date), Some(end_date)) = (
+        DateTime::<Utc>::from_timestamp(input.start_secs, 0),
+        DateTime::<Utc>::from_timestamp(input.end_secs, 0),
+    ) else {
+        return;
+    };
+    let months = months_between(start_date, end_date);
+    assert!(months >= 0);
+
+    let method = match input.method % 4 {
+        0 => DepreciationMethod::Linear,
+        1 => DepreciationMethod::DecliningBalance,
+        2 => DepreciationMethod::ImpairmentOnly,
+        _ => DepreciationMethod::UnitsOfProduction { unit: UsageUnit::Tokens, expected_units: input.expected_units },
+    };
+    let depreciation = DepreciationInput {
+        method,
+        initial_value: input.initial_value,
+        current_value: input.current_value,
+        salvage_value: input.salvage_value,
+        useful_life_months: input.useful_life_months,
+        start_date,
+        end_date,
+        rate_multiplier: input.rate_multiplier,
+        units: input.units,
+    };
+    // Declining balance iterates once per month; keep runs fast
+    if method == DepreciationMethod::DecliningBalance && months > 12_000 {
+        return;
+    }
+
+    if let Ok((amount, new_value)) = depreciate(&depreciation) {
+        assert!(amount.is_finite() && amount >= 0.0, "amount {amount} for {depreciation:?}");
+        assert!(new_value.is_finite(), "new value {new_value} for {depreciation:?}");
+        assert!(new_value <= depreciation.current_value, "book value rose to {new_value} for {depreciation:?}");
+        if depreciation.current_value >= depreciation.salvage_value {
+            assert!(new_value >= depreciation.salvage_value, "below salvage: {new_value} for {depreciation:?}");
+        }
+    }
+});
@@ -0,0 +1,122 @@
+#ifndef ICL_H
+#define ICL_H
+
+/* Generated by cbindgen from src/core/ffi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result code of every FFI call; values are stable across releases
+typedef enum IclStatus {
+  ICL_STATUS_OK = 0,
+  ICL_STATUS_NULL_POINTER = 1,
+  ICL_STATUS_INVALID_UTF8 = 2,
+  ICL_STATUS_INVALID_ARGUMENT = 3,
+  ICL_STATUS_NOT_FOUND = 4,
+  ICL_STATUS_ALREADY_EXISTS = 5,
+  ICL_STATUS_ASSET_RETIRED = 6,
+  ICL_STATUS_INTEGRITY_VIOLATION = 7,
+  ICL_STATUS_SERIALIZATION_ERROR = 8,
+  ICL_STATUS_STORAGE_ERROR = 9,
+  ICL_STATUS_UNSUPPORTED_FORMAT = 10,
+  ICL_STATUS_PANIC = 11,
+  // Any other ledger error
+  ICL_STATUS_ERROR = 12,
+  // Refused by the ledger's authorizer
+  ICL_STATUS_PERMISSION_DENIED = 13,
+} IclStatus;
+
+// Values of the depreciation method argument of `icl_capitalize`
+typedef enum IclDepreciationMethod {
+  ICL_DEPRECIATION_METHOD_LINEAR = 0,
+  ICL_DEPRECIATION_METHOD_DECLINING_BALANCE = 1,
+} IclDepreciationMethod;
+
+// Opaque ledger handle
+typedef struct IclLedger IclLedger;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Message of the last failed call on this thread, or NULL; valid until the next call
+const char *icl_last_error(void);
+
+void icl_string_free(char *value);
+
+// New, empty ledger
+struct IclLedger *icl_ledger_new(void);
+
+// Restore a ledger saved with `icl_ledger_to_json`
+enum IclStatus icl_ledger_from_json(const char *json, struct IclLedger **out);
+
+// Serialize the whole ledger for persistence
+enum IclStatus icl_ledger_to_json(const struct IclLedger *ledger, char **out);
+
+void icl_ledger_free(struct IclLedger *ledger);
+
+// Capitalize an asset; `asset_id` may be NULL to generate one. Writes the asset as JSON to `out`.
+// `depreciation_method` is an `IclDepreciationMethod` value; any other value is `InvalidArgument`.
+enum IclStatus icl_capitalize(struct IclLedger *ledger,
+                              const char *asset_id,
+                              const char *owner,
+                              double initial_value,
+                              uint32_t depreciation_method,
+                              int32_t useful_life_months,
+                              char **out);
+
+// Transfer an asset to `target_owner`; writes the event as JSON to `out`
+enum IclStatus icl_allocate(struct IclLedger *ledger,
+                            const char *asset_id,
+                            const char *target_owner,
+                            char **out);
+
+// Record utilization of an asset; writes the event as JSON to `out`
+enum IclStatus icl_utilize(struct IclLedger *ledger,
+                           const char *asset_id,
+                           double amount,
+                           char **out);
+
+// Depreciate an asset over `[start, end)`; writes the event as JSON to `out`
+enum IclStatus icl_depreciate(struct IclLedger *ledger,
+                              const char *asset_id,
+                              int64_t start,
+                              int64_t end,
+                              double salvage_value,
+                              double rate_multiplier,
+                              char **out);
+
+// Retire an asset; writes the event as JSON to `out`
+enum IclStatus icl_retire(struct IclLedger *ledger, const char *asset_id, char **out);
+
+// Asset as JSON
+enum IclStatus icl_get_asset(const struct IclLedger *ledger, const char *asset_id, char **out);
+
+// Events of an asset as a JSON array, oldest first
+enum IclStatus icl_get_events_for_asset(const struct IclLedger *ledger,
+                                        const char *asset_id,
+                                        char **out);
+
+// Fixed-asset register as of `as_of` in `format` (`json` or `csv`)
+enum IclStatus icl_asset_register(const struct IclLedger *ledger,
+                                  int64_t as_of,
+                                  const char *format,
+                                  char **out);
+
+// Audit trail in `format` (`json` or `csv`)
+enum IclStatus icl_export_audit_trail(const struct IclLedger *ledger,
+                                      const char *format,
+                                      char **out);
+
+// Run every integrity check, writing the report as JSON to `out`. Fails with the
+// integrity-violation status, after writing the report, when it contains errors.
+enum IclStatus icl_verify(const struct IclLedger *ledger, char **out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ICL_H */
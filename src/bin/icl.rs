@@ -1,6 +1,324 @@
-fn main() {
-    println!("{} layer running...", env!("CARGO_PKG_NAME"));
-    loop {
-        std::thread::sleep(std::time::Duration::from_secs(3600));
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+
+use icl::*;
+
+/// Operate on a ledger persisted as a JSON file
+#[derive(Debug, Parser)]
+#[command(name = "icl", version, about)]
+struct Cli {
+    /// Ledger file; created on the first write if it does not exist
+    #[arg(long, short, global = true, env = "ICL_LEDGER", default_value = "ledger.json")]
+    ledger: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Capitalize a new asset
+    Capitalize {
+        #[arg(long)]
+        owner: String,
+        #[arg(long)]
+        value: f64,
+        #[arg(long, value_enum, default_value_t = Method::Linear)]
+        method: Method,
+        #[arg(long)]
+        life_months: i32,
+        /// Asset ID; generated if omitted
+        #[arg(long)]
+        asset_id: Option<AssetId>,
+        #[arg(long)]
+        category: Option<String>,
+        #[arg(long, value_enum, default_value_t = Kind::Model)]
+        kind: Kind,
+    },
+    /// Record depreciation of an asset over a period
+    Depreciate {
+        asset_id: AssetId,
+        #[arg(long, value_parser = parse_timestamp)]
+        start: DateTime<Utc>,
+        #[arg(long, value_parser = parse_timestamp)]
+        end: DateTime<Utc>,
+        #[arg(long, default_value_t = 0.0)]
+        salvage: f64,
+        #[arg(long, default_value_t = 1.0)]
+        rate: f64,
+    },
+    /// Move an asset to another category, with its cost and accumulated depreciation
+    Reclassify {
+        asset_id: AssetId,
+        category: String,
+    },
+    /// Retire an asset, writing off its remaining value
+    Retire {
+        asset_id: AssetId,
+    },
+    /// Print a finance report
+    Report {
+        #[arg(value_enum)]
+        kind: ReportKind,
+        /// Valuation date for `register` and `aging`; now if omitted
+        #[arg(long, value_parser = parse_timestamp)]
+        as_of: Option<DateTime<Utc>>,
+        /// Period start for `depreciation`, `rollforward`, `by-owner`, and `utilization`
+        #[arg(long, value_parser = parse_timestamp)]
+        from: Option<DateTime<Utc>>,
+        /// Period end (exclusive); now if omitted
+        #[arg(long, value_parser = parse_timestamp)]
+        to: Option<DateTime<Utc>>,
+        #[arg(long, value_enum, default_value_t = Grouping::Owner)]
+        group_by: Grouping,
+        /// Group `depreciation` by a posting dimension such as `cost_center` instead
+        #[arg(long, conflicts_with = "group_by")]
+        dimension: Option<String>,
+        #[arg(long, default_value = "json")]
+        format: String,
+        /// Write `csv` amounts for a locale (`en-US`, `de-DE`, or `ja-JP`); plain numbers if omitted
+        #[arg(long)]
+        locale: Option<String>,
+    },
+    /// Run every integrity check; exits non-zero if any error is found
+    Verify,
+    /// Write the audit trail as `json`, `csv`, or `jsonl`
+    Export {
+        #[arg(long, default_value = "json")]
+        format: String,
+        /// Output file; stdout if omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Only records of this asset (`jsonl` and `csv`)
+        #[arg(long)]
+        asset_id: Option<AssetId>,
+    },
+    /// Capitalize assets from a `csv` or `json` file
+    Import {
+        file: PathBuf,
+        /// Input format; taken from the file extension if omitted
+        #[arg(long)]
+        format: Option<String>,
+        /// Import nothing if any row fails
+        #[arg(long)]
+        all_or_nothing: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Method {
+    Linear,
+    DecliningBalance,
+    /// Indefinite life, datasets only; pass `--life-months 0`
+    ImpairmentOnly,
+}
+
+impl From<Method> for DepreciationMethod {
+    fn from(method: Method) -> Self {
+        match method {
+            Method::Linear => DepreciationMethod::Linear,
+            Method::DecliningBalance => DepreciationMethod::DecliningBalance,
+            Method::ImpairmentOnly => DepreciationMethod::ImpairmentOnly,
+        }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Kind {
+    Model,
+    Dataset,
+    PromptLibrary,
+}
+
+impl From<Kind> for AssetKind {
+    fn from(kind: Kind) -> Self {
+        match kind {
+            Kind::Model => AssetKind::Model,
+            Kind::Dataset => AssetKind::Dataset,
+            Kind::PromptLibrary => AssetKind::PromptLibrary,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ReportKind {
+    Register,
+    Depreciation,
+    Rollforward,
+    Aging,
+    ByOwner,
+    Utilization,
+    Returns,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Grouping {
+    Owner,
+    Category,
+    Asset,
+}
+
+impl From<Grouping> for ReportGrouping {
+    fn from(grouping: Grouping) -> Self {
+        match grouping {
+            Grouping::Owner => ReportGrouping::Owner,
+            Grouping::Category => ReportGrouping::Category,
+            Grouping::Asset => ReportGrouping::Asset,
+        }
+    }
+}
+
+/// RFC 3339 timestamp or `YYYY-MM-DD` (midnight UTC)
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        .map_err(|_| format!("expected an RFC 3339 timestamp or YYYY-MM-DD date, got '{}'", value))
+}
+
+fn load(path: &Path) -> IclResult<IntelligenceCapitalLedger> {
+    if !path.exists() {
+        return Ok(IntelligenceCapitalLedger::new());
+    }
+    serde_json::from_reader(std::io::BufReader::new(File::open(path)?)).map_err(IclError::from)
+}
+
+/// Replace the ledger file atomically so a failed write never leaves it truncated
+fn save(ledger: &IntelligenceCapitalLedger, path: &Path) -> IclResult<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut file = File::create(&tmp)?;
+    serde_json::to_writer_pretty(&mut file, ledger)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> IclResult<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Exit with a usage error when a flag the chosen report needs is missing
+fn required(value: Option<DateTime<Utc>>, flag: &str) -> DateTime<Utc> {
+    value.unwrap_or_else(|| {
+        Cli::command()
+            .error(ErrorKind::MissingRequiredArgument, format!("this report requires --{}", flag))
+            .exit()
+    })
+}
+
+/// Returns whether the command succeeded; `verify` fails without an error when it finds problems
+fn run(cli: Cli) -> IclResult<bool> {
+    let mut ledger = load(&cli.ledger)?;
+    match cli.command {
+        Command::Capitalize { owner, value, method, life_months, asset_id, category, kind } => {
+            let asset_id = asset_id.unwrap_or_else(AssetId::random);
+            let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger).of_kind(kind.into());
+            if let Some(category) = category {
+                lifecycle = lifecycle.in_category(category);
+            }
+            let asset = lifecycle.capitalize(asset_id, owner, value, method.into(), life_months)?;
+            save(&ledger, &cli.ledger)?;
+            print_json(&asset)?;
+        },
+        Command::Depreciate { asset_id, start, end, salvage, rate } => {
+            let event = IntelligenceCapitalLifecycle::new(&mut ledger)
+                .depreciate(asset_id, start, end, salvage, rate)?;
+            save(&ledger, &cli.ledger)?;
+            print_json(&event)?;
+        },
+        Command::Reclassify { asset_id, category } => {
+            let event = IntelligenceCapitalLifecycle::new(&mut ledger).reclassify(asset_id, category)?;
+            save(&ledger, &cli.ledger)?;
+            print_json(&event)?;
+        },
+        Command::Retire { asset_id } => {
+            let event = IntelligenceCapitalLifecycle::new(&mut ledger).retire(asset_id)?;
+            save(&ledger, &cli.ledger)?;
+            print_json(&event)?;
+        },
+        Command::Report { kind, as_of, from, to, group_by, dimension, format, locale } => {
+            let number_format = match locale {
+                Some(locale) => NumberFormat::for_locale(&locale)
+                    .ok_or_else(|| IclError::UnsupportedFormat(format!("locale {}", locale)))?,
+                None => NumberFormat::plain(),
+            };
+            let reports = ReportGenerator::new(&ledger);
+            let as_of = as_of.unwrap_or_else(|| ledger.now());
+            let to = to.unwrap_or_else(|| ledger.now());
+            let output = match kind {
+                ReportKind::Register => reports.asset_register(as_of).export_with(&format, &number_format)?,
+                ReportKind::Aging => reports.asset_aging(as_of).export_with(&format, &number_format)?,
+                ReportKind::Depreciation => reports
+                    .depreciation_by_period(
+                        required(from, "from"),
+                        to,
+                        dimension.map_or_else(|| group_by.into(), ReportGrouping::Dimension),
+                    )?
+                    .export_with(&format, &number_format)?,
+                ReportKind::Rollforward => reports.capital_rollforward(required(from, "from"), to)?.export_with(&format, &number_format)?,
+                ReportKind::ByOwner => reports.by_owner(required(from, "from"), to)?.export_with(&format, &number_format)?,
+                ReportKind::Utilization => reports.utilization(required(from, "from"), to)?.export_with(&format, &number_format)?,
+                ReportKind::Returns => reports.asset_returns(required(from, "from"), to)?.export_with(&format, &number_format)?,
+            };
+            print!("{}", output);
+        },
+        Command::Verify => {
+            let report = IntegrityChecker::new(&ledger).check_all_integrity();
+            for finding in &report.findings {
+                println!("{}", finding);
+            }
+            println!("{} finding(s), {} error(s)", report.len(), report.errors().count());
+            return Ok(report.is_clean());
+        },
+        Command::Export { format, output, asset_id } => {
+            let mut writer: Box<dyn Write> = match &output {
+                Some(path) => Box::new(std::io::BufWriter::new(File::create(path)?)),
+                None => Box::new(std::io::stdout().lock()),
+            };
+            match (format.as_str(), asset_id) {
+                ("json", None) => writer.write_all(ledger.export_audit_trail("json")?.as_bytes())?,
+                ("json", Some(_)) => return Err(IclError::UnsupportedFormat("json with --asset-id (use jsonl)".into())),
+                (_, asset_id) => {
+                    let filter = asset_id.map_or_else(AuditTrailFilter::new, |id| AuditTrailFilter::new().asset(id));
+                    ledger.export_audit_trail_to(&mut writer, &format, &filter)?;
+                },
+            }
+            writer.flush()?;
+        },
+        Command::Import { file, format, all_or_nothing } => {
+            let format = format
+                .or_else(|| file.extension().and_then(|e| e.to_str()).map(str::to_lowercase))
+                .ok_or_else(|| IclError::UnsupportedFormat(file.display().to_string()))?;
+            let mode = if all_or_nothing { ImportMode::AllOrNothing } else { ImportMode::BestEffort };
+            let report = ledger.import_assets(File::open(&file)?, &format, mode)?;
+            if report.created > 0 {
+                save(&ledger, &cli.ledger)?;
+            }
+            print_json(&report)?;
+            return Ok(report.failed == 0);
+        },
+    }
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
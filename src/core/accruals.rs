@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+
+/// Event type recorded by `IntelligenceCapitalLifecycle::accrue`, effective at the last instant of the accrued period
+pub const ACCRUAL_EVENT: &str = "accrual";
+
+/// Event type recorded by `accrue` for the reversal, effective at the start of the next period
+pub const ACCRUAL_REVERSAL_EVENT: &str = "accrual_reversal";
+
+/// Detail of an accrual reversal naming the accrual event it reverses
+pub const REVERSED_ACCRUAL_DETAIL: &str = "reversed_accrual_id";
+
+/// A cost consumed in a period but not yet invoiced, such as inference billed in arrears
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccrualSpec {
+    pub asset_id: AssetId,
+    pub amount: f64,
+    pub debit_account: AccountType,
+    pub credit_account: AccountType,
+    pub description: String,
+}
+
+impl AccrualSpec {
+    /// Inference expense against accounts payable
+    pub fn inference_cost(asset_id: AssetId, amount: f64) -> Self {
+        Self {
+            asset_id,
+            amount,
+            debit_account: AccountType::InferenceExpense,
+            credit_account: AccountType::AccountsPayable,
+            description: "Accrued inference cost".into(),
+        }
+    }
+
+    pub fn with_accounts(mut self, debit_account: AccountType, credit_account: AccountType) -> Self {
+        self.debit_account = debit_account;
+        self.credit_account = credit_account;
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+}
+
+/// Outcome of `accrue`: the accrual in its period and the reversal opening the next one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Accrual {
+    pub period: FiscalPeriod,
+    pub accrual_event: CapitalEvent,
+    pub accrual_entry: JournalEntry,
+    pub reversal_event: CapitalEvent,
+    pub reversal_entry: JournalEntry,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::core::ledger::{IntelligenceCapitalLedger, REVERSED_ENTRY_METADATA};
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+    use crate::core::error::IclError;
+
+    #[test]
+    fn test_accrual_reverses_in_next_period() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        let start = ledger.now();
+        let period = FiscalPeriod::new(start, start + Duration::days(30));
+        let accrual = IntelligenceCapitalLifecycle::new(&mut ledger)
+            .accrue(period, AccrualSpec::inference_cost(asset_id, 300.0))
+            .unwrap();
+
+        assert!(period.contains(accrual.accrual_entry.effective_date()));
+        assert_eq!(accrual.reversal_entry.effective_date(), period.end);
+        assert_eq!(accrual.reversal_entry.debit_account, AccountType::AccountsPayable);
+        assert_eq!(accrual.reversal_entry.credit_account, AccountType::InferenceExpense);
+        assert_eq!(accrual.reversal_entry.description, "Reversal of Accrued inference cost");
+        assert_eq!(
+            accrual.reversal_entry.metadata[REVERSED_ENTRY_METADATA],
+            accrual.accrual_entry.entry_id.to_string()
+        );
+
+        assert!(matches!(
+            IntelligenceCapitalLifecycle::new(&mut ledger).accrue(period, AccrualSpec::inference_cost(asset_id, -1.0)),
+            Err(IclError::InvalidEntry(_))
+        ));
+        ledger.close_period(period.end).unwrap();
+        assert!(IntelligenceCapitalLifecycle::new(&mut ledger).accrue(period, AccrualSpec::inference_cost(asset_id, 300.0)).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::*;
+
+/// Commitment returned by an external notary for a submitted hash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorCommitment {
+    pub reference: String,
+    pub anchored_at: DateTime<Utc>,
+    pub receipt: serde_json::Value,
+}
+
+/// Receipt stored alongside a proof once its hash has been committed externally
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorReceipt {
+    pub anchor_id: Uuid,
+    pub proof_id: Uuid,
+    pub anchored_hash: String,
+    pub provider: String,
+    pub reference: String,
+    pub anchored_at: DateTime<Utc>,
+    pub receipt: serde_json::Value,
+}
+
+/// External notary (OpenTimestamps, RFC 3161 TSA, a blockchain) that commits proof hashes
+pub trait Anchor {
+    fn name(&self) -> &str;
+
+    fn anchor(&self, hash: &str) -> IclResult<AnchorCommitment>;
+}
@@ -0,0 +1,218 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::types::*;
+use crate::core::ledger::{AssetPosition, AssetTotals};
+use crate::core::hashing::HashAlgorithm;
+use crate::core::error::{IclError, IclResult};
+
+/// Version of the archive file format `archive_period` writes
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// Event type recorded by `archive_period`, committing the stub it leaves to the record chain
+pub const ARCHIVE_EVENT: &str = "period_archived";
+
+/// Detail of an archive event holding the digest of its `ArchiveStub`
+pub const ARCHIVE_DIGEST_DETAIL: &str = "archive_digest";
+
+/// Where an archived record sat in its hash chain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChainLink {
+    pub previous_hash: Option<String>,
+    pub record_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_date: Option<DateTime<Utc>>,
+}
+
+impl ChainLink {
+    pub fn new(previous_hash: &Option<String>, record_hash: &Option<String>) -> Self {
+        Self { previous_hash: previous_hash.clone(), record_hash: record_hash.clone(), effective_date: None }
+    }
+
+    pub fn with_effective_date(mut self, effective_date: DateTime<Utc>) -> Self {
+        self.effective_date = Some(effective_date);
+        self
+    }
+}
+
+/// What one asset's archived records amounted to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedAssetSummary {
+    pub closing_position: Option<AssetPosition>,
+    pub book_value_change: f64,
+    pub improvements: f64,
+    pub salvage_value: f64,
+    #[serde(default)]
+    pub pool_depreciation: f64,
+    pub totals: AssetTotals,
+}
+
+/// Summary left in the ledger in place of an archived period's records
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchiveStub {
+    pub archive_id: Uuid,
+    pub period: FiscalPeriod,
+    pub archived_at: DateTime<Utc>,
+    pub seal: String,
+    pub hash_algorithm: HashAlgorithm,
+    pub event_count: usize,
+    pub entry_count: usize,
+    pub journal_entry_count: usize,
+    pub assets: BTreeMap<AssetId, ArchivedAssetSummary>,
+    pub account_balances: HashMap<AccountType, f64>,
+    pub event_links: Vec<ChainLink>,
+    pub entry_links: Vec<ChainLink>,
+    pub journal_links: Vec<ChainLink>,
+}
+
+impl ArchiveStub {
+    pub fn digest(&self) -> IclResult<String> {
+        self.hash_algorithm.digest_record(self)
+    }
+
+    pub fn links_within_period(&self) -> bool {
+        self.event_links.iter().chain(&self.entry_links).chain(&self.journal_links)
+            .all(|link| link.effective_date.is_none_or(|date| self.period.contains(date)))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ArchivedRecords {
+    pub events: Vec<CapitalEvent>,
+    pub entries: Vec<LedgerEntry>,
+    pub journal_entries: Vec<JournalEntry>,
+}
+
+#[derive(Serialize)]
+struct SealedContent<'a> {
+    archive_id: Uuid,
+    period: FiscalPeriod,
+    records: &'a ArchivedRecords,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchiveContents {
+    version: u32,
+    archive_id: Uuid,
+    period: FiscalPeriod,
+    hash_algorithm: HashAlgorithm,
+    seal: String,
+    records: ArchivedRecords,
+}
+
+/// A gzip-compressed, hash-sealed archive of one period's records, written by `archive_period`
+/// for cold storage and restored with `load_archive`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveFile {
+    bytes: Vec<u8>,
+}
+
+impl ArchiveFile {
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn read_from(path: impl AsRef<Path>) -> IclResult<Self> {
+        Ok(Self { bytes: std::fs::read(path)? })
+    }
+
+    pub fn write_to(&self, path: impl AsRef<Path>) -> IclResult<()> {
+        std::fs::write(path, &self.bytes)?;
+        Ok(())
+    }
+
+    pub(crate) fn write(
+        archive_id: Uuid,
+        period: FiscalPeriod,
+        hash_algorithm: HashAlgorithm,
+        records: ArchivedRecords,
+    ) -> IclResult<(Self, String)> {
+        let seal = hash_algorithm.digest_record(&SealedContent { archive_id, period, records: &records })?;
+        let contents = ArchiveContents { version: ARCHIVE_FORMAT_VERSION, archive_id, period, hash_algorithm, seal, records };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        serde_json::to_writer(&mut encoder, &contents)?;
+        let bytes = encoder.finish()?;
+        Ok((Self { bytes }, contents.seal))
+    }
+
+    pub(crate) fn open(&self) -> IclResult<(Uuid, String, ArchivedRecords)> {
+        let mut json = Vec::new();
+        GzDecoder::new(self.bytes.as_slice()).read_to_end(&mut json)?;
+        let contents: ArchiveContents = serde_json::from_slice(&json)?;
+        if contents.version > ARCHIVE_FORMAT_VERSION {
+            return Err(IclError::UnsupportedFormat(format!(
+                "archive version {} is newer than supported version {}", contents.version, ARCHIVE_FORMAT_VERSION
+            )));
+        }
+        let sealed = SealedContent { archive_id: contents.archive_id, period: contents.period, records: &contents.records };
+        if contents.hash_algorithm.digest_record(&sealed)? != contents.seal {
+            return Err(IclError::IntegrityViolation(format!(
+                "Archive {} contents do not match its seal", contents.archive_id
+            )));
+        }
+        Ok((contents.archive_id, contents.seal, contents.records))
+    }
+}
+
+pub(crate) fn chain_bridge<'a>(links: impl Iterator<Item = &'a ChainLink>) -> HashMap<Option<&'a str>, &'a str> {
+    links
+        .filter_map(|link| Some((link.previous_hash.as_deref(), link.record_hash.as_deref()?)))
+        .collect()
+}
+
+pub(crate) fn bridges(bridge: &HashMap<Option<&str>, &str>, from: Option<&str>, to: Option<&str>) -> bool {
+    let mut cursor = from;
+    // Every step consumes a distinct link, so a corrupted, cyclic bridge cannot loop
+    for _ in 0..bridge.len() {
+        let Some(next) = bridge.get(&cursor) else {
+            return false;
+        };
+        cursor = Some(*next);
+        if cursor == to {
+            return true;
+        }
+    }
+    false
+}
+
+pub(crate) fn splice<T>(kept: Vec<T>, archived: Vec<T>, links: impl Fn(&T) -> ChainLink) -> Vec<T> {
+    let mut successors: HashMap<Option<String>, usize> = HashMap::new();
+    for (i, record) in archived.iter().enumerate() {
+        let link = links(record);
+        if link.record_hash.is_some() {
+            successors.entry(link.previous_hash).or_insert(i);
+        }
+    }
+    let mut archived: Vec<Option<T>> = archived.into_iter().map(Some).collect();
+
+    let mut merged = Vec::with_capacity(kept.len() + archived.len());
+    let mut follow = |merged: &mut Vec<T>, archived: &mut Vec<Option<T>>, mut cursor: Option<String>| {
+        while let Some(record) = successors.remove(&cursor).and_then(|i| archived[i].take()) {
+            cursor = links(&record).record_hash;
+            merged.push(record);
+        }
+    };
+    follow(&mut merged, &mut archived, None);
+    for record in kept {
+        let record_hash = links(&record).record_hash;
+        merged.push(record);
+        if record_hash.is_some() {
+            follow(&mut merged, &mut archived, record_hash);
+        }
+    }
+    merged.extend(archived.into_iter().flatten());
+    merged
+}
@@ -0,0 +1,130 @@
+use std::collections::{BTreeSet, HashMap};
+use crate::core::types::AssetId;
+use serde::{Deserialize, Serialize};
+
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::error::*;
+
+/// Lifecycle operations an `Authorizer` is consulted on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum LifecycleOperation {
+    Capitalize,
+    Allocate,
+    Utilize,
+    Depreciate,
+    Impair,
+    License,
+    RecordRevenue,
+    AccrueObligations,
+    Transfer,
+    Reclassify,
+    Retire,
+}
+
+impl std::fmt::Display for LifecycleOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LifecycleOperation::Capitalize => write!(f, "Capitalize"),
+            LifecycleOperation::Allocate => write!(f, "Allocate"),
+            LifecycleOperation::Utilize => write!(f, "Utilize"),
+            LifecycleOperation::Depreciate => write!(f, "Depreciate"),
+            LifecycleOperation::Impair => write!(f, "Impair"),
+            LifecycleOperation::License => write!(f, "License"),
+            LifecycleOperation::RecordRevenue => write!(f, "RecordRevenue"),
+            LifecycleOperation::AccrueObligations => write!(f, "AccrueObligations"),
+            LifecycleOperation::Transfer => write!(f, "Transfer"),
+            LifecycleOperation::Reclassify => write!(f, "Reclassify"),
+            LifecycleOperation::Retire => write!(f, "Retire"),
+        }
+    }
+}
+
+/// The user or service performing a lifecycle operation, with the roles it holds
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Principal {
+    pub id: String,
+    #[serde(default)]
+    pub roles: BTreeSet<String>,
+}
+
+impl Principal {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into(), roles: BTreeSet::new() }
+    }
+
+    pub fn with_role(mut self, role: impl Into<String>) -> Self {
+        self.roles.insert(role.into());
+        self
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.contains(role)
+    }
+}
+
+/// Decides whether a principal may perform a lifecycle operation on an asset
+pub trait Authorizer: Send + Sync + std::fmt::Debug {
+    fn authorize(
+        &self,
+        ledger: &IntelligenceCapitalLedger,
+        principal: Option<&Principal>,
+        operation: LifecycleOperation,
+        asset_id: AssetId
+    ) -> IclResult<()>;
+}
+
+/// Permits every operation; the ledger's default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllowAll;
+
+impl Authorizer for AllowAll {
+    fn authorize(
+        &self,
+        _ledger: &IntelligenceCapitalLedger,
+        _principal: Option<&Principal>,
+        _operation: LifecycleOperation,
+        _asset_id: AssetId
+    ) -> IclResult<()> {
+        Ok(())
+    }
+}
+
+/// Requires a role per operation, e.g. only `controller` may retire assets
+#[derive(Debug, Clone, Default)]
+pub struct RoleAuthorizer {
+    required: HashMap<LifecycleOperation, String>,
+}
+
+impl RoleAuthorizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn require(mut self, operation: LifecycleOperation, role: impl Into<String>) -> Self {
+        self.required.insert(operation, role.into());
+        self
+    }
+}
+
+impl Authorizer for RoleAuthorizer {
+    fn authorize(
+        &self,
+        _ledger: &IntelligenceCapitalLedger,
+        principal: Option<&Principal>,
+        operation: LifecycleOperation,
+        asset_id: AssetId
+    ) -> IclResult<()> {
+        let Some(role) = self.required.get(&operation) else {
+            return Ok(());
+        };
+        match principal {
+            Some(principal) if principal.has_role(role) => Ok(()),
+            Some(principal) => Err(IclError::Unauthorized(format!(
+                "{} may not {} asset {}: requires role {}", principal.id, operation, asset_id, role
+            ))),
+            None => Err(IclError::Unauthorized(format!(
+                "{} of asset {} requires role {}", operation, asset_id, role
+            ))),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+
+/// Event type recorded by `capitalize_or_expense` for a cost below the capitalization threshold
+pub const EXPENSED_EVENT: &str = "expensed";
+
+/// Which acquisition costs are capitalized as assets
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CapitalizationPolicy {
+    pub threshold: f64,
+    pub expense_account: AccountType,
+}
+
+impl Default for CapitalizationPolicy {
+    fn default() -> Self {
+        Self { threshold: 0.0, expense_account: AccountType::DepreciationExpense }
+    }
+}
+
+impl CapitalizationPolicy {
+    pub fn threshold(threshold: f64) -> Self {
+        Self { threshold, ..Self::default() }
+    }
+
+    pub fn with_expense_account(mut self, account: AccountType) -> Self {
+        self.expense_account = account;
+        self
+    }
+
+    pub fn expenses(&self, cost: f64) -> bool {
+        cost < self.threshold
+    }
+}
+
+/// Outcome of `capitalize_or_expense`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CapitalizationOutcome {
+    Capitalized(IntelligenceAsset),
+    Expensed(CapitalEvent),
+}
+
+impl CapitalizationOutcome {
+    pub fn is_capitalized(&self) -> bool {
+        matches!(self, CapitalizationOutcome::Capitalized(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ledger::IntelligenceCapitalLedger;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+
+    #[test]
+    fn test_costs_below_threshold_are_expensed() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        assert!(ledger.set_capitalization_policy(CapitalizationPolicy::threshold(f64::NAN)).is_err());
+        assert!(ledger.set_capitalization_policy(CapitalizationPolicy::threshold(-1.0)).is_err());
+        ledger.set_capitalization_policy(CapitalizationPolicy::threshold(5000.0)).unwrap();
+        let (small, large) = (AssetId::random(), AssetId::random());
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        let outcome = lifecycle.capitalize_or_expense(small, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let CapitalizationOutcome::Expensed(event) = outcome else {
+            panic!("cost below the threshold was capitalized");
+        };
+        assert_eq!(event.event_type, EXPENSED_EVENT);
+        assert!(lifecycle.capitalize_or_expense(large, "Team A", 6000.0, DepreciationMethod::Linear, 12).unwrap().is_capitalized());
+
+        let entries = ledger.get_journal_entries_for_asset(small);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].debit_account, AccountType::DepreciationExpense);
+        assert_eq!(entries[0].amount, 1200.0);
+        let asset = ledger.get_asset(small).unwrap();
+        assert_eq!(asset.status, AssetStatus::Depreciated);
+        assert_eq!(ledger.asset_position(small, ledger.now()).unwrap().book_value, 0.0);
+        assert!(ledger.assert_invariants().is_ok());
+
+        let restored: IntelligenceCapitalLedger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+        assert_eq!(restored.capitalization_policy(), CapitalizationPolicy::threshold(5000.0));
+    }
+}
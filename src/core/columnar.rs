@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray, TimestampMicrosecondArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use chrono::{DateTime, Utc};
+use parquet::arrow::ArrowWriter;
+
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::error::*;
+
+fn parquet_error(e: impl std::fmt::Display) -> IclError {
+    IclError::SerializationError(format!("Parquet: {}", e))
+}
+
+fn text(values: impl Iterator<Item = String>) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(values))
+}
+
+fn optional_text(values: impl Iterator<Item = Option<String>>) -> ArrayRef {
+    Arc::new(values.collect::<StringArray>())
+}
+
+fn timestamps(values: impl Iterator<Item = DateTime<Utc>>) -> ArrayRef {
+    Arc::new(TimestampMicrosecondArray::from_iter_values(values.map(|t| t.timestamp_micros())).with_timezone("UTC"))
+}
+
+fn timestamp_field(name: &str) -> Field {
+    Field::new(name, DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())), false)
+}
+
+fn write_batch<W: Write + Send>(writer: W, fields: Vec<Field>, columns: Vec<ArrayRef>) -> IclResult<()> {
+    let schema = Arc::new(Schema::new(fields));
+    let batch = RecordBatch::try_new(schema.clone(), columns).map_err(parquet_error)?;
+    let mut writer = ArrowWriter::try_new(writer, schema, None).map_err(parquet_error)?;
+    writer.write(&batch).map_err(parquet_error)?;
+    writer.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+impl IntelligenceCapitalLedger {
+    /// Write `events.parquet`, `entries.parquet`, and `journal_entries.parquet` into `dir`,
+    /// creating it if needed
+    pub fn export_audit_trail_parquet(&self, dir: impl AsRef<Path>) -> IclResult<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let events = dir.join("events.parquet");
+        let entries = dir.join("entries.parquet");
+        let journal_entries = dir.join("journal_entries.parquet");
+        self.write_events_parquet(File::create(&events)?)?;
+        self.write_entries_parquet(File::create(&entries)?)?;
+        self.write_journal_entries_parquet(File::create(&journal_entries)?)?;
+        Ok(vec![events, entries, journal_entries])
+    }
+
+    /// Events with `amount` as a typed column, the full details as a JSON string, and a
+    /// `dimension_<name>` column per registered dimension
+    pub fn write_events_parquet<W: Write + Send>(&self, writer: W) -> IclResult<()> {
+        let events = self.events();
+        let (dimension_fields, dimension_columns) = self.dimension_columns(events.iter().map(|e| &e.dimensions));
+        write_batch(
+            writer,
+            [vec![
+                Field::new("sequence", DataType::UInt64, false),
+                Field::new("event_id", DataType::Utf8, false),
+                Field::new("asset_id", DataType::Utf8, false),
+                Field::new("event_type", DataType::Utf8, false),
+                timestamp_field("timestamp"),
+                Field::new("amount", DataType::Float64, true),
+                Field::new("details", DataType::Utf8, false),
+                Field::new("actor", DataType::Utf8, true),
+                Field::new("previous_hash", DataType::Utf8, true),
+                Field::new("record_hash", DataType::Utf8, true),
+            ], dimension_fields].concat(),
+            [vec![
+                Arc::new(UInt64Array::from_iter_values(events.iter().map(|e| e.sequence))) as ArrayRef,
+                text(events.iter().map(|e| e.event_id.to_string())),
+                text(events.iter().map(|e| e.asset_id.to_string())),
+                text(events.iter().map(|e| e.event_type.clone())),
+                timestamps(events.iter().map(|e| e.timestamp)),
+                Arc::new(events.iter().map(|e| e.details.get("amount").and_then(|v| v.as_f64())).collect::<Float64Array>()),
+                text(events.iter().map(|e| serde_json::to_string(&e.details).unwrap_or_default())),
+                optional_text(events.iter().map(|e| e.actor.clone())),
+                optional_text(events.iter().map(|e| e.previous_hash.clone())),
+                optional_text(events.iter().map(|e| e.record_hash.clone())),
+            ], dimension_columns].concat(),
+        )
+    }
+
+    pub fn write_entries_parquet<W: Write + Send>(&self, writer: W) -> IclResult<()> {
+        let entries: Vec<_> = self.entries().iter().filter(|e| !self.is_quarantined(e.entry_id)).collect();
+        write_batch(
+            writer,
+            vec![
+                Field::new("sequence", DataType::UInt64, false),
+                Field::new("entry_id", DataType::Utf8, false),
+                Field::new("event_id", DataType::Utf8, false),
+                Field::new("asset_id", DataType::Utf8, false),
+                timestamp_field("timestamp"),
+                Field::new("amount", DataType::Float64, false),
+                Field::new("description", DataType::Utf8, false),
+                Field::new("metadata", DataType::Utf8, false),
+                Field::new("actor", DataType::Utf8, true),
+                Field::new("record_hash", DataType::Utf8, true),
+            ],
+            vec![
+                Arc::new(UInt64Array::from_iter_values(entries.iter().map(|e| e.sequence))),
+                text(entries.iter().map(|e| e.entry_id.to_string())),
+                text(entries.iter().map(|e| e.event_id.to_string())),
+                text(entries.iter().map(|e| e.asset_id.to_string())),
+                timestamps(entries.iter().map(|e| e.timestamp)),
+                Arc::new(Float64Array::from_iter_values(entries.iter().map(|e| e.amount))),
+                text(entries.iter().map(|e| e.description.clone())),
+                text(entries.iter().map(|e| serde_json::to_string(&e.metadata).unwrap_or_default())),
+                optional_text(entries.iter().map(|e| e.actor.clone())),
+                optional_text(entries.iter().map(|e| e.record_hash.clone())),
+            ],
+        )
+    }
+
+    /// Journal entries, with the asset each entry's event belongs to and a `dimension_<name>`
+    /// column per registered dimension
+    pub fn write_journal_entries_parquet<W: Write + Send>(&self, writer: W) -> IclResult<()> {
+        let journal_entries: Vec<_> = self.journal_entries().iter().filter(|j| !self.is_quarantined(j.entry_id)).collect();
+        let (dimension_fields, dimension_columns) = self.dimension_columns(journal_entries.iter().map(|j| &j.dimensions));
+        write_batch(
+            writer,
+            [vec![
+                Field::new("entry_id", DataType::Utf8, false),
+                Field::new("event_id", DataType::Utf8, false),
+                Field::new("asset_id", DataType::Utf8, true),
+                timestamp_field("timestamp"),
+                Field::new("debit_account", DataType::Utf8, false),
+                Field::new("credit_account", DataType::Utf8, false),
+                Field::new("amount", DataType::Float64, false),
+                Field::new("description", DataType::Utf8, false),
+                Field::new("metadata", DataType::Utf8, false),
+                Field::new("actor", DataType::Utf8, true),
+                Field::new("record_hash", DataType::Utf8, true),
+            ], dimension_fields].concat(),
+            [vec![
+                text(journal_entries.iter().map(|j| j.entry_id.to_string())),
+                text(journal_entries.iter().map(|j| j.event_id.to_string())),
+                optional_text(journal_entries.iter().map(|j| self.get_event(j.event_id).map(|e| e.asset_id.to_string()))),
+                timestamps(journal_entries.iter().map(|j| j.timestamp)),
+                text(journal_entries.iter().map(|j| j.debit_account.to_string())),
+                text(journal_entries.iter().map(|j| j.credit_account.to_string())),
+                Arc::new(Float64Array::from_iter_values(journal_entries.iter().map(|j| j.amount))),
+                text(journal_entries.iter().map(|j| j.description.clone())),
+                text(journal_entries.iter().map(|j| serde_json::to_string(&j.metadata).unwrap_or_default())),
+                optional_text(journal_entries.iter().map(|j| j.actor.clone())),
+                optional_text(journal_entries.iter().map(|j| j.record_hash.clone())),
+            ], dimension_columns].concat(),
+        )
+    }
+
+    fn dimension_columns<'r>(&self, records: impl Iterator<Item = &'r BTreeMap<String, String>> + Clone) -> (Vec<Field>, Vec<ArrayRef>) {
+        self.dimensions()
+            .map(|d| (
+                Field::new(format!("dimension_{}", d.name), DataType::Utf8, true),
+                optional_text(records.clone().map(|dimensions| dimensions.get(&d.name).cloned())),
+            ))
+            .unzip()
+    }
+}
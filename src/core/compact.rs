@@ -0,0 +1,538 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::error::*;
+
+type Symbol = u32;
+
+#[derive(Debug, Clone, Default)]
+struct StringTable {
+    strings: Vec<Arc<str>>,
+    symbols: HashMap<Arc<str>, Symbol>,
+}
+
+impl StringTable {
+    fn intern(&mut self, value: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(value) {
+            return symbol;
+        }
+        let symbol = self.strings.len() as Symbol;
+        let value: Arc<str> = Arc::from(value);
+        self.strings.push(value.clone());
+        self.symbols.insert(value, symbol);
+        symbol
+    }
+
+    fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol as usize]
+    }
+
+    fn bytes(&self) -> usize {
+        self.strings.iter().map(|s| s.len()).sum()
+    }
+}
+
+const TAG_NULL: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_U64: u8 = 3;
+const TAG_I64: u8 = 4;
+const TAG_F64: u8 = 5;
+const TAG_STRING: u8 = 6;
+const TAG_ARRAY: u8 = 7;
+const TAG_OBJECT: u8 = 8;
+
+fn encode_details(details: &HashMap<String, serde_json::Value>, strings: &mut StringTable) -> Box<[u8]> {
+    let mut keys: Vec<&String> = details.keys().collect();
+    keys.sort();
+    let mut out = Vec::new();
+    out.extend((keys.len() as u32).to_le_bytes());
+    for key in keys {
+        out.extend(strings.intern(key).to_le_bytes());
+        encode_value(&details[key], strings, &mut out);
+    }
+    out.into_boxed_slice()
+}
+
+fn encode_value(value: &serde_json::Value, strings: &mut StringTable, out: &mut Vec<u8>) {
+    match value {
+        serde_json::Value::Null => out.push(TAG_NULL),
+        serde_json::Value::Bool(false) => out.push(TAG_FALSE),
+        serde_json::Value::Bool(true) => out.push(TAG_TRUE),
+        serde_json::Value::Number(n) => {
+            if let Some(n) = n.as_u64() {
+                out.push(TAG_U64);
+                out.extend(n.to_le_bytes());
+            } else if let Some(n) = n.as_i64() {
+                out.push(TAG_I64);
+                out.extend(n.to_le_bytes());
+            } else {
+                out.push(TAG_F64);
+                out.extend(n.as_f64().unwrap_or_default().to_bits().to_le_bytes());
+            }
+        },
+        serde_json::Value::String(s) => {
+            out.push(TAG_STRING);
+            out.extend(strings.intern(s).to_le_bytes());
+        },
+        serde_json::Value::Array(items) => {
+            out.push(TAG_ARRAY);
+            out.extend((items.len() as u32).to_le_bytes());
+            for item in items {
+                encode_value(item, strings, out);
+            }
+        },
+        serde_json::Value::Object(map) => {
+            out.push(TAG_OBJECT);
+            out.extend((map.len() as u32).to_le_bytes());
+            for (key, item) in map {
+                out.extend(strings.intern(key).to_le_bytes());
+                encode_value(item, strings, out);
+            }
+        },
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    strings: &'a StringTable,
+}
+
+impl Decoder<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        head.try_into().unwrap_or([0; N])
+    }
+
+    fn u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.take())
+    }
+
+    fn string(&mut self) -> String {
+        let symbol = self.u32();
+        self.strings.resolve(symbol).to_string()
+    }
+
+    fn details(&mut self) -> HashMap<String, serde_json::Value> {
+        let len = self.u32() as usize;
+        let mut details = HashMap::with_capacity(len);
+        for _ in 0..len {
+            let key = self.string();
+            details.insert(key, self.value());
+        }
+        details
+    }
+
+    fn value(&mut self) -> serde_json::Value {
+        let [tag] = self.take();
+        match tag {
+            TAG_FALSE => serde_json::Value::Bool(false),
+            TAG_TRUE => serde_json::Value::Bool(true),
+            TAG_U64 => u64::from_le_bytes(self.take()).into(),
+            TAG_I64 => i64::from_le_bytes(self.take()).into(),
+            TAG_F64 => serde_json::Number::from_f64(f64::from_bits(u64::from_le_bytes(self.take())))
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            TAG_STRING => serde_json::Value::String(self.string()),
+            TAG_ARRAY => {
+                let len = self.u32() as usize;
+                serde_json::Value::Array((0..len).map(|_| self.value()).collect())
+            },
+            TAG_OBJECT => {
+                let len = self.u32() as usize;
+                let mut map = serde_json::Map::new();
+                for _ in 0..len {
+                    let key = self.string();
+                    map.insert(key, self.value());
+                }
+                serde_json::Value::Object(map)
+            },
+            _ => serde_json::Value::Null,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CompactHash {
+    Sha256([u8; 32]),
+    Text(Box<str>),
+}
+
+impl CompactHash {
+    fn new(hash: &str) -> Self {
+        let mut bytes = [0u8; 32];
+        let is_sha256 = hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+        if !is_sha256 {
+            return CompactHash::Text(Box::from(hash));
+        }
+        for (byte, pair) in bytes.iter_mut().zip(hash.as_bytes().chunks(2)) {
+            *byte = u8::from_str_radix(std::str::from_utf8(pair).unwrap_or_default(), 16).unwrap_or_default();
+        }
+        CompactHash::Sha256(bytes)
+    }
+
+    fn to_hex(&self) -> String {
+        match self {
+            CompactHash::Sha256(bytes) => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+            CompactHash::Text(text) => text.to_string(),
+        }
+    }
+
+    fn heap_bytes(&self) -> usize {
+        match self {
+            CompactHash::Sha256(_) => 0,
+            CompactHash::Text(text) => text.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ChainLinks {
+    previous: Option<PreviousHash>,
+    record: Option<CompactHash>,
+}
+
+#[derive(Debug, Clone)]
+enum PreviousHash {
+    Preceding,
+    Other(CompactHash),
+}
+
+impl ChainLinks {
+    fn new(previous_hash: Option<&str>, record_hash: Option<&str>, preceding: Option<&CompactHash>) -> Self {
+        let previous = previous_hash.map(CompactHash::new).map(|hash| match preceding {
+            Some(preceding) if *preceding == hash => PreviousHash::Preceding,
+            _ => PreviousHash::Other(hash),
+        });
+        Self { previous, record: record_hash.map(CompactHash::new) }
+    }
+
+    fn record_hash(&self) -> Option<String> {
+        self.record.as_ref().map(CompactHash::to_hex)
+    }
+
+    fn previous_hash(&self, preceding: Option<&ChainLinks>) -> Option<String> {
+        match self.previous.as_ref()? {
+            PreviousHash::Preceding => preceding.and_then(ChainLinks::record_hash),
+            PreviousHash::Other(hash) => Some(hash.to_hex()),
+        }
+    }
+
+    fn heap_bytes(&self) -> usize {
+        let previous = match &self.previous {
+            Some(PreviousHash::Other(hash)) => hash.heap_bytes(),
+            _ => 0,
+        };
+        previous + self.record.as_ref().map_or(0, CompactHash::heap_bytes)
+    }
+}
+
+fn chain_links<'r>(records: impl Iterator<Item = (Option<&'r str>, Option<&'r str>)>) -> Vec<ChainLinks> {
+    let mut links: Vec<ChainLinks> = Vec::new();
+    for (previous_hash, record_hash) in records {
+        let preceding = links.last().and_then(|l| l.record.as_ref());
+        let link = ChainLinks::new(previous_hash, record_hash, preceding);
+        links.push(link);
+    }
+    links
+}
+
+#[derive(Debug, Clone)]
+struct CompactEvent {
+    event_id: EventId,
+    asset_id: AssetId,
+    event_type: Symbol,
+    timestamp: DateTime<Utc>,
+    details: Box<[u8]>,
+    sequence: u64,
+    links: ChainLinks,
+    actor: Option<Symbol>,
+    org_id: Symbol,
+    effective_date: Option<DateTime<Utc>>,
+    recorded_at: Option<DateTime<Utc>>,
+    dimensions: Box<[(Symbol, Symbol)]>,
+}
+
+#[derive(Debug, Clone)]
+struct CompactLedgerEntry {
+    entry_id: EntryId,
+    event: u32,
+    amount: f64,
+    links: ChainLinks,
+}
+
+#[derive(Debug, Clone)]
+struct CompactJournalEntry {
+    entry_id: EntryId,
+    event_id: EventId,
+    timestamp: DateTime<Utc>,
+    debit_account: AccountType,
+    credit_account: AccountType,
+    amount: f64,
+    description: Symbol,
+    metadata: Box<[u8]>,
+    links: ChainLinks,
+    org_id: Symbol,
+    actor: Option<Symbol>,
+    effective_date: Option<DateTime<Utc>>,
+    recorded_at: Option<DateTime<Utc>>,
+    dimensions: Box<[(Symbol, Symbol)]>,
+}
+
+/// Read-only copy of a ledger's records in a compact binary form, for read-mostly workloads such as
+/// reporting replicas and archived periods
+#[derive(Debug, Clone)]
+pub struct CompactLedger {
+    assets: HashMap<AssetId, IntelligenceAsset>,
+    strings: StringTable,
+    events: Vec<CompactEvent>,
+    entries: Vec<CompactLedgerEntry>,
+    journal_entries: Vec<CompactJournalEntry>,
+    proofs: Vec<CapitalProof>,
+    event_index: HashMap<EventId, u32>,
+    events_by_asset: HashMap<AssetId, Vec<u32>>,
+    journal_entries_by_event: HashMap<EventId, Vec<u32>>,
+}
+
+impl CompactLedger {
+    pub fn get_asset(&self, asset_id: AssetId) -> Option<&IntelligenceAsset> {
+        self.assets.get(&asset_id)
+    }
+
+    pub fn assets_iter(&self) -> impl Iterator<Item = &IntelligenceAsset> {
+        self.assets.values()
+    }
+
+    pub fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn journal_entry_count(&self) -> usize {
+        self.journal_entries.len()
+    }
+
+    pub fn get_event(&self, event_id: EventId) -> Option<CapitalEvent> {
+        self.event_index.get(&event_id).map(|&i| self.event_at(i))
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = CapitalEvent> + '_ {
+        (0..self.events.len() as u32).map(|i| self.event_at(i))
+    }
+
+    pub fn get_events_for_asset(&self, asset_id: AssetId) -> Vec<CapitalEvent> {
+        self.events_by_asset.get(&asset_id)
+            .map_or_else(Vec::new, |indices| indices.iter().map(|&i| self.event_at(i)).collect())
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = LedgerEntry> + '_ {
+        (0..self.entries.len()).map(|i| self.ledger_entry(i))
+    }
+
+    pub fn journal_entries(&self) -> impl Iterator<Item = JournalEntry> + '_ {
+        (0..self.journal_entries.len()).map(|i| self.journal_entry(i))
+    }
+
+    pub fn get_journal_entries_for_asset(&self, asset_id: AssetId) -> Vec<JournalEntry> {
+        let Some(events) = self.events_by_asset.get(&asset_id) else {
+            return Vec::new();
+        };
+        let mut positions: Vec<u32> = events.iter()
+            .filter_map(|&i| self.journal_entries_by_event.get(&self.events[i as usize].event_id))
+            .flatten()
+            .copied()
+            .collect();
+        positions.sort_unstable();
+        positions.into_iter().map(|i| self.journal_entry(i as usize)).collect()
+    }
+
+    pub fn proofs(&self) -> &[CapitalProof] {
+        &self.proofs
+    }
+
+    pub fn approximate_size(&self) -> usize {
+        let events: usize = self.events.iter()
+            .map(|e| std::mem::size_of::<CompactEvent>() + e.details.len() + e.links.heap_bytes() + dimension_bytes(&e.dimensions))
+            .sum();
+        let entries: usize = self.entries.iter()
+            .map(|e| std::mem::size_of::<CompactLedgerEntry>() + e.links.heap_bytes())
+            .sum();
+        let journal_entries: usize = self.journal_entries.iter()
+            .map(|j| std::mem::size_of::<CompactJournalEntry>() + j.metadata.len() + j.links.heap_bytes() + dimension_bytes(&j.dimensions))
+            .sum();
+        events + entries + journal_entries + self.strings.bytes()
+    }
+
+    fn decode_details(&self, bytes: &[u8]) -> HashMap<String, serde_json::Value> {
+        Decoder { bytes, strings: &self.strings }.details()
+    }
+
+    fn decode_dimensions(&self, dimensions: &[(Symbol, Symbol)]) -> BTreeMap<String, String> {
+        dimensions.iter()
+            .map(|(name, value)| (self.strings.resolve(*name).to_string(), self.strings.resolve(*value).to_string()))
+            .collect()
+    }
+
+    fn event_at(&self, i: u32) -> CapitalEvent {
+        let i = i as usize;
+        let event = &self.events[i];
+        let preceding = i.checked_sub(1).map(|p| &self.events[p].links);
+        CapitalEvent {
+            event_id: event.event_id,
+            asset_id: event.asset_id,
+            event_type: self.strings.resolve(event.event_type).to_string(),
+            timestamp: event.timestamp,
+            details: self.decode_details(&event.details),
+            sequence: event.sequence,
+            previous_hash: event.links.previous_hash(preceding),
+            record_hash: event.links.record_hash(),
+            actor: event.actor.map(|a| self.strings.resolve(a).to_string()),
+            org_id: OrgId::new(self.strings.resolve(event.org_id)),
+            effective_date: event.effective_date,
+            recorded_at: event.recorded_at,
+            dimensions: self.decode_dimensions(&event.dimensions),
+        }
+    }
+
+    fn ledger_entry(&self, i: usize) -> LedgerEntry {
+        let entry = &self.entries[i];
+        let preceding = i.checked_sub(1).map(|p| &self.entries[p].links);
+        let event = self.event_at(entry.event);
+        LedgerEntry {
+            entry_id: entry.entry_id,
+            event_id: event.event_id,
+            asset_id: event.asset_id,
+            timestamp: event.timestamp,
+            amount: entry.amount,
+            description: event.event_type,
+            metadata: event.details,
+            sequence: event.sequence,
+            previous_hash: entry.links.previous_hash(preceding),
+            record_hash: entry.links.record_hash(),
+            actor: event.actor,
+            effective_date: event.effective_date,
+            recorded_at: event.recorded_at,
+        }
+    }
+
+    fn journal_entry(&self, i: usize) -> JournalEntry {
+        let entry = &self.journal_entries[i];
+        let preceding = i.checked_sub(1).map(|p| &self.journal_entries[p].links);
+        JournalEntry {
+            entry_id: entry.entry_id,
+            event_id: entry.event_id,
+            timestamp: entry.timestamp,
+            debit_account: entry.debit_account,
+            credit_account: entry.credit_account,
+            amount: entry.amount,
+            description: self.strings.resolve(entry.description).to_string(),
+            metadata: self.decode_details(&entry.metadata),
+            previous_hash: entry.links.previous_hash(preceding),
+            record_hash: entry.links.record_hash(),
+            org_id: OrgId::new(self.strings.resolve(entry.org_id)),
+            actor: entry.actor.map(|a| self.strings.resolve(a).to_string()),
+            effective_date: entry.effective_date,
+            recorded_at: entry.recorded_at,
+            dimensions: self.decode_dimensions(&entry.dimensions),
+        }
+    }
+}
+
+fn encode_dimensions(dimensions: &BTreeMap<String, String>, strings: &mut StringTable) -> Box<[(Symbol, Symbol)]> {
+    dimensions.iter().map(|(name, value)| (strings.intern(name), strings.intern(value))).collect()
+}
+
+fn dimension_bytes(dimensions: &[(Symbol, Symbol)]) -> usize {
+    std::mem::size_of_val(dimensions)
+}
+
+fn entry_mirrors_event(entry: &LedgerEntry, event: &CapitalEvent) -> bool {
+    entry.asset_id == event.asset_id
+        && entry.timestamp == event.timestamp
+        && entry.description == event.event_type
+        && entry.metadata == event.details
+        && entry.sequence == event.sequence
+        && entry.actor == event.actor
+        && entry.effective_date == event.effective_date
+        && entry.recorded_at == event.recorded_at
+}
+
+impl IntelligenceCapitalLedger {
+    /// Copy the ledger's assets, records, and proofs into a `CompactLedger`
+    pub fn compact(&self) -> IclResult<CompactLedger> {
+        let mut strings = StringTable::default();
+        let mut compact = CompactLedger {
+            assets: self.assets().clone(),
+            strings: StringTable::default(),
+            events: Vec::with_capacity(self.events().len()),
+            entries: Vec::with_capacity(self.entries().len()),
+            journal_entries: Vec::with_capacity(self.journal_entries().len()),
+            proofs: self.proofs().to_vec(),
+            event_index: HashMap::with_capacity(self.events().len()),
+            events_by_asset: HashMap::new(),
+            journal_entries_by_event: HashMap::new(),
+        };
+
+        let event_links = chain_links(self.events().iter().map(|e| (e.previous_hash.as_deref(), e.record_hash.as_deref())));
+        for ((i, event), links) in self.events().iter().enumerate().zip(event_links) {
+            compact.event_index.insert(event.event_id, i as u32);
+            compact.events_by_asset.entry(event.asset_id).or_default().push(i as u32);
+            compact.events.push(CompactEvent {
+                event_id: event.event_id,
+                asset_id: event.asset_id,
+                event_type: strings.intern(&event.event_type),
+                timestamp: event.timestamp,
+                details: encode_details(&event.details, &mut strings),
+                sequence: event.sequence,
+                links,
+                actor: event.actor.as_deref().map(|a| strings.intern(a)),
+                org_id: strings.intern(event.org_id.as_str()),
+                effective_date: event.effective_date,
+                recorded_at: event.recorded_at,
+                dimensions: encode_dimensions(&event.dimensions, &mut strings),
+            });
+        }
+
+        let entry_links = chain_links(self.entries().iter().map(|e| (e.previous_hash.as_deref(), e.record_hash.as_deref())));
+        for (entry, links) in self.entries().iter().zip(entry_links) {
+            let event = compact.event_index.get(&entry.event_id).copied()
+                .filter(|&i| entry_mirrors_event(entry, &self.events()[i as usize]))
+                .ok_or_else(|| IclError::InvalidEntry(format!(
+                    "Ledger entry {} does not mirror event {}", entry.entry_id, entry.event_id
+                )))?;
+            compact.entries.push(CompactLedgerEntry {
+                entry_id: entry.entry_id,
+                event,
+                amount: entry.amount,
+                links,
+            });
+        }
+
+        let journal_links = chain_links(self.journal_entries().iter().map(|j| (j.previous_hash.as_deref(), j.record_hash.as_deref())));
+        for ((i, journal_entry), links) in self.journal_entries().iter().enumerate().zip(journal_links) {
+            compact.journal_entries_by_event.entry(journal_entry.event_id).or_default().push(i as u32);
+            compact.journal_entries.push(CompactJournalEntry {
+                entry_id: journal_entry.entry_id,
+                event_id: journal_entry.event_id,
+                timestamp: journal_entry.timestamp,
+                debit_account: journal_entry.debit_account,
+                credit_account: journal_entry.credit_account,
+                amount: journal_entry.amount,
+                description: strings.intern(&journal_entry.description),
+                metadata: encode_details(&journal_entry.metadata, &mut strings),
+                links,
+                org_id: strings.intern(journal_entry.org_id.as_str()),
+                actor: journal_entry.actor.as_deref().map(|a| strings.intern(a)),
+                effective_date: journal_entry.effective_date,
+                recorded_at: journal_entry.recorded_at,
+                dimensions: encode_dimensions(&journal_entry.dimensions, &mut strings),
+            });
+        }
+
+        compact.strings = strings;
+        Ok(compact)
+    }
+}
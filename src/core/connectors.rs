@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::error::*;
+
+/// Outcome reported by a financial system for a pushed journal entry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum AcknowledgementStatus {
+    Accepted,
+    Rejected,
+    Pending,
+}
+
+impl std::fmt::Display for AcknowledgementStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcknowledgementStatus::Accepted => write!(f, "Accepted"),
+            AcknowledgementStatus::Rejected => write!(f, "Rejected"),
+            AcknowledgementStatus::Pending => write!(f, "Pending"),
+        }
+    }
+}
+
+/// A financial system's response for one journal entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalAcknowledgement {
+    pub entry_id: EntryId,
+    pub status: AcknowledgementStatus,
+    pub external_reference: Option<String>,
+    pub message: Option<String>,
+    pub acknowledged_at: DateTime<Utc>,
+}
+
+/// Receipt for a batch accepted for processing by a connector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchReceipt {
+    pub connector: String,
+    pub batch_id: String,
+    pub entry_count: usize,
+}
+
+/// Result of probing a connector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectorHealth {
+    pub connector: String,
+    pub healthy: bool,
+    pub error: Option<String>,
+}
+
+/// A general ledger, ERP, or other financial system that receives journal entries
+pub trait FinancialSystemConnector: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn push_journal_batch(&self, batch: &[JournalEntry]) -> IclResult<String>;
+
+    fn fetch_acknowledgements(&self) -> IclResult<Vec<JournalAcknowledgement>>;
+
+    fn health_check(&self) -> IclResult<()>;
+}
+
+struct RegisteredConnector {
+    connector: Box<dyn FinancialSystemConnector>,
+    cursor: usize,
+    pushed: Vec<EntryId>,
+    acknowledgements: HashMap<EntryId, JournalAcknowledgement>,
+}
+
+/// Registered financial-system connectors and their delivery state
+#[derive(Default)]
+pub struct ConnectorRegistry {
+    connectors: Vec<RegisteredConnector>,
+}
+
+impl ConnectorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, connector: impl FinancialSystemConnector + 'static) -> IclResult<()> {
+        if self.connectors.iter().any(|c| c.connector.name() == connector.name()) {
+            return Err(IclError::IntegrationError(format!(
+                "Financial system connector {} is already registered", connector.name()
+            )));
+        }
+        self.connectors.push(RegisteredConnector {
+            connector: Box::new(connector),
+            cursor: 0,
+            pushed: Vec::new(),
+            acknowledgements: HashMap::new(),
+        });
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.connectors.iter().map(|c| c.connector.name()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.connectors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.connectors.is_empty()
+    }
+
+    pub fn health_check_all(&self) -> Vec<ConnectorHealth> {
+        self.connectors.iter()
+            .map(|c| {
+                let result = c.connector.health_check();
+                ConnectorHealth {
+                    connector: c.connector.name().to_string(),
+                    healthy: result.is_ok(),
+                    error: result.err().map(|e| e.to_string()),
+                }
+            })
+            .collect()
+    }
+
+    /// Push every journal entry each connector has not yet received, as one batch per connector
+    pub fn push_new_journal_entries(&mut self, ledger: &IntelligenceCapitalLedger) -> IclResult<Vec<BatchReceipt>> {
+        let mut receipts = Vec::new();
+        let mut failures = Vec::new();
+        for registered in &mut self.connectors {
+            let batch: Vec<JournalEntry> = ledger.journal_entries().iter()
+                .skip(registered.cursor)
+                .filter(|j| !ledger.is_quarantined(j.entry_id))
+                .cloned()
+                .collect();
+            if batch.is_empty() {
+                registered.cursor = ledger.journal_entries().len();
+                continue;
+            }
+            match registered.connector.push_journal_batch(&batch) {
+                Ok(batch_id) => {
+                    registered.cursor = ledger.journal_entries().len();
+                    registered.pushed.extend(batch.iter().map(|j| j.entry_id));
+                    receipts.push(BatchReceipt {
+                        connector: registered.connector.name().to_string(),
+                        batch_id,
+                        entry_count: batch.len(),
+                    });
+                },
+                Err(e) => failures.push(format!("{}: {}", registered.connector.name(), e)),
+            }
+        }
+        if !failures.is_empty() {
+            return Err(IclError::IntegrationError(format!("Journal push failed for {}", failures.join("; "))));
+        }
+        Ok(receipts)
+    }
+
+    pub fn collect_acknowledgements(&mut self) -> IclResult<usize> {
+        let mut received = 0;
+        for registered in &mut self.connectors {
+            for ack in registered.connector.fetch_acknowledgements()? {
+                registered.acknowledgements.insert(ack.entry_id, ack);
+                received += 1;
+            }
+        }
+        Ok(received)
+    }
+
+    pub fn acknowledgement(&self, connector: &str, entry_id: EntryId) -> Option<&JournalAcknowledgement> {
+        self.find(connector)?.acknowledgements.get(&entry_id)
+    }
+
+    pub fn unacknowledged_entries(&self, connector: &str) -> Vec<EntryId> {
+        let Some(registered) = self.find(connector) else {
+            return Vec::new();
+        };
+        registered.pushed.iter()
+            .filter(|id| !matches!(
+                registered.acknowledgements.get(id).map(|a| a.status),
+                Some(AcknowledgementStatus::Accepted)
+            ))
+            .copied()
+            .collect()
+    }
+
+    fn find(&self, connector: &str) -> Option<&RegisteredConnector> {
+        self.connectors.iter().find(|c| c.connector.name() == connector)
+    }
+}
+
+impl std::fmt::Debug for ConnectorRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectorRegistry")
+            .field("connectors", &self.names())
+            .finish()
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::intercompany::{Elimination, INTERCOMPANY_DISPOSAL_EVENT};
+
+/// A record of a consolidated view with the ledger it came from: 0 for the ledger
+/// `consolidate` was called on, then the others in the order given
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Sourced<T> {
+    pub source: usize,
+    #[serde(flatten)]
+    pub record: T,
+}
+
+/// One intercompany transfer found while consolidating
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConsolidationElimination {
+    pub elimination: Elimination,
+    pub seller_source: Option<usize>,
+    pub buyer_source: Option<usize>,
+    pub eliminated_journal_entry_ids: Vec<EntryId>,
+}
+
+impl ConsolidationElimination {
+    pub fn is_matched(&self) -> bool {
+        self.seller_source.is_some() && self.buyer_source.is_some()
+    }
+}
+
+/// Combined reporting view over several ledgers, e.g. one per subsidiary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolidatedView {
+    pub source_count: usize,
+    pub assets: Vec<Sourced<IntelligenceAsset>>,
+    pub events: Vec<Sourced<CapitalEvent>>,
+    pub journal_entries: Vec<Sourced<JournalEntry>>,
+    pub eliminations: Vec<ConsolidationElimination>,
+}
+
+impl ConsolidatedView {
+    pub(crate) fn build(ledgers: &[&IntelligenceCapitalLedger]) -> Self {
+        let mut assets = Vec::new();
+        let mut events = Vec::new();
+        let mut journal_entries = Vec::new();
+        let mut eliminations: BTreeMap<Uuid, ConsolidationElimination> = BTreeMap::new();
+        for (source, ledger) in ledgers.iter().enumerate() {
+            let mut ledger_assets: Vec<&IntelligenceAsset> = ledger.assets_iter().collect();
+            ledger_assets.sort_by_key(|a| (a.created_at, a.asset_id));
+            assets.extend(ledger_assets.into_iter().map(|asset| Sourced { source, record: asset.clone() }));
+
+            for event in ledger.events().iter().filter(|e| !ledger.is_quarantined(e.event_id)) {
+                if let Some(elimination) = Elimination::of_event(event) {
+                    let entry = eliminations.entry(elimination.transfer_id).or_insert_with(|| ConsolidationElimination {
+                        elimination,
+                        seller_source: None,
+                        buyer_source: None,
+                        eliminated_journal_entry_ids: Vec::new(),
+                    });
+                    if event.event_type == INTERCOMPANY_DISPOSAL_EVENT {
+                        entry.seller_source = Some(source);
+                    } else {
+                        entry.buyer_source = Some(source);
+                    }
+                    entry.eliminated_journal_entry_ids.extend(
+                        ledger.journal_entries_for_event(event.event_id).into_iter()
+                            .filter(|j| !ledger.is_quarantined(j.entry_id))
+                            .map(|j| j.entry_id),
+                    );
+                }
+                events.push(Sourced { source, record: event.clone() });
+            }
+            journal_entries.extend(ledger.journal_entries().iter()
+                .filter(|j| !ledger.is_quarantined(j.entry_id))
+                .map(|j| Sourced { source, record: j.clone() }));
+        }
+
+        Self {
+            source_count: ledgers.len(),
+            assets,
+            events,
+            journal_entries,
+            eliminations: eliminations.into_values().collect(),
+        }
+    }
+
+    pub fn eliminated_journal_entry_ids(&self) -> HashSet<EntryId> {
+        self.eliminations.iter()
+            .filter(|e| e.is_matched())
+            .flat_map(|e| e.eliminated_journal_entry_ids.iter().copied())
+            .collect()
+    }
+
+    /// Transfers only one side of which was found, e.g. because the counterparty's ledger was
+    /// not consolidated
+    pub fn unmatched_eliminations(&self) -> impl Iterator<Item = &ConsolidationElimination> {
+        self.eliminations.iter().filter(|e| !e.is_matched())
+    }
+
+    pub fn account_balances(&self) -> HashMap<AccountType, f64> {
+        let eliminated = self.eliminated_journal_entry_ids();
+        let mut balances = HashMap::new();
+        for entry in self.journal_entries.iter().map(|j| &j.record).filter(|j| !eliminated.contains(&j.entry_id)) {
+            *balances.entry(entry.debit_account).or_default() += entry.amount;
+            *balances.entry(entry.credit_account).or_default() -= entry.amount;
+        }
+        balances
+    }
+
+    /// Book value of every asset that is not retired, less the unrealized gain on matched
+    /// intercompany transfers
+    pub fn total_book_value(&self) -> f64 {
+        let book_value: f64 = self.assets.iter()
+            .map(|a| &a.record)
+            .filter(|a| a.status != AssetStatus::Retired)
+            .map(|a| a.current_value.unwrap_or(a.initial_value))
+            .sum();
+        let unrealized_gain: f64 = self.eliminations.iter()
+            .filter(|e| e.is_matched())
+            .map(|e| e.elimination.unrealized_gain())
+            .sum();
+        book_value - unrealized_gain
+    }
+
+    /// Events that came from one source ledger
+    pub fn events_from(&self, source: usize) -> impl Iterator<Item = &CapitalEvent> {
+        self.events.iter().filter(move |e| e.source == source).map(|e| &e.record)
+    }
+}
+
+impl IntelligenceCapitalLedger {
+    /// Combine this ledger with `others` into one reporting view, e.g. a parent company's over
+    /// subsidiaries that each run their own ledger
+    pub fn consolidate(&self, others: &[IntelligenceCapitalLedger]) -> ConsolidatedView {
+        let ledgers: Vec<&IntelligenceCapitalLedger> = std::iter::once(self).chain(others).collect();
+        ConsolidatedView::build(&ledgers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::intercompany::ELIMINATION_DETAIL;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+
+    fn subsidiary_ledger(initial_value: f64) -> (IntelligenceCapitalLedger, AssetId) {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .in_org(OrgId::new("subsidiary"))
+            .capitalize(asset_id, "Team B", initial_value, DepreciationMethod::Linear, 12)
+            .unwrap();
+        (ledger, asset_id)
+    }
+
+    #[test]
+    fn test_consolidation_eliminates_intercompany_transfer() {
+        let mut parent = IntelligenceCapitalLedger::new();
+        let (seller_asset_id, buyer_asset_id) = (AssetId::random(), AssetId::random());
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut parent).in_org(OrgId::new("parent"));
+        lifecycle.capitalize(seller_asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let transfer = lifecycle
+            .transfer_to_org(seller_asset_id, OrgId::new("branch"), buyer_asset_id, "Team A", 1500.0)
+            .unwrap();
+        let (subsidiary, subsidiary_asset_id) = subsidiary_ledger(600.0);
+
+        let view = parent.consolidate(&[subsidiary]);
+        assert_eq!(view.source_count, 2);
+        assert_eq!(view.eliminations.len(), 1);
+        let elimination = &view.eliminations[0];
+        assert_eq!(elimination.elimination, transfer.elimination);
+        assert_eq!((elimination.seller_source, elimination.buyer_source), (Some(0), Some(0)));
+        assert_eq!(view.unmatched_eliminations().count(), 0);
+
+        let transfer_entries: HashSet<EntryId> = view.journal_entries.iter()
+            .map(|j| &j.record)
+            .filter(|j| [transfer.disposal_event.event_id, transfer.capitalization_event.event_id].contains(&j.event_id))
+            .map(|j| j.entry_id)
+            .collect();
+        assert!(!transfer_entries.is_empty());
+        assert_eq!(view.eliminated_journal_entry_ids(), transfer_entries);
+        let balances = view.account_balances();
+        assert_eq!(balances.get(&AccountType::Asset).copied(), Some(1800.0));
+        assert_eq!(balances.get(&AccountType::AccountsReceivable).copied().unwrap_or(0.0), 0.0);
+
+        // The buyer's 1500 is carried at the group's 1200; the retired seller asset drops out
+        assert_eq!(view.total_book_value(), 1800.0);
+
+        let source_of = |asset_id: AssetId| view.assets.iter().find(|a| a.record.asset_id == asset_id).map(|a| a.source);
+        assert_eq!(source_of(seller_asset_id), Some(0));
+        assert_eq!(source_of(buyer_asset_id), Some(0));
+        assert_eq!(source_of(subsidiary_asset_id), Some(1));
+        assert!(view.events_from(1).all(|e| e.asset_id == subsidiary_asset_id));
+        assert_eq!(view.events_from(0).count(), parent.events().len());
+        assert!(view.journal_entries.iter().filter(|j| j.source == 1).all(|j| !transfer_entries.contains(&j.record.entry_id)));
+    }
+
+    #[test]
+    fn test_one_sided_transfer_is_not_eliminated() {
+        let (mut ledger, asset_id) = subsidiary_ledger(600.0);
+        let elimination = Elimination {
+            transfer_id: Uuid::new_v4(),
+            seller_org: OrgId::new("parent"),
+            buyer_org: OrgId::new("subsidiary"),
+            seller_asset_id: AssetId::random(),
+            buyer_asset_id: asset_id,
+            transfer_price: 600.0,
+            carrying_value: 400.0,
+        };
+        let mut details = HashMap::new();
+        details.insert(ELIMINATION_DETAIL.to_string(), serde_json::to_value(&elimination).unwrap());
+        ledger.record_event(CapitalEvent::new(asset_id, "intercompany_note", ledger.now(), details)).unwrap();
+
+        let view = ledger.consolidate(&[]);
+        let unmatched: Vec<_> = view.unmatched_eliminations().collect();
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!((unmatched[0].seller_source, unmatched[0].buyer_source), (None, Some(0)));
+        assert!(view.eliminated_journal_entry_ids().is_empty());
+        assert_eq!(view.total_book_value(), 600.0);
+    }
+}
@@ -2,6 +2,87 @@ use chrono::{DateTime, Utc, Datelike};
 use crate::core::types::*;
 use crate::core::error::*;
 
+/// Everything a depreciation calculation depends on, detached from any asset or ledger
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepreciationInput {
+    pub method: DepreciationMethod,
+    pub initial_value: f64,
+    pub current_value: f64,
+    pub salvage_value: f64,
+    pub useful_life_months: i32,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub rate_multiplier: f64,
+    pub units: f64,
+}
+
+impl DepreciationInput {
+    /// The input for depreciating `asset` from its current book value over a period
+    pub fn for_asset(
+        asset: &IntelligenceAsset,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        salvage_value: f64,
+        rate_multiplier: f64
+    ) -> Self {
+        Self {
+            method: asset.depreciation_method,
+            initial_value: asset.initial_value,
+            current_value: asset.current_value.unwrap_or(asset.initial_value),
+            salvage_value,
+            useful_life_months: asset.useful_life_months,
+            start_date,
+            end_date,
+            rate_multiplier,
+            units: 0.0,
+        }
+    }
+
+    pub fn with_units(mut self, units: f64) -> Self {
+        self.units = units;
+        self
+    }
+}
+
+/// Depreciation for an input, as (depreciation amount, new book value)
+pub fn depreciate(input: &DepreciationInput) -> IclResult<(f64, f64)> {
+    if !(input.initial_value.is_finite() && input.current_value.is_finite() && input.salvage_value.is_finite()) {
+        return Err(IclError::DepreciationError("Values must be finite".into()));
+    }
+    if input.salvage_value < 0.0 {
+        return Err(IclError::DepreciationError("Salvage value cannot be negative".into()));
+    }
+    if input.salvage_value > input.initial_value {
+        return Err(IclError::DepreciationError("Salvage value cannot exceed initial value".into()));
+    }
+
+    match input.method {
+        DepreciationMethod::Linear => {
+            check_period(input)?;
+            Ok(linear_depreciation(input))
+        },
+        DepreciationMethod::DecliningBalance => {
+            check_period(input)?;
+            if !(input.rate_multiplier > 0.0 && input.rate_multiplier.is_finite()) {
+                return Err(IclError::DepreciationError("Rate multiplier must be positive".into()));
+            }
+            Ok(declining_balance_depreciation(input))
+        },
+        DepreciationMethod::ImpairmentOnly => Err(IclError::DepreciationError(
+            "Assets with an indefinite life are impaired rather than depreciated".into()
+        )),
+        DepreciationMethod::UnitsOfProduction { expected_units, .. } => {
+            if !(input.units >= 0.0 && input.units.is_finite()) {
+                return Err(IclError::DepreciationError("Usage cannot be negative".into()));
+            }
+            if expected_units == 0 {
+                return Err(IclError::DepreciationError("Expected units must be positive".into()));
+            }
+            Ok(units_of_production_depreciation(input, expected_units))
+        },
+    }
+}
+
 pub fn calculate_depreciation(
     asset: &IntelligenceAsset,
     start_date: DateTime<Utc>,
@@ -9,33 +90,46 @@ pub fn calculate_depreciation(
     salvage_value: f64,
     rate_multiplier: f64
 ) -> IclResult<(f64, f64)> {
-    if start_date >= end_date {
-        return Err(IclError::InvalidDateRange {
-            start: start_date.to_rfc3339(),
-            end: end_date.to_rfc3339(),
-        });
+    match asset.depreciation_method {
+        DepreciationMethod::ImpairmentOnly => Err(IclError::DepreciationError(format!(
+            "Asset {} has an indefinite life and is impaired rather than depreciated", asset.asset_id
+        ))),
+        DepreciationMethod::UnitsOfProduction { .. } => Err(IclError::DepreciationError(format!(
+            "Asset {} is depreciated by usage; use calculate_units_of_production_depreciation", asset.asset_id
+        ))),
+        _ => depreciate(&DepreciationInput::for_asset(asset, start_date, end_date, salvage_value, rate_multiplier)),
     }
+}
 
-    if salvage_value < 0.0 {
-        return Err(IclError::DepreciationError("Salvage value cannot be negative".into()));
+/// Depreciation of a units-of-production asset for `units` of usage in the period, as
+/// (depreciation amount, new book value)
+pub fn calculate_units_of_production_depreciation(
+    asset: &IntelligenceAsset,
+    units: f64,
+    salvage_value: f64
+) -> IclResult<(f64, f64)> {
+    if !matches!(asset.depreciation_method, DepreciationMethod::UnitsOfProduction { .. }) {
+        return Err(IclError::DepreciationError(format!("Asset {} is not depreciated by usage", asset.asset_id)));
     }
+    let input = DepreciationInput::for_asset(asset, asset.created_at, asset.created_at, salvage_value, 1.0).with_units(units);
+    depreciate(&input)
+}
 
-    if salvage_value > asset.initial_value {
-        return Err(IclError::DepreciationError("Salvage value cannot exceed initial value".into()));
+fn check_period(input: &DepreciationInput) -> IclResult<()> {
+    if input.start_date >= input.end_date {
+        return Err(IclError::InvalidDateRange {
+            start: input.start_date.to_rfc3339(),
+            end: input.end_date.to_rfc3339(),
+        });
     }
-
-    match asset.depreciation_method {
-        DepreciationMethod::Linear => {
-            linear_depreciation(asset, start_date, end_date, salvage_value)
-        },
-        DepreciationMethod::DecliningBalance => {
-            declining_balance_depreciation(asset, start_date, end_date, salvage_value, rate_multiplier)
-        },
+    if input.useful_life_months <= 0 {
+        return Err(IclError::DepreciationError("Useful life must be positive".into()));
     }
+    Ok(())
 }
 
 /// Calculate months between two dates
-fn months_between(start: DateTime<Utc>, end: DateTime<Utc>) -> i32 {
+pub fn months_between(start: DateTime<Utc>, end: DateTime<Utc>) -> i32 {
     let years = end.year() - start.year();
     let months = end.month() as i32 - start.month() as i32;
     let total_months = years * 12 + months;
@@ -48,44 +142,34 @@ fn months_between(start: DateTime<Utc>, end: DateTime<Utc>) -> i32 {
     }
 }
 
-fn linear_depreciation(
-    asset: &IntelligenceAsset,
-    start_date: DateTime<Utc>,
-    end_date: DateTime<Utc>,
-    salvage_value: f64
-) -> IclResult<(f64, f64)> {
-    let months = months_between(start_date, end_date);
+fn linear_depreciation(input: &DepreciationInput) -> (f64, f64) {
+    let months = months_between(input.start_date, input.end_date);
     
     if months <= 0 {
-        return Ok((0.0, asset.current_value.unwrap_or(asset.initial_value)));
+        return (0.0, input.current_value);
     }
 
-    let depreciable_base = asset.initial_value - salvage_value;
-    let monthly_rate = 1.0 / asset.useful_life_months as f64;
+    let depreciable_base = input.initial_value - input.salvage_value;
+    let monthly_rate = 1.0 / input.useful_life_months as f64;
     let max_depreciation = depreciable_base * monthly_rate * months as f64;
     
-    let current = asset.current_value.unwrap_or(asset.initial_value);
-    let depreciation_amount = max_depreciation.min(current - salvage_value).max(0.0);
-    let new_value = (current - depreciation_amount).max(salvage_value);
+    let current = input.current_value;
+    let depreciation_amount = max_depreciation.min(current - input.salvage_value).max(0.0);
+    let new_value = (current - depreciation_amount).max(input.salvage_value.min(current));
     
-    Ok((depreciation_amount, new_value))
+    (depreciation_amount, new_value)
 }
 
-fn declining_balance_depreciation(
-    asset: &IntelligenceAsset,
-    start_date: DateTime<Utc>,
-    end_date: DateTime<Utc>,
-    salvage_value: f64,
-    rate_multiplier: f64
-) -> IclResult<(f64, f64)> {
-    let months = months_between(start_date, end_date);
+fn declining_balance_depreciation(input: &DepreciationInput) -> (f64, f64) {
+    let months = months_between(input.start_date, input.end_date);
+    let salvage_value = input.salvage_value;
     
-    if months <= 0 {
-        return Ok((0.0, asset.current_value.unwrap_or(asset.initial_value)));
+    if months <= 0 || input.current_value <= salvage_value {
+        return (0.0, input.current_value);
     }
 
-    let rate = rate_multiplier / asset.useful_life_months as f64;
-    let mut current_value = asset.current_value.unwrap_or(asset.initial_value);
+    let rate = input.rate_multiplier / input.useful_life_months as f64;
+    let mut current_value = input.current_value;
     
     let mut depreciation_amount = 0.0;
     for _ in 0..months {
@@ -94,6 +178,9 @@ fn declining_balance_depreciation(
             depreciation_amount += current_value - salvage_value;
             current_value = salvage_value;
             break;
+        } else if monthly_depreciation <= 0.0 {
+            // Book value has decayed to nothing depreciable; further months change nothing
+            break;
         } else {
             depreciation_amount += monthly_depreciation;
             current_value -= monthly_depreciation;
@@ -101,7 +188,18 @@ fn declining_balance_depreciation(
     }
     
     let new_value = current_value.max(salvage_value);
-    Ok((depreciation_amount, new_value))
+    (depreciation_amount, new_value)
+}
+
+fn units_of_production_depreciation(input: &DepreciationInput, expected_units: u64) -> (f64, f64) {
+    let depreciable_base = input.initial_value - input.salvage_value;
+    let current = input.current_value;
+    let depreciation_amount = (depreciable_base * input.units / expected_units as f64)
+        .min(current - input.salvage_value)
+        .max(0.0);
+    let new_value = (current - depreciation_amount).max(input.salvage_value.min(current));
+
+    (depreciation_amount, new_value)
 }
 
 #[cfg(test)]
@@ -111,7 +209,7 @@ mod tests {
 
     fn test_asset() -> IntelligenceAsset {
         IntelligenceAsset {
-            asset_id: uuid::Uuid::new_v4(),
+            asset_id: AssetId::random(),
             owner: "Test".into(),
             initial_value: 12000.0,
             depreciation_method: DepreciationMethod::Linear,
@@ -119,6 +217,12 @@ mod tests {
             created_at: Utc::now(),
             status: AssetStatus::Active,
             current_value: Some(12000.0),
+            category: None,
+            dimensions: Default::default(),
+            org_id: Default::default(),
+            artifact: None,
+            parent_asset_id: None,
+            kind: AssetKind::default(),
         }
     }
 
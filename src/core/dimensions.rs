@@ -0,0 +1,94 @@
+use std::collections::{BTreeMap, BTreeSet};
+use serde::{Deserialize, Serialize};
+
+/// A dimension events and journal entries may be tagged with, such as `cost_center`, `project`,
+/// or `product_line`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DimensionDefinition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub allowed_values: BTreeSet<String>,
+}
+
+impl DimensionDefinition {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), allowed_values: BTreeSet::new() }
+    }
+
+    pub fn with_values<I, S>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_values.extend(values.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn allows(&self, value: &str) -> bool {
+        !value.trim().is_empty() && (self.allowed_values.is_empty() || self.allowed_values.contains(value))
+    }
+}
+
+pub(crate) fn check_dimensions(
+    registered: &BTreeMap<String, DimensionDefinition>,
+    dimensions: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    for (name, value) in dimensions {
+        let Some(definition) = registered.get(name) else {
+            return Err(format!("Dimension {} is not registered", name));
+        };
+        if !definition.allows(value) {
+            return Err(format!("{:?} is not an allowed value of dimension {}", value, name));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::core::types::*;
+    use crate::core::ledger::IntelligenceCapitalLedger;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+    use crate::core::reports::{ReportGenerator, ReportGrouping, UNASSIGNED};
+    use crate::core::error::IclError;
+
+    #[test]
+    fn test_postings_carry_registered_dimensions() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.register_dimension(DimensionDefinition::new("cost_center").with_values(["research", "platform"])).unwrap();
+        ledger.register_dimension(DimensionDefinition::new("project")).unwrap();
+        let tagged = AssetId::random();
+        let untagged = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(untagged, "Team B", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.capitalize(tagged, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        ledger.update_asset_metadata(tagged, |a| {
+            a.dimensions.insert("cost_center".into(), "research".into());
+            a.dimensions.insert("unregistered".into(), "ignored".into());
+        }).unwrap();
+
+        let start = ledger.get_asset(tagged).unwrap().created_at;
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        let event = lifecycle.depreciate(tagged, start, start + Duration::days(62), 0.0, 1.0).unwrap();
+        lifecycle.depreciate(untagged, start, start + Duration::days(62), 0.0, 1.0).unwrap();
+        assert_eq!(event.dimensions.get("cost_center").map(String::as_str), Some("research"));
+        assert!(!event.dimensions.contains_key("unregistered"));
+        let journal_entry = ledger.get_journal_entries_for_asset(tagged).into_iter()
+            .find(|j| j.event_id == event.event_id)
+            .unwrap();
+        assert_eq!(journal_entry.dimensions, event.dimensions);
+
+        let rejected = CapitalEvent::new(tagged, "note", ledger.now(), Default::default()).with_dimension("cost_center", "sales");
+        assert!(matches!(ledger.record_event(rejected), Err(IclError::InvalidEvent(_))));
+        let rejected = CapitalEvent::new(tagged, "note", ledger.now(), Default::default()).with_dimension("region", "emea");
+        assert!(matches!(ledger.record_event(rejected), Err(IclError::InvalidEvent(_))));
+
+        let report = ReportGenerator::new(&ledger)
+            .depreciation_by_period(start, start + Duration::days(90), ReportGrouping::Dimension("cost_center".into()))
+            .unwrap();
+        let groups: Vec<&str> = report.rows.iter().map(|r| r.group.as_str()).collect();
+        assert_eq!(groups, ["research", UNASSIGNED]);
+    }
+}
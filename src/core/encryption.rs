@@ -0,0 +1,116 @@
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+use age::secrecy::ExposeSecret;
+use age::x25519;
+
+use crate::core::ledger::{AuditTrailFilter, IntelligenceCapitalLedger};
+use crate::core::error::*;
+
+fn encryption_error(e: impl std::fmt::Display) -> IclError {
+    IclError::SerializationError(format!("Encryption: {}", e))
+}
+
+/// Generate an X25519 key pair, returned as `(identity, recipient)`: the `AGE-SECRET-KEY-1...`
+/// secret used to decrypt and the `age1...` public key exports are encrypted to
+pub fn generate_encryption_key() -> (String, String) {
+    let identity = x25519::Identity::generate();
+    let recipient = identity.to_public().to_string();
+    (identity.to_string().expose_secret().to_string(), recipient)
+}
+
+/// Encrypt `plaintext` into an age file any of `recipient_keys` (`age1...` public keys) can open
+pub fn encrypt_to_recipients(plaintext: &[u8], recipient_keys: &[&str]) -> IclResult<Vec<u8>> {
+    let recipients = recipient_keys.iter()
+        .map(|key| x25519::Recipient::from_str(key.trim()).map_err(encryption_error))
+        .collect::<IclResult<Vec<_>>>()?;
+    let encryptor = age::Encryptor::with_recipients(recipients.iter().map(|r| r as &dyn age::Recipient))
+        .map_err(encryption_error)?;
+    let mut ciphertext = Vec::new();
+    let mut writer = encryptor.wrap_output(&mut ciphertext)?;
+    writer.write_all(plaintext)?;
+    writer.finish()?;
+    Ok(ciphertext)
+}
+
+/// Decrypt an age file with an `AGE-SECRET-KEY-1...` identity
+pub fn decrypt_with_identity(ciphertext: &[u8], identity_key: &str) -> IclResult<Vec<u8>> {
+    let identity = x25519::Identity::from_str(identity_key.trim()).map_err(encryption_error)?;
+    let decryptor = age::Decryptor::new_buffered(ciphertext).map_err(encryption_error)?;
+    let mut reader = decryptor.decrypt(std::iter::once(&identity as &dyn age::Identity))
+        .map_err(encryption_error)?;
+    let mut plaintext = Vec::new();
+    reader.read_to_end(&mut plaintext).map_err(encryption_error)?;
+    Ok(plaintext)
+}
+
+impl IntelligenceCapitalLedger {
+    /// Export the audit trail as `json`, `jsonl`, or `csv`, encrypted with age to every key in
+    /// `recipient_keys`
+    pub fn export_audit_trail_encrypted(&self, format: &str, recipient_keys: &[&str]) -> IclResult<Vec<u8>> {
+        let plaintext = match format {
+            "jsonl" => {
+                let mut buffer = Vec::new();
+                self.export_audit_trail_to(&mut buffer, format, &AuditTrailFilter::default())?;
+                buffer
+            },
+            _ => self.export_audit_trail(format)?.into_bytes(),
+        };
+        encrypt_to_recipients(&plaintext, recipient_keys)
+    }
+
+    /// Decrypt a `json` or `jsonl` export from `export_audit_trail_encrypted` and rebuild the
+    /// ledger from it, as `from_audit_trail` does
+    pub fn import_encrypted_audit_trail(ciphertext: &[u8], format: &str, identity_key: &str) -> IclResult<Self> {
+        let plaintext = decrypt_with_identity(ciphertext, identity_key)?;
+        let data = String::from_utf8(plaintext).map_err(encryption_error)?;
+        Self::from_audit_trail(&data, format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+    use crate::core::types::*;
+
+    fn audited_ledger() -> IntelligenceCapitalLedger {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.allocate(asset_id, "Team B").unwrap();
+        ledger
+    }
+
+    #[test]
+    fn test_encrypted_audit_trail_round_trip() {
+        let ledger = audited_ledger();
+        let (identity, recipient) = generate_encryption_key();
+        let (_, other_recipient) = generate_encryption_key();
+        for format in ["json", "jsonl"] {
+            let ciphertext = ledger.export_audit_trail_encrypted(format, &[&other_recipient, &recipient]).unwrap();
+            assert!(ciphertext.starts_with(b"age-encryption.org/v1"));
+            assert!(!ciphertext.windows(b"Team B".len()).any(|w| w == b"Team B"));
+
+            let imported = IntelligenceCapitalLedger::import_encrypted_audit_trail(&ciphertext, format, &identity).unwrap();
+            let records = |ledger: &IntelligenceCapitalLedger| {
+                serde_json::to_value((ledger.events(), ledger.journal_entries())).unwrap()
+            };
+            assert_eq!(records(&imported), records(&ledger));
+        }
+    }
+
+    #[test]
+    fn test_wrong_identity_cannot_decrypt() {
+        let ledger = audited_ledger();
+        let (_, recipient) = generate_encryption_key();
+        let (other_identity, _) = generate_encryption_key();
+        let ciphertext = ledger.export_audit_trail_encrypted("json", &[&recipient]).unwrap();
+
+        let result = IntelligenceCapitalLedger::import_encrypted_audit_trail(&ciphertext, "json", &other_identity);
+        assert!(matches!(result, Err(IclError::SerializationError(message)) if message.starts_with("Encryption:")));
+        assert!(decrypt_with_identity(&ciphertext, "not a key").is_err());
+        assert!(encrypt_to_recipients(b"audit", &["age1notakey"]).is_err());
+    }
+}
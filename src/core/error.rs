@@ -1,13 +1,45 @@
 use thiserror::Error;
-use uuid::Uuid;
+use crate::core::types::{AssetId, EntryId, EventId, OwnerId};
 
+/// Broad class of an `IclError`, for mapping errors onto transport status codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ErrorCategory {
+    NotFound,
+    Conflict,
+    Validation,
+    Unauthorized,
+    Unsupported,
+    Internal,
+}
+
+impl std::fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorCategory::NotFound => write!(f, "NotFound"),
+            ErrorCategory::Conflict => write!(f, "Conflict"),
+            ErrorCategory::Validation => write!(f, "Validation"),
+            ErrorCategory::Unauthorized => write!(f, "Unauthorized"),
+            ErrorCategory::Unsupported => write!(f, "Unsupported"),
+            ErrorCategory::Internal => write!(f, "Internal"),
+        }
+    }
+}
+
+/// Errors of every ledger operation
 #[derive(Error, Debug, Clone)]
+#[non_exhaustive]
 pub enum IclError {
     #[error("Asset {0} not found")]
-    AssetNotFound(Uuid),
+    AssetNotFound(AssetId),
 
     #[error("Asset {0} already exists")]
-    AssetAlreadyExists(Uuid),
+    AssetAlreadyExists(AssetId),
+
+    #[error("Owner {0} not found")]
+    OwnerNotFound(OwnerId),
+
+    #[error("Owner {0} already exists")]
+    OwnerAlreadyExists(OwnerId),
 
     #[error("Invalid asset: {0}")]
     InvalidAsset(String),
@@ -36,17 +68,145 @@ pub enum IclError {
     #[error("Overlapping depreciation period detected")]
     OverlappingDepreciation,
 
+    #[error("Event {0} not found")]
+    EventNotFound(EventId),
+
+    #[error("Event {0} has already been recorded")]
+    DuplicateEvent(EventId),
+
+    #[error("Journal entry {0} has already been recorded")]
+    DuplicateEntry(EntryId),
+
     #[error("Asset {0} is retired and cannot be modified")]
-    AssetRetired(Uuid),
+    AssetRetired(AssetId),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
 
     #[error("Unsupported format: {0}")]
     UnsupportedFormat(String),
+
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
+    #[error("Hashing error: {0}")]
+    HashingError(String),
+
+    #[error("{}: {source}", context_label(.op, .asset_id))]
+    Context {
+        op: String,
+        asset_id: Option<AssetId>,
+        #[source]
+        source: Box<IclError>,
+    },
+}
+
+fn context_label(op: &str, asset_id: &Option<AssetId>) -> String {
+    match asset_id {
+        Some(asset_id) => format!("{} (asset {})", op, asset_id),
+        None => op.to_string(),
+    }
 }
 
 pub type IclResult<T> = Result<T, IclError>;
 
+impl IclError {
+    /// Wrap the error with the step of an operation that failed and the asset it was acting on
+    pub fn context(self, op: impl Into<String>, asset_id: Option<AssetId>) -> Self {
+        IclError::Context { op: op.into(), asset_id, source: Box::new(self) }
+    }
+
+    pub fn root(&self) -> &IclError {
+        match self {
+            IclError::Context { source, .. } => source.root(),
+            other => other,
+        }
+    }
+
+    pub fn context_chain(&self) -> Vec<(&str, Option<AssetId>)> {
+        let mut chain = Vec::new();
+        let mut error = self;
+        while let IclError::Context { op, asset_id, source } = error {
+            chain.push((op.as_str(), *asset_id));
+            error = source;
+        }
+        chain
+    }
+
+    /// Stable machine-readable code of the error, e.g. `ICL-ASSET-404`
+    pub fn code(&self) -> &'static str {
+        match self.root() {
+            IclError::AssetNotFound(_) => "ICL-ASSET-404",
+            IclError::AssetAlreadyExists(_) => "ICL-ASSET-409",
+            IclError::AssetRetired(_) => "ICL-ASSET-410",
+            IclError::InvalidAsset(_) => "ICL-ASSET-422",
+            IclError::OwnerNotFound(_) => "ICL-OWNER-404",
+            IclError::OwnerAlreadyExists(_) => "ICL-OWNER-409",
+            IclError::EventNotFound(_) => "ICL-EVENT-404",
+            IclError::DuplicateEvent(_) => "ICL-EVENT-409",
+            IclError::InvalidEvent(_) => "ICL-EVENT-422",
+            IclError::DuplicateEntry(_) => "ICL-ENTRY-409",
+            IclError::InvalidEntry(_) => "ICL-ENTRY-422",
+            IclError::OverlappingDepreciation => "ICL-DEPRECIATION-409",
+            IclError::DepreciationError(_) => "ICL-DEPRECIATION-422",
+            IclError::InvalidDateRange { .. } => "ICL-DATE-422",
+            IclError::IntegrityViolation(_) => "ICL-INTEGRITY-422",
+            IclError::Unauthorized(_) => "ICL-AUTH-403",
+            IclError::UnsupportedFormat(_) => "ICL-FORMAT-400",
+            IclError::SerializationError(_) => "ICL-SERIALIZATION-500",
+            IclError::StorageError(_) => "ICL-STORAGE-500",
+            IclError::HashingError(_) => "ICL-HASHING-500",
+            IclError::IntegrationError(_) => "ICL-INTEGRATION-502",
+            IclError::Context { .. } => unreachable!("root() never returns a Context"),
+        }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self.root() {
+            IclError::AssetNotFound(_) | IclError::OwnerNotFound(_) | IclError::EventNotFound(_) => ErrorCategory::NotFound,
+            IclError::AssetAlreadyExists(_)
+            | IclError::OwnerAlreadyExists(_)
+            | IclError::DuplicateEvent(_)
+            | IclError::DuplicateEntry(_)
+            | IclError::AssetRetired(_)
+            | IclError::OverlappingDepreciation => ErrorCategory::Conflict,
+            IclError::InvalidAsset(_)
+            | IclError::InvalidEvent(_)
+            | IclError::InvalidEntry(_)
+            | IclError::DepreciationError(_)
+            | IclError::IntegrityViolation(_)
+            | IclError::InvalidDateRange { .. } => ErrorCategory::Validation,
+            IclError::Unauthorized(_) => ErrorCategory::Unauthorized,
+            IclError::UnsupportedFormat(_) => ErrorCategory::Unsupported,
+            IclError::SerializationError(_)
+            | IclError::IntegrationError(_)
+            | IclError::StorageError(_)
+            | IclError::HashingError(_) => ErrorCategory::Internal,
+            IclError::Context { .. } => unreachable!("root() never returns a Context"),
+        }
+    }
+}
+
+/// Adds context to the error of a failed step
+pub trait IclResultExt<T> {
+    /// Wrap an error in `IclError::Context`
+    fn with_context<S: Into<String>>(self, op: impl FnOnce() -> S, asset_id: Option<AssetId>) -> IclResult<T>;
+}
+
+impl<T> IclResultExt<T> for IclResult<T> {
+    fn with_context<S: Into<String>>(self, op: impl FnOnce() -> S, asset_id: Option<AssetId>) -> IclResult<T> {
+        self.map_err(|e| e.context(op(), asset_id))
+    }
+}
+
 impl From<serde_json::Error> for IclError {
     fn from(e: serde_json::Error) -> Self {
         IclError::SerializationError(e.to_string())
     }
-}
\ No newline at end of file
+}
+
+impl From<std::io::Error> for IclError {
+    fn from(e: std::io::Error) -> Self {
+        IclError::StorageError(e.to_string())
+    }
+}
@@ -0,0 +1,833 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::ledger::{IntelligenceCapitalLedger, PostingAssets};
+use crate::core::error::*;
+
+/// An account in the target system's chart of accounts
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChartAccount {
+    pub code: String,
+    pub name: String,
+}
+
+impl ChartAccount {
+    pub fn new(code: impl Into<String>, name: impl Into<String>) -> Self {
+        Self { code: code.into(), name: name.into() }
+    }
+}
+
+/// Which of its parent's postings a sub-account receives
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SubAccountRule {
+    Kind(AssetKind),
+    Category(String),
+}
+
+impl SubAccountRule {
+    fn matches(&self, asset: &IntelligenceAsset) -> bool {
+        match self {
+            SubAccountRule::Kind(kind) => asset.kind == *kind,
+            SubAccountRule::Category(category) => asset.category.as_deref() == Some(category.as_str()),
+        }
+    }
+}
+
+impl std::fmt::Display for SubAccountRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubAccountRule::Kind(kind) => write!(f, "kind {:?}", kind),
+            SubAccountRule::Category(category) => write!(f, "category {}", category),
+        }
+    }
+}
+
+/// A child account that takes the postings its rule selects from its parent's account type
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubAccount {
+    pub account: ChartAccount,
+    pub parent_code: String,
+    pub account_type: AccountType,
+    pub rule: SubAccountRule,
+}
+
+/// Net balance (debit positive) of a chart account with its sub-accounts rolled in
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrialBalanceLine {
+    pub account_code: String,
+    pub account_name: String,
+    pub parent_code: Option<String>,
+    pub level: usize,
+    pub balance: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+/// Maps ledger account types to the codes and names used by an external accounting system
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChartOfAccounts {
+    accounts: HashMap<AccountType, ChartAccount>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sub_accounts: Vec<SubAccount>,
+}
+
+impl Default for ChartOfAccounts {
+    fn default() -> Self {
+        let mut accounts = HashMap::new();
+        accounts.insert(AccountType::Asset, ChartAccount::new("1500", "Intelligence Assets"));
+        accounts.insert(AccountType::AccumulatedDepreciation, ChartAccount::new("1590", "Accumulated Depreciation - Intelligence Assets"));
+        accounts.insert(AccountType::DepreciationExpense, ChartAccount::new("6400", "Depreciation Expense"));
+        accounts.insert(AccountType::InferenceExpense, ChartAccount::new("6500", "Inference Expense"));
+        accounts.insert(AccountType::AccountsPayable, ChartAccount::new("2000", "Accounts Payable"));
+        accounts.insert(AccountType::AccountsReceivable, ChartAccount::new("1200", "Accounts Receivable"));
+        accounts.insert(AccountType::Revenue, ChartAccount::new("4000", "Intelligence Asset Revenue"));
+        accounts.insert(AccountType::RoyaltyExpense, ChartAccount::new("6600", "Royalty and Cost-Sharing Expense"));
+        accounts.insert(AccountType::DisposalGainLoss, ChartAccount::new("7100", "Gain or Loss on Disposal of Intelligence Assets"));
+        accounts.insert(AccountType::RetainedEarnings, ChartAccount::new("3000", "Retained Earnings"));
+        Self { accounts, sub_accounts: Vec::new() }
+    }
+}
+
+impl ChartOfAccounts {
+    /// Chart with no mappings; every account type used must be mapped before exporting
+    pub fn empty() -> Self {
+        Self { accounts: HashMap::new(), sub_accounts: Vec::new() }
+    }
+
+    /// Map an account type; sub-accounts of an earlier mapping move under the new account
+    pub fn map(mut self, account_type: AccountType, account: ChartAccount) -> Self {
+        if let Some(previous) = self.accounts.get(&account_type) {
+            for sub in self.sub_accounts.iter_mut() {
+                if sub.account_type == account_type && sub.parent_code == previous.code {
+                    sub.parent_code = account.code.clone();
+                }
+            }
+        }
+        self.accounts.insert(account_type, account);
+        self
+    }
+
+    /// Add a sub-account under `parent_code`, a mapped account or an earlier sub-account, taking
+    /// the parent's postings for assets matching `rule`
+    pub fn sub_account(mut self, parent_code: &str, account: ChartAccount, rule: SubAccountRule) -> IclResult<Self> {
+        let mut parent_types = self.accounts.iter()
+            .filter(|(_, mapped)| mapped.code == parent_code)
+            .map(|(account_type, _)| *account_type)
+            .chain(self.sub_accounts.iter().filter(|s| s.account.code == parent_code).map(|s| s.account_type));
+        let account_type = match (parent_types.next(), parent_types.next()) {
+            (Some(account_type), None) => account_type,
+            (None, _) => return Err(IclError::IntegrationError(format!(
+                "No account {} to add sub-account {} under", parent_code, account.code
+            ))),
+            (Some(_), Some(_)) => return Err(IclError::IntegrationError(format!(
+                "Account code {} is mapped to several account types; sub-accounts need a single parent", parent_code
+            ))),
+        };
+        if self.account(&account.code).is_some() {
+            return Err(IclError::IntegrationError(format!("Account code {} is already in the chart", account.code)));
+        }
+        if self.sub_accounts.iter().any(|s| s.parent_code == parent_code && s.rule == rule) {
+            return Err(IclError::IntegrationError(format!(
+                "Account {} already has a sub-account for {}", parent_code, rule
+            )));
+        }
+        self.sub_accounts.push(SubAccount { account, parent_code: parent_code.to_string(), account_type, rule });
+        Ok(self)
+    }
+
+    pub fn get(&self, account_type: AccountType) -> Option<&ChartAccount> {
+        self.accounts.get(&account_type)
+    }
+
+    pub fn account(&self, code: &str) -> Option<&ChartAccount> {
+        self.accounts.values()
+            .chain(self.sub_accounts.iter().map(|s| &s.account))
+            .find(|a| a.code == code)
+    }
+
+    pub fn sub_accounts(&self) -> &[SubAccount] {
+        &self.sub_accounts
+    }
+
+    pub fn parent_code(&self, code: &str) -> Option<&str> {
+        self.sub_accounts.iter().find(|s| s.account.code == code).map(|s| s.parent_code.as_str())
+    }
+
+    pub fn level(&self, code: &str) -> usize {
+        std::iter::successors(self.parent_code(code), |parent| self.parent_code(parent)).count()
+    }
+
+    /// Account a posting to `account_type` lands in: the deepest sub-account whose rule, and
+    /// whose ancestors' rules, match the asset, or the mapped account
+    pub fn posting_account(&self, account_type: AccountType, asset: Option<&IntelligenceAsset>) -> Option<&ChartAccount> {
+        let mut account = self.get(account_type)?;
+        let Some(asset) = asset else {
+            return Some(account);
+        };
+        while let Some(sub) = self.sub_accounts.iter().find(|s| {
+            s.account_type == account_type && s.parent_code == account.code && s.rule.matches(asset)
+        }) {
+            account = &sub.account;
+        }
+        Some(account)
+    }
+
+    /// Balances of the accounts at `level` of the hierarchy, and of shallower accounts with no
+    /// sub-accounts, each including its sub-accounts, ordered by code
+    pub fn roll_up(&self, posted: &BTreeMap<String, f64>, level: usize) -> Vec<TrialBalanceLine> {
+        let mut rolled: BTreeMap<&str, f64> = BTreeMap::new();
+        for (code, balance) in posted {
+            *rolled.entry(code.as_str()).or_default() += balance;
+            for ancestor in std::iter::successors(self.parent_code(code), |parent| self.parent_code(parent)) {
+                *rolled.entry(ancestor).or_default() += balance;
+            }
+        }
+        rolled.into_iter()
+            .filter(|(code, _)| {
+                let depth = self.level(code);
+                depth == level || (depth < level && !self.sub_accounts.iter().any(|s| s.parent_code == *code))
+            })
+            .map(|(code, balance)| TrialBalanceLine {
+                account_code: code.to_string(),
+                account_name: self.account(code).map(|a| a.name.clone()).unwrap_or_else(|| code.to_string()),
+                parent_code: self.parent_code(code).map(str::to_string),
+                level: self.level(code),
+                balance,
+                group: None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn posted_balances<'e>(
+        &self,
+        posting_assets: &PostingAssets<'_>,
+        entries: impl IntoIterator<Item = &'e JournalEntry>,
+        archived: impl IntoIterator<Item = (AccountType, f64)>,
+    ) -> BTreeMap<String, f64> {
+        let code = |account_type: AccountType, asset: Option<&IntelligenceAsset>| {
+            self.posting_account(account_type, asset)
+                .map(|a| a.code.clone())
+                .unwrap_or_else(|| account_type.to_string())
+        };
+        let mut balances: BTreeMap<String, f64> = BTreeMap::new();
+        for (account_type, balance) in archived {
+            *balances.entry(code(account_type, None)).or_default() += balance;
+        }
+        for entry in entries {
+            let debit = posting_assets.journal_entry_posting_asset(entry, PostingSide::Debit);
+            let credit = posting_assets.journal_entry_posting_asset(entry, PostingSide::Credit);
+            *balances.entry(code(entry.debit_account, debit.as_deref())).or_default() += entry.amount;
+            *balances.entry(code(entry.credit_account, credit.as_deref())).or_default() -= entry.amount;
+        }
+        balances
+    }
+
+    fn resolve(&self, account_type: AccountType, asset: Option<&IntelligenceAsset>) -> IclResult<&ChartAccount> {
+        self.posting_account(account_type, asset).ok_or_else(|| {
+            IclError::IntegrationError(format!("No chart-of-accounts mapping for {}", account_type))
+        })
+    }
+}
+
+/// Value written into a column of a flat-file export
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FlatFileField {
+    DocumentNumber,
+    PostingDate,
+    LineNumber,
+    AccountCode,
+    AccountName,
+    DebitCredit,
+    Amount,
+    Currency,
+    Text,
+    EntryId,
+    EventId,
+    AssetId,
+    Dimension(String),
+    Constant(String),
+}
+
+/// One column of a flat-file record
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FlatFileColumn {
+    pub name: String,
+    pub field: FlatFileField,
+    pub max_width: Option<usize>,
+}
+
+impl FlatFileColumn {
+    pub fn new(name: impl Into<String>, field: FlatFileField) -> Self {
+        Self { name: name.into(), field, max_width: None }
+    }
+
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+}
+
+/// Record layout for delimited ERP batch files: each journal entry becomes one document
+/// header record followed by a debit line and a credit line
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FlatFileLayout {
+    pub delimiter: char,
+    pub include_column_names: bool,
+    pub header_record_type: String,
+    pub line_record_type: String,
+    pub header_columns: Vec<FlatFileColumn>,
+    pub line_columns: Vec<FlatFileColumn>,
+    pub date_format: String,
+    pub debit_indicator: String,
+    pub credit_indicator: String,
+}
+
+impl FlatFileLayout {
+    /// SAP FI document batch layout (BKPF header, BSEG lines with posting keys 40/50)
+    pub fn sap(company_code: &str) -> Self {
+        Self {
+            delimiter: ';',
+            include_column_names: false,
+            header_record_type: "H".into(),
+            line_record_type: "L".into(),
+            header_columns: vec![
+                FlatFileColumn::new("BUKRS", FlatFileField::Constant(company_code.into())),
+                FlatFileColumn::new("BLART", FlatFileField::Constant("SA".into())),
+                FlatFileColumn::new("BLDAT", FlatFileField::PostingDate),
+                FlatFileColumn::new("BUDAT", FlatFileField::PostingDate),
+                FlatFileColumn::new("WAERS", FlatFileField::Currency),
+                FlatFileColumn::new("XBLNR", FlatFileField::EntryId).max_width(16),
+                FlatFileColumn::new("BKTXT", FlatFileField::Text).max_width(25),
+            ],
+            line_columns: vec![
+                FlatFileColumn::new("BUZEI", FlatFileField::LineNumber),
+                FlatFileColumn::new("NEWBS", FlatFileField::DebitCredit),
+                FlatFileColumn::new("HKONT", FlatFileField::AccountCode),
+                FlatFileColumn::new("WRBTR", FlatFileField::Amount),
+                FlatFileColumn::new("SGTXT", FlatFileField::Text).max_width(50),
+            ],
+            date_format: "%Y%m%d".into(),
+            debit_indicator: "40".into(),
+            credit_indicator: "50".into(),
+        }
+    }
+}
+
+/// A journal entry whose account has no chart-of-accounts mapping
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UnmappedAccount {
+    pub entry_id: EntryId,
+    pub account_type: AccountType,
+}
+
+impl std::fmt::Display for UnmappedAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} in journal entry {}", self.account_type, self.entry_id)
+    }
+}
+
+struct AccountActivity<'e> {
+    account_type: AccountType,
+    account: ChartAccount,
+    opening_balance: f64,
+    lines: Vec<(&'e JournalEntry, f64)>,
+}
+
+impl AccountActivity<'_> {
+    fn closing_balance(&self) -> f64 {
+        self.opening_balance + self.lines.iter().map(|(_, amount)| amount).sum::<f64>()
+    }
+}
+
+/// Renders journal entries in the import formats of external accounting packages
+#[derive(Debug)]
+pub struct JournalExporter<'a> {
+    ledger: &'a IntelligenceCapitalLedger,
+    posting_assets: PostingAssets<'a>,
+    chart: ChartOfAccounts,
+    period: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    currency: String,
+    tracking: Vec<String>,
+}
+
+impl<'a> JournalExporter<'a> {
+    pub fn new(ledger: &'a IntelligenceCapitalLedger) -> Self {
+        Self {
+            ledger,
+            posting_assets: ledger.posting_assets(),
+            chart: ChartOfAccounts::default(),
+            period: None,
+            currency: "USD".into(),
+            tracking: Vec::new(),
+        }
+    }
+
+    pub fn with_chart(mut self, chart: ChartOfAccounts) -> Self {
+        self.chart = chart;
+        self
+    }
+
+    pub fn period(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.period = Some((start, end));
+        self
+    }
+
+    pub fn with_currency(mut self, currency: impl Into<String>) -> Self {
+        self.currency = currency.into();
+        self
+    }
+
+    /// Export a posting dimension as a tracking category, for formats that have them; Xero
+    /// takes up to two
+    pub fn with_tracking(mut self, dimension: impl Into<String>) -> Self {
+        self.tracking.push(dimension.into());
+        self
+    }
+
+    pub fn export_journal(&self, format: &str) -> IclResult<String> {
+        match format {
+            "quickbooks_iif" => self.export_quickbooks_iif(),
+            "xero_csv" => self.export_xero_csv(),
+            "camt053" => self.export_camt053(),
+            "ofx" => self.export_ofx(),
+            "sap_flat_file" => self.export_flat_file(&FlatFileLayout::sap("1000")),
+            _ => Err(IclError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    pub fn export_quickbooks_iif(&self) -> IclResult<String> {
+        let mut iif = String::from(
+            "!TRNS\tTRNSID\tTRNSTYPE\tDATE\tACCNT\tAMOUNT\tDOCNUM\tMEMO\n\
+             !SPL\tSPLID\tTRNSTYPE\tDATE\tACCNT\tAMOUNT\tDOCNUM\tMEMO\n\
+             !ENDTRNS\n",
+        );
+        for entry in self.journal_entries() {
+            let debit = self.chart.resolve(
+                entry.debit_account,
+                self.posting_assets.journal_entry_posting_asset(entry, PostingSide::Debit).as_deref(),
+            )?;
+            let credit = self.chart.resolve(
+                entry.credit_account,
+                self.posting_assets.journal_entry_posting_asset(entry, PostingSide::Credit).as_deref(),
+            )?;
+            let date = entry.effective_date().format("%m/%d/%Y");
+            let docnum = &entry.entry_id.as_uuid().simple().to_string()[..8];
+            let memo = iif_field(&entry.description);
+            iif.push_str(&format!(
+                "TRNS\t\tGENERAL JOURNAL\t{}\t{}\t{:.2}\t{}\t{}\n",
+                date, iif_field(&debit.name), entry.amount, docnum, memo
+            ));
+            iif.push_str(&format!(
+                "SPL\t\tGENERAL JOURNAL\t{}\t{}\t{:.2}\t{}\t{}\n",
+                date, iif_field(&credit.name), -entry.amount, docnum, memo
+            ));
+            iif.push_str("ENDTRNS\n");
+        }
+        Ok(iif)
+    }
+
+    /// Xero manual journal import CSV: debit line positive, credit line negative, accounts by
+    /// code, with a tracking name and option column pair per tracked dimension
+    pub fn export_xero_csv(&self) -> IclResult<String> {
+        if self.tracking.len() > 2 {
+            return Err(IclError::IntegrationError(format!(
+                "Xero journals take at most two tracking categories, not {}", self.tracking.len()
+            )));
+        }
+        let mut csv = String::from("*Narration,*Date,Description,*AccountCode,*TaxRate,*Amount");
+        for n in 1..=self.tracking.len() {
+            csv.push_str(&format!(",TrackingName{},TrackingOption{}", n, n));
+        }
+        csv.push('\n');
+        for entry in self.journal_entries() {
+            let tracking: String = self.tracking.iter()
+                .map(|name| format!(
+                    ",{},{}",
+                    csv_field(name),
+                    csv_field(entry.dimensions.get(name).map_or("", String::as_str))
+                ))
+                .collect();
+            let debit = self.chart.resolve(
+                entry.debit_account,
+                self.posting_assets.journal_entry_posting_asset(entry, PostingSide::Debit).as_deref(),
+            )?;
+            let credit = self.chart.resolve(
+                entry.credit_account,
+                self.posting_assets.journal_entry_posting_asset(entry, PostingSide::Credit).as_deref(),
+            )?;
+            let narration = csv_field(&format!("{} [{}]", entry.description, entry.entry_id));
+            let date = entry.effective_date().format("%Y-%m-%d");
+            for (account, amount) in [(debit, entry.amount), (credit, -entry.amount)] {
+                csv.push_str(&format!(
+                    "{},{},{},{},Tax Exempt,{:.2}{}\n",
+                    narration, date, csv_field(&entry.description), csv_field(&account.code), amount, tracking
+                ));
+            }
+        }
+        Ok(csv)
+    }
+
+    pub fn export_camt053(&self) -> IclResult<String> {
+        let (start, end) = self.statement_period();
+        let now = self.ledger.now();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<Document xmlns=\"urn:iso:std:iso:20022:tech:xsd:camt.053.001.08\">\n");
+        xml.push_str("  <BkToCstmrStmt>\n");
+        xml.push_str(&format!(
+            "    <GrpHdr>\n      <MsgId>ICL-{}</MsgId>\n      <CreDtTm>{}</CreDtTm>\n    </GrpHdr>\n",
+            now.format("%Y%m%d%H%M%S"), now.to_rfc3339()
+        ));
+
+        for activity in self.account_activity()? {
+            xml.push_str("    <Stmt>\n");
+            xml.push_str(&format!(
+                "      <Id>{}-{}</Id>\n      <CreDtTm>{}</CreDtTm>\n",
+                xml_escape(&activity.account.code), start.format("%Y%m%d"), now.to_rfc3339()
+            ));
+            xml.push_str(&format!(
+                "      <FrToDt>\n        <FrDtTm>{}</FrDtTm>\n        <ToDtTm>{}</ToDtTm>\n      </FrToDt>\n",
+                start.to_rfc3339(), end.to_rfc3339()
+            ));
+            xml.push_str(&format!(
+                "      <Acct>\n        <Id>\n          <Othr>\n            <Id>{}</Id>\n          </Othr>\n        </Id>\n        <Ccy>{}</Ccy>\n        <Nm>{}</Nm>\n      </Acct>\n",
+                xml_escape(&activity.account.code), xml_escape(&self.currency), xml_escape(&activity.account.name)
+            ));
+            xml.push_str(&self.camt_balance("OPBD", activity.opening_balance, start));
+            xml.push_str(&self.camt_balance("CLBD", activity.closing_balance(), end));
+            for (entry, amount) in &activity.lines {
+                xml.push_str("      <Ntry>\n");
+                xml.push_str(&format!(
+                    "        <Amt Ccy=\"{}\">{:.2}</Amt>\n        <CdtDbtInd>{}</CdtDbtInd>\n        <Sts>\n          <Cd>BOOK</Cd>\n        </Sts>\n",
+                    xml_escape(&self.currency), amount.abs(), camt_indicator(*amount)
+                ));
+                xml.push_str(&format!(
+                    "        <BookgDt>\n          <Dt>{}</Dt>\n        </BookgDt>\n        <ValDt>\n          <Dt>{}</Dt>\n        </ValDt>\n",
+                    entry.recorded_at().format("%Y-%m-%d"), entry.effective_date().format("%Y-%m-%d")
+                ));
+                xml.push_str(&format!("        <AcctSvcrRef>{}</AcctSvcrRef>\n", entry.entry_id));
+                xml.push_str("        <BkTxCd>\n          <Prtry>\n");
+                xml.push_str(&format!("            <Cd>{}</Cd>\n", activity.account_type));
+                xml.push_str("          </Prtry>\n        </BkTxCd>\n");
+                xml.push_str(&format!(
+                    "        <NtryDtls>\n          <TxDtls>\n            <Refs>\n              <EndToEndId>{}</EndToEndId>\n            </Refs>\n            <RmtInf>\n              <Ustrd>{}</Ustrd>\n            </RmtInf>\n          </TxDtls>\n        </NtryDtls>\n",
+                    entry.event_id, xml_escape(&entry.description)
+                ));
+                xml.push_str("      </Ntry>\n");
+            }
+            xml.push_str("    </Stmt>\n");
+        }
+
+        xml.push_str("  </BkToCstmrStmt>\n</Document>\n");
+        Ok(xml)
+    }
+
+    pub fn export_ofx(&self) -> IclResult<String> {
+        let (start, end) = self.statement_period();
+        let now = self.ledger.now();
+        let mut ofx = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        ofx.push_str("<?OFX OFXHEADER=\"200\" VERSION=\"220\" SECURITY=\"NONE\" OLDFILEUID=\"NONE\" NEWFILEUID=\"NONE\"?>\n");
+        ofx.push_str("<OFX>\n");
+        ofx.push_str(&format!(
+            "  <SIGNONMSGSRSV1>\n    <SONRS>\n      <STATUS>\n        <CODE>0</CODE>\n        <SEVERITY>INFO</SEVERITY>\n      </STATUS>\n      <DTSERVER>{}</DTSERVER>\n      <LANGUAGE>ENG</LANGUAGE>\n    </SONRS>\n  </SIGNONMSGSRSV1>\n",
+            ofx_datetime(now)
+        ));
+        ofx.push_str("  <BANKMSGSRSV1>\n");
+
+        for activity in self.account_activity()? {
+            ofx.push_str("    <STMTTRNRS>\n");
+            ofx.push_str(&format!(
+                "      <TRNUID>{}</TRNUID>\n      <STATUS>\n        <CODE>0</CODE>\n        <SEVERITY>INFO</SEVERITY>\n      </STATUS>\n",
+                xml_escape(&activity.account.code)
+            ));
+            ofx.push_str("      <STMTRS>\n");
+            ofx.push_str(&format!("        <CURDEF>{}</CURDEF>\n", xml_escape(&self.currency)));
+            ofx.push_str(&format!(
+                "        <BANKACCTFROM>\n          <BANKID>ICL</BANKID>\n          <ACCTID>{}</ACCTID>\n          <ACCTTYPE>CHECKING</ACCTTYPE>\n        </BANKACCTFROM>\n",
+                xml_escape(&activity.account.code)
+            ));
+            ofx.push_str(&format!(
+                "        <BANKTRANLIST>\n          <DTSTART>{}</DTSTART>\n          <DTEND>{}</DTEND>\n",
+                ofx_datetime(start), ofx_datetime(end)
+            ));
+            for (entry, amount) in &activity.lines {
+                ofx.push_str(&format!(
+                    "          <STMTTRN>\n            <TRNTYPE>{}</TRNTYPE>\n            <DTPOSTED>{}</DTPOSTED>\n            <TRNAMT>{:.2}</TRNAMT>\n            <FITID>{}</FITID>\n            <NAME>{}</NAME>\n            <MEMO>{}</MEMO>\n          </STMTTRN>\n",
+                    if *amount >= 0.0 { "DEBIT" } else { "CREDIT" },
+                    ofx_datetime(entry.effective_date()),
+                    amount,
+                    entry.entry_id,
+                    xml_escape(&activity.account.name),
+                    xml_escape(&entry.description)
+                ));
+            }
+            ofx.push_str("        </BANKTRANLIST>\n");
+            ofx.push_str(&format!(
+                "        <LEDGERBAL>\n          <BALAMT>{:.2}</BALAMT>\n          <DTASOF>{}</DTASOF>\n        </LEDGERBAL>\n",
+                activity.closing_balance(), ofx_datetime(end)
+            ));
+            ofx.push_str("      </STMTRS>\n    </STMTTRNRS>\n");
+        }
+
+        ofx.push_str("  </BANKMSGSRSV1>\n</OFX>\n");
+        Ok(ofx)
+    }
+
+    pub fn unmapped_accounts(&self) -> Vec<UnmappedAccount> {
+        self.journal_entries()
+            .flat_map(|entry| [entry.debit_account, entry.credit_account].map(|account_type| (entry.entry_id, account_type)))
+            .filter(|(_, account_type)| self.chart.get(*account_type).is_none())
+            .map(|(entry_id, account_type)| UnmappedAccount { entry_id, account_type })
+            .collect()
+    }
+
+    /// Delimited ERP batch file in the given layout
+    pub fn export_flat_file(&self, layout: &FlatFileLayout) -> IclResult<String> {
+        let unmapped = self.unmapped_accounts();
+        if !unmapped.is_empty() {
+            return Err(IclError::IntegrationError(format!(
+                "Unmapped accounts: {}",
+                unmapped.iter().map(|u| u.to_string()).collect::<Vec<_>>().join("; ")
+            )));
+        }
+
+        let delimiter = layout.delimiter.to_string();
+        let mut out = String::new();
+        if layout.include_column_names {
+            for (record_type, columns) in [(&layout.header_record_type, &layout.header_columns), (&layout.line_record_type, &layout.line_columns)] {
+                let mut row = vec![record_type.clone()];
+                row.extend(columns.iter().map(|c| c.name.clone()));
+                out.push_str(&row.join(&delimiter));
+                out.push('\n');
+            }
+        }
+
+        for (index, entry) in self.journal_entries().enumerate() {
+            let document_number = index + 1;
+            let header = self.flat_file_record(layout, &layout.header_record_type, &layout.header_columns, entry, document_number, None)?;
+            out.push_str(&header);
+            for (line_number, (side, account_type, indicator)) in [
+                (PostingSide::Debit, entry.debit_account, &layout.debit_indicator),
+                (PostingSide::Credit, entry.credit_account, &layout.credit_indicator),
+            ].into_iter().enumerate() {
+                let line = self.flat_file_record(
+                    layout, &layout.line_record_type, &layout.line_columns, entry, document_number,
+                    Some((line_number + 1, side, account_type, indicator)),
+                )?;
+                out.push_str(&line);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Validate and write a flat-file export; nothing is written if any account is unmapped
+    pub fn write_flat_file(&self, layout: &FlatFileLayout, path: impl AsRef<Path>) -> IclResult<usize> {
+        let contents = self.export_flat_file(layout)?;
+        std::fs::write(path, contents)?;
+        Ok(self.journal_entries().count())
+    }
+
+    fn flat_file_record(
+        &self,
+        layout: &FlatFileLayout,
+        record_type: &str,
+        columns: &[FlatFileColumn],
+        entry: &JournalEntry,
+        document_number: usize,
+        line: Option<(usize, PostingSide, AccountType, &String)>,
+    ) -> IclResult<String> {
+        let mut values = vec![record_type.to_string()];
+        for column in columns {
+            let value = match &column.field {
+                FlatFileField::DocumentNumber => document_number.to_string(),
+                FlatFileField::PostingDate => entry.effective_date().format(&layout.date_format).to_string(),
+                FlatFileField::Currency => self.currency.clone(),
+                FlatFileField::Text => entry.description.clone(),
+                FlatFileField::EntryId => entry.entry_id.as_uuid().simple().to_string(),
+                FlatFileField::EventId => entry.event_id.as_uuid().simple().to_string(),
+                FlatFileField::AssetId => entry.metadata.get("asset_id")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                FlatFileField::Dimension(name) => entry.dimensions.get(name).cloned().unwrap_or_default(),
+                FlatFileField::Amount => format!("{:.2}", entry.amount),
+                FlatFileField::Constant(value) => value.clone(),
+                FlatFileField::LineNumber => line.map(|(n, _, _, _)| n.to_string()).unwrap_or_default(),
+                FlatFileField::DebitCredit => line.map(|(_, _, _, indicator)| indicator.clone()).unwrap_or_default(),
+                FlatFileField::AccountCode => match line {
+                    Some((_, side, account_type, _)) => {
+                        let asset = self.posting_assets.journal_entry_posting_asset(entry, side);
+                        self.chart.resolve(account_type, asset.as_deref())?.code.clone()
+                    },
+                    None => String::new(),
+                },
+                FlatFileField::AccountName => match line {
+                    Some((_, side, account_type, _)) => {
+                        let asset = self.posting_assets.journal_entry_posting_asset(entry, side);
+                        self.chart.resolve(account_type, asset.as_deref())?.name.clone()
+                    },
+                    None => String::new(),
+                },
+            };
+            let mut value = value.replace([layout.delimiter, '\r', '\n'], " ");
+            if let Some(max_width) = column.max_width {
+                value = value.chars().take(max_width).collect();
+            }
+            values.push(value);
+        }
+        let mut record = values.join(&layout.delimiter.to_string());
+        record.push('\n');
+        Ok(record)
+    }
+
+    fn camt_balance(&self, code: &str, balance: f64, at: DateTime<Utc>) -> String {
+        format!(
+            "      <Bal>\n        <Tp>\n          <CdOrPrtry>\n            <Cd>{}</Cd>\n          </CdOrPrtry>\n        </Tp>\n        <Amt Ccy=\"{}\">{:.2}</Amt>\n        <CdtDbtInd>{}</CdtDbtInd>\n        <Dt>\n          <DtTm>{}</DtTm>\n        </Dt>\n      </Bal>\n",
+            code, xml_escape(&self.currency), balance.abs(), camt_indicator(balance), at.to_rfc3339()
+        )
+    }
+
+    fn statement_period(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        if let Some(period) = self.period {
+            return period;
+        }
+        let start = self.journal_entries().map(|j| j.effective_date()).min().unwrap_or_else(|| self.ledger.now());
+        let end = self.journal_entries().map(|j| j.effective_date()).max().unwrap_or(start);
+        (start, end)
+    }
+
+    fn account_activity(&self) -> IclResult<Vec<AccountActivity<'a>>> {
+        let mut activity = Vec::new();
+        for account_type in AccountType::ALL {
+            let mut opening_balance = 0.0;
+            let mut lines = Vec::new();
+            for entry in self.ledger.journal_entries().iter().filter(|j| !self.ledger.is_quarantined(j.entry_id)) {
+                let amount = if entry.debit_account == account_type {
+                    entry.amount
+                } else if entry.credit_account == account_type {
+                    -entry.amount
+                } else {
+                    continue;
+                };
+                match self.period {
+                    Some((start, _)) if entry.effective_date() < start => opening_balance += amount,
+                    Some((_, end)) if entry.effective_date() >= end => {},
+                    _ => lines.push((entry, amount)),
+                }
+            }
+            if lines.is_empty() && opening_balance == 0.0 {
+                continue;
+            }
+            activity.push(AccountActivity {
+                account_type,
+                account: self.chart.resolve(account_type, None)?.clone(),
+                opening_balance,
+                lines,
+            });
+        }
+        Ok(activity)
+    }
+
+    fn journal_entries(&self) -> impl Iterator<Item = &'a JournalEntry> + '_ {
+        self.ledger.journal_entries().iter()
+            .filter(|j| !self.ledger.is_quarantined(j.entry_id))
+            .filter(|j| match self.period {
+                Some((start, end)) => j.effective_date() >= start && j.effective_date() < end,
+                None => true,
+            })
+    }
+}
+
+fn camt_indicator(amount: f64) -> &'static str {
+    if amount >= 0.0 { "DBIT" } else { "CRDT" }
+}
+
+fn ofx_datetime(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%d%H%M%S").to_string()
+}
+
+fn xml_escape(value: &str) -> String {
+    value.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn iif_field(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+    use crate::core::reports::ReportGenerator;
+
+    #[test]
+    fn test_sub_accounts_roll_up() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let model = AssetId::random();
+        let dataset = AssetId::random();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .capitalize(model, "Research", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .of_kind(AssetKind::Dataset)
+            .capitalize(dataset, "Data", 800.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+
+        let chart = ChartOfAccounts::default()
+            .sub_account("1500", ChartAccount::new("1510", "Models"), SubAccountRule::Kind(AssetKind::Model)).unwrap()
+            .sub_account("1500", ChartAccount::new("1520", "Datasets"), SubAccountRule::Kind(AssetKind::Dataset)).unwrap();
+        assert!(chart.clone().sub_account("1500", ChartAccount::new("1530", "More models"), SubAccountRule::Kind(AssetKind::Model)).is_err());
+        assert!(chart.clone().sub_account("9999", ChartAccount::new("1530", "Orphan"), SubAccountRule::Kind(AssetKind::Model)).is_err());
+
+        let generator = ReportGenerator::new(&ledger);
+        let top = generator.trial_balance(&chart, ledger.now(), 0).unwrap();
+        let asset_line = top.lines.iter().find(|l| l.account_code == "1500").unwrap();
+        assert!((asset_line.balance - 2000.0).abs() < 1e-9);
+        assert!((top.total_debits - top.total_credits).abs() < 1e-9);
+
+        let detail = generator.trial_balance(&chart, ledger.now(), 1).unwrap();
+        let balance = |code: &str| detail.lines.iter().find(|l| l.account_code == code).map(|l| l.balance);
+        assert_eq!(balance("1500"), None);
+        assert_eq!(balance("1510"), Some(1200.0));
+        assert_eq!(balance("1520"), Some(800.0));
+        assert_eq!(balance("1590"), Some(-2000.0));
+    }
+
+    #[test]
+    fn test_reclassification_moves_balances() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .in_category("model")
+            .capitalize(asset_id, "Research", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        let chart = ChartOfAccounts::default()
+            .sub_account("1500", ChartAccount::new("1510", "Models"), SubAccountRule::Category("model".into())).unwrap()
+            .sub_account("1500", ChartAccount::new("1520", "Datasets"), SubAccountRule::Category("dataset".into())).unwrap();
+
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.reclassify(asset_id, "dataset").unwrap();
+        assert!(lifecycle.reclassify(asset_id, "dataset").is_err());
+        assert!(ledger.assert_invariants().is_ok());
+
+        let detail = ReportGenerator::new(&ledger).trial_balance(&chart, ledger.now(), 1).unwrap();
+        let balance = |code: &str| detail.lines.iter().find(|l| l.account_code == code).map(|l| l.balance);
+        assert_eq!(balance("1510"), Some(0.0));
+        assert_eq!(balance("1520"), Some(1200.0));
+        assert!((detail.total_debits - detail.total_credits).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,499 @@
+//! C ABI over the ledger; the header is `include/icl.h`.
+//!
+//! Every function returns an `IclStatus`; on failure `icl_last_error` describes the error on the
+//! calling thread. String arguments are NUL-terminated UTF-8 and may be NULL only where noted.
+//! Strings returned through `out` pointers are owned by the caller and released with
+//! `icl_string_free`; ledgers are released with `icl_ledger_free`. Timestamps are Unix seconds.
+#![allow(unsafe_code)]
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use chrono::{DateTime, Utc};
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+use crate::core::integrity::IntegrityChecker;
+use crate::core::reports::{ReportExport, ReportGenerator};
+use crate::core::error::*;
+
+/// Result code of every FFI call; values are stable across releases
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IclStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    InvalidArgument = 3,
+    NotFound = 4,
+    AlreadyExists = 5,
+    AssetRetired = 6,
+    IntegrityViolation = 7,
+    SerializationError = 8,
+    StorageError = 9,
+    UnsupportedFormat = 10,
+    Panic = 11,
+    /// Any other ledger error
+    Error = 12,
+    /// Refused by the ledger's authorizer
+    PermissionDenied = 13,
+}
+
+impl From<&IclError> for IclStatus {
+    fn from(e: &IclError) -> Self {
+        match e.root() {
+            IclError::AssetNotFound(_)
+            | IclError::OwnerNotFound(_)
+            | IclError::EventNotFound(_) => IclStatus::NotFound,
+            IclError::AssetAlreadyExists(_)
+            | IclError::OwnerAlreadyExists(_)
+            | IclError::DuplicateEvent(_)
+            | IclError::DuplicateEntry(_) => IclStatus::AlreadyExists,
+            IclError::AssetRetired(_) => IclStatus::AssetRetired,
+            IclError::IntegrityViolation(_) => IclStatus::IntegrityViolation,
+            IclError::InvalidAsset(_)
+            | IclError::InvalidEvent(_)
+            | IclError::InvalidEntry(_)
+            | IclError::InvalidDateRange { .. } => IclStatus::InvalidArgument,
+            IclError::SerializationError(_) => IclStatus::SerializationError,
+            IclError::StorageError(_) => IclStatus::StorageError,
+            IclError::UnsupportedFormat(_) => IclStatus::UnsupportedFormat,
+            IclError::Unauthorized(_) => IclStatus::PermissionDenied,
+            IclError::DepreciationError(_)
+            | IclError::OverlappingDepreciation
+            | IclError::IntegrationError(_)
+            | IclError::HashingError(_)
+            | IclError::Context { .. } => IclStatus::Error,
+        }
+    }
+}
+
+/// Values of the depreciation method argument of `icl_capitalize`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IclDepreciationMethod {
+    Linear = 0,
+    DecliningBalance = 1,
+}
+
+impl TryFrom<u32> for IclDepreciationMethod {
+    /// The value that names no method
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, u32> {
+        match value {
+            0 => Ok(IclDepreciationMethod::Linear),
+            1 => Ok(IclDepreciationMethod::DecliningBalance),
+            other => Err(other),
+        }
+    }
+}
+
+impl From<IclDepreciationMethod> for DepreciationMethod {
+    fn from(method: IclDepreciationMethod) -> Self {
+        match method {
+            IclDepreciationMethod::Linear => DepreciationMethod::Linear,
+            IclDepreciationMethod::DecliningBalance => DepreciationMethod::DecliningBalance,
+        }
+    }
+}
+
+/// Opaque ledger handle
+pub struct IclLedger {
+    ledger: IntelligenceCapitalLedger,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Failure of an FFI call: the status returned and the message kept for `icl_last_error`
+struct FfiError(IclStatus, String);
+
+impl From<IclError> for FfiError {
+    fn from(e: IclError) -> Self {
+        FfiError(IclStatus::from(&e), e.to_string())
+    }
+}
+
+type FfiResult<T> = Result<T, FfiError>;
+
+/// Run `body`, recording any error or panic for `icl_last_error`
+fn guard(body: impl FnOnce() -> FfiResult<()>) -> IclStatus {
+    let (status, message) = match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => (IclStatus::Ok, None),
+        Ok(Err(FfiError(status, message))) => (status, Some(message)),
+        Err(_) => (IclStatus::Panic, Some("panic inside icl".to_string())),
+    };
+    LAST_ERROR.with(|last| {
+        *last.borrow_mut() = message.map(|m| CString::new(m.replace('\0', " ")).unwrap_or_default());
+    });
+    status
+}
+
+unsafe fn ledger_ref<'a>(ledger: *const IclLedger) -> FfiResult<&'a IntelligenceCapitalLedger> {
+    ledger.as_ref()
+        .map(|handle| &handle.ledger)
+        .ok_or_else(|| FfiError(IclStatus::NullPointer, "ledger is NULL".into()))
+}
+
+unsafe fn ledger_mut<'a>(ledger: *mut IclLedger) -> FfiResult<&'a mut IntelligenceCapitalLedger> {
+    ledger.as_mut()
+        .map(|handle| &mut handle.ledger)
+        .ok_or_else(|| FfiError(IclStatus::NullPointer, "ledger is NULL".into()))
+}
+
+unsafe fn optional_str<'a>(value: *const c_char, name: &str) -> FfiResult<Option<&'a str>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(value).to_str()
+        .map(Some)
+        .map_err(|_| FfiError(IclStatus::InvalidUtf8, format!("{} is not valid UTF-8", name)))
+}
+
+unsafe fn required_str<'a>(value: *const c_char, name: &str) -> FfiResult<&'a str> {
+    optional_str(value, name)?.ok_or_else(|| FfiError(IclStatus::NullPointer, format!("{} is NULL", name)))
+}
+
+fn parse_id<T: std::str::FromStr<Err = uuid::Error>>(value: &str, name: &str) -> FfiResult<T> {
+    value.parse().map_err(|e| FfiError(IclStatus::InvalidArgument, format!("{}: {}", name, e)))
+}
+
+fn timestamp(seconds: i64, name: &str) -> FfiResult<DateTime<Utc>> {
+    DateTime::from_timestamp(seconds, 0)
+        .ok_or_else(|| FfiError(IclStatus::InvalidArgument, format!("{} is out of range", name)))
+}
+
+/// Hand `value` to the caller through `out`, which must not be NULL
+unsafe fn write_string(out: *mut *mut c_char, value: String) -> FfiResult<()> {
+    if out.is_null() {
+        return Err(FfiError(IclStatus::NullPointer, "out is NULL".into()));
+    }
+    let value = CString::new(value)
+        .map_err(|_| FfiError(IclStatus::SerializationError, "output contains a NUL byte".into()))?;
+    *out = value.into_raw();
+    Ok(())
+}
+
+unsafe fn write_json<T: serde::Serialize>(out: *mut *mut c_char, value: &T) -> FfiResult<()> {
+    write_string(out, serde_json::to_string(value).map_err(IclError::from)?)
+}
+
+/// Message of the last failed call on this thread, or NULL; valid until the next call
+#[no_mangle]
+pub extern "C" fn icl_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr()))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icl_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+/// New, empty ledger
+#[no_mangle]
+pub extern "C" fn icl_ledger_new() -> *mut IclLedger {
+    Box::into_raw(Box::new(IclLedger { ledger: IntelligenceCapitalLedger::new() }))
+}
+
+/// Restore a ledger saved with `icl_ledger_to_json`
+#[no_mangle]
+pub unsafe extern "C" fn icl_ledger_from_json(json: *const c_char, out: *mut *mut IclLedger) -> IclStatus {
+    guard(|| {
+        if out.is_null() {
+            return Err(FfiError(IclStatus::NullPointer, "out is NULL".into()));
+        }
+        let ledger: IntelligenceCapitalLedger = serde_json::from_str(required_str(json, "json")?).map_err(IclError::from)?;
+        *out = Box::into_raw(Box::new(IclLedger { ledger }));
+        Ok(())
+    })
+}
+
+/// Serialize the whole ledger for persistence
+#[no_mangle]
+pub unsafe extern "C" fn icl_ledger_to_json(ledger: *const IclLedger, out: *mut *mut c_char) -> IclStatus {
+    guard(|| write_json(out, ledger_ref(ledger)?))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn icl_ledger_free(ledger: *mut IclLedger) {
+    if !ledger.is_null() {
+        drop(Box::from_raw(ledger));
+    }
+}
+
+/// Capitalize an asset; `asset_id` may be NULL to generate one. Writes the asset as JSON to `out`.
+/// `depreciation_method` is an `IclDepreciationMethod` value; any other value is `InvalidArgument`.
+#[no_mangle]
+pub unsafe extern "C" fn icl_capitalize(
+    ledger: *mut IclLedger,
+    asset_id: *const c_char,
+    owner: *const c_char,
+    initial_value: f64,
+    depreciation_method: u32,
+    useful_life_months: i32,
+    out: *mut *mut c_char
+) -> IclStatus {
+    guard(|| {
+        let depreciation_method = IclDepreciationMethod::try_from(depreciation_method).map_err(|value| {
+            FfiError(IclStatus::InvalidArgument, format!("depreciation_method: unknown method {}", value))
+        })?;
+        let ledger = ledger_mut(ledger)?;
+        let asset_id = match optional_str(asset_id, "asset_id")? {
+            Some(id) => parse_id(id, "asset_id")?,
+            None => ledger.next_id().into(),
+        };
+        let owner = required_str(owner, "owner")?.to_string();
+        let asset = IntelligenceCapitalLifecycle::new(ledger)
+            .capitalize(asset_id, owner, initial_value, depreciation_method.into(), useful_life_months)?;
+        write_json(out, &asset)
+    })
+}
+
+/// Transfer an asset to `target_owner`; writes the event as JSON to `out`
+#[no_mangle]
+pub unsafe extern "C" fn icl_allocate(
+    ledger: *mut IclLedger,
+    asset_id: *const c_char,
+    target_owner: *const c_char,
+    out: *mut *mut c_char
+) -> IclStatus {
+    guard(|| {
+        let asset_id = parse_id(required_str(asset_id, "asset_id")?, "asset_id")?;
+        let target_owner = required_str(target_owner, "target_owner")?.to_string();
+        let event = IntelligenceCapitalLifecycle::new(ledger_mut(ledger)?).allocate(asset_id, target_owner)?;
+        write_json(out, &event)
+    })
+}
+
+/// Record utilization of an asset; writes the event as JSON to `out`
+#[no_mangle]
+pub unsafe extern "C" fn icl_utilize(
+    ledger: *mut IclLedger,
+    asset_id: *const c_char,
+    amount: f64,
+    out: *mut *mut c_char
+) -> IclStatus {
+    guard(|| {
+        let asset_id = parse_id(required_str(asset_id, "asset_id")?, "asset_id")?;
+        let event = IntelligenceCapitalLifecycle::new(ledger_mut(ledger)?).utilize(asset_id, amount)?;
+        write_json(out, &event)
+    })
+}
+
+/// Depreciate an asset over `[start, end)`; writes the event as JSON to `out`
+#[no_mangle]
+pub unsafe extern "C" fn icl_depreciate(
+    ledger: *mut IclLedger,
+    asset_id: *const c_char,
+    start: i64,
+    end: i64,
+    salvage_value: f64,
+    rate_multiplier: f64,
+    out: *mut *mut c_char
+) -> IclStatus {
+    guard(|| {
+        let asset_id = parse_id(required_str(asset_id, "asset_id")?, "asset_id")?;
+        let (start, end) = (timestamp(start, "start")?, timestamp(end, "end")?);
+        let event = IntelligenceCapitalLifecycle::new(ledger_mut(ledger)?)
+            .depreciate(asset_id, start, end, salvage_value, rate_multiplier)?;
+        write_json(out, &event)
+    })
+}
+
+/// Retire an asset; writes the event as JSON to `out`
+#[no_mangle]
+pub unsafe extern "C" fn icl_retire(ledger: *mut IclLedger, asset_id: *const c_char, out: *mut *mut c_char) -> IclStatus {
+    guard(|| {
+        let asset_id = parse_id(required_str(asset_id, "asset_id")?, "asset_id")?;
+        let event = IntelligenceCapitalLifecycle::new(ledger_mut(ledger)?).retire(asset_id)?;
+        write_json(out, &event)
+    })
+}
+
+/// Asset as JSON
+#[no_mangle]
+pub unsafe extern "C" fn icl_get_asset(ledger: *const IclLedger, asset_id: *const c_char, out: *mut *mut c_char) -> IclStatus {
+    guard(|| {
+        let asset_id = parse_id(required_str(asset_id, "asset_id")?, "asset_id")?;
+        let asset = ledger_ref(ledger)?.get_asset(asset_id).ok_or(IclError::AssetNotFound(asset_id))?;
+        write_json(out, asset)
+    })
+}
+
+/// Events of an asset as a JSON array, oldest first
+#[no_mangle]
+pub unsafe extern "C" fn icl_get_events_for_asset(
+    ledger: *const IclLedger,
+    asset_id: *const c_char,
+    out: *mut *mut c_char
+) -> IclStatus {
+    guard(|| {
+        let asset_id = parse_id(required_str(asset_id, "asset_id")?, "asset_id")?;
+        write_json(out, &ledger_ref(ledger)?.get_events_for_asset(asset_id))
+    })
+}
+
+/// Fixed-asset register as of `as_of` in `format` (`json` or `csv`)
+#[no_mangle]
+pub unsafe extern "C" fn icl_asset_register(
+    ledger: *const IclLedger,
+    as_of: i64,
+    format: *const c_char,
+    out: *mut *mut c_char
+) -> IclStatus {
+    guard(|| {
+        let register = ReportGenerator::new(ledger_ref(ledger)?).asset_register(timestamp(as_of, "as_of")?);
+        write_string(out, register.export(required_str(format, "format")?)?)
+    })
+}
+
+/// Audit trail in `format` (`json` or `csv`)
+#[no_mangle]
+pub unsafe extern "C" fn icl_export_audit_trail(
+    ledger: *const IclLedger,
+    format: *const c_char,
+    out: *mut *mut c_char
+) -> IclStatus {
+    guard(|| write_string(out, ledger_ref(ledger)?.export_audit_trail(required_str(format, "format")?)?))
+}
+
+/// Run every integrity check, writing the report as JSON to `out`. Fails with the
+/// integrity-violation status, after writing the report, when it contains errors.
+#[no_mangle]
+pub unsafe extern "C" fn icl_verify(ledger: *const IclLedger, out: *mut *mut c_char) -> IclStatus {
+    guard(|| {
+        let report = IntegrityChecker::new(ledger_ref(ledger)?).check_all_integrity();
+        write_json(out, &report)?;
+        let first_error = report.errors().next().map(|finding| finding.to_string());
+        match first_error {
+            Some(message) => Err(FfiError(IclStatus::IntegrityViolation, message)),
+            None => Ok(()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Copy out a string returned by the library and release it
+    unsafe fn take_string(value: *mut c_char) -> String {
+        assert!(!value.is_null());
+        let copy = CStr::from_ptr(value).to_str().unwrap().to_string();
+        icl_string_free(value);
+        copy
+    }
+
+    unsafe fn last_error() -> String {
+        let message = icl_last_error();
+        assert!(!message.is_null());
+        CStr::from_ptr(message).to_str().unwrap().to_string()
+    }
+
+    unsafe fn capitalize(ledger: *mut IclLedger, owner: &CStr) -> String {
+        let mut out = ptr::null_mut();
+        let status = icl_capitalize(ledger, ptr::null(), owner.as_ptr(), 1200.0, IclDepreciationMethod::Linear as u32, 12, &mut out);
+        assert_eq!(status, IclStatus::Ok);
+        let asset: serde_json::Value = serde_json::from_str(&take_string(out)).unwrap();
+        asset["asset_id"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_ledger_round_trip_through_c_abi() {
+        unsafe {
+            let ledger = icl_ledger_new();
+            let asset_id = CString::new(capitalize(ledger, c"Team A")).unwrap();
+            assert!(icl_last_error().is_null());
+
+            let mut out = ptr::null_mut();
+            assert_eq!(icl_utilize(ledger, asset_id.as_ptr(), 10.0, &mut out), IclStatus::Ok);
+            let event: CapitalEvent = serde_json::from_str(&take_string(out)).unwrap();
+            assert_eq!(event.event_type, "utilization");
+
+            assert_eq!(icl_get_asset(ledger, asset_id.as_ptr(), &mut out), IclStatus::Ok);
+            let asset: IntelligenceAsset = serde_json::from_str(&take_string(out)).unwrap();
+            assert_eq!((asset.owner.as_str(), asset.initial_value), ("Team A", 1200.0));
+
+            assert_eq!(icl_get_events_for_asset(ledger, asset_id.as_ptr(), &mut out), IclStatus::Ok);
+            let events: Vec<CapitalEvent> = serde_json::from_str(&take_string(out)).unwrap();
+            assert_eq!(events.last().map(|e| e.event_id), Some(event.event_id));
+
+            assert_eq!(icl_export_audit_trail(ledger, c"csv".as_ptr(), &mut out), IclStatus::Ok);
+            let csv = take_string(out);
+            assert!(csv.starts_with("entry_id,event_id,asset_id,"));
+            assert!(csv.contains(asset_id.to_str().unwrap()));
+            assert_eq!(icl_asset_register(ledger, Utc::now().timestamp() + 1, c"json".as_ptr(), &mut out), IclStatus::Ok);
+            assert!(take_string(out).contains(asset_id.to_str().unwrap()));
+
+            assert_eq!(icl_ledger_to_json(ledger, &mut out), IclStatus::Ok);
+            let json = CString::new(take_string(out)).unwrap();
+            let mut restored = ptr::null_mut();
+            assert_eq!(icl_ledger_from_json(json.as_ptr(), &mut restored), IclStatus::Ok);
+            assert_eq!(icl_verify(restored, &mut out), IclStatus::Ok);
+            take_string(out);
+            assert_eq!((*restored).ledger.events().len(), events.len());
+
+            icl_ledger_free(restored);
+            icl_ledger_free(ledger);
+            icl_ledger_free(ptr::null_mut());
+            icl_string_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_null_pointers_are_reported() {
+        unsafe {
+            let mut out = ptr::null_mut();
+            assert_eq!(icl_get_asset(ptr::null(), c"00000000-0000-0000-0000-000000000000".as_ptr(), &mut out), IclStatus::NullPointer);
+            assert_eq!(last_error(), "ledger is NULL");
+            assert!(out.is_null());
+
+            let ledger = icl_ledger_new();
+            let status = icl_capitalize(ledger, ptr::null(), ptr::null(), 1.0, 0, 12, &mut out);
+            assert_eq!(status, IclStatus::NullPointer);
+            assert_eq!(last_error(), "owner is NULL");
+            assert_eq!(icl_ledger_to_json(ledger, ptr::null_mut()), IclStatus::NullPointer);
+            assert_eq!(icl_ledger_from_json(ptr::null(), &mut ptr::null_mut()), IclStatus::NullPointer);
+            assert!((*ledger).ledger.assets_iter().next().is_none());
+            icl_ledger_free(ledger);
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments_map_to_status_codes() {
+        unsafe {
+            let ledger = icl_ledger_new();
+            let mut out = ptr::null_mut();
+
+            let invalid_utf8 = [b'T', 0xff, 0];
+            let status = icl_capitalize(ledger, ptr::null(), invalid_utf8.as_ptr().cast(), 1.0, 0, 12, &mut out);
+            assert_eq!(status, IclStatus::InvalidUtf8);
+            assert_eq!(last_error(), "owner is not valid UTF-8");
+
+            assert_eq!(icl_capitalize(ledger, ptr::null(), c"Team A".as_ptr(), 1.0, 7, 12, &mut out), IclStatus::InvalidArgument);
+            assert_eq!(icl_get_asset(ledger, c"not-a-uuid".as_ptr(), &mut out), IclStatus::InvalidArgument);
+            assert_eq!(icl_get_asset(ledger, c"00000000-0000-0000-0000-000000000001".as_ptr(), &mut out), IclStatus::NotFound);
+
+            let asset_id = CString::new(capitalize(ledger, c"Team A")).unwrap();
+            let status = icl_capitalize(ledger, asset_id.as_ptr(), c"Team A".as_ptr(), 1.0, 0, 12, &mut out);
+            assert_eq!(status, IclStatus::AlreadyExists);
+            assert_eq!(icl_export_audit_trail(ledger, c"xml".as_ptr(), &mut out), IclStatus::UnsupportedFormat);
+            assert_eq!(icl_ledger_from_json(c"{".as_ptr(), &mut ptr::null_mut()), IclStatus::SerializationError);
+
+            assert_eq!(icl_retire(ledger, asset_id.as_ptr(), &mut out), IclStatus::Ok);
+            take_string(out);
+            out = ptr::null_mut();
+            assert_eq!(icl_allocate(ledger, asset_id.as_ptr(), c"Team B".as_ptr(), &mut out), IclStatus::AssetRetired);
+            assert!(out.is_null());
+            icl_ledger_free(ledger);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+
+/// Side of the amount a currency symbol is written on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum SymbolPlacement {
+    #[default]
+    Before,
+    After,
+}
+
+/// How negative amounts are written
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum NegativeStyle {
+    #[default]
+    Minus,
+    Parentheses,
+}
+
+/// How report exports write amounts: separators, decimal places, currency symbol, and
+/// negative style
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+    pub thousands_separator: Option<char>,
+    pub decimals: usize,
+    pub currency_symbol: Option<String>,
+    pub symbol_placement: SymbolPlacement,
+    pub symbol_spaced: bool,
+    pub negative_style: NegativeStyle,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::plain()
+    }
+}
+
+impl NumberFormat {
+    /// `-1234.56`: machine-readable, and what exports write unless given a format
+    pub fn plain() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: None,
+            decimals: 2,
+            currency_symbol: None,
+            symbol_placement: SymbolPlacement::Before,
+            symbol_spaced: false,
+            negative_style: NegativeStyle::Minus,
+        }
+    }
+
+    /// `$1,234.56`
+    pub fn en_us() -> Self {
+        Self {
+            thousands_separator: Some(','),
+            currency_symbol: Some("$".into()),
+            ..Self::plain()
+        }
+    }
+
+    /// `1.234,56 €`
+    pub fn de_de() -> Self {
+        Self {
+            decimal_separator: ',',
+            thousands_separator: Some('.'),
+            currency_symbol: Some("€".into()),
+            symbol_placement: SymbolPlacement::After,
+            symbol_spaced: true,
+            ..Self::plain()
+        }
+    }
+
+    /// `¥1,235`; yen have no minor unit
+    pub fn ja_jp() -> Self {
+        Self {
+            thousands_separator: Some(','),
+            decimals: 0,
+            currency_symbol: Some("¥".into()),
+            ..Self::plain()
+        }
+    }
+
+    /// Built-in format of a locale tag such as `en-US`, `de_DE`, or `ja`; `None` for other locales
+    pub fn for_locale(locale: &str) -> Option<Self> {
+        match locale.replace('_', "-").to_ascii_lowercase().as_str() {
+            "en" | "en-us" => Some(Self::en_us()),
+            "de" | "de-de" => Some(Self::de_de()),
+            "ja" | "ja-jp" => Some(Self::ja_jp()),
+            _ => None,
+        }
+    }
+
+    pub fn with_currency_symbol(mut self, symbol: impl Into<String>, placement: SymbolPlacement) -> Self {
+        self.currency_symbol = Some(symbol.into());
+        self.symbol_placement = placement;
+        self
+    }
+
+    pub fn without_currency_symbol(mut self) -> Self {
+        self.currency_symbol = None;
+        self
+    }
+
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn accounting(mut self) -> Self {
+        self.negative_style = NegativeStyle::Parentheses;
+        self
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        self.render(value, self.decimals, self.currency_symbol.as_deref())
+    }
+
+    /// A quantity or ratio, such as GPU hours or ROI, to `decimals` places without the
+    /// currency symbol
+    pub fn format_number(&self, value: f64, decimals: usize) -> String {
+        self.render(value, decimals, None)
+    }
+
+    fn render(&self, value: f64, decimals: usize, symbol: Option<&str>) -> String {
+        let rounded = format!("{:.*}", decimals, value.abs());
+        let (whole, fraction) = match rounded.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (rounded.as_str(), None),
+        };
+        let mut digits = String::new();
+        for (i, digit) in whole.chars().enumerate() {
+            if let Some(separator) = self.thousands_separator.filter(|_| i > 0 && (whole.len() - i) % 3 == 0) {
+                digits.push(separator);
+            }
+            digits.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            digits.push(self.decimal_separator);
+            digits.push_str(fraction);
+        }
+
+        let amount = match symbol {
+            Some(symbol) => {
+                let space = if self.symbol_spaced { " " } else { "" };
+                match self.symbol_placement {
+                    SymbolPlacement::Before => format!("{}{}{}", symbol, space, digits),
+                    SymbolPlacement::After => format!("{}{}{}", digits, space, symbol),
+                }
+            },
+            None => digits,
+        };
+        // An amount that rounds to zero is written without a sign
+        let negative = value < 0.0 && rounded.bytes().any(|b| (b'1'..=b'9').contains(&b));
+        match (negative, self.negative_style) {
+            (false, _) => amount,
+            (true, NegativeStyle::Minus) => format!("-{}", amount),
+            (true, NegativeStyle::Parentheses) => format!("({})", amount),
+        }
+    }
+
+    /// Excel number format code for amounts
+    pub fn xlsx_num_format(&self) -> String {
+        let mut number = String::from(if self.thousands_separator.is_some() { "#,##0" } else { "0" });
+        if self.decimals > 0 {
+            number.push('.');
+            number.push_str(&"0".repeat(self.decimals));
+        }
+        let amount = match &self.currency_symbol {
+            Some(symbol) => {
+                let space = if self.symbol_spaced { " " } else { "" };
+                match self.symbol_placement {
+                    SymbolPlacement::Before => format!("\"{}\"{}{}", symbol, space, number),
+                    SymbolPlacement::After => format!("{}{}\"{}\"", number, space, symbol),
+                }
+            },
+            None => number,
+        };
+        match self.negative_style {
+            NegativeStyle::Minus => amount,
+            NegativeStyle::Parentheses => format!("{};({})", amount, amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_locales() {
+        assert_eq!(NumberFormat::plain().format(-1234567.891), "-1234567.89");
+        assert_eq!(NumberFormat::en_us().format(1234567.891), "$1,234,567.89");
+        assert_eq!(NumberFormat::de_de().format(-1234.5), "-1.234,50 €");
+        assert_eq!(NumberFormat::ja_jp().format(1234.6), "¥1,235");
+        assert_eq!(NumberFormat::en_us().accounting().format(-12.0), "($12.00)");
+        assert_eq!(NumberFormat::en_us().format(-0.001), "$0.00");
+    }
+}
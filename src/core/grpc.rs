@@ -0,0 +1,468 @@
+use chrono::{DateTime, Utc};
+use tonic::{Request, Response, Status};
+
+use crate::core::types::*;
+use crate::core::ledger::SharedLedger;
+use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+use crate::core::error::*;
+use crate::core::grpc_proto as proto;
+use crate::core::grpc_proto::ledger_service_server::{LedgerService, LedgerServiceServer};
+
+impl From<IclError> for Status {
+    fn from(e: IclError) -> Self {
+        let message = e.to_string();
+        let code = e.code();
+        let mut status = match e.root() {
+            IclError::AssetNotFound(_)
+            | IclError::OwnerNotFound(_)
+            | IclError::EventNotFound(_) => Status::not_found(message),
+            IclError::AssetAlreadyExists(_)
+            | IclError::OwnerAlreadyExists(_)
+            | IclError::DuplicateEvent(_)
+            | IclError::DuplicateEntry(_) => Status::already_exists(message),
+            IclError::AssetRetired(_) | IclError::OverlappingDepreciation => Status::failed_precondition(message),
+            IclError::Unauthorized(_) => Status::permission_denied(message),
+            IclError::InvalidAsset(_)
+            | IclError::InvalidEvent(_)
+            | IclError::InvalidEntry(_)
+            | IclError::DepreciationError(_)
+            | IclError::IntegrityViolation(_)
+            | IclError::InvalidDateRange { .. }
+            | IclError::UnsupportedFormat(_) => Status::invalid_argument(message),
+            IclError::SerializationError(_)
+            | IclError::IntegrationError(_)
+            | IclError::StorageError(_)
+            | IclError::HashingError(_)
+            | IclError::Context { .. } => Status::internal(message),
+        };
+        status.metadata_mut().insert("icl-error-code", tonic::metadata::MetadataValue::from_static(code));
+        status
+    }
+}
+
+fn parse_id<T: std::str::FromStr<Err = uuid::Error>>(value: &str, field: &str) -> Result<T, Status> {
+    value.parse().map_err(|e| Status::invalid_argument(format!("{}: {}", field, e)))
+}
+
+fn parse_optional_id<T: std::str::FromStr<Err = uuid::Error>>(value: Option<&str>, field: &str) -> Result<Option<T>, Status> {
+    value.map(|v| parse_id(v, field)).transpose()
+}
+
+fn timestamp(value: DateTime<Utc>) -> Option<prost_types::Timestamp> {
+    Some(prost_types::Timestamp {
+        seconds: value.timestamp(),
+        nanos: value.timestamp_subsec_nanos() as i32,
+    })
+}
+
+fn parse_timestamp(value: Option<prost_types::Timestamp>, field: &str) -> Result<DateTime<Utc>, Status> {
+    value
+        .and_then(|t| DateTime::from_timestamp(t.seconds, u32::try_from(t.nanos).ok()?))
+        .ok_or_else(|| Status::invalid_argument(format!("{}: missing or out-of-range timestamp", field)))
+}
+
+fn json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+impl From<DepreciationMethod> for proto::DepreciationMethod {
+    fn from(method: DepreciationMethod) -> Self {
+        match method {
+            DepreciationMethod::Linear => proto::DepreciationMethod::Linear,
+            DepreciationMethod::DecliningBalance => proto::DepreciationMethod::DecliningBalance,
+            DepreciationMethod::ImpairmentOnly => proto::DepreciationMethod::ImpairmentOnly,
+            DepreciationMethod::UnitsOfProduction { .. } => proto::DepreciationMethod::UnitsOfProduction,
+        }
+    }
+}
+
+impl TryFrom<i32> for DepreciationMethod {
+    type Error = Status;
+
+    fn try_from(value: i32) -> Result<Self, Status> {
+        match proto::DepreciationMethod::try_from(value) {
+            Ok(proto::DepreciationMethod::Linear) => Ok(DepreciationMethod::Linear),
+            Ok(proto::DepreciationMethod::DecliningBalance) => Ok(DepreciationMethod::DecliningBalance),
+            Ok(proto::DepreciationMethod::ImpairmentOnly) => Ok(DepreciationMethod::ImpairmentOnly),
+            Ok(proto::DepreciationMethod::UnitsOfProduction) => Err(Status::invalid_argument(
+                "depreciation_method: units of production needs a usage basis, which gRPC cannot carry yet",
+            )),
+            _ => Err(Status::invalid_argument(format!("depreciation_method: unknown value {}", value))),
+        }
+    }
+}
+
+impl From<AssetStatus> for proto::AssetStatus {
+    fn from(status: AssetStatus) -> Self {
+        match status {
+            AssetStatus::Active => proto::AssetStatus::Active,
+            AssetStatus::Depreciated => proto::AssetStatus::Depreciated,
+            AssetStatus::Retired => proto::AssetStatus::Retired,
+        }
+    }
+}
+
+impl From<AccountType> for proto::AccountType {
+    fn from(account: AccountType) -> Self {
+        match account {
+            AccountType::Asset => proto::AccountType::Asset,
+            AccountType::AccumulatedDepreciation => proto::AccountType::AccumulatedDepreciation,
+            AccountType::DepreciationExpense => proto::AccountType::DepreciationExpense,
+            AccountType::InferenceExpense => proto::AccountType::InferenceExpense,
+            AccountType::AccountsPayable => proto::AccountType::AccountsPayable,
+            AccountType::AccountsReceivable => proto::AccountType::AccountsReceivable,
+            AccountType::Revenue => proto::AccountType::Revenue,
+            AccountType::RoyaltyExpense => proto::AccountType::RoyaltyExpense,
+            AccountType::DisposalGainLoss => proto::AccountType::DisposalGainLoss,
+            AccountType::RetainedEarnings => proto::AccountType::RetainedEarnings,
+        }
+    }
+}
+
+impl From<&IntelligenceAsset> for proto::Asset {
+    fn from(asset: &IntelligenceAsset) -> Self {
+        Self {
+            asset_id: asset.asset_id.to_string(),
+            owner: asset.owner.to_string(),
+            initial_value: asset.initial_value,
+            depreciation_method: proto::DepreciationMethod::from(asset.depreciation_method).into(),
+            useful_life_months: asset.useful_life_months,
+            created_at: timestamp(asset.created_at),
+            status: proto::AssetStatus::from(asset.status).into(),
+            current_value: asset.current_value,
+            category: asset.category.clone(),
+            dimensions: asset.dimensions.clone().into_iter().collect(),
+        }
+    }
+}
+
+impl From<&CapitalEvent> for proto::CapitalEvent {
+    fn from(event: &CapitalEvent) -> Self {
+        Self {
+            event_id: event.event_id.to_string(),
+            asset_id: event.asset_id.to_string(),
+            event_type: event.event_type.clone(),
+            timestamp: timestamp(event.timestamp),
+            details_json: json(&event.details),
+            sequence: event.sequence,
+            previous_hash: event.previous_hash.clone(),
+            record_hash: event.record_hash.clone(),
+        }
+    }
+}
+
+impl From<&JournalEntry> for proto::JournalEntry {
+    fn from(entry: &JournalEntry) -> Self {
+        Self {
+            entry_id: entry.entry_id.to_string(),
+            event_id: entry.event_id.to_string(),
+            timestamp: timestamp(entry.timestamp),
+            debit_account: proto::AccountType::from(entry.debit_account).into(),
+            credit_account: proto::AccountType::from(entry.credit_account).into(),
+            amount: entry.amount,
+            description: entry.description.clone(),
+            metadata_json: json(&entry.metadata),
+            previous_hash: entry.previous_hash.clone(),
+            record_hash: entry.record_hash.clone(),
+        }
+    }
+}
+
+impl From<&CapitalProof> for proto::CapitalProof {
+    fn from(proof: &CapitalProof) -> Self {
+        Self {
+            proof_id: proof.proof_id.to_string(),
+            asset_id: proof.asset_id.to_string(),
+            event_id: proof.event_id.map(|id| id.to_string()),
+            timestamp: timestamp(proof.timestamp),
+            origin: proof.origin.clone(),
+            content_json: json(&proof.content),
+            previous_proof_hash: proof.previous_proof_hash.clone(),
+            proof_hash: proof.proof_hash.clone(),
+            hash_version: proof.hash_version,
+            hash_algorithm: proof.hash_algorithm.to_string(),
+        }
+    }
+}
+
+/// `icl.v1.LedgerService` over a shared ledger; serve it with `into_server` on a tonic `Server`
+#[derive(Debug, Clone)]
+pub struct LedgerGrpcService {
+    ledger: SharedLedger,
+}
+
+impl LedgerGrpcService {
+    pub fn new(ledger: SharedLedger) -> Self {
+        Self { ledger }
+    }
+
+    pub fn into_server(self) -> LedgerServiceServer<Self> {
+        LedgerServiceServer::new(self)
+    }
+}
+
+#[tonic::async_trait]
+impl LedgerService for LedgerGrpcService {
+    async fn capitalize(&self, request: Request<proto::CapitalizeRequest>) -> Result<Response<proto::Asset>, Status> {
+        let request = request.into_inner();
+        let depreciation_method = DepreciationMethod::try_from(request.depreciation_method)?;
+        let mut ledger = self.ledger.write();
+        let asset_id = match parse_optional_id(request.asset_id.as_deref(), "asset_id")? {
+            Some(id) => id,
+            None => ledger.next_id().into(),
+        };
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        if let Some(category) = request.category {
+            lifecycle = lifecycle.in_category(category);
+        }
+        let mut asset = lifecycle.capitalize(
+            asset_id,
+            request.owner,
+            request.initial_value,
+            depreciation_method,
+            request.useful_life_months,
+        )?;
+        if !request.dimensions.is_empty() {
+            asset = ledger.update_asset_metadata(asset_id, |a| a.dimensions = request.dimensions.into_iter().collect())?;
+        }
+        Ok(Response::new((&asset).into()))
+    }
+
+    async fn allocate(&self, request: Request<proto::AllocateRequest>) -> Result<Response<proto::CapitalEvent>, Status> {
+        let request = request.into_inner();
+        let asset_id = parse_id(&request.asset_id, "asset_id")?;
+        let event = IntelligenceCapitalLifecycle::new(&mut self.ledger.write()).allocate(asset_id, request.target_owner)?;
+        Ok(Response::new((&event).into()))
+    }
+
+    async fn utilize(&self, request: Request<proto::UtilizeRequest>) -> Result<Response<proto::CapitalEvent>, Status> {
+        let request = request.into_inner();
+        let asset_id = parse_id(&request.asset_id, "asset_id")?;
+        let usage = Usage::new()
+            .with_tokens(request.tokens)
+            .with_requests(request.requests)
+            .with_gpu_hours(request.gpu_hours);
+        let event = IntelligenceCapitalLifecycle::new(&mut self.ledger.write())
+            .utilize(asset_id, Utilization::new(usage).with_cost(request.amount))?;
+        Ok(Response::new((&event).into()))
+    }
+
+    async fn depreciate(&self, request: Request<proto::DepreciateRequest>) -> Result<Response<proto::CapitalEvent>, Status> {
+        let request = request.into_inner();
+        let asset_id = parse_id(&request.asset_id, "asset_id")?;
+        let start_date = parse_timestamp(request.start_date, "start_date")?;
+        let end_date = parse_timestamp(request.end_date, "end_date")?;
+        let event = IntelligenceCapitalLifecycle::new(&mut self.ledger.write()).depreciate(
+            asset_id,
+            start_date,
+            end_date,
+            request.salvage_value,
+            request.rate_multiplier.unwrap_or(1.0),
+        )?;
+        Ok(Response::new((&event).into()))
+    }
+
+    async fn retire(&self, request: Request<proto::RetireRequest>) -> Result<Response<proto::CapitalEvent>, Status> {
+        let asset_id = parse_id(&request.into_inner().asset_id, "asset_id")?;
+        let event = IntelligenceCapitalLifecycle::new(&mut self.ledger.write()).retire(asset_id)?;
+        Ok(Response::new((&event).into()))
+    }
+
+    async fn get_asset(&self, request: Request<proto::GetAssetRequest>) -> Result<Response<proto::Asset>, Status> {
+        let asset_id = parse_id(&request.into_inner().asset_id, "asset_id")?;
+        let ledger = self.ledger.read();
+        let asset = ledger.get_asset(asset_id).ok_or(IclError::AssetNotFound(asset_id))?;
+        Ok(Response::new(asset.into()))
+    }
+
+    async fn list_assets(
+        &self,
+        request: Request<proto::ListAssetsRequest>
+    ) -> Result<Response<proto::ListAssetsResponse>, Status> {
+        let request = request.into_inner();
+        let ledger = self.ledger.read();
+        let mut assets: Vec<&IntelligenceAsset> = ledger.assets_iter()
+            .filter(|a| request.owner.as_ref().is_none_or(|owner| a.owner.as_str() == owner))
+            .filter(|a| request.status.is_none_or(|status| i32::from(proto::AssetStatus::from(a.status)) == status))
+            .collect();
+        assets.sort_by_key(|a| (a.created_at, a.asset_id));
+        Ok(Response::new(proto::ListAssetsResponse {
+            assets: assets.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn list_events(
+        &self,
+        request: Request<proto::ListEventsRequest>
+    ) -> Result<Response<proto::ListEventsResponse>, Status> {
+        let asset_id: Option<AssetId> = parse_optional_id(request.into_inner().asset_id.as_deref(), "asset_id")?;
+        let ledger = self.ledger.read();
+        let events = match asset_id {
+            Some(asset_id) => ledger.get_events_for_asset(asset_id),
+            None => ledger.events().iter().collect(),
+        };
+        Ok(Response::new(proto::ListEventsResponse {
+            events: events.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn list_journal_entries(
+        &self,
+        request: Request<proto::ListJournalEntriesRequest>
+    ) -> Result<Response<proto::ListJournalEntriesResponse>, Status> {
+        let event_id = parse_optional_id(request.into_inner().event_id.as_deref(), "event_id")?;
+        let ledger = self.ledger.read();
+        let journal_entries = match event_id {
+            Some(event_id) => ledger.journal_entries_for_event(event_id),
+            None => ledger.journal_entries().iter().collect(),
+        };
+        Ok(Response::new(proto::ListJournalEntriesResponse {
+            journal_entries: journal_entries.into_iter()
+                .filter(|j| !ledger.is_quarantined(j.entry_id))
+                .map(Into::into)
+                .collect(),
+        }))
+    }
+
+    async fn list_proofs(
+        &self,
+        request: Request<proto::ListProofsRequest>
+    ) -> Result<Response<proto::ListProofsResponse>, Status> {
+        let asset_id: Option<AssetId> = parse_optional_id(request.into_inner().asset_id.as_deref(), "asset_id")?;
+        let ledger = self.ledger.read();
+        Ok(Response::new(proto::ListProofsResponse {
+            proofs: ledger.proofs().iter()
+                .filter(|p| !ledger.is_quarantined(p.proof_id))
+                .filter(|p| asset_id.is_none_or(|id| p.asset_id == id))
+                .map(Into::into)
+                .collect(),
+        }))
+    }
+
+    async fn generate_proof(
+        &self,
+        request: Request<proto::GenerateProofRequest>
+    ) -> Result<Response<proto::CapitalProof>, Status> {
+        let request = request.into_inner();
+        let asset_id = parse_id(&request.asset_id, "asset_id")?;
+        let event_id = parse_optional_id(request.event_id.as_deref(), "event_id")?;
+        let proof = self.ledger.write().generate_proof(asset_id, event_id)?;
+        Ok(Response::new((&proof).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    type Declarations = BTreeMap<String, BTreeMap<i32, String>>;
+
+    const PROTO: &str = include_str!("../../proto/icl.proto");
+    const GENERATED: &str = include_str!("grpc_proto.rs");
+
+    fn prost_attribute(label: Option<&str>, field_type: &str, enums: &[&str], tag: i32) -> String {
+        let kind = if let Some(entry) = field_type.strip_prefix("map<").and_then(|t| t.strip_suffix('>')) {
+            format!("map = \"{}\"", entry.replace(',', ", ").split_whitespace().collect::<Vec<_>>().join(" "))
+        } else if enums.contains(&field_type) {
+            format!("enumeration = \"{}\"", field_type)
+        } else if matches!(field_type, "string" | "bytes" | "bool" | "double" | "float" | "int32" | "int64" | "uint32" | "uint64") {
+            field_type.to_string()
+        } else {
+            // Singular message fields are always optional in prost
+            return format!("message, {}, tag = \"{}\"", label.unwrap_or("optional"), tag);
+        };
+        match label {
+            Some(label) => format!("{}, {}, tag = \"{}\"", kind, label, tag),
+            None => format!("{}, tag = \"{}\"", kind, tag),
+        }
+    }
+
+    fn camel_case(name: &str) -> String {
+        name.split('_').map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|c| c.to_ascii_uppercase().to_string() + &chars.as_str().to_ascii_lowercase()).unwrap_or_default()
+        }).collect()
+    }
+
+    fn parse_proto() -> (Declarations, Declarations, Vec<String>) {
+        let lines: Vec<&str> = PROTO.lines().map(|l| l.split("//").next().unwrap_or("").trim()).collect();
+        let enums: Vec<&str> = lines.iter().filter_map(|l| l.strip_prefix("enum ")?.strip_suffix(" {")).collect();
+        let (mut messages, mut enum_values, mut rpcs) = (Declarations::new(), Declarations::new(), Vec::new());
+        let mut current: Option<(bool, String)> = None;
+        for line in lines {
+            if let Some(name) = line.strip_prefix("message ").and_then(|l| l.strip_suffix(" {")) {
+                current = Some((true, name.to_string()));
+            } else if let Some(name) = line.strip_prefix("enum ").and_then(|l| l.strip_suffix(" {")) {
+                current = Some((false, name.to_string()));
+            } else if let Some(rpc) = line.strip_prefix("rpc ") {
+                rpcs.push(rpc.split('(').next().unwrap_or_default().to_string());
+            } else if line == "}" {
+                current = None;
+            } else if let (Some((is_message, name)), Some(declaration)) = (&current, line.strip_suffix(';')) {
+                let (lhs, tag) = declaration.rsplit_once('=').expect("field without a tag");
+                let tag: i32 = tag.trim().parse().expect("non-numeric tag");
+                let rendering = if *is_message {
+                    let mut words = lhs.split_whitespace().collect::<Vec<_>>();
+                    let field = words.pop().expect("field without a name");
+                    let label = words.first().copied().filter(|w| matches!(*w, "optional" | "repeated"));
+                    let field_type = words[usize::from(label.is_some())..].concat();
+                    format!("{} {}", field, prost_attribute(label, &field_type, &enums, tag))
+                } else {
+                    let prefix = format!("{}_", name.chars().fold(String::new(), |mut s, c| {
+                        if c.is_uppercase() && !s.is_empty() {
+                            s.push('_');
+                        }
+                        s.push(c.to_ascii_uppercase());
+                        s
+                    }));
+                    camel_case(lhs.trim().strip_prefix(&prefix).expect("enum value without the type prefix"))
+                };
+                let declarations = if *is_message { &mut messages } else { &mut enum_values };
+                declarations.entry(name.clone()).or_default().insert(tag, rendering);
+            }
+        }
+        (messages, enum_values, rpcs)
+    }
+
+    fn parse_generated() -> (Declarations, Declarations) {
+        let (mut messages, mut enum_values) = (Declarations::new(), Declarations::new());
+        let mut current: Option<(bool, String)> = None;
+        let mut attribute: Option<String> = None;
+        for line in GENERATED.lines() {
+            if let Some(name) = line.strip_prefix("pub struct ").and_then(|l| l.strip_suffix(" {")) {
+                current = Some((true, name.to_string()));
+            } else if let Some(name) = line.strip_prefix("pub enum ").and_then(|l| l.strip_suffix(" {")) {
+                current = Some((false, name.to_string()));
+            } else if line == "}" {
+                current = None;
+            } else if let Some((is_message, name)) = &current {
+                let line = line.trim();
+                if let Some(attr) = line.strip_prefix("#[prost(").and_then(|l| l.strip_suffix(")]")) {
+                    attribute = Some(attr.to_string());
+                } else if let (true, Some(field)) = (*is_message, line.strip_prefix("pub ")) {
+                    let attr = attribute.take().expect("field without a prost attribute");
+                    let tag = attr.rsplit_once("tag = \"").and_then(|(_, t)| t.trim_end_matches('"').parse().ok()).expect("prost attribute without a tag");
+                    let field = field.split(':').next().unwrap_or_default().trim_start_matches("r#");
+                    messages.entry(name.clone()).or_default().insert(tag, format!("{} {}", field, attr));
+                } else if let (false, Some((variant, value))) = (*is_message, line.strip_suffix(',').and_then(|l| l.split_once(" = "))) {
+                    enum_values.entry(name.clone()).or_default().insert(value.parse().expect("non-numeric enum value"), variant.to_string());
+                }
+            }
+        }
+        (messages, enum_values)
+    }
+
+    #[test]
+    fn test_generated_code_matches_proto() {
+        let (messages, enum_values, rpcs) = parse_proto();
+        let (generated_messages, generated_enums) = parse_generated();
+        assert_eq!(generated_messages, messages, "grpc_proto.rs messages have drifted from proto/icl.proto");
+        assert_eq!(generated_enums, enum_values, "grpc_proto.rs enums have drifted from proto/icl.proto");
+        for rpc in rpcs {
+            assert!(
+                GENERATED.contains(&format!("\"/icl.v1.LedgerService/{}\"", rpc)),
+                "grpc_proto.rs has no route for rpc {}", rpc
+            );
+        }
+    }
+}
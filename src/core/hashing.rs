@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::core::error::*;
+
+/// Hash scheme used by proofs generated before canonical encoding was introduced
+pub const PROOF_HASH_VERSION_LEGACY: u32 = 1;
+
+/// Hash scheme used for all newly generated proofs
+pub const PROOF_HASH_VERSION: u32 = 2;
+
+/// Digest algorithm used to seal proofs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl std::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashAlgorithm::Sha256 => write!(f, "Sha256"),
+            HashAlgorithm::Sha512 => write!(f, "Sha512"),
+            HashAlgorithm::Blake3 => write!(f, "Blake3"),
+        }
+    }
+}
+
+impl HashAlgorithm {
+    pub fn digest_hex(&self, bytes: &[u8]) -> String {
+        match self {
+            HashAlgorithm::Sha256 => sha256_hex(bytes),
+            HashAlgorithm::Sha512 => {
+                let mut hasher = Sha512::new();
+                hasher.update(bytes);
+                format!("{:x}", hasher.finalize())
+            },
+            HashAlgorithm::Blake3 => blake3::hash(bytes).to_hex().to_string(),
+        }
+    }
+
+    /// Hex-encoded digest of a record's canonical JSON encoding under this algorithm
+    pub fn digest_record<T: Serialize>(&self, record: &T) -> IclResult<String> {
+        let value = serde_json::to_value(record).map_err(|e| IclError::HashingError(e.to_string()))?;
+        Ok(self.digest_hex(canonical_json(&value).as_bytes()))
+    }
+}
+
+/// Serialize a JSON value canonically: object keys sorted, no insignificant whitespace
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {
+            out.push_str(&value.to_string());
+        },
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        },
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        },
+    }
+}
+
+/// Hex-encoded SHA-256 digest of the given bytes
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Hex-encoded SHA-256 digest of a record's canonical JSON encoding
+pub fn digest_record<T: Serialize>(record: &T) -> IclResult<String> {
+    HashAlgorithm::Sha256.digest_record(record)
+}
+
+/// Seal for a chained ledger record: digest over every field except its own `record_hash`
+pub fn record_chain_hash<T: Serialize>(algorithm: HashAlgorithm, record: &T) -> IclResult<String> {
+    let mut value = serde_json::to_value(record).map_err(|e| IclError::HashingError(e.to_string()))?;
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("record_hash");
+    }
+    Ok(algorithm.digest_hex(canonical_json(&value).as_bytes()))
+}
+
+/// Latest record hash and record count of a hash chain
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainHead {
+    pub head: Option<String>,
+    pub length: usize,
+}
+
+impl ChainHead {
+    pub(crate) fn extend(&mut self, record_hash: &Option<String>) {
+        self.head = record_hash.clone();
+        self.length += 1;
+    }
+}
+
+/// Heads of the event, ledger entry, and journal entry chains
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordChainHeads {
+    pub events: ChainHead,
+    pub entries: ChainHead,
+    pub journal_entries: ChainHead,
+}
+
+/// One step of a Merkle inclusion path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleStep {
+    pub sibling: String,
+    pub sibling_on_left: bool,
+}
+
+fn merkle_leaf(algorithm: HashAlgorithm, value: &str) -> String {
+    algorithm.digest_hex(format!("leaf:{}", value).as_bytes())
+}
+
+fn merkle_node(algorithm: HashAlgorithm, left: &str, right: &str) -> String {
+    algorithm.digest_hex(format!("node:{}{}", left, right).as_bytes())
+}
+
+/// Merkle root over the given leaf values; an odd node is promoted to the next level unchanged
+pub fn merkle_root(algorithm: HashAlgorithm, leaves: &[String]) -> Option<String> {
+    if leaves.is_empty() {
+        return None;
+    }
+    let mut level: Vec<String> = leaves.iter().map(|l| merkle_leaf(algorithm, l)).collect();
+    while level.len() > 1 {
+        level = level.chunks(2)
+            .map(|pair| match pair {
+                [left, right] => merkle_node(algorithm, left, right),
+                [single] => single.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level.pop()
+}
+
+/// Inclusion path for the leaf at `index`, from the leaf level up to the root
+pub fn merkle_path(algorithm: HashAlgorithm, leaves: &[String], index: usize) -> Option<Vec<MerkleStep>> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut path = Vec::new();
+    let mut level: Vec<String> = leaves.iter().map(|l| merkle_leaf(algorithm, l)).collect();
+    let mut position = index;
+    while level.len() > 1 {
+        let sibling = position ^ 1;
+        if sibling < level.len() {
+            path.push(MerkleStep {
+                sibling: level[sibling].clone(),
+                sibling_on_left: sibling < position,
+            });
+        }
+        level = level.chunks(2)
+            .map(|pair| match pair {
+                [left, right] => merkle_node(algorithm, left, right),
+                [single] => single.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        position /= 2;
+    }
+    Some(path)
+}
+
+/// Check that `leaf` is included under `root` via `path`
+pub fn verify_merkle_path(algorithm: HashAlgorithm, leaf: &str, path: &[MerkleStep], root: &str) -> bool {
+    let computed = path.iter().fold(merkle_leaf(algorithm, leaf), |acc, step| {
+        if step.sibling_on_left {
+            merkle_node(algorithm, &step.sibling, &acc)
+        } else {
+            merkle_node(algorithm, &acc, &step.sibling)
+        }
+    });
+    computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_json_sorts_nested_keys() {
+        let value = json!({"b": 1, "a": {"z": [1, {"y": true, "x": null}], "c": "s"}});
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":{"c":"s","z":[1,{"x":null,"y":true}]},"b":1}"#
+        );
+    }
+
+    #[test]
+    fn test_canonical_json_escapes_strings() {
+        let value = json!({"k\"ey": "line\nbreak"});
+        assert_eq!(canonical_json(&value), r#"{"k\"ey":"line\nbreak"}"#);
+    }
+
+    #[test]
+    fn test_merkle_paths_verify_for_every_leaf() {
+        let leaves: Vec<String> = (0..5).map(|i| format!("leaf-{}", i)).collect();
+        for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Sha512, HashAlgorithm::Blake3] {
+            let root = merkle_root(algorithm, &leaves).unwrap();
+            for (i, leaf) in leaves.iter().enumerate() {
+                let path = merkle_path(algorithm, &leaves, i).unwrap();
+                assert!(verify_merkle_path(algorithm, leaf, &path, &root));
+                assert!(!verify_merkle_path(algorithm, "other", &path, &root));
+            }
+        }
+    }
+
+    #[test]
+    fn test_legacy_proofs_are_verified_before_migration() {
+        use crate::core::ledger::IntelligenceCapitalLedger;
+        use crate::core::types::AssetId;
+
+        // Legacy proofs hashed their content in the order its keys were serialized in
+        let legacy_proof = |content: &str| {
+            let (proof_id, asset_id, timestamp) = (uuid::Uuid::new_v4(), AssetId::random(), chrono::Utc::now());
+            let hash = sha256_hex(format!("{}{}{}", proof_id, timestamp.timestamp(), r#"{"owner":"Team A","initial_value":1200.0}"#).as_bytes());
+            format!(
+                r#"{{"proof_id":"{}","asset_id":"{}","event_id":null,"timestamp":"{}","origin":"ICL","content":{},"previous_proof_hash":null,"proof_hash":"{}"}}"#,
+                proof_id, asset_id, timestamp.to_rfc3339(), content, hash
+            )
+        };
+        let ledger_with = |proof: String| -> IntelligenceCapitalLedger {
+            let json = serde_json::to_string(&IntelligenceCapitalLedger::new()).unwrap();
+            serde_json::from_str(&json.replace(r#""proofs":[]"#, &format!(r#""proofs":[{}]"#, proof))).unwrap()
+        };
+
+        let ledger = ledger_with(legacy_proof(r#"{"owner":"Team A","initial_value":1200.0}"#));
+        let reloaded: IntelligenceCapitalLedger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+        assert!(reloaded.proofs()[0].verify_hash().unwrap());
+        let mut upgraded = reloaded.clone();
+        assert_eq!(upgraded.migrate_proof_hashes().unwrap(), 1);
+        assert_eq!(upgraded.proofs()[0].hash_version, PROOF_HASH_VERSION);
+
+        let mut tampered = ledger_with(legacy_proof(r#"{"owner":"Team B","initial_value":1200.0}"#));
+        assert!(tampered.migrate_proof_hashes().is_err());
+        assert_eq!(tampered.proofs()[0].hash_version, PROOF_HASH_VERSION_LEGACY);
+    }
+
+    #[test]
+    fn test_record_chain_uses_the_configured_algorithm() {
+        use crate::core::integrity::IntegrityChecker;
+        use crate::core::ledger::IntelligenceCapitalLedger;
+        use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+        use crate::core::types::{AssetId, DepreciationMethod};
+
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_record_hash_algorithm(HashAlgorithm::Sha512).unwrap();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .capitalize(AssetId::random(), "Team A", 1000.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        assert_eq!(ledger.events()[0].record_hash.as_ref().unwrap().len(), 128);
+        assert!(ledger.set_record_hash_algorithm(HashAlgorithm::Blake3).is_err());
+
+        let restored: IntelligenceCapitalLedger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+        assert_eq!(restored.record_hash_algorithm(), HashAlgorithm::Sha512);
+        assert!(IntegrityChecker::new(&restored).check_all_integrity().is_clean());
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::types::{AssetId, EventId};
+
+/// A quality or drift measurement for one asset, posted by a monitoring system
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImpairmentIndicator {
+    pub asset_id: AssetId,
+    pub metric: String,
+    pub value: f64,
+    pub observed_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl ImpairmentIndicator {
+    pub fn new(asset_id: AssetId, metric: impl Into<String>, value: f64) -> Self {
+        Self {
+            asset_id,
+            metric: metric.into(),
+            value,
+            observed_at: None,
+            source: None,
+        }
+    }
+
+    pub fn observed_at(mut self, observed_at: DateTime<Utc>) -> Self {
+        self.observed_at = Some(observed_at);
+        self
+    }
+
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+}
+
+/// Which side of its limit a metric breaches on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum BreachDirection {
+    Above,
+    Below,
+}
+
+impl std::fmt::Display for BreachDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BreachDirection::Above => write!(f, "above"),
+            BreachDirection::Below => write!(f, "below"),
+        }
+    }
+}
+
+/// Limit on one metric beyond which an asset is flagged for impairment review
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImpairmentThreshold {
+    pub metric: String,
+    pub direction: BreachDirection,
+    pub limit: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft_fraction: Option<f64>,
+}
+
+impl ImpairmentThreshold {
+    pub fn above(metric: impl Into<String>, limit: f64) -> Self {
+        Self { metric: metric.into(), direction: BreachDirection::Above, limit, draft_fraction: None }
+    }
+
+    pub fn below(metric: impl Into<String>, limit: f64) -> Self {
+        Self { metric: metric.into(), direction: BreachDirection::Below, limit, draft_fraction: None }
+    }
+
+    pub fn with_draft_impairment(mut self, fraction: f64) -> Self {
+        self.draft_fraction = Some(fraction);
+        self
+    }
+
+    pub fn is_breached_by(&self, value: f64) -> bool {
+        match self.direction {
+            BreachDirection::Above => value > self.limit,
+            BreachDirection::Below => value < self.limit,
+        }
+    }
+}
+
+/// Impairment proposed when a review is opened; nothing is posted until the review is approved
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DraftImpairment {
+    pub amount: f64,
+    pub book_value_before: f64,
+    pub book_value_after: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ReviewStatus {
+    Open,
+    Impaired(EventId),
+    Dismissed,
+}
+
+impl std::fmt::Display for ReviewStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReviewStatus::Open => write!(f, "Open"),
+            ReviewStatus::Impaired(event_id) => write!(f, "Impaired({})", event_id),
+            ReviewStatus::Dismissed => write!(f, "Dismissed"),
+        }
+    }
+}
+
+/// An asset flagged for impairment review by breaching indicators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpairmentReview {
+    pub review_id: Uuid,
+    pub asset_id: AssetId,
+    pub flagged_at: DateTime<Utc>,
+    pub indicators: Vec<ImpairmentIndicator>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft: Option<DraftImpairment>,
+    pub status: ReviewStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution_note: Option<String>,
+}
+
+impl ImpairmentReview {
+    pub fn is_open(&self) -> bool {
+        self.status == ReviewStatus::Open
+    }
+
+    pub(crate) fn reason(&self) -> String {
+        let metrics: Vec<String> = self.indicators.iter()
+            .map(|indicator| format!("{}={}", indicator.metric, indicator.value))
+            .collect();
+        format!("Impairment review {}: {}", self.review_id, metrics.join(", "))
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::error::*;
+
+/// Whether a bulk import commits the rows that succeeded when others fail
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ImportMode {
+    #[default]
+    BestEffort,
+    AllOrNothing,
+}
+
+impl std::fmt::Display for ImportMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportMode::BestEffort => write!(f, "BestEffort"),
+            ImportMode::AllOrNothing => write!(f, "AllOrNothing"),
+        }
+    }
+}
+
+/// One asset to capitalize; a missing `asset_id` is assigned on import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetImportRow {
+    #[serde(default)]
+    pub asset_id: Option<AssetId>,
+    pub owner: String,
+    pub initial_value: f64,
+    pub depreciation_method: DepreciationMethod,
+    pub useful_life_months: i32,
+}
+
+/// What happened to one row of a bulk asset import
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AssetImportOutcome {
+    Created,
+    SkippedDuplicate,
+    ValidationError(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetImportRowResult {
+    pub row_number: usize,
+    pub asset_id: Option<AssetId>,
+    pub outcome: AssetImportOutcome,
+}
+
+/// Per-row results of `IntelligenceCapitalLedger::import_assets`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetImportReport {
+    pub rows: Vec<AssetImportRowResult>,
+    pub created: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub rolled_back: bool,
+}
+
+impl AssetImportReport {
+    pub(crate) fn push(&mut self, row_number: usize, asset_id: Option<AssetId>, outcome: AssetImportOutcome) {
+        match outcome {
+            AssetImportOutcome::Created => self.created += 1,
+            AssetImportOutcome::SkippedDuplicate => self.skipped += 1,
+            AssetImportOutcome::ValidationError(_) => self.failed += 1,
+        }
+        self.rows.push(AssetImportRowResult { row_number, asset_id, outcome });
+    }
+
+    pub(crate) fn roll_back(&mut self) {
+        self.created = 0;
+        self.rolled_back = true;
+    }
+}
+
+/// Parse asset rows from `json` (an array of objects) or `csv` (header row naming the
+/// `AssetImportRow` fields)
+pub fn parse_asset_rows(input: &str, format: &str) -> IclResult<Vec<Result<AssetImportRow, String>>> {
+    match format {
+        "json" => {
+            let values: Vec<serde_json::Value> = serde_json::from_str(input)?;
+            Ok(values.into_iter()
+                .map(|v| serde_json::from_value(v).map_err(|e| e.to_string()))
+                .collect())
+        },
+        "csv" => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(input.as_bytes());
+            let header = reader.headers()
+                .map_err(|e| IclError::SerializationError(format!("Invalid CSV header: {}", e)))?
+                .clone();
+            if header.is_empty() {
+                return Err(IclError::SerializationError("CSV input has no header row".into()));
+            }
+            Ok(reader.records()
+                .map(|record| record
+                    .and_then(|record| record.deserialize(Some(&header)))
+                    .map_err(|e| e.to_string()))
+                .collect())
+        },
+        _ => Err(IclError::UnsupportedFormat(format.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ledger::IntelligenceCapitalLedger;
+
+    const CSV: &str = "asset_id,owner,initial_value,depreciation_method,useful_life_months\n\
+        ,\"Research, \"\"Vision\"\"\nTeam\",1200.0,Linear,12\n\
+        ,Team B,not-a-number,Linear,12\n";
+
+    #[test]
+    fn test_csv_rows_parse_quoted_fields() {
+        let rows = parse_asset_rows(CSV, "csv").unwrap();
+        assert_eq!(rows.len(), 2);
+        let row = rows[0].as_ref().unwrap();
+        assert_eq!(row.asset_id, None);
+        assert_eq!(row.owner, "Research, \"Vision\"\nTeam");
+        assert!(rows[1].is_err());
+    }
+
+    #[test]
+    fn test_all_or_nothing_rollback_clears_created_count() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let report = ledger.import_assets(CSV.as_bytes(), "csv", ImportMode::AllOrNothing).unwrap();
+        assert!(report.rolled_back);
+        assert_eq!((report.created, report.failed), (0, 1));
+        assert_eq!(ledger.asset_count(), 0);
+    }
+}
@@ -2,6 +2,13 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::connectors::*;
+use crate::core::outbox::OutboxMessage;
+use crate::core::reconciliation::{ExternalGlEntry, ReconciliationReport, Reconciler};
+use crate::core::journal_templates::JournalTemplate;
+use crate::core::integrity::IntegrityChecker;
 use crate::core::error::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,65 +20,554 @@ pub struct ICAEAttribution {
     pub model_version: String,
 }
 
-#[derive(Debug)]
+/// Maximum number of per-line errors kept in an `ImportSummary`; further errors are only counted
+pub const MAX_REPORTED_IMPORT_ERRORS: usize = 1_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AttributionLine {
+    attribution_id: String,
+    #[serde(flatten)]
+    attribution: ICAEAttribution,
+}
+
+/// A line that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportLineError {
+    pub line_number: usize,
+    pub attribution_id: Option<String>,
+    pub message: String,
+}
+
+/// Outcome of a bulk attribution import
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub lines_read: usize,
+    pub imported: usize,
+    pub events_recorded: usize,
+    pub duplicates_skipped: usize,
+    pub suspended: usize,
+    pub error_count: usize,
+    pub errors: Vec<ImportLineError>,
+}
+
+impl ImportSummary {
+    fn push_error(&mut self, line_number: usize, attribution_id: Option<String>, message: String) {
+        self.error_count += 1;
+        if self.errors.len() < MAX_REPORTED_IMPORT_ERRORS {
+            self.errors.push(ImportLineError { line_number, attribution_id, message });
+        }
+    }
+}
+
+/// What to do with an attribution whose asset ID is well-formed but unknown to the ledger
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum UnknownAssetPolicy {
+    #[default]
+    Reject,
+    Suspense,
+}
+
+impl std::fmt::Display for UnknownAssetPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnknownAssetPolicy::Reject => write!(f, "Reject"),
+            UnknownAssetPolicy::Suspense => write!(f, "Suspense"),
+        }
+    }
+}
+
+/// An attributed cost parked because its asset was not yet in the ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuspenseItem {
+    pub attribution_id: String,
+    pub asset_id: AssetId,
+    pub attribution: ICAEAttribution,
+    pub parked_at: DateTime<Utc>,
+}
+
+/// How aggregated inference spend is booked by `amortize_inference_costs`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AmortizationPolicy {
+    Expense,
+    UnitsOfProduction { expected_lifetime_cost: f64 },
+}
+
+/// Amortization posted for one asset in a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmortizationLine {
+    pub asset_id: AssetId,
+    pub inference_cost: f64,
+    pub attribution_count: usize,
+    pub posted_amount: f64,
+    pub event_id: Option<EventId>,
+}
+
+/// Result of one amortization run over a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmortizationRun {
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub policy: AmortizationPolicy,
+    pub lines: Vec<AmortizationLine>,
+    pub total_cost: f64,
+    pub total_posted: f64,
+}
+
+/// Outcome of one pass over the emission outbox
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EmissionRun {
+    pub queued: usize,
+    pub delivered: usize,
+    pub failed: usize,
+}
+
+/// Destination that receives ledger records as they are emitted
+pub trait EventSink: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn emit_event(&self, event: &CapitalEvent) -> IclResult<()>;
+
+    fn emit_journal_entry(&self, journal_entry: &JournalEntry) -> IclResult<()>;
+}
+
 pub struct IntegrationAdapter {
     icae_data: std::collections::HashMap<String, ICAEAttribution>,
-    financial_systems: Vec<String>, // Simulated financial systems
+    processed_attributions: std::collections::HashSet<String>,
+    amortized_attributions: std::collections::HashSet<String>,
+    sinks: Vec<Box<dyn EventSink>>,
+    financial_systems: ConnectorRegistry,
+    unknown_asset_policy: UnknownAssetPolicy,
+    suspense: std::collections::BTreeMap<String, SuspenseItem>,
 }
 
 impl IntegrationAdapter {
     pub fn new() -> Self {
         Self {
             icae_data: std::collections::HashMap::new(),
-            financial_systems: vec![],
+            processed_attributions: std::collections::HashSet::new(),
+            amortized_attributions: std::collections::HashSet::new(),
+            sinks: Vec::new(),
+            financial_systems: ConnectorRegistry::new(),
+            unknown_asset_policy: UnknownAssetPolicy::default(),
+            suspense: std::collections::BTreeMap::new(),
         }
     }
 
-    pub fn consume_icae_attribution(&mut self, attribution_data: &serde_json::Value) -> IclResult<()> {
-        // In a real implementation, we'd validate and process the data
-        if let Some(obj) = attribution_data.as_object() {
-            for (key, value) in obj {
-                if let Ok(attribution) = serde_json::from_value::<ICAEAttribution>(value.clone()) {
-                    if attribution.inference_cost < 0.0 {
-                        return Err(IclError::IntegrationError(
-                            format!("Invalid inference cost for {}: must be non-negative", key)
-                        ));
+    pub fn unknown_asset_policy(&self) -> UnknownAssetPolicy {
+        self.unknown_asset_policy
+    }
+
+    pub fn set_unknown_asset_policy(&mut self, policy: UnknownAssetPolicy) {
+        self.unknown_asset_policy = policy;
+    }
+
+    /// Ingest ICAE attribution records keyed by attribution ID, recording a `utilization` event
+    /// (amount = inference cost) against each attributed asset
+    pub fn consume_icae_attribution(
+        &mut self,
+        ledger: &mut IntelligenceCapitalLedger,
+        attribution_data: &serde_json::Value
+    ) -> IclResult<Vec<CapitalEvent>> {
+        let obj = attribution_data.as_object()
+            .ok_or_else(|| IclError::IntegrationError("Attribution data must be an object".into()))?;
+
+        let mut batch = Vec::new();
+        let mut parked = Vec::new();
+        for (key, value) in obj {
+            if self.processed_attributions.contains(key) {
+                continue;
+            }
+            let attribution = serde_json::from_value::<ICAEAttribution>(value.clone()).map_err(|_| {
+                IclError::IntegrationError(format!("Invalid attribution data format for {}", key))
+            })?;
+            match self.validate_icae_record(ledger, key, &attribution) {
+                Ok(asset_id) => batch.push((key.clone(), asset_id, attribution)),
+                Err(IclError::AssetNotFound(asset_id)) if self.unknown_asset_policy == UnknownAssetPolicy::Suspense => {
+                    parked.push((key.clone(), asset_id, attribution));
+                },
+                Err(e) => return Err(e),
+            }
+        }
+
+        // Events already recorded for the batch are rolled back if a later one is rejected
+        let recorded = ledger.atomically(|ledger| {
+            let mut recorded = Vec::new();
+            for (attribution_id, asset_id, attribution) in &batch {
+                recorded.extend(self.ingest_attribution(ledger, attribution_id, *asset_id, attribution)?);
+            }
+            Ok(recorded)
+        })?;
+
+        for (attribution_id, asset_id, attribution) in parked {
+            self.park(attribution_id, asset_id, attribution, ledger.now());
+        }
+        for (attribution_id, _, attribution) in batch {
+            self.processed_attributions.insert(attribution_id.clone());
+            self.icae_data.insert(attribution_id, attribution);
+        }
+        Ok(recorded)
+    }
+
+    /// Stream ICAE attributions from JSON Lines, one object per line carrying an `attribution_id`
+    /// alongside the attribution fields
+    pub fn import_attributions_jsonl<R: std::io::BufRead>(
+        &mut self,
+        ledger: &mut IntelligenceCapitalLedger,
+        reader: R
+    ) -> IclResult<ImportSummary> {
+        let mut summary = ImportSummary::default();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_number = index + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            summary.lines_read += 1;
+
+            let parsed = match serde_json::from_str::<AttributionLine>(&line) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    summary.push_error(line_number, None, format!("Invalid attribution: {}", e));
+                    continue;
+                },
+            };
+            let AttributionLine { attribution_id, attribution } = parsed;
+            if self.processed_attributions.contains(&attribution_id) {
+                summary.duplicates_skipped += 1;
+                continue;
+            }
+
+            let result = self.validate_icae_record(ledger, &attribution_id, &attribution)
+                .and_then(|asset_id| self.ingest_attribution(ledger, &attribution_id, asset_id, &attribution));
+            match result {
+                Ok(event) => {
+                    summary.imported += 1;
+                    if event.is_some() {
+                        summary.events_recorded += 1;
                     }
-                    self.icae_data.insert(key.clone(), attribution);
-                } else {
-                    return Err(IclError::IntegrationError(
-                        format!("Invalid attribution data format for {}", key)
-                    ));
-                }
+                    self.processed_attributions.insert(attribution_id.clone());
+                    self.icae_data.insert(attribution_id, attribution);
+                },
+                Err(IclError::AssetNotFound(asset_id)) if self.unknown_asset_policy == UnknownAssetPolicy::Suspense => {
+                    self.park(attribution_id, asset_id, attribution, ledger.now());
+                    summary.suspended += 1;
+                },
+                Err(e) => summary.push_error(line_number, Some(attribution_id), e.to_string()),
+            }
+        }
+        Ok(summary)
+    }
+
+    fn park(&mut self, attribution_id: String, asset_id: AssetId, attribution: ICAEAttribution, parked_at: DateTime<Utc>) {
+        self.processed_attributions.insert(attribution_id.clone());
+        self.suspense.insert(attribution_id.clone(), SuspenseItem {
+            attribution_id,
+            asset_id,
+            attribution,
+            parked_at,
+        });
+    }
+
+    pub fn suspense_items(&self) -> impl Iterator<Item = &SuspenseItem> {
+        self.suspense.values()
+    }
+
+    pub fn suspense_total(&self) -> f64 {
+        self.suspense.values().map(|item| item.attribution.inference_cost).sum()
+    }
+
+    /// Ingest every suspended attribution for an asset that now exists in the ledger
+    pub fn resolve_suspense(
+        &mut self,
+        ledger: &mut IntelligenceCapitalLedger,
+        asset_id: AssetId
+    ) -> IclResult<Vec<CapitalEvent>> {
+        if ledger.get_asset(asset_id).is_none() {
+            return Err(IclError::AssetNotFound(asset_id));
+        }
+        let attribution_ids: Vec<String> = self.suspense.values()
+            .filter(|item| item.asset_id == asset_id)
+            .map(|item| item.attribution_id.clone())
+            .collect();
+
+        let mut recorded = Vec::new();
+        for attribution_id in attribution_ids {
+            let attribution = self.suspense[&attribution_id].attribution.clone();
+            if let Some(event) = self.ingest_attribution(ledger, &attribution_id, asset_id, &attribution)? {
+                recorded.push(event);
             }
-            Ok(())
-        } else {
-            Err(IclError::IntegrationError("Attribution data must be an object".into()))
+            self.suspense.remove(&attribution_id);
+            self.icae_data.insert(attribution_id, attribution);
         }
+        Ok(recorded)
     }
 
+    fn validate_icae_record(
+        &self,
+        ledger: &IntelligenceCapitalLedger,
+        attribution_id: &str,
+        attribution: &ICAEAttribution
+    ) -> IclResult<AssetId> {
+        if attribution.inference_cost < 0.0 {
+            return Err(IclError::IntegrationError(
+                format!("Invalid inference cost for {}: must be non-negative", attribution_id)
+            ));
+        }
+        let asset_id: AssetId = attribution.asset_id.parse().map_err(|_| {
+            IclError::IntegrationError(format!("Invalid asset ID '{}' for {}", attribution.asset_id, attribution_id))
+        })?;
+        if ledger.get_asset(asset_id).is_none() {
+            return Err(IclError::AssetNotFound(asset_id));
+        }
+        Ok(asset_id)
+    }
+
+    fn ingest_attribution(
+        &self,
+        ledger: &mut IntelligenceCapitalLedger,
+        attribution_id: &str,
+        asset_id: AssetId,
+        attribution: &ICAEAttribution
+    ) -> IclResult<Option<CapitalEvent>> {
+        if attribution.inference_cost <= 0.0 {
+            return Ok(None);
+        }
+        let mut details = std::collections::HashMap::new();
+        details.insert("amount".to_string(), serde_json::json!(attribution.inference_cost));
+        details.insert("attribution_id".to_string(), serde_json::json!(attribution_id));
+        details.insert("execution_time".to_string(), serde_json::json!(attribution.execution_time));
+        details.insert("model_version".to_string(), serde_json::json!(attribution.model_version));
+        details.insert("attributed_at".to_string(), serde_json::json!(attribution.timestamp.to_rfc3339()));
+        details.insert("source".to_string(), serde_json::json!("icae"));
+
+        let event = CapitalEvent::new(asset_id, "utilization", ledger.now(), details).with_id(ledger.next_id());
+        ledger.record_event(event).map(Some)
+    }
+
+    /// Aggregate ingested inference costs per asset for the period and post them according to the
+    /// policy
+    pub fn amortize_inference_costs(
+        &mut self,
+        ledger: &mut IntelligenceCapitalLedger,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        policy: AmortizationPolicy
+    ) -> IclResult<AmortizationRun> {
+        if period_start >= period_end {
+            return Err(IclError::InvalidDateRange {
+                start: period_start.to_rfc3339(),
+                end: period_end.to_rfc3339(),
+            });
+        }
+        if let AmortizationPolicy::UnitsOfProduction { expected_lifetime_cost } = policy {
+            if expected_lifetime_cost <= 0.0 {
+                return Err(IclError::IntegrationError("Expected lifetime cost must be positive".into()));
+            }
+        }
+
+        let mut costs: std::collections::BTreeMap<AssetId, (f64, Vec<String>)> = std::collections::BTreeMap::new();
+        for (attribution_id, attribution) in &self.icae_data {
+            if self.amortized_attributions.contains(attribution_id)
+                || attribution.timestamp < period_start
+                || attribution.timestamp >= period_end
+            {
+                continue;
+            }
+            if let Ok(asset_id) = attribution.asset_id.parse::<AssetId>() {
+                let entry = costs.entry(asset_id).or_default();
+                entry.0 += attribution.inference_cost;
+                entry.1.push(attribution_id.clone());
+            }
+        }
+
+        let mut run = AmortizationRun {
+            period_start,
+            period_end,
+            policy,
+            lines: Vec::new(),
+            total_cost: 0.0,
+            total_posted: 0.0,
+        };
+
+        // A failure part-way through rolls back the postings already made for earlier assets
+        let checkpoint = ledger.checkpoint();
+        let mut amortized = Vec::new();
+        for (asset_id, (cost, attribution_ids)) in costs {
+            let posted = match policy {
+                AmortizationPolicy::Expense => post_inference_expense(ledger, asset_id, cost, period_start, period_end),
+                AmortizationPolicy::UnitsOfProduction { expected_lifetime_cost } => post_units_of_production(
+                    ledger, asset_id, cost, expected_lifetime_cost, period_start, period_end
+                ),
+            };
+            let event = match posted {
+                Ok(event) => event,
+                Err(e) => {
+                    ledger.rollback(checkpoint);
+                    return Err(e);
+                },
+            };
+            let posted_amount = event.as_ref()
+                .and_then(|e| e.details.get("amount").and_then(|v| v.as_f64()))
+                .unwrap_or(0.0);
+
+            run.total_cost += cost;
+            run.total_posted += posted_amount;
+            run.lines.push(AmortizationLine {
+                asset_id,
+                inference_cost: cost,
+                attribution_count: attribution_ids.len(),
+                posted_amount,
+                event_id: event.map(|e| e.event_id),
+            });
+            amortized.extend(attribution_ids);
+        }
+        ledger.commit(checkpoint);
+        self.amortized_attributions.extend(amortized);
+
+        Ok(run)
+    }
+
+    pub fn is_attribution_processed(&self, attribution_id: &str) -> bool {
+        self.processed_attributions.contains(attribution_id)
+    }
+
+    pub fn register_sink(&mut self, sink: impl EventSink + 'static) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    pub fn sink_count(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Forward a serialized capital event or journal entry to every registered sink
     pub fn emit_to_financial_system(&self, event: &serde_json::Value) -> IclResult<bool> {
         if event.is_null() {
             return Err(IclError::IntegrationError("Event cannot be null".into()));
         }
-        // Production: integrate with actual financial systems
-        Ok(true)
+        if let Ok(capital_event) = serde_json::from_value::<CapitalEvent>(event.clone()) {
+            return Ok(self.emit_event(&capital_event)? > 0);
+        }
+        if let Ok(journal_entry) = serde_json::from_value::<JournalEntry>(event.clone()) {
+            return Ok(self.emit_journal_entry(&journal_entry)? > 0);
+        }
+        Err(IclError::IntegrationError("Payload is neither a capital event nor a journal entry".into()))
+    }
+
+    pub fn emit_event(&self, event: &CapitalEvent) -> IclResult<usize> {
+        self.dispatch(|sink| sink.emit_event(event))
+    }
+
+    /// Deliver a journal entry to every sink, returning how many accepted it
+    pub fn emit_journal_entry(&self, journal_entry: &JournalEntry) -> IclResult<usize> {
+        self.dispatch(|sink| sink.emit_journal_entry(journal_entry))
+    }
+
+    /// Queue every event and journal entry recorded since the previous call in the ledger's
+    /// emission outbox, then deliver everything that is due
+    pub fn emit_new_records(&self, ledger: &mut IntelligenceCapitalLedger) -> IclResult<EmissionRun> {
+        let queued = self.queue_new_records(ledger)?;
+        let mut run = self.deliver_pending(ledger)?;
+        run.queued = queued;
+        Ok(run)
+    }
+
+    /// Add one outbox message per registered sink for each record the sink has not yet been sent
+    pub fn queue_new_records(&self, ledger: &mut IntelligenceCapitalLedger) -> IclResult<usize> {
+        let mut queued = 0;
+        for sink in &self.sinks {
+            let events_stream = format!("{}:events", sink.name());
+            let journal_stream = format!("{}:journal_entries", sink.name());
+
+            let events = ledger.events().iter()
+                .skip(ledger.emission_outbox().position(&events_stream))
+                .map(|e| emission_message(ledger, sink.name(), "capital_event", e.event_id.into(), e))
+                .collect::<IclResult<Vec<_>>>()?;
+            let journal_entries = ledger.journal_entries().iter()
+                .skip(ledger.emission_outbox().position(&journal_stream))
+                .map(|j| emission_message(ledger, sink.name(), "journal_entry", j.entry_id.into(), j))
+                .collect::<IclResult<Vec<_>>>()?;
+
+            queued += events.len() + journal_entries.len();
+            let (event_count, journal_count) = (ledger.events().len(), ledger.journal_entries().len());
+            let outbox = ledger.emission_outbox_mut();
+            outbox.enqueue_batch(events, &events_stream, event_count)?;
+            outbox.enqueue_batch(journal_entries, &journal_stream, journal_count)?;
+        }
+        Ok(queued)
     }
 
-    pub fn validate_attribution(&self, asset_id: Uuid, _execution_details: &serde_json::Value) -> bool {
-        self.icae_data.contains_key(&asset_id.to_string())
+    /// Attempt delivery of every due outbox message to the sink it is addressed to
+    pub fn deliver_pending(&self, ledger: &mut IntelligenceCapitalLedger) -> IclResult<EmissionRun> {
+        let mut run = EmissionRun::default();
+        let due: Vec<OutboxMessage> = ledger.emission_outbox().due(ledger.now()).cloned().collect();
+        for message in due {
+            let Some(sink) = self.sinks.iter().find(|s| s.name() == message.topic) else {
+                continue;
+            };
+            match deliver_message(sink.as_ref(), &message) {
+                Ok(()) => {
+                    ledger.mark_delivered(message.message_id)?;
+                    run.delivered += 1;
+                },
+                Err(e) => {
+                    ledger.emission_outbox_mut().record_failure(message.message_id, &e.to_string())?;
+                    run.failed += 1;
+                },
+            }
+        }
+        ledger.emission_outbox_mut().prune_delivered()?;
+        Ok(run)
     }
 
-    pub fn get_execution_attribution(&self, asset_id: Uuid) -> Option<&ICAEAttribution> {
-        self.icae_data.get(&asset_id.to_string())
+    fn dispatch(&self, deliver: impl Fn(&dyn EventSink) -> IclResult<()>) -> IclResult<usize> {
+        let mut failures = Vec::new();
+        for sink in &self.sinks {
+            if let Err(e) = deliver(sink.as_ref()) {
+                failures.push(format!("{}: {}", sink.name(), e));
+            }
+        }
+        if !failures.is_empty() {
+            return Err(IclError::IntegrationError(format!("Emission failed for {}", failures.join("; "))));
+        }
+        Ok(self.sinks.len())
     }
 
-    pub fn reconcile_with_financial_systems(&self) -> serde_json::Value {
-        serde_json::json!({
-            "status": "reconciled",
-            "timestamp": Utc::now().to_rfc3339(),
-            "attribution_count": self.icae_data.len(),
-        })
+    pub fn validate_attribution(&self, asset_id: AssetId, _execution_details: &serde_json::Value) -> bool {
+        self.get_execution_attribution(asset_id).is_some()
+    }
+
+    /// Most recent attribution ingested for the asset
+    pub fn get_execution_attribution(&self, asset_id: AssetId) -> Option<&ICAEAttribution> {
+        let asset_id = asset_id.to_string();
+        self.icae_data.values()
+            .filter(|a| a.asset_id == asset_id)
+            .max_by_key(|a| a.timestamp)
+    }
+
+    pub fn register_financial_system(&mut self, connector: impl FinancialSystemConnector + 'static) -> IclResult<()> {
+        self.financial_systems.register(connector)
+    }
+
+    pub fn financial_systems(&self) -> &ConnectorRegistry {
+        &self.financial_systems
+    }
+
+    pub fn financial_systems_mut(&mut self) -> &mut ConnectorRegistry {
+        &mut self.financial_systems
+    }
+
+    /// Push journal entries not yet delivered to each registered financial system
+    pub fn push_journal_entries(&mut self, ledger: &IntelligenceCapitalLedger) -> IclResult<Vec<BatchReceipt>> {
+        self.financial_systems.push_new_journal_entries(ledger)
+    }
+
+    /// Match the ledger's journal entries against postings exported from an external general ledger
+    pub fn reconcile_with_financial_systems(
+        &self,
+        ledger: &IntelligenceCapitalLedger,
+        external_entries: &[ExternalGlEntry]
+    ) -> ReconciliationReport {
+        Reconciler::new(ledger).reconcile_entries(external_entries)
     }
 
     pub fn clear_attributions(&mut self) {
@@ -83,8 +579,223 @@ impl IntegrationAdapter {
     }
 }
 
+impl std::fmt::Debug for IntegrationAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IntegrationAdapter")
+            .field("attributions", &self.icae_data.len())
+            .field("processed_attributions", &self.processed_attributions.len())
+            .field("sinks", &self.sinks.iter().map(|s| s.name()).collect::<Vec<_>>())
+            .field("financial_systems", &self.financial_systems)
+            .field("unknown_asset_policy", &self.unknown_asset_policy)
+            .field("suspense", &self.suspense.len())
+            .finish()
+    }
+}
+
 impl Default for IntegrationAdapter {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+fn emission_message<T: Serialize>(
+    ledger: &IntelligenceCapitalLedger,
+    sink: &str,
+    kind: &str,
+    record_id: Uuid,
+    record: &T
+) -> IclResult<OutboxMessage> {
+    let payload = serde_json::to_vec(&serde_json::json!({ "kind": kind, "data": record }))?;
+    Ok(OutboxMessage::new(sink, record_id.to_string(), payload, ledger.now()).with_id(ledger.next_id()))
+}
+
+fn deliver_message(sink: &dyn EventSink, message: &OutboxMessage) -> IclResult<()> {
+    let envelope: serde_json::Value = serde_json::from_slice(&message.payload)?;
+    let data = envelope.get("data").cloned().unwrap_or_default();
+    match envelope.get("kind").and_then(|k| k.as_str()) {
+        Some("capital_event") => sink.emit_event(&serde_json::from_value(data)?),
+        Some("journal_entry") => sink.emit_journal_entry(&serde_json::from_value(data)?),
+        other => Err(IclError::IntegrationError(format!("Unknown emission kind {:?}", other))),
+    }
+}
+
+fn post_inference_expense(
+    ledger: &mut IntelligenceCapitalLedger,
+    asset_id: AssetId,
+    cost: f64,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>
+) -> IclResult<Option<CapitalEvent>> {
+    if cost <= 0.0 {
+        return Ok(None);
+    }
+
+    let mut details = std::collections::HashMap::new();
+    details.insert("amount".to_string(), serde_json::json!(cost));
+    details.insert("start_date".to_string(), serde_json::json!(period_start.to_rfc3339()));
+    details.insert("end_date".to_string(), serde_json::json!(period_end.to_rfc3339()));
+    details.insert("policy".to_string(), serde_json::json!("expense"));
+    let event = CapitalEvent::new(asset_id, "inference_expense", ledger.now(), details).with_id(ledger.next_id());
+    let event = ledger.record_event(event)?;
+
+    let mut values = std::collections::HashMap::new();
+    values.insert("asset_id".to_string(), serde_json::json!(asset_id.to_string()));
+    values.insert("amount".to_string(), serde_json::json!(cost));
+    ledger.post_journal_template(&JournalTemplate::inference_expense(), event.event_id, &values)?;
+
+    Ok(Some(event))
+}
+
+fn post_units_of_production(
+    ledger: &mut IntelligenceCapitalLedger,
+    asset_id: AssetId,
+    cost: f64,
+    expected_lifetime_cost: f64,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>
+) -> IclResult<Option<CapitalEvent>> {
+    let asset = ledger.get_asset(asset_id).ok_or(IclError::AssetNotFound(asset_id))?;
+    if asset.status == AssetStatus::Retired {
+        return Ok(None);
+    }
+    if let Some(pool) = ledger.pool_of(asset_id) {
+        return Err(IclError::DepreciationError(format!(
+            "Asset {} is depreciated with pool {}; use depreciate_pool", asset_id, pool.name
+        )));
+    }
+    IntegrityChecker::new(ledger).validate_depreciation_period(asset_id, period_start, period_end)?;
+
+    let previous_value = asset.current_value.unwrap_or(asset.initial_value);
+    let amount = (asset.initial_value * cost / expected_lifetime_cost).min(previous_value);
+    if amount <= 0.0 {
+        return Ok(None);
+    }
+    let new_value = previous_value - amount;
+
+    let mut details = std::collections::HashMap::new();
+    details.insert("amount".to_string(), serde_json::json!(amount));
+    details.insert("start_date".to_string(), serde_json::json!(period_start.to_rfc3339()));
+    details.insert("end_date".to_string(), serde_json::json!(period_end.to_rfc3339()));
+    details.insert("salvage_value".to_string(), serde_json::json!(0.0));
+    details.insert("previous_value".to_string(), serde_json::json!(previous_value));
+    details.insert("new_value".to_string(), serde_json::json!(new_value));
+    details.insert("basis".to_string(), serde_json::json!("units_of_production"));
+    details.insert("inference_cost".to_string(), serde_json::json!(cost));
+    details.insert("expected_lifetime_cost".to_string(), serde_json::json!(expected_lifetime_cost));
+    let event = CapitalEvent::new(asset_id, "depreciation", ledger.now(), details).with_id(ledger.next_id());
+    let event = ledger.record_event(event)?;
+
+    ledger.update_asset(asset_id, |asset| {
+        asset.current_value = Some(new_value);
+        if new_value <= 0.0 {
+            asset.status = AssetStatus::Depreciated;
+        }
+    })?;
+
+    let mut metadata = event.details.clone();
+    metadata.insert("asset_id".to_string(), serde_json::json!(asset_id.to_string()));
+    ledger.record_journal_entry(JournalEntry::new(
+        event.event_id,
+        event.timestamp,
+        AccountType::DepreciationExpense,
+        AccountType::AccumulatedDepreciation,
+        amount,
+        "Units-of-production depreciation",
+        metadata,
+    ).with_id(ledger.next_id()))?;
+
+    Ok(Some(event))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use chrono::Duration;
+    use crate::core::clock::{Clock, ManualClock};
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+
+    const FIRST: AssetId = AssetId::from_uuid(Uuid::from_u128(1));
+    const SECOND: AssetId = AssetId::from_uuid(Uuid::from_u128(2));
+
+    /// January's inference costs: 100 on the first asset over two attributions, 50 on the second
+    fn attributed_ledger() -> (IntelligenceCapitalLedger, IntegrationAdapter, DateTime<Utc>, DateTime<Utc>) {
+        let start: DateTime<Utc> = "2026-01-01T00:00:00Z".parse().unwrap();
+        let clock = Arc::new(ManualClock::new(start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(FIRST, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.capitalize(SECOND, "Team A", 600.0, DepreciationMethod::Linear, 12).unwrap();
+        clock.advance(Duration::days(31));
+
+        let attribution = |asset_id: AssetId, cost: f64, day: i64| serde_json::json!({
+            "asset_id": asset_id.to_string(),
+            "inference_cost": cost,
+            "execution_time": 1.5,
+            "timestamp": start + Duration::days(day),
+            "model_version": "v1",
+        });
+        let mut adapter = IntegrationAdapter::new();
+        adapter.consume_icae_attribution(&mut ledger, &serde_json::json!({
+            "a1": attribution(FIRST, 60.0, 3),
+            "a2": attribution(FIRST, 40.0, 20),
+            "b1": attribution(SECOND, 50.0, 10),
+        })).unwrap();
+        (ledger, adapter, start, clock.now())
+    }
+
+    #[test]
+    fn test_expense_policy_posts_operating_expense() {
+        let (mut ledger, mut adapter, start, end) = attributed_ledger();
+        let run = adapter.amortize_inference_costs(&mut ledger, start, end, AmortizationPolicy::Expense).unwrap();
+        assert_eq!((run.total_cost, run.total_posted), (150.0, 150.0));
+        let first = &run.lines[0];
+        assert_eq!((first.asset_id, first.attribution_count, first.posted_amount), (FIRST, 2, 100.0));
+
+        let event = ledger.get_event(first.event_id.unwrap()).unwrap();
+        assert_eq!(event.event_type, "inference_expense");
+        let posted: f64 = ledger.journal_entries_for_event(event.event_id).iter().map(|j| j.amount).sum();
+        assert_eq!(posted, 100.0);
+        assert_eq!(ledger.get_asset(FIRST).unwrap().current_value.unwrap_or(1200.0), 1200.0);
+
+        let rerun = adapter.amortize_inference_costs(&mut ledger, start, end, AmortizationPolicy::Expense).unwrap();
+        assert!(rerun.lines.is_empty());
+    }
+
+    #[test]
+    fn test_units_of_production_policy_depreciates_by_share_of_lifetime_cost() {
+        let (mut ledger, mut adapter, start, end) = attributed_ledger();
+        let policy = AmortizationPolicy::UnitsOfProduction { expected_lifetime_cost: 1000.0 };
+        let run = adapter.amortize_inference_costs(&mut ledger, start, end, policy).unwrap();
+        let posted: Vec<(AssetId, f64)> = run.lines.iter().map(|l| (l.asset_id, l.posted_amount)).collect();
+        assert_eq!(posted, vec![(FIRST, 120.0), (SECOND, 30.0)]);
+        assert_eq!(ledger.get_asset(FIRST).unwrap().current_value, Some(1080.0));
+
+        let event = ledger.get_event(run.lines[0].event_id.unwrap()).unwrap();
+        assert_eq!(event.event_type, "depreciation");
+        assert_eq!(event.details["basis"], "units_of_production");
+        let entries = ledger.journal_entries_for_event(event.event_id);
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].debit_account, entries[0].amount), (AccountType::DepreciationExpense, 120.0));
+
+        let invalid = AmortizationPolicy::UnitsOfProduction { expected_lifetime_cost: 0.0 };
+        assert!(adapter.amortize_inference_costs(&mut ledger, start, end, invalid).is_err());
+    }
+
+    #[test]
+    fn test_failed_amortization_rolls_back_earlier_postings() {
+        let (mut ledger, mut adapter, start, end) = attributed_ledger();
+        // The second asset's January is already depreciated, so its posting overlaps
+        IntelligenceCapitalLifecycle::new(&mut ledger).depreciate(SECOND, start, end, 0.0, 1.0).unwrap();
+        let before = serde_json::to_value(&ledger).unwrap();
+
+        let policy = AmortizationPolicy::UnitsOfProduction { expected_lifetime_cost: 1000.0 };
+        assert!(adapter.amortize_inference_costs(&mut ledger, start, end, policy).is_err());
+        assert_eq!(serde_json::to_value(&ledger).unwrap(), before);
+        assert!(ledger.assert_invariants().is_ok());
+
+        // Nothing was marked amortized, so the period can be booked another way
+        let run = adapter.amortize_inference_costs(&mut ledger, start, end, AmortizationPolicy::Expense).unwrap();
+        assert_eq!(run.total_posted, 150.0);
+    }
+}
@@ -1,10 +1,411 @@
+use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use serde::{Deserialize, Serialize};
 
 use crate::core::types::*;
-use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::ledger::{voided_event_id, IntelligenceCapitalLedger, LEDGER_SUBJECT, REVERSED_ENTRY_METADATA, VOID_EVENT};
+use crate::core::hashing::{record_chain_hash, ChainHead};
+use crate::core::archive::{bridges, chain_bridge, ArchiveStub, ChainLink, ARCHIVE_DIGEST_DETAIL, ARCHIVE_EVENT};
+use crate::core::capitalization::EXPENSED_EVENT;
+use crate::core::pools::{pool_share, POOL_DEPRECIATION_EVENT, POOL_DISPOSAL_EVENT};
+use crate::core::owners::OwnerRewrite;
 use crate::core::error::*;
 
+/// Absolute tolerance when comparing monetary amounts derived from floating-point arithmetic
+pub const VALUE_TOLERANCE: f64 = 1e-6;
+
+/// Amount an event moves, for the event types that must be reflected in the journal
+pub fn event_value_amount(event: &CapitalEvent) -> Option<f64> {
+    let key = match event.event_type.as_str() {
+        "capitalization" | "depreciation" | "impairment" | "revenue" | "obligation_expense" | EXPENSED_EVENT
+        | POOL_DEPRECIATION_EVENT | POOL_DISPOSAL_EVENT => "amount",
+        "retirement" => "retired_value",
+        _ => return None,
+    };
+    event.details.get(key).and_then(|v| v.as_f64())
+}
+
+/// Which record attribute time-ordering checks compare
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum OrderingKey {
+    #[default]
+    Timestamp,
+    Sequence,
+}
+
+/// How strictly records must be ordered when they arrive from multiple services
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct OrderingPolicy {
+    pub key: OrderingKey,
+    pub skew_tolerance_ms: i64,
+}
+
+impl OrderingPolicy {
+    pub fn with_skew_tolerance(skew_tolerance: chrono::Duration) -> Self {
+        Self {
+            key: OrderingKey::Timestamp,
+            skew_tolerance_ms: skew_tolerance.num_milliseconds(),
+        }
+    }
+
+    pub fn by_sequence() -> Self {
+        Self {
+            key: OrderingKey::Sequence,
+            skew_tolerance_ms: 0,
+        }
+    }
+
+    /// True when a record at (`timestamp`, `sequence`) may not follow one at (`prev_timestamp`, `prev_sequence`)
+    pub fn is_out_of_order(
+        &self,
+        prev_timestamp: DateTime<Utc>,
+        prev_sequence: u64,
+        timestamp: DateTime<Utc>,
+        sequence: u64
+    ) -> bool {
+        match self.key {
+            OrderingKey::Timestamp => {
+                timestamp + chrono::Duration::milliseconds(self.skew_tolerance_ms) < prev_timestamp
+            },
+            OrderingKey::Sequence => sequence <= prev_sequence,
+        }
+    }
+}
+
+/// Event detail holding the token of the `BackdatingApproval` an event is posted under
+pub const BACKDATING_APPROVAL_DETAIL: &str = "backdating_approval";
+/// Event detail recording who approved a backdated posting, added when the approval is spent
+pub const APPROVED_BY_DETAIL: &str = "approved_by";
+
+/// Limits on posting records with effective dates in the past
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct BackdatingPolicy {
+    pub allow_backdated_until: Option<DateTime<Utc>>,
+}
+
+impl BackdatingPolicy {
+    pub fn allow_backdated_until(until: DateTime<Utc>) -> Self {
+        Self { allow_backdated_until: Some(until) }
+    }
+
+    pub fn requires_approval(&self, effective_date: DateTime<Utc>) -> bool {
+        self.allow_backdated_until.is_some_and(|until| effective_date < until)
+    }
+}
+
+/// Single-use approval to post one event effective as early as `effective_from`, issued by
+/// `IntelligenceCapitalLedger::approve_backdating`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackdatingApproval {
+    pub token: Uuid,
+    pub approver: String,
+    pub effective_from: DateTime<Utc>,
+    pub issued_at: DateTime<Utc>,
+    #[serde(default)]
+    pub used_by: Option<EventId>,
+}
+
+/// How urgently an integrity finding needs attention
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "Info"),
+            Severity::Warning => write!(f, "Warning"),
+            Severity::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// Category of an integrity finding
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum FindingKind {
+    InvalidAsset,
+    InvalidEvent,
+    InvalidEntry,
+    TimeOrdering,
+    ProofChainBreak,
+    UnbookedEvent,
+    OrphanJournalEntry,
+    JournalAmountMismatch,
+    RecordChainBreak,
+    RecordTampered,
+    UnchainedRecord,
+    RuleViolation,
+    DepreciationOverage,
+    BookValueMismatch,
+    DuplicateRecord,
+    ConflictingRecord,
+    UnreversedVoid,
+}
+
+impl std::fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FindingKind::InvalidAsset => write!(f, "InvalidAsset"),
+            FindingKind::InvalidEvent => write!(f, "InvalidEvent"),
+            FindingKind::InvalidEntry => write!(f, "InvalidEntry"),
+            FindingKind::TimeOrdering => write!(f, "TimeOrdering"),
+            FindingKind::ProofChainBreak => write!(f, "ProofChainBreak"),
+            FindingKind::UnbookedEvent => write!(f, "UnbookedEvent"),
+            FindingKind::OrphanJournalEntry => write!(f, "OrphanJournalEntry"),
+            FindingKind::JournalAmountMismatch => write!(f, "JournalAmountMismatch"),
+            FindingKind::RecordChainBreak => write!(f, "RecordChainBreak"),
+            FindingKind::RecordTampered => write!(f, "RecordTampered"),
+            FindingKind::UnchainedRecord => write!(f, "UnchainedRecord"),
+            FindingKind::RuleViolation => write!(f, "RuleViolation"),
+            FindingKind::DepreciationOverage => write!(f, "DepreciationOverage"),
+            FindingKind::BookValueMismatch => write!(f, "BookValueMismatch"),
+            FindingKind::DuplicateRecord => write!(f, "DuplicateRecord"),
+            FindingKind::ConflictingRecord => write!(f, "ConflictingRecord"),
+            FindingKind::UnreversedVoid => write!(f, "UnreversedVoid"),
+        }
+    }
+}
+
+/// A single problem found by an integrity check, linked to the offending record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityFinding {
+    pub finding_kind: FindingKind,
+    pub severity: Severity,
+    pub record_id: Option<Uuid>,
+    pub message: String,
+}
+
+impl IntegrityFinding {
+    pub fn new(finding_kind: FindingKind, severity: Severity, record_id: Option<Uuid>, message: impl Into<String>) -> Self {
+        Self {
+            finding_kind,
+            severity,
+            record_id,
+            message: message.into(),
+        }
+    }
+
+    pub fn error(finding_kind: FindingKind, record_id: Option<Uuid>, message: impl Into<String>) -> Self {
+        Self::new(finding_kind, Severity::Error, record_id, message)
+    }
+
+    pub fn warning(finding_kind: FindingKind, record_id: Option<Uuid>, message: impl Into<String>) -> Self {
+        Self::new(finding_kind, Severity::Warning, record_id, message)
+    }
+}
+
+impl std::fmt::Display for IntegrityFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.record_id {
+            Some(id) => write!(f, "[{}] {} {}: {}", self.severity, self.finding_kind, id, self.message),
+            None => write!(f, "[{}] {}: {}", self.severity, self.finding_kind, self.message),
+        }
+    }
+}
+
+/// Result of a full integrity pass over the ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub checked_at: DateTime<Utc>,
+    pub findings: Vec<IntegrityFinding>,
+}
+
+impl IntegrityReport {
+    /// Empty report of a check run at `checked_at`, typically the ledger's `now()`
+    pub fn new(checked_at: DateTime<Utc>) -> Self {
+        Self {
+            checked_at,
+            findings: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, finding: IntegrityFinding) {
+        self.findings.push(finding);
+    }
+
+    pub fn extend(&mut self, findings: impl IntoIterator<Item = IntegrityFinding>) {
+        self.findings.extend(findings);
+    }
+
+    pub fn is_clean(&self) -> bool {
+        !self.has_errors()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|f| f.severity == Severity::Error)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &IntegrityFinding> {
+        self.findings.iter().filter(|f| f.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &IntegrityFinding> {
+        self.findings.iter().filter(|f| f.severity == Severity::Warning)
+    }
+
+    pub fn by_kind(&self, kind: FindingKind) -> impl Iterator<Item = &IntegrityFinding> {
+        self.findings.iter().filter(move |f| f.finding_kind == kind)
+    }
+
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Records whose parent record no longer exists
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrphanReport {
+    pub ledger_entries: Vec<EntryId>,
+    pub journal_entries: Vec<EntryId>,
+    pub proofs: Vec<Uuid>,
+}
+
+impl OrphanReport {
+    pub fn len(&self) -> usize {
+        self.ledger_entries.len() + self.journal_entries.len() + self.proofs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn record_ids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.ledger_entries.iter()
+            .chain(&self.journal_entries)
+            .map(|&id| id.into())
+            .chain(self.proofs.iter().copied())
+    }
+}
+
+/// Custom invariant registered on a ledger and evaluated by `check_all_integrity`
+pub trait Rule: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn check(&self, _ledger: &IntelligenceCapitalLedger) -> Vec<IntegrityFinding> {
+        Vec::new()
+    }
+
+    /// Check a single event, either already recorded or about to be
+    fn check_event(&self, _ledger: &IntelligenceCapitalLedger, _event: &CapitalEvent) -> IclResult<()> {
+        Ok(())
+    }
+
+    /// Check a single journal entry, either already recorded or about to be
+    fn check_journal_entry(&self, _ledger: &IntelligenceCapitalLedger, _entry: &JournalEntry) -> IclResult<()> {
+        Ok(())
+    }
+}
+
+/// Rules registered on a ledger
+#[derive(Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<std::sync::Arc<dyn Rule>>,
+}
+
+impl RuleSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, rule: impl Rule + 'static) {
+        self.rules.push(std::sync::Arc::new(rule));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|r| r.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+impl std::fmt::Debug for RuleSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.rules.iter().map(|r| r.name())).finish()
+    }
+}
+
+/// Rejects any single depreciation larger than a fraction of the asset's initial value
+#[derive(Debug, Clone)]
+pub struct MaxDepreciationFractionRule {
+    pub max_fraction: f64,
+}
+
+impl Rule for MaxDepreciationFractionRule {
+    fn name(&self) -> &str {
+        "max_depreciation_fraction"
+    }
+
+    fn check_event(&self, ledger: &IntelligenceCapitalLedger, event: &CapitalEvent) -> IclResult<()> {
+        if event.event_type != "depreciation" {
+            return Ok(());
+        }
+        let (Some(asset), Some(amount)) = (
+            ledger.get_asset(event.asset_id),
+            event.details.get("amount").and_then(|v| v.as_f64()),
+        ) else {
+            return Ok(());
+        };
+        if amount > asset.initial_value * self.max_fraction + VALUE_TOLERANCE {
+            return Err(IclError::IntegrityViolation(format!(
+                "Depreciation of {} exceeds {}% of initial value {}",
+                amount, self.max_fraction * 100.0, asset.initial_value
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Requires every asset owner to appear in an approved directory
+#[derive(Debug, Clone)]
+pub struct OwnerDirectoryRule {
+    pub owners: std::collections::HashSet<String>,
+}
+
+impl Rule for OwnerDirectoryRule {
+    fn name(&self) -> &str {
+        "owner_directory"
+    }
+
+    fn check(&self, ledger: &IntelligenceCapitalLedger) -> Vec<IntegrityFinding> {
+        ledger.assets_iter()
+            .filter(|a| !self.owners.contains(a.owner.as_str()))
+            .map(|a| IntegrityFinding::error(
+                FindingKind::RuleViolation,
+                Some(a.asset_id.into()),
+                format!("owner_directory: owner '{}' is not in the directory", a.owner),
+            ))
+            .collect()
+    }
+
+    fn check_event(&self, _ledger: &IntelligenceCapitalLedger, event: &CapitalEvent) -> IclResult<()> {
+        let owner = match event.event_type.as_str() {
+            "capitalization" => event.details.get("owner"),
+            "allocation" => event.details.get("to_owner"),
+            _ => None,
+        };
+        match owner.and_then(|v| v.as_str()) {
+            Some(owner) if !self.owners.contains(owner) => Err(IclError::IntegrityViolation(
+                format!("Owner '{}' is not in the directory", owner)
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct IntegrityChecker<'a> {
     pub ledger: &'a IntelligenceCapitalLedger,
@@ -24,9 +425,7 @@ impl<'a> IntegrityChecker<'a> {
             return Err(IclError::IntegrityViolation("Initial value must be positive".into()));
         }
         
-        if asset.useful_life_months <= 0 {
-            return Err(IclError::IntegrityViolation("Useful life must be positive".into()));
-        }
+        asset.validate_schedule().map_err(IclError::IntegrityViolation)?;
 
         if let Some(cv) = asset.current_value {
             if cv < 0.0 {
@@ -36,13 +435,24 @@ impl<'a> IntegrityChecker<'a> {
                 return Err(IclError::IntegrityViolation("Current value cannot exceed initial value".into()));
             }
         }
+
+        if let Some(parent_asset_id) = asset.parent_asset_id {
+            if parent_asset_id == asset.asset_id || self.ledger.get_asset(parent_asset_id).is_none() {
+                return Err(IclError::IntegrityViolation(format!("Parent asset {} not found", parent_asset_id)));
+            }
+        }
         
         Ok(())
     }
 
     pub fn validate_event(&self, event: &CapitalEvent) -> IclResult<()> {
-        if !self.ledger.assets.contains_key(&event.asset_id) {
-            return Err(IclError::AssetNotFound(event.asset_id));
+        if event.asset_id != LEDGER_SUBJECT {
+            let asset = self.ledger.get_asset(event.asset_id).ok_or(IclError::AssetNotFound(event.asset_id))?;
+            if event.org_id != asset.org_id {
+                return Err(IclError::IntegrityViolation(format!(
+                    "Event namespace {} does not match asset namespace {}", event.org_id, asset.org_id
+                )));
+            }
         }
 
         if event.event_type.is_empty() {
@@ -53,13 +463,14 @@ impl<'a> IntegrityChecker<'a> {
     }
 
     pub fn validate_entry(&self, entry: &LedgerEntry) -> IclResult<()> {
-        if !self.ledger.assets.contains_key(&entry.asset_id) {
+        if entry.asset_id != LEDGER_SUBJECT && !self.ledger.assets().contains_key(&entry.asset_id) {
             return Err(IclError::AssetNotFound(entry.asset_id));
         }
 
-        if !self.ledger.entries.is_empty() {
-            let last_entry = &self.ledger.entries[self.ledger.entries.len() - 1];
-            if entry.timestamp < last_entry.timestamp {
+        if let Some(last_entry) = self.ledger.entries().last() {
+            if self.ledger.ordering_policy().is_out_of_order(
+                last_entry.recorded_at(), last_entry.sequence, entry.recorded_at(), entry.sequence
+            ) {
                 return Err(IclError::IntegrityViolation("Ledger entries must be time-ordered".into()));
             }
         }
@@ -67,41 +478,371 @@ impl<'a> IntegrityChecker<'a> {
         Ok(())
     }
 
-    pub fn check_all_integrity(&self) -> Vec<String> {
-        let mut errors = Vec::new();
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(duration_us = tracing::field::Empty)
+    ))]
+    pub fn check_all_integrity(&self) -> IntegrityReport {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let mut report = IntegrityReport::new(self.ledger.now());
 
-        for asset in self.ledger.assets.values() {
+        for asset in self.ledger.assets_iter() {
             if let Err(e) = self.validate_asset(asset) {
-                errors.push(format!("Asset {}: {}", asset.asset_id, e));
+                report.push(IntegrityFinding::error(FindingKind::InvalidAsset, Some(asset.asset_id.into()), e.to_string()));
             }
         }
 
         // Check events
-        for event in &self.ledger.events {
+        for event in self.ledger.events() {
             if let Err(e) = self.validate_event(event) {
-                errors.push(format!("Event {}: {}", event.event_id, e));
+                report.push(IntegrityFinding::error(FindingKind::InvalidEvent, Some(event.event_id.into()), e.to_string()));
             }
         }
 
-        // Check entries
-        for entry in &self.ledger.entries {
-            if let Err(e) = self.validate_entry(entry) {
-                errors.push(format!("Entry {}: {}", entry.entry_id, e));
-            }
+        // Check entries, each against its predecessor
+        for (i, entry) in self.ledger.entries().iter().enumerate() {
+            report.extend(self.check_entry(i, entry));
         }
 
+        report.extend(self.check_event_journal_reconciliation());
+        report.extend(self.check_void_reversals());
+        report.extend(self.check_depreciation_totals());
+        report.extend(self.find_duplicates());
+        report.extend(self.verify_record_chain());
+
         // Verify proof chain integrity
-        let proof_errors = self.verify_proof_chain();
-        errors.extend(proof_errors);
+        report.extend(self.verify_proof_chain());
+
+        report.extend(self.run_rules());
+
+        self.ledger.metrics_recorder().integrity_checked(report.errors().count());
+        report
+    }
 
-        errors
+    /// Cross-check the event stream against the journal: every value-affecting event with a
+    /// positive amount must be booked in full, and every journal entry must point at an event
+    pub fn check_event_journal_reconciliation(&self) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+
+        let mut booked: std::collections::HashMap<EventId, f64> = std::collections::HashMap::new();
+        for journal_entry in self.ledger.journal_entries() {
+            *booked.entry(journal_entry.event_id).or_default() += journal_entry.amount;
+        }
+
+        let mut event_orgs = std::collections::HashMap::new();
+        for event in self.ledger.events() {
+            event_orgs.insert(event.event_id, &event.org_id);
+
+            let amount = match event_value_amount(event) {
+                Some(amount) if amount > 0.0 => amount,
+                _ => continue,
+            };
+            match booked.get(&event.event_id) {
+                None => findings.push(IntegrityFinding::error(
+                    FindingKind::UnbookedEvent,
+                    Some(event.event_id.into()),
+                    format!("{} event of {} has no journal entry", event.event_type, amount),
+                )),
+                Some(total) if (total - amount).abs() > VALUE_TOLERANCE => findings.push(IntegrityFinding::error(
+                    FindingKind::JournalAmountMismatch,
+                    Some(event.event_id.into()),
+                    format!("{} event of {} is booked as {}", event.event_type, amount, total),
+                )),
+                Some(_) => {},
+            }
+        }
+
+        for journal_entry in self.ledger.journal_entries() {
+            match event_orgs.get(&journal_entry.event_id) {
+                None => findings.push(IntegrityFinding::error(
+                    FindingKind::OrphanJournalEntry,
+                    Some(journal_entry.entry_id.into()),
+                    format!("Journal entry references unknown event {}", journal_entry.event_id),
+                )),
+                Some(org_id) if **org_id != journal_entry.org_id => findings.push(IntegrityFinding::error(
+                    FindingKind::InvalidEntry,
+                    Some(journal_entry.entry_id.into()),
+                    format!("Journal entry namespace {} does not match event namespace {}", journal_entry.org_id, org_id),
+                )),
+                Some(_) => {},
+            }
+        }
+
+        findings
+    }
+
+    /// Check every void: the voided event must exist, and each of its journal entries must be
+    /// reversed in full, debit and credit swapped, by a journal entry posted against the void
+    pub fn check_void_reversals(&self) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+        for void in self.ledger.events_of_type(VOID_EVENT) {
+            let Some(voided) = voided_event_id(void).and_then(|id| self.ledger.get_event(id)) else {
+                findings.push(IntegrityFinding::error(
+                    FindingKind::InvalidEvent,
+                    Some(void.event_id.into()),
+                    "Void does not reference a recorded event",
+                ));
+                continue;
+            };
+            let reversals = self.ledger.journal_entries_for_event(void.event_id);
+            for journal_entry in self.ledger.journal_entries_for_event(voided.event_id) {
+                let reversed = reversals.iter().any(|r| {
+                    r.metadata.get(REVERSED_ENTRY_METADATA).and_then(|v| v.as_str()) == Some(&journal_entry.entry_id.to_string())
+                        && r.debit_account == journal_entry.credit_account
+                        && r.credit_account == journal_entry.debit_account
+                        && (r.amount - journal_entry.amount).abs() <= VALUE_TOLERANCE
+                });
+                if !reversed {
+                    findings.push(IntegrityFinding::error(
+                        FindingKind::UnreversedVoid,
+                        Some(journal_entry.entry_id.into()),
+                        format!(
+                            "Journal entry of {} voided by event {} has no compensating reversal",
+                            journal_entry.amount, void.event_id
+                        ),
+                    ));
+                }
+            }
+        }
+        findings
+    }
+
+    /// Sweep for ledger entries and journal entries pointing at missing events, and proofs for
+    /// assets that are no longer in the ledger
+    pub fn find_orphans(&self) -> OrphanReport {
+        OrphanReport {
+            ledger_entries: self.ledger.entries().iter()
+                .filter(|e| self.ledger.get_event(e.event_id).is_none())
+                .map(|e| e.entry_id)
+                .collect(),
+            journal_entries: self.ledger.journal_entries().iter()
+                .filter(|j| self.ledger.get_event(j.event_id).is_none())
+                .map(|j| j.entry_id)
+                .collect(),
+            proofs: self.ledger.proofs().iter()
+                .filter(|p| !self.ledger.assets().contains_key(&p.asset_id))
+                .map(|p| p.proof_id)
+                .collect(),
+        }
+    }
+
+    /// Cross-check each asset's stored value against its event history: recorded depreciation
+    /// must stay within the depreciable base, and `current_value` must equal the initial value
+    /// less recorded depreciation, impairments, and any amount expensed
+    pub fn check_depreciation_totals(&self) -> Vec<IntegrityFinding> {
+        self.ledger.assets_iter().flat_map(|asset| self.check_asset_depreciation_totals(asset)).collect()
+    }
+
+    pub(crate) fn check_asset_depreciation_totals(&self, asset: &IntelligenceAsset) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+        let events = self.ledger.get_events_for_asset(asset.asset_id);
+        let amount_of = |kind: &str| -> f64 {
+            events.iter()
+                .filter(|e| e.event_type == kind && !self.ledger.is_voided(e.event_id))
+                .filter_map(|e| e.details.get("amount").and_then(|v| v.as_f64()))
+                .sum()
+        };
+        let archived = || self.ledger.archived_summaries(asset.asset_id, DateTime::<Utc>::MAX_UTC);
+        let archived_amount_of = |kind: &str| -> f64 {
+            archived().filter_map(|summary| summary.totals.amount_by_type.get(kind)).sum()
+        };
+        let total_depreciation = amount_of("depreciation") + archived_amount_of("depreciation");
+        let total_impairment = amount_of("impairment") + archived_amount_of("impairment");
+        let total_expensed = amount_of(EXPENSED_EVENT) + archived_amount_of(EXPENSED_EVENT);
+        let total_pool_depreciation = self.ledger.pool_depreciation_charging(asset.asset_id)
+            .filter_map(|e| pool_share(e, asset.asset_id))
+            .chain(archived().map(|summary| summary.pool_depreciation))
+            .sum::<f64>();
+        let salvage_value = events.iter()
+            .filter(|e| e.event_type == "depreciation")
+            .filter_map(|e| e.details.get("salvage_value").and_then(|v| v.as_f64()))
+            .chain(archived().map(|summary| summary.salvage_value))
+            .fold(0.0, f64::max);
+
+        let depreciable_base = asset.initial_value - salvage_value;
+        if total_depreciation > depreciable_base + VALUE_TOLERANCE {
+            findings.push(IntegrityFinding::error(
+                FindingKind::DepreciationOverage,
+                Some(asset.asset_id.into()),
+                format!("Recorded depreciation {} exceeds depreciable base {}", total_depreciation, depreciable_base),
+            ));
+        }
+
+        let expected_value = if asset.status == AssetStatus::Retired {
+            0.0
+        } else {
+            asset.initial_value - total_depreciation - total_impairment - total_expensed - total_pool_depreciation
+        };
+        if let Some(current_value) = asset.current_value {
+            if (current_value - expected_value).abs() > VALUE_TOLERANCE {
+                findings.push(IntegrityFinding::error(
+                    FindingKind::BookValueMismatch,
+                    Some(asset.asset_id.into()),
+                    format!("Current value {} does not match event history value {}", current_value, expected_value),
+                ));
+            }
+        }
+        findings
+    }
+
+    /// Find repeated event and journal entry IDs, and unvoided depreciation postings that repeat
+    /// (same asset, period, and amount) or conflict with (same asset and period) an earlier one
+    pub fn find_duplicates(&self) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+
+        let mut seen_events = std::collections::HashSet::new();
+        for event in self.ledger.events() {
+            if !seen_events.insert(event.event_id) {
+                findings.push(IntegrityFinding::error(
+                    FindingKind::DuplicateRecord,
+                    Some(event.event_id.into()),
+                    "Event ID is recorded more than once",
+                ));
+            }
+        }
+
+        let mut seen_entries = std::collections::HashSet::new();
+        for journal_entry in self.ledger.journal_entries() {
+            if !seen_entries.insert(journal_entry.entry_id) {
+                findings.push(IntegrityFinding::error(
+                    FindingKind::DuplicateRecord,
+                    Some(journal_entry.entry_id.into()),
+                    "Journal entry ID is recorded more than once",
+                ));
+            }
+        }
+
+        let mut postings: std::collections::HashMap<(AssetId, String, String), (EventId, f64)> = std::collections::HashMap::new();
+        for event in self.ledger.events_of_type("depreciation").filter(|e| !self.ledger.is_voided(e.event_id)) {
+            let detail = |key: &str| event.details.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+            let amount = event.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let key = (event.asset_id, detail("start_date"), detail("end_date"));
+            match postings.get(&key) {
+                Some((first_id, first_amount)) if (first_amount - amount).abs() <= VALUE_TOLERANCE => {
+                    findings.push(IntegrityFinding::error(
+                        FindingKind::DuplicateRecord,
+                        Some(event.event_id.into()),
+                        format!("Depreciation duplicates event {}", first_id),
+                    ));
+                },
+                Some((first_id, _)) => {
+                    findings.push(IntegrityFinding::error(
+                        FindingKind::ConflictingRecord,
+                        Some(event.event_id.into()),
+                        format!("Depreciation conflicts with event {} for the same period", first_id),
+                    ));
+                },
+                None => {
+                    postings.insert(key, (event.event_id, amount));
+                },
+            }
+        }
+
+        findings
+    }
+
+    pub fn run_rules(&self) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+        for rule in self.ledger.rules().iter() {
+            findings.extend(rule.check(self.ledger));
+            for event in self.ledger.events() {
+                if let Err(e) = rule.check_event(self.ledger, event) {
+                    findings.push(IntegrityFinding::error(
+                        FindingKind::RuleViolation,
+                        Some(event.event_id.into()),
+                        format!("{}: {}", rule.name(), e),
+                    ));
+                }
+            }
+            for journal_entry in self.ledger.journal_entries() {
+                if let Err(e) = rule.check_journal_entry(self.ledger, journal_entry) {
+                    findings.push(IntegrityFinding::error(
+                        FindingKind::RuleViolation,
+                        Some(journal_entry.entry_id.into()),
+                        format!("{}: {}", rule.name(), e),
+                    ));
+                }
+            }
+        }
+        findings
+    }
+
+    /// Walk the event, ledger entry, and journal hash chains, detecting inserted, removed,
+    /// reordered, or mutated records
+    pub fn verify_record_chain(&self) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+        let archives = self.committed_archives(&mut findings);
+        let purges: Vec<_> = self.ledger.retention_purges().collect();
+        let rewrites: Vec<_> = self.ledger.owner_rewrites().collect();
+        let rewritten = rewritten_digests(&rewrites);
+        let heads = self.ledger.record_chain_heads();
+        let algorithm = self.ledger.record_hash_algorithm();
+        let event_links: Vec<_> = archives.iter().flat_map(|a| &a.event_links).chain(purges.iter().flat_map(|p| &p.event_links)).collect();
+        verify_chain(
+            "Event",
+            self.ledger.events().iter().map(|e| (e.event_id, &e.previous_hash, &e.record_hash, record_chain_hash(algorithm, e))),
+            &chain_bridge(event_links.iter().copied()),
+            &rewritten,
+            &mut findings,
+        );
+        verify_chain_head("Event", self.ledger.events().iter().map(|e| &e.record_hash), &event_links, &heads.events, &mut findings);
+        let entry_links: Vec<_> = archives.iter().flat_map(|a| &a.entry_links).chain(purges.iter().flat_map(|p| &p.entry_links)).collect();
+        verify_chain(
+            "Ledger entry",
+            self.ledger.entries().iter().map(|e| (e.entry_id, &e.previous_hash, &e.record_hash, record_chain_hash(algorithm, e))),
+            &chain_bridge(entry_links.iter().copied()),
+            &rewritten,
+            &mut findings,
+        );
+        verify_chain_head("Ledger entry", self.ledger.entries().iter().map(|e| &e.record_hash), &entry_links, &heads.entries, &mut findings);
+        let journal_links: Vec<_> = archives.iter().flat_map(|a| &a.journal_links).collect();
+        verify_chain(
+            "Journal entry",
+            self.ledger.journal_entries().iter().map(|j| (j.entry_id, &j.previous_hash, &j.record_hash, record_chain_hash(algorithm, j))),
+            &chain_bridge(journal_links.iter().copied()),
+            &rewritten,
+            &mut findings,
+        );
+        verify_chain_head(
+            "Journal entry",
+            self.ledger.journal_entries().iter().map(|j| &j.record_hash),
+            &journal_links,
+            &heads.journal_entries,
+            &mut findings,
+        );
+        findings
+    }
+
+    pub(crate) fn committed_archives(&self, findings: &mut Vec<IntegrityFinding>) -> Vec<&'a ArchiveStub> {
+        let committed: HashMap<&str, &str> = self.ledger.events_of_type(ARCHIVE_EVENT)
+            .filter_map(|e| Some((e.details.get("archive_id")?.as_str()?, e.details.get(ARCHIVE_DIGEST_DETAIL)?.as_str()?)))
+            .collect();
+        self.ledger.archives().iter()
+            .filter(|stub| {
+                let is_committed = stub.digest().is_ok_and(|digest| {
+                    committed.get(stub.archive_id.to_string().as_str()) == Some(&digest.as_str())
+                });
+                if !is_committed || !stub.links_within_period() {
+                    findings.push(IntegrityFinding::error(
+                        FindingKind::RecordTampered,
+                        Some(stub.archive_id),
+                        format!("Archive stub for period {} is not committed to the record chain", stub.period),
+                    ));
+                }
+                is_committed && stub.links_within_period()
+            })
+            .collect()
     }
 
     pub fn ensure_no_retroactive_modification(&self, new_event: &CapitalEvent) -> IclResult<()> {
-        if let Some(last_event) = self.ledger.events.last() {
-            if new_event.timestamp < last_event.timestamp {
+        if let Some(last_event) = self.ledger.events().last() {
+            // Sequence numbers are assigned on write, so only wall-clock ordering applies to new events
+            let ordering = self.ledger.ordering_policy();
+            if ordering.key == OrderingKey::Timestamp
+                && ordering.is_out_of_order(last_event.recorded_at(), last_event.sequence, new_event.recorded_at(), new_event.sequence)
+            {
                 return Err(IclError::IntegrityViolation(
-                    "Cannot add event with timestamp before last recorded event".into()
+                    "Cannot add event recorded before last recorded event".into()
                 ));
             }
         }
@@ -110,7 +851,7 @@ impl<'a> IntegrityChecker<'a> {
     
     pub fn validate_depreciation_period(
         &self,
-        asset_id: Uuid,
+        asset_id: AssetId,
         start: DateTime<Utc>,
         end: DateTime<Utc>
     ) -> IclResult<()> {
@@ -123,7 +864,7 @@ impl<'a> IntegrityChecker<'a> {
 
         let existing_depreciations: Vec<&CapitalEvent> = self.ledger.get_events_for_asset(asset_id)
             .into_iter()
-            .filter(|e| e.event_type == "depreciation")
+            .filter(|e| e.event_type == "depreciation" && !self.ledger.is_voided(e.event_id))
             .collect();
         
         for dep_event in existing_depreciations {
@@ -149,36 +890,130 @@ impl<'a> IntegrityChecker<'a> {
         Ok(())
     }
 
-    pub fn verify_proof_chain(&self) -> Vec<String> {
-        let mut errors = Vec::new();
-        let mut proofs_by_asset: std::collections::HashMap<Uuid, Vec<&CapitalProof>> = 
-            std::collections::HashMap::new();
-        
-        for proof in &self.ledger.proofs {
-            proofs_by_asset.entry(proof.asset_id).or_default().push(proof);
-        }
-
-        for (asset_id, proofs) in proofs_by_asset {
-            let mut sorted_proofs = proofs;
-            sorted_proofs.sort_by_key(|p| p.timestamp);
-            
-            for i in 1..sorted_proofs.len() {
-                let prev = sorted_proofs[i - 1];
-                let curr = sorted_proofs[i];
-                
-                if let (Some(prev_hash), Some(curr_prev_hash)) = 
-                    (&prev.proof_hash, &curr.previous_proof_hash) 
-                {
-                    if prev_hash != curr_prev_hash {
-                        errors.push(format!(
-                            "Proof chain break for asset {}: proof {} references wrong previous hash",
-                            asset_id, curr.proof_id
-                        ));
-                    }
-                }
+    pub fn verify_proof_chain(&self) -> Vec<IntegrityFinding> {
+        self.ledger.proof_chains()
+            .flat_map(|(asset_id, proofs)| verify_asset_proof_chain(asset_id, proofs))
+            .collect()
+    }
+
+    pub(crate) fn check_entry(&self, i: usize, entry: &LedgerEntry) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+        if entry.asset_id != LEDGER_SUBJECT && !self.ledger.assets().contains_key(&entry.asset_id) {
+            findings.push(IntegrityFinding::error(
+                FindingKind::InvalidEntry,
+                Some(entry.entry_id.into()),
+                IclError::AssetNotFound(entry.asset_id).to_string(),
+            ));
+        }
+        let ordering = self.ledger.ordering_policy();
+        if i > 0 && ordering.is_out_of_order(
+            self.ledger.entries()[i - 1].recorded_at(), self.ledger.entries()[i - 1].sequence, entry.recorded_at(), entry.sequence
+        ) {
+            findings.push(IntegrityFinding::error(
+                FindingKind::TimeOrdering,
+                Some(entry.entry_id.into()),
+                "Ledger entries must be time-ordered",
+            ));
+        }
+        findings
+    }
+}
+
+pub(crate) fn verify_asset_proof_chain(asset_id: AssetId, mut proofs: Vec<&CapitalProof>) -> Vec<IntegrityFinding> {
+    let mut errors = Vec::new();
+    proofs.sort_by_key(|p| p.timestamp);
+    for pair in proofs.windows(2) {
+        let (prev, curr) = (pair[0], pair[1]);
+        if let (Some(prev_hash), Some(curr_prev_hash)) = (&prev.proof_hash, &curr.previous_proof_hash) {
+            if prev_hash != curr_prev_hash {
+                errors.push(IntegrityFinding::error(
+                    FindingKind::ProofChainBreak,
+                    Some(curr.proof_id),
+                    format!("Proof chain break for asset {}: references wrong previous hash", asset_id),
+                ));
             }
         }
-        
-        errors
     }
-}
\ No newline at end of file
+    errors
+}
+
+
+pub(crate) fn verify_chain_head<'r>(
+    label: &str,
+    record_hashes: impl ExactSizeIterator<Item = &'r Option<String>>,
+    links: &[&ChainLink],
+    expected: &ChainHead,
+    findings: &mut Vec<IntegrityFinding>
+) {
+    let held = record_hashes.len();
+    let last = record_hashes.last().and_then(Option::as_deref);
+    let head = expected.head.as_deref();
+    if last != head && !bridges(&chain_bridge(links.iter().copied()), last, head) {
+        findings.push(IntegrityFinding::error(
+            FindingKind::RecordChainBreak,
+            None,
+            format!("{} chain does not end at its recorded head", label),
+        ));
+    }
+    if held + links.len() != expected.length {
+        findings.push(IntegrityFinding::error(
+            FindingKind::RecordChainBreak,
+            None,
+            format!("{} chain holds {} records where {} were recorded", label, held + links.len(), expected.length),
+        ));
+    }
+}
+
+/// Digest each record rewritten by an owner anonymization hashes to now; a later rewrite of a
+/// record supersedes an earlier one
+pub(crate) fn rewritten_digests(rewrites: &[OwnerRewrite]) -> HashMap<Uuid, &str> {
+    rewrites.iter()
+        .flat_map(|r| r.rewritten_records.iter().map(|(record_id, digest)| (*record_id, digest.as_str())))
+        .collect()
+}
+
+pub(crate) fn verify_chain<'r, I: Into<Uuid>>(
+    label: &str,
+    records: impl Iterator<Item = (I, &'r Option<String>, &'r Option<String>, IclResult<String>)>,
+    archived: &std::collections::HashMap<Option<&str>, &str>,
+    rewritten: &HashMap<Uuid, &str>,
+    findings: &mut Vec<IntegrityFinding>
+) {
+    let mut expected_previous: Option<&String> = None;
+    for (record_id, previous_hash, record_hash, computed) in records {
+        let record_id: Uuid = record_id.into();
+        let Some(stored) = record_hash else {
+            findings.push(IntegrityFinding::warning(
+                FindingKind::UnchainedRecord,
+                Some(record_id),
+                format!("{} was recorded without a chain hash", label),
+            ));
+            expected_previous = None;
+            continue;
+        };
+
+        // Records moved to an archive leave a gap their chain links bridge
+        if previous_hash.as_ref() != expected_previous
+            && !bridges(archived, expected_previous.map(String::as_str), previous_hash.as_deref())
+        {
+            findings.push(IntegrityFinding::error(
+                FindingKind::RecordChainBreak,
+                Some(record_id),
+                format!("{} does not link to its predecessor", label),
+            ));
+        }
+
+        match computed {
+            // Records rewritten by an owner anonymization keep the hash they were sealed with
+            Ok(hash) if &hash == stored || rewritten.get(&record_id) == Some(&hash.as_str()) => {},
+            Ok(_) => findings.push(IntegrityFinding::error(
+                FindingKind::RecordTampered,
+                Some(record_id),
+                format!("{} contents do not match its chain hash", label),
+            )),
+            Err(e) => findings.push(IntegrityFinding::error(FindingKind::RecordTampered, Some(record_id), e.to_string())),
+        }
+
+        expected_previous = Some(stored);
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::types::*;
+
+/// Event type of the seller's side of an intercompany transfer
+pub const INTERCOMPANY_DISPOSAL_EVENT: &str = "intercompany_disposal";
+
+/// Detail on both events of an intercompany transfer holding its `Elimination`
+pub const ELIMINATION_DETAIL: &str = "elimination";
+
+/// What consolidation must eliminate for one intercompany transfer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Elimination {
+    pub transfer_id: Uuid,
+    pub seller_org: OrgId,
+    pub buyer_org: OrgId,
+    pub seller_asset_id: AssetId,
+    pub buyer_asset_id: AssetId,
+    pub transfer_price: f64,
+    pub carrying_value: f64,
+}
+
+impl Elimination {
+    pub fn unrealized_gain(&self) -> f64 {
+        self.transfer_price - self.carrying_value
+    }
+
+    /// The elimination an event carries, if it is one side of an intercompany transfer
+    pub fn of_event(event: &CapitalEvent) -> Option<Elimination> {
+        event.details.get(ELIMINATION_DETAIL).and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+}
+
+/// Both sides of a transfer made by `IntelligenceCapitalLifecycle::transfer_to_org`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntercompanyTransfer {
+    pub elimination: Elimination,
+    pub transferred_at: DateTime<Utc>,
+    pub disposal_event: CapitalEvent,
+    pub buyer_asset: IntelligenceAsset,
+    pub capitalization_event: CapitalEvent,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::IclError;
+    use crate::core::ledger::IntelligenceCapitalLedger;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+
+    fn transferred_ledger() -> (IntelligenceCapitalLedger, IntercompanyTransfer) {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let (seller_asset_id, buyer_asset_id) = (AssetId::random(), AssetId::random());
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger).in_org(OrgId::new("parent"));
+        lifecycle.capitalize(seller_asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let transfer = lifecycle
+            .transfer_to_org(seller_asset_id, OrgId::new("subsidiary"), buyer_asset_id, "Team B", 1500.0)
+            .unwrap();
+        (ledger, transfer)
+    }
+
+    #[test]
+    fn test_transfer_disposes_seller_asset() {
+        let (ledger, transfer) = transferred_ledger();
+        let seller = ledger.get_asset(transfer.elimination.seller_asset_id).unwrap();
+        assert_eq!(seller.status, AssetStatus::Retired);
+        assert_eq!(seller.current_value, Some(0.0));
+        assert_eq!(transfer.disposal_event.event_type, INTERCOMPANY_DISPOSAL_EVENT);
+        assert_eq!(transfer.disposal_event.asset_id, seller.asset_id);
+
+        let postings: Vec<(AccountType, AccountType, f64)> = ledger.journal_entries().iter()
+            .filter(|j| j.event_id == transfer.disposal_event.event_id)
+            .map(|j| (j.debit_account, j.credit_account, j.amount))
+            .collect();
+        assert_eq!(postings, vec![
+            (AccountType::DisposalGainLoss, AccountType::Asset, 1200.0),
+            (AccountType::AccountsReceivable, AccountType::DisposalGainLoss, 1500.0),
+        ]);
+    }
+
+    #[test]
+    fn test_transfer_capitalizes_buyer_asset_at_transfer_price() {
+        let (ledger, transfer) = transferred_ledger();
+        let buyer = ledger.get_asset(transfer.elimination.buyer_asset_id).unwrap();
+        assert_eq!(buyer.org_id, OrgId::new("subsidiary"));
+        assert_eq!(buyer.owner, OwnerId::from("Team B"));
+        assert_eq!(buyer.initial_value, 1500.0);
+        assert_eq!(buyer.status, AssetStatus::Active);
+        assert_eq!(transfer.capitalization_event.asset_id, buyer.asset_id);
+        assert!(ledger.get_journal_entries_for_asset(buyer.asset_id).iter()
+            .any(|j| j.event_id == transfer.capitalization_event.event_id && j.amount == 1500.0));
+    }
+
+    #[test]
+    fn test_transfer_links_both_events_with_elimination() {
+        let (_, transfer) = transferred_ledger();
+        let elimination = &transfer.elimination;
+        assert_eq!((elimination.seller_org.as_str(), elimination.buyer_org.as_str()), ("parent", "subsidiary"));
+        assert_eq!((elimination.transfer_price, elimination.carrying_value), (1500.0, 1200.0));
+        assert_eq!(elimination.unrealized_gain(), 300.0);
+        assert_eq!(Elimination::of_event(&transfer.disposal_event).as_ref(), Some(elimination));
+        assert_eq!(Elimination::of_event(&transfer.capitalization_event).as_ref(), Some(elimination));
+
+        let disposal = &transfer.disposal_event.details;
+        assert_eq!(disposal["buyer_asset_id"], serde_json::json!(elimination.buyer_asset_id.to_string()));
+        assert_eq!(disposal["buyer_org"], serde_json::json!("subsidiary"));
+        let capitalization = &transfer.capitalization_event.details;
+        assert_eq!(capitalization["seller_event_id"], serde_json::json!(transfer.disposal_event.event_id.to_string()));
+        assert_eq!(capitalization["seller_asset_id"], serde_json::json!(elimination.seller_asset_id.to_string()));
+    }
+
+    #[test]
+    fn test_transfer_to_own_org_is_rejected() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger).in_org(OrgId::new("parent"));
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let result = lifecycle.transfer_to_org(asset_id, OrgId::new("parent"), AssetId::random(), "Team B", 1500.0);
+        assert!(matches!(result, Err(IclError::InvalidEvent(_))));
+        assert_eq!(ledger.get_asset(asset_id).unwrap().status, AssetStatus::Active);
+        assert_eq!(ledger.events().len(), 1);
+    }
+}
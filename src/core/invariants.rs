@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::integrity::{event_value_amount, VALUE_TOLERANCE};
+
+/// A broken accounting invariant found by `IntelligenceCapitalLedger::assert_invariants`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum InvariantViolation {
+    NegativeValue { asset_id: AssetId, value: f64 },
+    DepreciationExceedsBasis { asset_id: AssetId, depreciation: f64, basis: f64 },
+    JournalUnbalanced { event_id: EventId, amount: f64, booked: f64 },
+    InvalidJournalEntry { entry_id: EntryId, amount: f64 },
+    IndexInconsistent { index: String, detail: String },
+}
+
+impl std::fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvariantViolation::NegativeValue { asset_id, value } => {
+                write!(f, "Asset {} has book value {}", asset_id, value)
+            },
+            InvariantViolation::DepreciationExceedsBasis { asset_id, depreciation, basis } => {
+                write!(f, "Asset {} is depreciated by {}, more than its basis of {}", asset_id, depreciation, basis)
+            },
+            InvariantViolation::JournalUnbalanced { event_id, amount, booked } => {
+                write!(f, "Event {} of {} is booked as {}", event_id, amount, booked)
+            },
+            InvariantViolation::InvalidJournalEntry { entry_id, amount } => {
+                write!(f, "Journal entry {} of {} is invalid", entry_id, amount)
+            },
+            InvariantViolation::IndexInconsistent { index, detail } => {
+                write!(f, "Index {} is inconsistent: {}", index, detail)
+            },
+        }
+    }
+}
+
+impl IntelligenceCapitalLedger {
+    /// Check the core accounting invariants: book values are never negative, no asset is
+    /// depreciated beyond its basis, the journal books every value-affecting event in full, and the
+    /// lookup indexes agree with the record vectors
+    pub fn assert_invariants(&self) -> Result<(), Vec<InvariantViolation>> {
+        let mut violations = Vec::new();
+
+        let mut assets: Vec<_> = self.assets_iter().collect();
+        assets.sort_by_key(|a| a.asset_id);
+        for asset in assets {
+            let value = asset.current_value.unwrap_or(asset.initial_value);
+            if value.is_nan() || value < -VALUE_TOLERANCE {
+                violations.push(InvariantViolation::NegativeValue { asset_id: asset.asset_id, value });
+            }
+            let depreciation: f64 = self.get_events_for_asset(asset.asset_id)
+                .into_iter()
+                .filter(|e| e.event_type == "depreciation" && !self.is_voided(e.event_id))
+                .filter_map(|e| e.details.get("amount").and_then(|v| v.as_f64()))
+                .chain(self.archived_summaries(asset.asset_id, DateTime::<Utc>::MAX_UTC).map(|s| s.totals.total_depreciation()))
+                .sum();
+            if depreciation > asset.initial_value + VALUE_TOLERANCE {
+                violations.push(InvariantViolation::DepreciationExceedsBasis {
+                    asset_id: asset.asset_id,
+                    depreciation,
+                    basis: asset.initial_value,
+                });
+            }
+        }
+
+        let mut booked: HashMap<EventId, f64> = HashMap::new();
+        for journal_entry in self.journal_entries() {
+            if !(journal_entry.amount >= 0.0 && journal_entry.amount.is_finite())
+                || (journal_entry.debit_account == journal_entry.credit_account && !self.is_reclassification_entry(journal_entry))
+            {
+                violations.push(InvariantViolation::InvalidJournalEntry {
+                    entry_id: journal_entry.entry_id,
+                    amount: journal_entry.amount,
+                });
+            }
+            *booked.entry(journal_entry.event_id).or_default() += journal_entry.amount;
+        }
+        for event in self.events() {
+            let Some(amount) = event_value_amount(event).filter(|amount| *amount > 0.0) else {
+                continue;
+            };
+            let booked = booked.get(&event.event_id).copied().unwrap_or(0.0);
+            if (booked - amount).abs() > VALUE_TOLERANCE {
+                violations.push(InvariantViolation::JournalUnbalanced { event_id: event.event_id, amount, booked });
+            }
+        }
+
+        violations.extend(self.index_violations());
+
+        if violations.is_empty() { Ok(()) } else { Err(violations) }
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::{BTreeSet, HashMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::error::{IclError, IclResult};
+
+/// Metadata key naming the template a journal entry was generated from
+pub const JOURNAL_TEMPLATE_METADATA: &str = "journal_template";
+
+/// Amount of one leg of a journal template
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum LegAmount {
+    Fixed(f64),
+    Value(String),
+}
+
+/// One debit/credit pair of a journal template, posted as one journal entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JournalLeg {
+    pub debit_account: AccountType,
+    pub credit_account: AccountType,
+    pub amount: LegAmount,
+    pub description: String,
+}
+
+/// Named pattern of journal legs for a recurring posting, such as monthly depreciation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JournalTemplate {
+    pub name: String,
+    pub legs: Vec<JournalLeg>,
+}
+
+impl JournalTemplate {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), legs: Vec::new() }
+    }
+
+    pub fn leg(mut self, debit_account: AccountType, credit_account: AccountType, amount: LegAmount, description: impl Into<String>) -> Self {
+        self.legs.push(JournalLeg { debit_account, credit_account, amount, description: description.into() });
+        self
+    }
+
+    /// Depreciation expense against accumulated depreciation, for the `amount` value
+    pub fn depreciation() -> Self {
+        Self::new("depreciation").leg(
+            AccountType::DepreciationExpense,
+            AccountType::AccumulatedDepreciation,
+            LegAmount::Value("amount".into()),
+            "Asset depreciation",
+        )
+    }
+
+    /// Inference expense against accounts payable, for the `amount` value
+    pub fn inference_expense() -> Self {
+        Self::new("inference_expense").leg(
+            AccountType::InferenceExpense,
+            AccountType::AccountsPayable,
+            LegAmount::Value("amount".into()),
+            "Inference cost expense",
+        )
+    }
+
+    pub fn placeholders(&self) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        for leg in &self.legs {
+            if let LegAmount::Value(name) = &leg.amount {
+                names.insert(name.clone());
+            }
+            let mut rest = leg.description.as_str();
+            while let Some((name, after)) = rest.split_once('{').and_then(|(_, s)| s.split_once('}')) {
+                names.insert(name.to_string());
+                rest = after;
+            }
+        }
+        names
+    }
+
+    pub fn validate(&self) -> IclResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(IclError::InvalidEntry("Journal template name cannot be empty".into()));
+        }
+        if self.legs.is_empty() {
+            return Err(IclError::InvalidEntry(format!("Journal template {} has no legs", self.name)));
+        }
+        for leg in &self.legs {
+            if leg.debit_account == leg.credit_account {
+                return Err(IclError::InvalidEntry(format!(
+                    "Journal template {} debits and credits {} in one leg", self.name, leg.debit_account
+                )));
+            }
+            if let LegAmount::Fixed(amount) = leg.amount {
+                if !(amount.is_finite() && amount > 0.0) {
+                    return Err(IclError::InvalidEntry(format!(
+                        "Journal template {} has a fixed amount {} that is not positive", self.name, amount
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The journal entries for `event_id`, one per leg with a non-zero amount, with the values as
+    /// their metadata
+    pub fn instantiate(
+        &self,
+        event_id: EventId,
+        timestamp: DateTime<Utc>,
+        values: &HashMap<String, serde_json::Value>,
+    ) -> IclResult<Vec<JournalEntry>> {
+        self.validate()?;
+        let missing: Vec<String> = self.placeholders().into_iter().filter(|name| !values.contains_key(name)).collect();
+        if !missing.is_empty() {
+            return Err(IclError::InvalidEntry(format!(
+                "Journal template {} is missing values for {}", self.name, missing.join(", ")
+            )));
+        }
+
+        let mut metadata = values.clone();
+        metadata.insert(JOURNAL_TEMPLATE_METADATA.to_string(), serde_json::Value::String(self.name.clone()));
+        let mut entries = Vec::new();
+        for leg in &self.legs {
+            let amount = match &leg.amount {
+                LegAmount::Fixed(amount) => *amount,
+                LegAmount::Value(name) => values[name].as_f64()
+                    .filter(|amount| amount.is_finite() && *amount >= 0.0)
+                    .ok_or_else(|| IclError::InvalidEntry(format!(
+                        "Journal template {} value {} must be a non-negative number, not {}", self.name, name, values[name]
+                    )))?,
+            };
+            if amount == 0.0 {
+                continue;
+            }
+            let mut description = leg.description.clone();
+            for (name, value) in values {
+                let text = match value {
+                    serde_json::Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                description = description.replace(&format!("{{{}}}", name), &text);
+            }
+            entries.push(JournalEntry::new(
+                event_id,
+                timestamp,
+                leg.debit_account,
+                leg.credit_account,
+                amount,
+                description,
+                metadata.clone(),
+            ));
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ledger::IntelligenceCapitalLedger;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+
+    #[test]
+    fn test_instantiate_fills_placeholders() {
+        let template = JournalTemplate::new("license_amortization")
+            .leg(AccountType::RoyaltyExpense, AccountType::AccountsPayable, LegAmount::Value("amount".into()), "Amortization of {license}")
+            .leg(AccountType::RoyaltyExpense, AccountType::AccountsPayable, LegAmount::Value("fee".into()), "Fee");
+        assert_eq!(template.placeholders(), BTreeSet::from(["amount".to_string(), "fee".to_string(), "license".to_string()]));
+
+        let event_id = EventId::random();
+        let values = HashMap::from([
+            ("amount".to_string(), serde_json::json!(250.0)),
+            ("fee".to_string(), serde_json::json!(0.0)),
+            ("license".to_string(), serde_json::json!("L-7")),
+        ]);
+        let entries = template.instantiate(event_id, Utc::now(), &values).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].description, "Amortization of L-7");
+        assert_eq!(entries[0].amount, 250.0);
+        assert_eq!(entries[0].metadata[JOURNAL_TEMPLATE_METADATA], "license_amortization");
+
+        assert!(template.instantiate(event_id, Utc::now(), &HashMap::new()).is_err());
+
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        ledger.register_journal_template(template).unwrap();
+        let restored: IntelligenceCapitalLedger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+        assert!(restored.journal_template("license_amortization").is_some());
+
+        let event = CapitalEvent::new(asset_id, "license_amortization", ledger.now(), HashMap::new());
+        let event = ledger.record_event(event).unwrap();
+        let posted = ledger.post_template("license_amortization", event.event_id, &values).unwrap();
+        assert_eq!(posted.len(), 1);
+        assert_eq!(posted[0].timestamp, event.timestamp);
+        assert!(ledger.get_journal_entries_for_asset(asset_id).iter().any(|j| j.entry_id == posted[0].entry_id));
+        assert!(ledger.post_template("unregistered", event.event_id, &values).is_err());
+    }
+}
@@ -1,22 +1,582 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::borrow::Cow;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 
 use crate::core::types::*;
+use crate::core::anchoring::*;
+use crate::core::exporters::{csv_field, JournalExporter};
+use crate::core::outbox::{Outbox, OutboxMessage, OutboxState};
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::authorization::{AllowAll, Authorizer};
+use crate::core::ids::{IdGenerator, RandomIdGenerator};
+use crate::core::metrics::{LedgerMetrics, MetricsRecorder};
+use crate::core::work_in_progress::{CapitalWorkInProgress, WipCost, WipStatus};
+use crate::core::licensing::{License, LicenseTerms, LICENSE_EVENT, REVENUE_EVENT};
+use crate::core::obligations::{ObligationBasis, RecurringObligation, OBLIGATION_EVENT};
+use crate::core::intercompany::INTERCOMPANY_DISPOSAL_EVENT;
+use crate::core::impairment::{DraftImpairment, ImpairmentIndicator, ImpairmentReview, ImpairmentThreshold, ReviewStatus};
+use crate::core::import::*;
+use crate::core::invariants::InvariantViolation;
+use crate::core::migrations::{
+    audit_trail_version, AuditTrailMigrations, UpgradeReport, UpgradeStep, AUDIT_TRAIL_VERSION, LEDGER_DATA_MODEL_VERSION,
+    LEDGER_MIGRATIONS, UNVERSIONED_DATA_MODEL,
+};
+use crate::core::archive::{
+    splice, ArchiveFile, ArchiveStub, ArchivedAssetSummary, ArchivedRecords, ChainLink, ARCHIVE_DIGEST_DETAIL, ARCHIVE_EVENT,
+};
+use crate::core::retention::{RetentionPurge, RetentionReport, RetentionRule, RETENTION_PURGE_EVENT};
+use crate::core::dimensions::{check_dimensions, DimensionDefinition};
+use crate::core::journal_templates::JournalTemplate;
+use crate::core::accruals::{ACCRUAL_EVENT, ACCRUAL_REVERSAL_EVENT};
+use crate::core::year_end::{YearEndClose, YEAR_END_CLOSE_EVENT};
+use crate::core::capitalization::{CapitalizationPolicy, EXPENSED_EVENT};
+use crate::core::pools::{
+    pool_allocations, pool_share, DepreciationPool, POOL_ADDITION_EVENT, POOL_DEPRECIATION_EVENT, POOL_DISPOSAL_EVENT,
+};
+use crate::core::query::{Query, QueryRecord};
+use crate::core::view::LedgerView;
+use crate::core::owners::{Owner, OwnerAnonymization, OwnerKind, OwnerMigration, OwnerPseudonym, OwnerRegistry, OwnerRewrite, OWNER_ANONYMIZED_EVENT};
+use crate::core::reports::{book_value_change, IMPAIRMENT_EVENT, IMPROVEMENT_EVENT};
+use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+use crate::core::integrity::{
+    BackdatingApproval, BackdatingPolicy, IntegrityChecker, OrderingPolicy, OrphanReport, Rule, RuleSet,
+    APPROVED_BY_DETAIL, BACKDATING_APPROVAL_DETAIL, VALUE_TOLERANCE,
+};
+use crate::core::proofs::EVENT_DIGEST_CONTENT;
+use crate::core::hashing::{
+    merkle_root, record_chain_hash, ChainHead, HashAlgorithm, RecordChainHeads, PROOF_HASH_VERSION, PROOF_HASH_VERSION_LEGACY,
+};
 use crate::core::error::*;
 
+/// How much validation the ledger performs when records are written
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ValidationMode {
+    #[default]
+    Lenient,
+    Strict,
+}
+
+impl std::fmt::Display for ValidationMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationMode::Lenient => write!(f, "Lenient"),
+            ValidationMode::Strict => write!(f, "Strict"),
+        }
+    }
+}
+
+/// Event types the crate itself records, always accepted by the unknown event type check
+pub const LEDGER_EVENT_TYPES: &[&str] = &[
+    "capitalization",
+    "allocation",
+    "utilization",
+    "depreciation",
+    "retirement",
+    "inference_expense",
+    ARTIFACT_LINKED_EVENT,
+    IMPROVEMENT_EVENT,
+    IMPAIRMENT_EVENT,
+    LICENSE_EVENT,
+    REVENUE_EVENT,
+    OBLIGATION_EVENT,
+    INTERCOMPANY_DISPOSAL_EVENT,
+    VOID_EVENT,
+    RETENTION_PURGE_EVENT,
+    ACCRUAL_EVENT,
+    ACCRUAL_REVERSAL_EVENT,
+    RECLASSIFICATION_EVENT,
+    YEAR_END_CLOSE_EVENT,
+    EXPENSED_EVENT,
+    POOL_ADDITION_EVENT,
+    POOL_DEPRECIATION_EVENT,
+    POOL_DISPOSAL_EVENT,
+    ARCHIVE_EVENT,
+    OWNER_ANONYMIZED_EVENT,
+];
+
+/// Subject of events about the ledger as a whole rather than one asset, such as archiving a period
+pub const LEDGER_SUBJECT: AssetId = AssetId::from_uuid(Uuid::nil());
+
+/// Event detail naming the currency of an event's amounts
+pub const CURRENCY_DETAIL: &str = "currency";
+
+/// Which checks the ledger enforces when records are written, each strict or lenient
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ValidationConfig {
+    pub events: ValidationMode,
+    pub retroactive_timestamps: ValidationMode,
+    pub zero_amounts: ValidationMode,
+    pub unknown_event_types: ValidationMode,
+    pub currency_checks: ValidationMode,
+    pub currency: String,
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub event_types: BTreeSet<String>,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            events: ValidationMode::Lenient,
+            retroactive_timestamps: ValidationMode::Lenient,
+            zero_amounts: ValidationMode::Strict,
+            unknown_event_types: ValidationMode::Lenient,
+            currency_checks: ValidationMode::Lenient,
+            currency: "USD".into(),
+            event_types: BTreeSet::new(),
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// Enforce every check, as production ledgers should
+    pub fn strict() -> Self {
+        Self::all(ValidationMode::Strict)
+    }
+
+    /// Enforce none of the checks, e.g. for test environments
+    pub fn lenient() -> Self {
+        Self::all(ValidationMode::Lenient)
+    }
+
+    fn all(mode: ValidationMode) -> Self {
+        Self {
+            events: mode,
+            retroactive_timestamps: mode,
+            zero_amounts: mode,
+            unknown_event_types: mode,
+            currency_checks: mode,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_currency(mut self, currency: impl Into<String>) -> Self {
+        self.currency = currency.into();
+        self
+    }
+
+    pub fn with_event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_types.insert(event_type.into());
+        self
+    }
+
+    pub fn is_known_event_type(&self, event_type: &str) -> bool {
+        LEDGER_EVENT_TYPES.contains(&event_type) || self.event_types.contains(event_type)
+    }
+
+    pub fn rejects_amount(&self, amount: f64) -> bool {
+        !(amount >= 0.0 && amount.is_finite()) || (amount == 0.0 && self.zero_amounts == ValidationMode::Strict)
+    }
+}
+
+impl From<ValidationMode> for ValidationConfig {
+    fn from(mode: ValidationMode) -> Self {
+        Self { events: mode, retroactive_timestamps: mode, ..Self::default() }
+    }
+}
+
+/// Category key for assets that have none assigned
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// Event type recorded when a model artifact is linked to an asset
+pub const ARTIFACT_LINKED_EVENT: &str = "artifact_linked";
+
+/// Capitalization detail naming the parent asset of a component improvement
+pub const PARENT_ASSET_DETAIL: &str = "parent_asset_id";
+
+/// Event type recorded by `void_event`, against the voided event's asset
+pub const VOID_EVENT: &str = "void";
+
+/// Detail of a void event naming the event it voids
+pub const VOIDED_EVENT_DETAIL: &str = "voided_event_id";
+
+/// Metadata of a void's compensating journal entry naming the entry it reverses
+pub const REVERSED_ENTRY_METADATA: &str = "reversed_entry_id";
+
+/// Event type recorded by `IntelligenceCapitalLifecycle::reclassify` when an asset moves to
+/// another category
+pub const RECLASSIFICATION_EVENT: &str = "reclassification";
+
+/// Metadata of a reclassification entry naming the category its debit posts under
+pub const DEBIT_CATEGORY_METADATA: &str = "debit_category";
+
+/// Metadata of a reclassification entry naming the category its credit posts under
+pub const CREDIT_CATEGORY_METADATA: &str = "credit_category";
+
+/// Categories journal entries posted under, resolved against a per-asset index of
+/// reclassifications built once by `IntelligenceCapitalLedger::posting_assets`
+#[derive(Debug)]
+pub struct PostingAssets<'a> {
+    ledger: &'a IntelligenceCapitalLedger,
+    reclassifications: HashMap<AssetId, Vec<(u64, Option<String>)>>,
+}
+
+impl<'a> PostingAssets<'a> {
+    /// Asset one side of a journal entry posts against, with the category it had for that posting
+    pub fn journal_entry_posting_asset(&self, entry: &JournalEntry, side: PostingSide) -> Option<Cow<'a, IntelligenceAsset>> {
+        let event = self.ledger.get_event(entry.event_id)?;
+        let asset = self.ledger.get_asset(event.asset_id)?;
+        let category = if event.event_type == RECLASSIFICATION_EVENT {
+            let key = match side {
+                PostingSide::Debit => DEBIT_CATEGORY_METADATA,
+                PostingSide::Credit => CREDIT_CATEGORY_METADATA,
+            };
+            entry.metadata.get(key).map(|v| v.as_str().map(str::to_string))
+        } else {
+            self.reclassifications.get(&asset.asset_id).and_then(|reclassifications| {
+                let later = reclassifications.partition_point(|(sequence, _)| *sequence <= event.sequence);
+                reclassifications.get(later).map(|(_, from_category)| from_category.clone())
+            })
+        };
+        match category {
+            Some(category) if category != asset.category => Some(Cow::Owned(IntelligenceAsset { category, ..asset.clone() })),
+            _ => Some(Cow::Borrowed(asset)),
+        }
+    }
+}
+
+/// Event types `void_event` accepts
+pub const VOIDABLE_EVENT_TYPES: &[&str] = &[
+    "utilization",
+    "depreciation",
+    "inference_expense",
+    IMPAIRMENT_EVENT,
+    REVENUE_EVENT,
+    OBLIGATION_EVENT,
+];
+
+const VALUE_CHANGING_EVENT_TYPES: &[&str] = &[
+    "depreciation",
+    "retirement",
+    IMPAIRMENT_EVENT,
+    IMPROVEMENT_EVENT,
+    INTERCOMPANY_DISPOSAL_EVENT,
+    POOL_DISPOSAL_EVENT,
+];
+
+/// Event types retention rules cannot purge: positions, book values, and lookups are replayed
+/// from them, and voids and purges are the audit record of other events
+pub const RETENTION_PROTECTED_EVENT_TYPES: &[&str] = &[
+    "capitalization",
+    "allocation",
+    "depreciation",
+    "retirement",
+    ARTIFACT_LINKED_EVENT,
+    IMPROVEMENT_EVENT,
+    IMPAIRMENT_EVENT,
+    INTERCOMPANY_DISPOSAL_EVENT,
+    RECLASSIFICATION_EVENT,
+    YEAR_END_CLOSE_EVENT,
+    EXPENSED_EVENT,
+    POOL_DISPOSAL_EVENT,
+    ARCHIVE_EVENT,
+    OWNER_ANONYMIZED_EVENT,
+    VOID_EVENT,
+    RETENTION_PURGE_EVENT,
+];
+
+/// The event a void event voids
+pub fn voided_event_id(event: &CapitalEvent) -> Option<EventId> {
+    if event.event_type != VOID_EVENT {
+        return None;
+    }
+    event.details.get(VOIDED_EVENT_DETAIL).and_then(|v| v.as_str()).and_then(|s| s.parse().ok())
+}
+
+/// Selects the records included in a streamed audit trail export
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditTrailFilter {
+    pub asset_id: Option<AssetId>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl AuditTrailFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn asset(mut self, asset_id: AssetId) -> Self {
+        self.asset_id = Some(asset_id);
+        self
+    }
+
+    pub fn between(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.start = Some(start);
+        self.end = Some(end);
+        self
+    }
+
+    fn query<R: QueryRecord>(&self, mut query: Query<R>) -> Query<R> {
+        if let Some(asset_id) = self.asset_id {
+            query = query.asset(asset_id);
+        }
+        if let Some(start) = self.start {
+            query = query.since(start);
+        }
+        if let Some(end) = self.end {
+            query = query.until(end);
+        }
+        query
+    }
+
+    fn selects(&self, asset_id: Option<AssetId>, timestamp: DateTime<Utc>) -> bool {
+        self.asset_id.is_none_or(|id| asset_id == Some(id))
+            && self.start.is_none_or(|start| timestamp >= start)
+            && self.end.is_none_or(|end| timestamp < end)
+    }
+
+    fn selects_asset(&self, asset: &IntelligenceAsset) -> bool {
+        self.selects(Some(asset.asset_id), asset.created_at)
+    }
+}
+
+#[derive(Serialize)]
+struct JsonlRecord<'a, T> {
+    record_type: &'a str,
+    data: &'a T,
+}
+
+fn write_jsonl_record<W: std::io::Write, T: Serialize>(writer: &mut W, record_type: &str, data: &T) -> IclResult<usize> {
+    serde_json::to_writer(&mut *writer, &JsonlRecord { record_type, data })?;
+    writer.write_all(b"\n")?;
+    Ok(1)
+}
+
+fn replace_values(
+    map: &mut HashMap<String, serde_json::Value>,
+    originals: &BTreeSet<String>,
+    replacement: &serde_json::Value
+) -> bool {
+    let mut replaced = false;
+    for value in map.values_mut() {
+        if value.as_str().is_some_and(|s| originals.contains(s)) {
+            *value = replacement.clone();
+            replaced = true;
+        }
+    }
+    replaced
+}
+
+#[derive(Deserialize)]
+struct AuditTrail {
+    assets: Vec<IntelligenceAsset>,
+    events: Vec<CapitalEvent>,
+    entries: Vec<LedgerEntry>,
+    journal_entries: Vec<JournalEntry>,
+    proofs: Vec<CapitalProof>,
+    #[serde(default)]
+    archives: Vec<ArchiveStub>,
+    #[serde(default)]
+    record_chain_heads: Option<RecordChainHeads>,
+}
+
+#[derive(Deserialize)]
+struct JsonlLine {
+    record_type: String,
+    data: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PostingOrigin {
+    Caller,
+    Ledger,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RecordLengths {
+    events: usize,
+    entries: usize,
+    journal_entries: usize,
+    proofs: usize,
+    anchor_receipts: usize,
+    portfolio_proofs: usize,
+    archives: usize,
+    year_end_closes: usize,
+}
+
+// A record an operation changed other than by appending it, with the positions needed to put
+// it back
+#[derive(Debug, Clone)]
+enum RecordUndo {
+    Event(usize, CapitalEvent),
+    Entry(usize, LedgerEntry),
+    JournalEntry(usize, JournalEntry),
+    Proof(usize, CapitalProof),
+    Removed {
+        events: Vec<(usize, CapitalEvent)>,
+        entries: Vec<(usize, LedgerEntry)>,
+        journal_entries: Vec<(usize, JournalEntry)>,
+    },
+    Inserted {
+        events: Vec<usize>,
+        entries: Vec<usize>,
+        journal_entries: Vec<usize>,
+    },
+    RemovedArchive(usize, ArchiveStub),
+}
+
+#[derive(Debug, Clone)]
+struct UndoFrame {
+    lengths: RecordLengths,
+    next_sequence: u64,
+    record_chain_heads: RecordChainHeads,
+    id_position: Option<u64>,
+    assets: HashMap<AssetId, Option<IntelligenceAsset>>,
+    totals: HashMap<AssetId, Option<AssetTotals>>,
+    records: Vec<RecordUndo>,
+    // Everything but the records, copied before the first write to it
+    settings: Option<Box<IntelligenceCapitalLedger>>,
+}
+
 #[derive(Debug)]
+#[must_use]
+pub(crate) struct LedgerCheckpoint {
+    depth: usize,
+}
+
+/// Running totals of one asset's activity, kept up to date as records are written so summaries
+/// do not rescan the asset's history
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AssetTotals {
+    pub event_count: usize,
+    pub journal_entry_count: usize,
+    pub amount_by_type: BTreeMap<String, f64>,
+    pub last_event_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub usage: Usage,
+}
+
+impl AssetTotals {
+    pub fn total_depreciation(&self) -> f64 {
+        self.amount_by_type.get("depreciation").copied().unwrap_or(0.0)
+    }
+
+    pub fn total_revenue(&self) -> f64 {
+        self.amount_by_type.get(REVENUE_EVENT).copied().unwrap_or(0.0)
+    }
+
+    fn add_event(&mut self, event: &CapitalEvent) {
+        self.event_count += 1;
+        if let Some(amount) = event.details.get("amount").and_then(|v| v.as_f64()) {
+            *self.amount_by_type.entry(event.event_type.clone()).or_default() += amount;
+        }
+        if event.event_type == "utilization" {
+            self.usage.add(&Usage::from_details(&event.details));
+        }
+        self.last_event_at = self.last_event_at.max(Some(event.timestamp));
+        if let Some(purge) = RetentionPurge::from_event(event) {
+            self.merge(&purge.purged_totals);
+        }
+    }
+
+    fn merge(&mut self, other: &AssetTotals) {
+        self.event_count += other.event_count;
+        self.journal_entry_count += other.journal_entry_count;
+        for (event_type, amount) in &other.amount_by_type {
+            *self.amount_by_type.entry(event_type.clone()).or_default() += amount;
+        }
+        self.usage.add(&other.usage);
+        self.last_event_at = self.last_event_at.max(other.last_event_at);
+    }
+
+    fn remove_voided(&mut self, event: &CapitalEvent) {
+        if let Some(amount) = event.details.get("amount").and_then(|v| v.as_f64()) {
+            *self.amount_by_type.entry(event.event_type.clone()).or_default() -= amount;
+        }
+        if event.event_type == "utilization" {
+            let usage = Usage::from_details(&event.details);
+            self.usage.tokens = self.usage.tokens.saturating_sub(usage.tokens);
+            self.usage.requests = self.usage.requests.saturating_sub(usage.requests);
+            self.usage.gpu_hours -= usage.gpu_hours;
+        }
+    }
+}
+
+/// State of one asset at a point in time, reconstructed from its events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AssetPosition {
+    pub asset_id: AssetId,
+    pub owner: OwnerId,
+    pub status: AssetStatus,
+    pub book_value: f64,
+    pub accumulated_depreciation: f64,
+}
+
+/// Portfolio-wide valuation at a point in time, as returned by `portfolio_summary`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioSummary {
+    pub as_of: DateTime<Utc>,
+    pub asset_count: usize,
+    pub total_initial_value: f64,
+    pub total_book_value: f64,
+    pub accumulated_depreciation: f64,
+    pub assets_by_status: HashMap<AssetStatus, usize>,
+    pub assets_by_owner: BTreeMap<OwnerId, usize>,
+    pub value_by_category: BTreeMap<String, f64>,
+}
+
+/// Ledger shared between request handlers; lock it directly to use the ledger alongside a server
+pub type SharedLedger = Arc<parking_lot::RwLock<IntelligenceCapitalLedger>>;
+
+#[derive(Debug, Clone)]
 pub struct IntelligenceCapitalLedger {
-    pub assets: HashMap<Uuid, IntelligenceAsset>,
-    pub events: Vec<CapitalEvent>,
-    pub entries: Vec<LedgerEntry>,
-    pub journal_entries: Vec<JournalEntry>,
-    pub proofs: Vec<CapitalProof>,
+    assets: HashMap<AssetId, IntelligenceAsset>,
+    events: Vec<CapitalEvent>,
+    entries: Vec<LedgerEntry>,
+    journal_entries: Vec<JournalEntry>,
+    proofs: Vec<CapitalProof>,
+    anchor_receipts: Vec<AnchorReceipt>,
+    portfolio_proofs: Vec<PortfolioProof>,
+    owners: OwnerRegistry,
+    owner_pseudonyms: BTreeMap<OwnerId, OwnerPseudonym>,
+    hash_algorithm: HashAlgorithm,
+    record_hash_algorithm: HashAlgorithm,
+    rules: RuleSet,
+    validation: ValidationConfig,
+    ordering_policy: OrderingPolicy,
+    backdating_policy: BackdatingPolicy,
+    capitalization_policy: CapitalizationPolicy,
+    closed_until: Option<DateTime<Utc>>,
+    backdating_approvals: BTreeMap<Uuid, BackdatingApproval>,
+    work_in_progress: BTreeMap<Uuid, CapitalWorkInProgress>,
+    depreciation_pools: BTreeMap<Uuid, DepreciationPool>,
+    record_chain_heads: RecordChainHeads,
+    impairment_thresholds: BTreeMap<String, ImpairmentThreshold>,
+    impairment_indicators: BTreeMap<AssetId, BTreeMap<String, ImpairmentIndicator>>,
+    impairment_reviews: BTreeMap<Uuid, ImpairmentReview>,
+    retention_rules: BTreeMap<String, RetentionRule>,
+    dimensions: BTreeMap<String, DimensionDefinition>,
+    journal_templates: BTreeMap<String, JournalTemplate>,
+    licenses: BTreeMap<Uuid, License>,
+    obligations: BTreeMap<Uuid, RecurringObligation>,
+    next_sequence: u64,
+    quarantine: HashSet<Uuid>,
+    archives: Vec<ArchiveStub>,
+    year_end_closes: Vec<YearEndClose>,
+    data_model_version: u32,
+    emissions: Outbox,
+    clock: Arc<dyn Clock>,
+    id_generator: Arc<dyn IdGenerator>,
+    authorizer: Arc<dyn Authorizer>,
+    metrics: MetricsRecorder,
+    undo_log: Vec<UndoFrame>,
     
-    // Indexes for performance
-    _events_by_asset: HashMap<Uuid, Vec<CapitalEvent>>,
-    _entries_by_asset: HashMap<Uuid, Vec<LedgerEntry>>,
-    _journal_entries_by_asset: HashMap<Uuid, Vec<JournalEntry>>,
+    // Indexes for performance, holding positions in the record vectors
+    _events_by_asset: HashMap<AssetId, Vec<usize>>,
+    _events_by_type: HashMap<String, Vec<usize>>,
+    _entries_by_asset: HashMap<AssetId, Vec<usize>>,
+    _journal_entries_by_event: HashMap<EventId, Vec<usize>>,
+    _journal_entries_by_asset: HashMap<AssetId, Vec<usize>>,
+    _proofs_by_asset: HashMap<AssetId, Vec<usize>>,
+    _event_index: HashMap<EventId, usize>,
+    _journal_entry_index: HashMap<EntryId, usize>,
+    _asset_totals: HashMap<AssetId, AssetTotals>,
+    _components_by_parent: HashMap<AssetId, Vec<AssetId>>,
+    _voids: HashMap<EventId, usize>,
 }
 
 impl IntelligenceCapitalLedger {
@@ -27,9 +587,52 @@ impl IntelligenceCapitalLedger {
             entries: Vec::new(),
             journal_entries: Vec::new(),
             proofs: Vec::new(),
+            anchor_receipts: Vec::new(),
+            portfolio_proofs: Vec::new(),
+            owners: OwnerRegistry::new(),
+            owner_pseudonyms: BTreeMap::new(),
+            hash_algorithm: HashAlgorithm::default(),
+            record_hash_algorithm: HashAlgorithm::default(),
+            rules: RuleSet::new(),
+            validation: ValidationConfig::default(),
+            ordering_policy: OrderingPolicy::default(),
+            backdating_policy: BackdatingPolicy::default(),
+            capitalization_policy: CapitalizationPolicy::default(),
+            closed_until: None,
+            backdating_approvals: BTreeMap::new(),
+            work_in_progress: BTreeMap::new(),
+            depreciation_pools: BTreeMap::new(),
+            record_chain_heads: RecordChainHeads::default(),
+            impairment_thresholds: BTreeMap::new(),
+            impairment_indicators: BTreeMap::new(),
+            impairment_reviews: BTreeMap::new(),
+            retention_rules: BTreeMap::new(),
+            dimensions: BTreeMap::new(),
+            journal_templates: BTreeMap::new(),
+            licenses: BTreeMap::new(),
+            obligations: BTreeMap::new(),
+            next_sequence: 1,
+            quarantine: HashSet::new(),
+            archives: Vec::new(),
+            year_end_closes: Vec::new(),
+            data_model_version: LEDGER_DATA_MODEL_VERSION,
+            emissions: Outbox::new(),
+            clock: Arc::new(SystemClock),
+            id_generator: Arc::new(RandomIdGenerator),
+            authorizer: Arc::new(AllowAll),
+            metrics: MetricsRecorder::default(),
+            undo_log: Vec::new(),
             _events_by_asset: HashMap::new(),
+            _events_by_type: HashMap::new(),
             _entries_by_asset: HashMap::new(),
+            _journal_entries_by_event: HashMap::new(),
             _journal_entries_by_asset: HashMap::new(),
+            _proofs_by_asset: HashMap::new(),
+            _event_index: HashMap::new(),
+            _journal_entry_index: HashMap::new(),
+            _asset_totals: HashMap::new(),
+            _components_by_parent: HashMap::new(),
+            _voids: HashMap::new(),
         }
     }
 }
@@ -40,194 +643,3219 @@ impl Default for IntelligenceCapitalLedger {
     }
 }
 
+#[derive(Serialize)]
+struct LedgerStateRef<'a> {
+    assets: &'a HashMap<AssetId, IntelligenceAsset>,
+    events: &'a [CapitalEvent],
+    entries: &'a [LedgerEntry],
+    journal_entries: &'a [JournalEntry],
+    proofs: &'a [CapitalProof],
+    anchor_receipts: &'a [AnchorReceipt],
+    portfolio_proofs: &'a [PortfolioProof],
+    #[serde(skip_serializing_if = "OwnerRegistry::is_empty")]
+    owners: &'a OwnerRegistry,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    owner_pseudonyms: &'a BTreeMap<OwnerId, OwnerPseudonym>,
+    hash_algorithm: HashAlgorithm,
+    record_hash_algorithm: HashAlgorithm,
+    validation_mode: ValidationMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validation: Option<&'a ValidationConfig>,
+    ordering_policy: OrderingPolicy,
+    #[serde(skip_serializing_if = "is_default_backdating_policy")]
+    backdating_policy: BackdatingPolicy,
+    #[serde(skip_serializing_if = "is_default_capitalization_policy")]
+    capitalization_policy: CapitalizationPolicy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    closed_until: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    backdating_approvals: &'a BTreeMap<Uuid, BackdatingApproval>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    work_in_progress: &'a BTreeMap<Uuid, CapitalWorkInProgress>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    depreciation_pools: &'a BTreeMap<Uuid, DepreciationPool>,
+    record_chain_heads: &'a RecordChainHeads,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    impairment_thresholds: &'a BTreeMap<String, ImpairmentThreshold>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    impairment_indicators: &'a BTreeMap<AssetId, BTreeMap<String, ImpairmentIndicator>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    impairment_reviews: &'a BTreeMap<Uuid, ImpairmentReview>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    retention_rules: &'a BTreeMap<String, RetentionRule>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    dimensions: &'a BTreeMap<String, DimensionDefinition>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    journal_templates: &'a BTreeMap<String, JournalTemplate>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    licenses: &'a BTreeMap<Uuid, License>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    obligations: &'a BTreeMap<Uuid, RecurringObligation>,
+    #[serde(skip_serializing_if = "OutboxState::is_empty")]
+    emissions: OutboxState,
+    next_sequence: u64,
+    quarantine: &'a HashSet<Uuid>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    archives: &'a [ArchiveStub],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    year_end_closes: &'a [YearEndClose],
+    data_model_version: u32,
+}
+
+#[derive(Deserialize)]
+struct LedgerState {
+    assets: HashMap<AssetId, IntelligenceAsset>,
+    events: Vec<CapitalEvent>,
+    entries: Vec<LedgerEntry>,
+    journal_entries: Vec<JournalEntry>,
+    proofs: Vec<CapitalProof>,
+    #[serde(default)]
+    anchor_receipts: Vec<AnchorReceipt>,
+    #[serde(default)]
+    portfolio_proofs: Vec<PortfolioProof>,
+    #[serde(default)]
+    owners: OwnerRegistry,
+    #[serde(default)]
+    owner_pseudonyms: BTreeMap<OwnerId, OwnerPseudonym>,
+    #[serde(default)]
+    hash_algorithm: HashAlgorithm,
+    #[serde(default)]
+    record_hash_algorithm: HashAlgorithm,
+    #[serde(default)]
+    validation_mode: ValidationMode,
+    #[serde(default)]
+    validation: Option<ValidationConfig>,
+    #[serde(default)]
+    ordering_policy: OrderingPolicy,
+    #[serde(default)]
+    backdating_policy: BackdatingPolicy,
+    #[serde(default)]
+    capitalization_policy: CapitalizationPolicy,
+    #[serde(default)]
+    closed_until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    backdating_approvals: BTreeMap<Uuid, BackdatingApproval>,
+    #[serde(default)]
+    work_in_progress: BTreeMap<Uuid, CapitalWorkInProgress>,
+    #[serde(default)]
+    depreciation_pools: BTreeMap<Uuid, DepreciationPool>,
+    #[serde(default)]
+    record_chain_heads: Option<RecordChainHeads>,
+    #[serde(default)]
+    impairment_thresholds: BTreeMap<String, ImpairmentThreshold>,
+    #[serde(default)]
+    impairment_indicators: BTreeMap<AssetId, BTreeMap<String, ImpairmentIndicator>>,
+    #[serde(default)]
+    impairment_reviews: BTreeMap<Uuid, ImpairmentReview>,
+    #[serde(default)]
+    retention_rules: BTreeMap<String, RetentionRule>,
+    #[serde(default)]
+    dimensions: BTreeMap<String, DimensionDefinition>,
+    #[serde(default)]
+    journal_templates: BTreeMap<String, JournalTemplate>,
+    #[serde(default)]
+    licenses: BTreeMap<Uuid, License>,
+    #[serde(default)]
+    obligations: BTreeMap<Uuid, RecurringObligation>,
+    #[serde(default)]
+    emissions: OutboxState,
+    next_sequence: u64,
+    #[serde(default)]
+    quarantine: HashSet<Uuid>,
+    #[serde(default)]
+    archives: Vec<ArchiveStub>,
+    #[serde(default)]
+    year_end_closes: Vec<YearEndClose>,
+    #[serde(default = "unversioned_data_model")]
+    data_model_version: u32,
+}
+
+fn unversioned_data_model() -> u32 {
+    UNVERSIONED_DATA_MODEL
+}
+
+fn positions<K: Ord + Clone, V: Ord + Clone>(index: &HashMap<K, Vec<V>>) -> BTreeMap<K, Vec<V>> {
+    index.iter()
+        .filter(|(_, values)| !values.is_empty())
+        .map(|(key, values)| {
+            let mut values = values.clone();
+            values.sort();
+            (key.clone(), values)
+        })
+        .collect()
+}
+
+fn truncate_positions(positions: &mut Vec<usize>, len: usize) {
+    let keep = positions.partition_point(|&p| p < len);
+    positions.truncate(keep);
+}
+
+fn component_parent(event: &CapitalEvent) -> Option<AssetId> {
+    if event.event_type != "capitalization" {
+        return None;
+    }
+    event.details.get(PARENT_ASSET_DETAIL).and_then(|v| v.as_str()).and_then(|s| s.parse().ok())
+}
+
+fn take_where<T>(records: &mut Vec<T>, matches: impl Fn(&T) -> bool) -> Vec<(usize, T)> {
+    let mut taken = Vec::new();
+    let mut kept = Vec::with_capacity(records.len());
+    for (i, record) in std::mem::take(records).into_iter().enumerate() {
+        if matches(&record) {
+            taken.push((i, record));
+        } else {
+            kept.push(record);
+        }
+    }
+    *records = kept;
+    taken
+}
+
+fn positions_where<T>(records: &[T], matches: impl Fn(&T) -> bool) -> Vec<usize> {
+    records.iter().enumerate().filter(|(_, record)| matches(record)).map(|(i, _)| i).collect()
+}
+
+fn index_diff<K: Ord + std::fmt::Debug, V: PartialEq + std::fmt::Debug>(
+    name: &str,
+    actual: &BTreeMap<K, V>,
+    expected: &BTreeMap<K, V>,
+    violations: &mut Vec<InvariantViolation>,
+) {
+    let keys: BTreeSet<&K> = actual.keys().chain(expected.keys()).collect();
+    let Some(key) = keys.into_iter().find(|key| actual.get(key) != expected.get(key)) else {
+        return;
+    };
+    violations.push(InvariantViolation::IndexInconsistent {
+        index: name.into(),
+        detail: format!("{:?} holds {:?}, expected {:?}", key, actual.get(key), expected.get(key)),
+    });
+}
+
+fn is_default_backdating_policy(policy: &BackdatingPolicy) -> bool {
+    *policy == BackdatingPolicy::default()
+}
+
+fn is_default_capitalization_policy(policy: &CapitalizationPolicy) -> bool {
+    *policy == CapitalizationPolicy::default()
+}
+
+impl Serialize for IntelligenceCapitalLedger {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        LedgerStateRef {
+            assets: &self.assets,
+            events: &self.events,
+            entries: &self.entries,
+            journal_entries: &self.journal_entries,
+            proofs: &self.proofs,
+            anchor_receipts: &self.anchor_receipts,
+            portfolio_proofs: &self.portfolio_proofs,
+            owners: &self.owners,
+            owner_pseudonyms: &self.owner_pseudonyms,
+            hash_algorithm: self.hash_algorithm,
+            record_hash_algorithm: self.record_hash_algorithm,
+            validation_mode: self.validation.events,
+            // Configurations a bare mode describes are written as the mode alone
+            validation: Some(&self.validation).filter(|v| **v != ValidationConfig::from(v.events)),
+            ordering_policy: self.ordering_policy,
+            backdating_policy: self.backdating_policy,
+            capitalization_policy: self.capitalization_policy,
+            closed_until: self.closed_until,
+            backdating_approvals: &self.backdating_approvals,
+            work_in_progress: &self.work_in_progress,
+            depreciation_pools: &self.depreciation_pools,
+            record_chain_heads: &self.record_chain_heads,
+            impairment_thresholds: &self.impairment_thresholds,
+            impairment_indicators: &self.impairment_indicators,
+            impairment_reviews: &self.impairment_reviews,
+            retention_rules: &self.retention_rules,
+            dimensions: &self.dimensions,
+            journal_templates: &self.journal_templates,
+            licenses: &self.licenses,
+            obligations: &self.obligations,
+            emissions: self.emissions.undelivered_state(),
+            next_sequence: self.next_sequence,
+            quarantine: &self.quarantine,
+            archives: &self.archives,
+            year_end_closes: &self.year_end_closes,
+            data_model_version: self.data_model_version,
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for IntelligenceCapitalLedger {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = LedgerState::deserialize(deserializer)?;
+        if state.data_model_version > LEDGER_DATA_MODEL_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "ledger data model version {} is newer than supported version {}",
+                state.data_model_version, LEDGER_DATA_MODEL_VERSION
+            )));
+        }
+        let mut ledger = Self {
+            assets: state.assets,
+            events: state.events,
+            entries: state.entries,
+            journal_entries: state.journal_entries,
+            proofs: state.proofs,
+            anchor_receipts: state.anchor_receipts,
+            portfolio_proofs: state.portfolio_proofs,
+            owners: state.owners,
+            owner_pseudonyms: state.owner_pseudonyms,
+            hash_algorithm: state.hash_algorithm,
+            record_hash_algorithm: state.record_hash_algorithm,
+            validation: state.validation.unwrap_or_else(|| state.validation_mode.into()),
+            ordering_policy: state.ordering_policy,
+            backdating_policy: state.backdating_policy,
+            capitalization_policy: state.capitalization_policy,
+            closed_until: state.closed_until,
+            backdating_approvals: state.backdating_approvals,
+            work_in_progress: state.work_in_progress,
+            depreciation_pools: state.depreciation_pools,
+            impairment_thresholds: state.impairment_thresholds,
+            impairment_indicators: state.impairment_indicators,
+            impairment_reviews: state.impairment_reviews,
+            retention_rules: state.retention_rules,
+            dimensions: state.dimensions,
+            journal_templates: state.journal_templates,
+            licenses: state.licenses,
+            obligations: state.obligations,
+            emissions: Outbox::from_state(state.emissions),
+            next_sequence: state.next_sequence,
+            quarantine: state.quarantine,
+            archives: state.archives,
+            year_end_closes: state.year_end_closes,
+            data_model_version: state.data_model_version,
+            ..Self::new()
+        };
+        ledger.rebuild_indexes().map_err(serde::de::Error::custom)?;
+        ledger.record_chain_heads = state.record_chain_heads.unwrap_or_else(|| ledger.derived_chain_heads());
+        Ok(ledger)
+    }
+}
+
 impl IntelligenceCapitalLedger {
-    pub fn create_asset(
-        &mut self,
-        asset_id: Uuid,
-        owner: String,
-        initial_value: f64,
-        depreciation_method: DepreciationMethod,
-        useful_life_months: i32
-    ) -> IclResult<IntelligenceAsset> {
-        if self.assets.contains_key(&asset_id) {
-            return Err(IclError::AssetAlreadyExists(asset_id));
+    fn rebuild_indexes(&mut self) -> IclResult<()> {
+        self._events_by_asset.clear();
+        self._events_by_type.clear();
+        self._entries_by_asset.clear();
+        self._journal_entries_by_event.clear();
+        self._journal_entries_by_asset.clear();
+        self._proofs_by_asset.clear();
+        self._event_index.clear();
+        self._journal_entry_index.clear();
+        self._asset_totals.clear();
+        self._components_by_parent.clear();
+        self._voids.clear();
+
+        for i in 0..self.journal_entries.len() {
+            if self._journal_entry_index.contains_key(&self.journal_entries[i].entry_id) {
+                return Err(IclError::DuplicateEntry(self.journal_entries[i].entry_id));
+            }
+            self.index_journal_entry(i);
+        }
+        for i in 0..self.events.len() {
+            if self._event_index.contains_key(&self.events[i].event_id) {
+                return Err(IclError::DuplicateEvent(self.events[i].event_id));
+            }
+            self.index_event(i);
         }
+        for (i, entry) in self.entries.iter().enumerate() {
+            self._entries_by_asset.entry(entry.asset_id).or_default().push(i);
+        }
+        for (i, proof) in self.proofs.iter().enumerate() {
+            self._proofs_by_asset.entry(proof.asset_id).or_default().push(i);
+        }
+        for archive in &self.archives {
+            for (asset_id, summary) in &archive.assets {
+                self._asset_totals.entry(*asset_id).or_default().merge(&summary.totals);
+            }
+        }
+        let last_sequence = self.events.iter().map(|e| e.sequence).max().unwrap_or(0);
+        self.next_sequence = self.next_sequence.max(last_sequence + 1);
+        Ok(())
+    }
 
-        if owner.is_empty() {
-            return Err(IclError::InvalidAsset("Owner cannot be empty".into()));
+    pub(crate) fn index_violations(&self) -> Vec<InvariantViolation> {
+        let mut rebuilt = Self {
+            events: self.events.clone(),
+            entries: self.entries.clone(),
+            journal_entries: self.journal_entries.clone(),
+            proofs: self.proofs.clone(),
+            archives: self.archives.clone(),
+            ..Self::new()
+        };
+        if let Err(e) = rebuilt.rebuild_indexes() {
+            return vec![InvariantViolation::IndexInconsistent { index: "records".into(), detail: e.to_string() }];
+        }
+        let mut violations = Vec::new();
+        index_diff("events_by_asset", &positions(&self._events_by_asset), &positions(&rebuilt._events_by_asset), &mut violations);
+        index_diff("events_by_type", &positions(&self._events_by_type), &positions(&rebuilt._events_by_type), &mut violations);
+        index_diff("entries_by_asset", &positions(&self._entries_by_asset), &positions(&rebuilt._entries_by_asset), &mut violations);
+        index_diff(
+            "journal_entries_by_event",
+            &positions(&self._journal_entries_by_event),
+            &positions(&rebuilt._journal_entries_by_event),
+            &mut violations,
+        );
+        index_diff(
+            "journal_entries_by_asset",
+            &positions(&self._journal_entries_by_asset),
+            &positions(&rebuilt._journal_entries_by_asset),
+            &mut violations,
+        );
+        index_diff("proofs_by_asset", &positions(&self._proofs_by_asset), &positions(&rebuilt._proofs_by_asset), &mut violations);
+        index_diff("event_index", &self._event_index.iter().collect(), &rebuilt._event_index.iter().collect(), &mut violations);
+        index_diff(
+            "journal_entry_index",
+            &self._journal_entry_index.iter().collect(),
+            &rebuilt._journal_entry_index.iter().collect(),
+            &mut violations,
+        );
+        index_diff("asset_totals", &self._asset_totals.iter().collect(), &rebuilt._asset_totals.iter().collect(), &mut violations);
+        index_diff(
+            "components_by_parent",
+            &positions(&self._components_by_parent),
+            &positions(&rebuilt._components_by_parent),
+            &mut violations,
+        );
+        index_diff("voids", &self._voids.iter().collect(), &rebuilt._voids.iter().collect(), &mut violations);
+        violations
+    }
+
+    fn index_event(&mut self, i: usize) {
+        self.log_totals_write(self.events[i].asset_id);
+        let event = &self.events[i];
+        self._event_index.insert(event.event_id, i);
+        self._events_by_asset.entry(event.asset_id).or_default().push(i);
+        self._events_by_type.entry(event.event_type.clone()).or_default().push(i);
+        if let Some(parent_asset_id) = component_parent(event) {
+            self._components_by_parent.entry(parent_asset_id).or_default().push(event.asset_id);
+        }
+        let totals = self._asset_totals.entry(event.asset_id).or_default();
+        totals.add_event(event);
+        if let Some(&voided) = voided_event_id(event).and_then(|id| self._event_index.get(&id)) {
+            totals.remove_voided(&self.events[voided]);
+            self._voids.insert(self.events[voided].event_id, i);
+        }
+        if let Some(journal_positions) = self._journal_entries_by_event.get(&event.event_id) {
+            totals.journal_entry_count += journal_positions.len();
+            let by_asset = self._journal_entries_by_asset.entry(event.asset_id).or_default();
+            by_asset.extend(journal_positions);
+            by_asset.sort_unstable();
+        }
+    }
+
+    fn index_journal_entry(&mut self, i: usize) {
+        let journal_entry = &self.journal_entries[i];
+        self._journal_entry_index.insert(journal_entry.entry_id, i);
+        self._journal_entries_by_event.entry(journal_entry.event_id).or_default().push(i);
+        if let Some(asset_id) = self._event_index.get(&journal_entry.event_id).map(|&e| self.events[e].asset_id) {
+            self.log_totals_write(asset_id);
+            self._journal_entries_by_asset.entry(asset_id).or_default().push(i);
+            self._asset_totals.entry(asset_id).or_default().journal_entry_count += 1;
+        }
+    }
+
+    /// Current time according to the ledger's clock; every timestamp the ledger and its
+    /// helpers generate comes from here
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.emissions.set_clock(clock.clone());
+        self.log_settings_write();
+        self.clock = clock;
+    }
+
+    pub fn authorizer(&self) -> Arc<dyn Authorizer> {
+        self.authorizer.clone()
+    }
+
+    pub fn set_authorizer(&mut self, authorizer: Arc<dyn Authorizer>) {
+        self.log_settings_write();
+        self.authorizer = authorizer;
+    }
+
+    /// Snapshot of the counters kept since the ledger was created or loaded: writes, rejections,
+    /// proofs, integrity findings, and write latencies
+    pub fn metrics(&self) -> LedgerMetrics {
+        self.metrics.snapshot()
+    }
+
+    pub(crate) fn metrics_recorder(&self) -> &MetricsRecorder {
+        &self.metrics
+    }
+
+    pub fn next_id(&self) -> Uuid {
+        self.id_generator.next_id()
+    }
+
+    pub fn set_id_generator(&mut self, id_generator: Arc<dyn IdGenerator>) {
+        self.log_settings_write();
+        self.id_generator = id_generator;
+    }
+
+    pub(crate) fn checkpoint(&mut self) -> LedgerCheckpoint {
+        let frame = UndoFrame {
+            lengths: self.record_lengths(),
+            next_sequence: self.next_sequence,
+            record_chain_heads: self.record_chain_heads.clone(),
+            id_position: self.id_generator.position(),
+            assets: HashMap::new(),
+            totals: HashMap::new(),
+            records: Vec::new(),
+            settings: None,
+        };
+        self.undo_log.push(frame);
+        LedgerCheckpoint { depth: self.undo_log.len() - 1 }
+    }
+
+    pub(crate) fn commit(&mut self, checkpoint: LedgerCheckpoint) {
+        let committed: Vec<UndoFrame> = self.undo_log.drain(checkpoint.depth..).collect();
+        // An enclosing checkpoint can still roll the committed rewrites back
+        if let Some(parent) = self.undo_log.last_mut() {
+            parent.records.extend(committed.into_iter().flat_map(|frame| frame.records));
+        }
+    }
+
+    pub(crate) fn rollback(&mut self, checkpoint: LedgerCheckpoint) {
+        let mut frames: Vec<UndoFrame> = self.undo_log.drain(checkpoint.depth..).collect();
+        let Some(mut frame) = (!frames.is_empty()).then(|| frames.remove(0)) else {
+            return;
+        };
+        frame.records.extend(frames.into_iter().flat_map(|frame| frame.records));
+        let rewritten = !frame.records.is_empty();
+        for undo in frame.records.into_iter().rev() {
+            self.undo_record(undo);
+        }
+        if rewritten {
+            self.events.truncate(frame.lengths.events);
+            self.entries.truncate(frame.lengths.entries);
+            self.journal_entries.truncate(frame.lengths.journal_entries);
+            self.proofs.truncate(frame.lengths.proofs);
+        } else {
+            self.truncate_records(frame.lengths);
+        }
+        self.anchor_receipts.truncate(frame.lengths.anchor_receipts);
+        self.portfolio_proofs.truncate(frame.lengths.portfolio_proofs);
+        self.archives.truncate(frame.lengths.archives);
+        self.year_end_closes.truncate(frame.lengths.year_end_closes);
+        for (asset_id, asset) in frame.assets {
+            match asset {
+                Some(asset) => self.assets.insert(asset_id, asset),
+                None => self.assets.remove(&asset_id),
+            };
+        }
+        if let Some(mut restored) = frame.settings {
+            self.swap_records(&mut restored);
+            restored.undo_log = std::mem::take(&mut self.undo_log);
+            restored.emissions = std::mem::take(&mut self.emissions);
+            *self = *restored;
+        }
+        self.next_sequence = frame.next_sequence;
+        self.record_chain_heads = frame.record_chain_heads;
+        if rewritten {
+            // The records are back as they were indexed at the checkpoint, so this finds no duplicates
+            let _ = self.rebuild_indexes();
+        } else {
+            for (asset_id, totals) in frame.totals {
+                match totals {
+                    Some(totals) => self._asset_totals.insert(asset_id, totals),
+                    None => self._asset_totals.remove(&asset_id),
+                };
+            }
+        }
+        if let Some(position) = frame.id_position {
+            self.id_generator.rewind(position);
+        }
+    }
+
+    pub(crate) fn atomically<T>(&mut self, operation: impl FnOnce(&mut Self) -> IclResult<T>) -> IclResult<T> {
+        let checkpoint = self.checkpoint();
+        let result = operation(self);
+        match result {
+            Ok(_) => self.commit(checkpoint),
+            Err(_) => self.rollback(checkpoint),
+        }
+        result
+    }
+
+    pub(crate) fn changed_assets(&self, checkpoint: &LedgerCheckpoint) -> Vec<(Option<IntelligenceAsset>, &IntelligenceAsset)> {
+        let Some(frame) = self.undo_log.get(checkpoint.depth) else {
+            return Vec::new();
+        };
+        let before: BTreeMap<AssetId, Option<&IntelligenceAsset>> =
+            frame.assets.iter().map(|(asset_id, asset)| (*asset_id, asset.as_ref())).collect();
+        before.into_iter()
+            .filter_map(|(asset_id, before)| Some((before.cloned(), self.assets.get(&asset_id)?)))
+            .collect()
+    }
+
+    fn record_lengths(&self) -> RecordLengths {
+        RecordLengths {
+            events: self.events.len(),
+            entries: self.entries.len(),
+            journal_entries: self.journal_entries.len(),
+            proofs: self.proofs.len(),
+            anchor_receipts: self.anchor_receipts.len(),
+            portfolio_proofs: self.portfolio_proofs.len(),
+            archives: self.archives.len(),
+            year_end_closes: self.year_end_closes.len(),
+        }
+    }
+
+    fn swap_records(&mut self, other: &mut Self) {
+        std::mem::swap(&mut self.assets, &mut other.assets);
+        std::mem::swap(&mut self.events, &mut other.events);
+        std::mem::swap(&mut self.entries, &mut other.entries);
+        std::mem::swap(&mut self.journal_entries, &mut other.journal_entries);
+        std::mem::swap(&mut self.proofs, &mut other.proofs);
+        std::mem::swap(&mut self.anchor_receipts, &mut other.anchor_receipts);
+        std::mem::swap(&mut self.portfolio_proofs, &mut other.portfolio_proofs);
+        std::mem::swap(&mut self.archives, &mut other.archives);
+        std::mem::swap(&mut self.year_end_closes, &mut other.year_end_closes);
+        std::mem::swap(&mut self._events_by_asset, &mut other._events_by_asset);
+        std::mem::swap(&mut self._events_by_type, &mut other._events_by_type);
+        std::mem::swap(&mut self._entries_by_asset, &mut other._entries_by_asset);
+        std::mem::swap(&mut self._journal_entries_by_event, &mut other._journal_entries_by_event);
+        std::mem::swap(&mut self._journal_entries_by_asset, &mut other._journal_entries_by_asset);
+        std::mem::swap(&mut self._proofs_by_asset, &mut other._proofs_by_asset);
+        std::mem::swap(&mut self._event_index, &mut other._event_index);
+        std::mem::swap(&mut self._journal_entry_index, &mut other._journal_entry_index);
+        std::mem::swap(&mut self._asset_totals, &mut other._asset_totals);
+        std::mem::swap(&mut self._components_by_parent, &mut other._components_by_parent);
+        std::mem::swap(&mut self._voids, &mut other._voids);
+    }
+
+    fn truncate_records(&mut self, lengths: RecordLengths) {
+        for i in (lengths.journal_entries..self.journal_entries.len()).rev() {
+            let journal_entry = &self.journal_entries[i];
+            self._journal_entry_index.remove(&journal_entry.entry_id);
+            if let Some(positions) = self._journal_entries_by_event.get_mut(&journal_entry.event_id) {
+                truncate_positions(positions, i);
+            }
+            if let Some(&e) = self._event_index.get(&journal_entry.event_id) {
+                if let Some(positions) = self._journal_entries_by_asset.get_mut(&self.events[e].asset_id) {
+                    truncate_positions(positions, i);
+                }
+            }
+        }
+        for i in (lengths.events..self.events.len()).rev() {
+            let event = &self.events[i];
+            self._event_index.remove(&event.event_id);
+            if let Some(positions) = self._events_by_asset.get_mut(&event.asset_id) {
+                truncate_positions(positions, i);
+            }
+            if let Some(positions) = self._events_by_type.get_mut(&event.event_type) {
+                truncate_positions(positions, i);
+            }
+            if let Some(components) = component_parent(event).and_then(|p| self._components_by_parent.get_mut(&p)) {
+                components.retain(|asset_id| *asset_id != event.asset_id);
+            }
+            if let Some(voided) = voided_event_id(event) {
+                if self._voids.get(&voided) == Some(&i) {
+                    self._voids.remove(&voided);
+                }
+            }
+            // Journal entries posted before the event was recorded lose their asset again
+            if let Some(journal_positions) = self._journal_entries_by_event.get(&event.event_id) {
+                if let Some(by_asset) = self._journal_entries_by_asset.get_mut(&event.asset_id) {
+                    by_asset.retain(|p| !journal_positions.contains(p));
+                }
+            }
+        }
+        for i in (lengths.entries..self.entries.len()).rev() {
+            if let Some(positions) = self._entries_by_asset.get_mut(&self.entries[i].asset_id) {
+                truncate_positions(positions, i);
+            }
         }
+        for i in (lengths.proofs..self.proofs.len()).rev() {
+            if let Some(positions) = self._proofs_by_asset.get_mut(&self.proofs[i].asset_id) {
+                truncate_positions(positions, i);
+            }
+        }
+        self.events.truncate(lengths.events);
+        self.entries.truncate(lengths.entries);
+        self.journal_entries.truncate(lengths.journal_entries);
+        self.proofs.truncate(lengths.proofs);
+    }
+
+    fn undo_record(&mut self, undo: RecordUndo) {
+        match undo {
+            RecordUndo::Event(i, event) => self.events[i] = event,
+            RecordUndo::Entry(i, entry) => self.entries[i] = entry,
+            RecordUndo::JournalEntry(i, journal_entry) => self.journal_entries[i] = journal_entry,
+            RecordUndo::Proof(i, proof) => self.proofs[i] = proof,
+            RecordUndo::Removed { events, entries, journal_entries } => {
+                for (i, event) in events {
+                    self.events.insert(i, event);
+                }
+                for (i, entry) in entries {
+                    self.entries.insert(i, entry);
+                }
+                for (i, journal_entry) in journal_entries {
+                    self.journal_entries.insert(i, journal_entry);
+                }
+            },
+            RecordUndo::Inserted { events, entries, journal_entries } => {
+                for i in events.into_iter().rev() {
+                    self.events.remove(i);
+                }
+                for i in entries.into_iter().rev() {
+                    self.entries.remove(i);
+                }
+                for i in journal_entries.into_iter().rev() {
+                    self.journal_entries.remove(i);
+                }
+            },
+            RecordUndo::RemovedArchive(i, stub) => self.archives.insert(i, stub),
+        }
+    }
+
+    fn log_asset_write(&mut self, asset_id: AssetId) {
+        for frame in self.undo_log.iter_mut() {
+            frame.assets.entry(asset_id).or_insert_with(|| self.assets.get(&asset_id).cloned());
+        }
+    }
+
+    fn log_totals_write(&mut self, asset_id: AssetId) {
+        for frame in self.undo_log.iter_mut() {
+            frame.totals.entry(asset_id).or_insert_with(|| self._asset_totals.get(&asset_id).cloned());
+        }
+    }
+
+    fn log_settings_write(&mut self) {
+        if self.undo_log.iter().all(|f| f.settings.is_some()) {
+            return;
+        }
+        let undo_log = std::mem::take(&mut self.undo_log);
+        let emissions = std::mem::take(&mut self.emissions);
+        let mut records = Self::new();
+        self.swap_records(&mut records);
+        let settings = Box::new(self.clone());
+        self.swap_records(&mut records);
+        self.undo_log = undo_log;
+        self.emissions = emissions;
+        for frame in self.undo_log.iter_mut().filter(|f| f.settings.is_none()) {
+            frame.settings = Some(settings.clone());
+        }
+    }
+
+    fn log_record_undo(&mut self, undo: impl FnOnce(&Self) -> RecordUndo) {
+        if !self.undo_log.is_empty() {
+            let undo = undo(self);
+            if let Some(frame) = self.undo_log.last_mut() {
+                frame.records.push(undo);
+            }
+        }
+    }
+
+    fn event_mut(&mut self, i: usize) -> &mut CapitalEvent {
+        self.log_record_undo(|ledger| RecordUndo::Event(i, ledger.events[i].clone()));
+        &mut self.events[i]
+    }
+
+    fn entry_mut(&mut self, i: usize) -> &mut LedgerEntry {
+        self.log_record_undo(|ledger| RecordUndo::Entry(i, ledger.entries[i].clone()));
+        &mut self.entries[i]
+    }
+
+    fn journal_entry_mut(&mut self, i: usize) -> &mut JournalEntry {
+        self.log_record_undo(|ledger| RecordUndo::JournalEntry(i, ledger.journal_entries[i].clone()));
+        &mut self.journal_entries[i]
+    }
+
+    fn proof_mut(&mut self, i: usize) -> &mut CapitalProof {
+        self.log_record_undo(|ledger| RecordUndo::Proof(i, ledger.proofs[i].clone()));
+        &mut self.proofs[i]
+    }
+
+    // Remove the events with these IDs and their ledger and journal entries; the caller rebuilds
+    // the indexes
+    fn remove_records(&mut self, event_ids: &HashSet<EventId>) {
+        let events = take_where(&mut self.events, |e| event_ids.contains(&e.event_id));
+        let entries = take_where(&mut self.entries, |e| event_ids.contains(&e.event_id));
+        let journal_entries = take_where(&mut self.journal_entries, |j| event_ids.contains(&j.event_id));
+        self.log_record_undo(|_| RecordUndo::Removed { events, entries, journal_entries });
+    }
+
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash_algorithm
+    }
+
+    pub fn set_hash_algorithm(&mut self, algorithm: HashAlgorithm) {
+        self.log_settings_write();
+        self.hash_algorithm = algorithm;
+    }
+
+    /// Algorithm chaining events, ledger entries, and journal entries
+    pub fn record_hash_algorithm(&self) -> HashAlgorithm {
+        self.record_hash_algorithm
+    }
+
+    /// Choose the record chain algorithm; only possible before the first record is chained
+    pub fn set_record_hash_algorithm(&mut self, algorithm: HashAlgorithm) -> IclResult<()> {
+        let heads = &self.record_chain_heads;
+        let chained = heads.events.length + heads.entries.length + heads.journal_entries.length > 0;
+        if algorithm != self.record_hash_algorithm && chained {
+            return Err(IclError::IntegrityViolation(format!(
+                "The record chain is already hashed with {}; it cannot switch to {}", self.record_hash_algorithm, algorithm
+            )));
+        }
+        self.log_settings_write();
+        self.record_hash_algorithm = algorithm;
+        Ok(())
+    }
+
+    pub fn register_rule(&mut self, rule: impl Rule + 'static) {
+        self.log_settings_write();
+        self.rules.register(rule);
+    }
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    pub fn validation_mode(&self) -> ValidationMode {
+        self.validation.events
+    }
+
+    /// Set event validation, registered rules, and the retroactivity check to `mode`,
+    /// leaving the other checks as configured
+    pub fn set_validation_mode(&mut self, mode: ValidationMode) {
+        self.log_settings_write();
+        self.validation.events = mode;
+        self.validation.retroactive_timestamps = mode;
+    }
+
+    pub fn validation_config(&self) -> &ValidationConfig {
+        &self.validation
+    }
+
+    pub fn set_validation_config(&mut self, config: ValidationConfig) {
+        self.log_settings_write();
+        self.validation = config;
+    }
+
+    fn guard_duplicate_depreciation(&self, event: &CapitalEvent) -> IclResult<()> {
+        if event.event_type != "depreciation" {
+            return Ok(());
+        }
+        let period = (event.details.get("start_date"), event.details.get("end_date"));
+        for existing in self.get_events_for_asset(event.asset_id) {
+            if existing.event_type != "depreciation"
+                || self.is_voided(existing.event_id)
+                || (existing.details.get("start_date"), existing.details.get("end_date")) != period
+            {
+                continue;
+            }
+            let amount = |e: &CapitalEvent| e.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            if (amount(existing) - amount(event)).abs() <= VALUE_TOLERANCE {
+                return Err(IclError::IntegrityViolation(format!(
+                    "Depreciation duplicates event {} for the same period and amount", existing.event_id
+                )));
+            }
+            return Err(IclError::OverlappingDepreciation);
+        }
+        Ok(())
+    }
+
+    fn guard_cumulative_depreciation(&self, event: &CapitalEvent) -> IclResult<()> {
+        if event.event_type != "depreciation" {
+            return Ok(());
+        }
+        let asset = self.assets.get(&event.asset_id).ok_or(IclError::AssetNotFound(event.asset_id))?;
+        let amount = event.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let salvage_value = event.details.get("salvage_value").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let recorded = self.asset_totals(event.asset_id).map_or(0.0, AssetTotals::total_depreciation);
+
+        let depreciable_base = asset.initial_value - salvage_value;
+        if recorded + amount > depreciable_base + VALUE_TOLERANCE {
+            return Err(IclError::DepreciationError(format!(
+                "Cumulative depreciation {} would exceed depreciable base {}",
+                recorded + amount, depreciable_base
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn ordering_policy(&self) -> OrderingPolicy {
+        self.ordering_policy
+    }
+
+    pub fn set_ordering_policy(&mut self, policy: OrderingPolicy) {
+        self.log_settings_write();
+        self.ordering_policy = policy;
+    }
+
+    pub fn backdating_policy(&self) -> BackdatingPolicy {
+        self.backdating_policy
+    }
+
+    pub fn set_backdating_policy(&mut self, policy: BackdatingPolicy) {
+        self.log_settings_write();
+        self.backdating_policy = policy;
+    }
+
+    pub fn capitalization_policy(&self) -> CapitalizationPolicy {
+        self.capitalization_policy
+    }
+
+    /// Configure the cost below which `capitalize_or_expense` expenses rather than capitalizes
+    pub fn set_capitalization_policy(&mut self, policy: CapitalizationPolicy) -> IclResult<()> {
+        if !(policy.threshold.is_finite() && policy.threshold >= 0.0) {
+            return Err(IclError::InvalidAsset(format!(
+                "Capitalization threshold must be a finite non-negative amount, not {}", policy.threshold
+            )));
+        }
+        self.log_settings_write();
+        self.capitalization_policy = policy;
+        Ok(())
+    }
+
+    /// Close every period ending at or before `period_end`: no event or journal entry effective
+    /// before it can be posted from now on
+    pub fn close_period(&mut self, period_end: DateTime<Utc>) -> IclResult<()> {
+        if let Some(closed_until) = self.closed_until.filter(|closed_until| period_end < *closed_until) {
+            return Err(IclError::IntegrityViolation(format!(
+                "Periods before {} are already closed and cannot be reopened", closed_until.to_rfc3339()
+            )));
+        }
+        self.log_settings_write();
+        self.closed_until = Some(period_end);
+        Ok(())
+    }
+
+    pub fn closed_until(&self) -> Option<DateTime<Utc>> {
+        self.closed_until
+    }
+
+    /// Issue a single-use approval for posting one event effective as early as `effective_from`
+    pub fn approve_backdating(
+        &mut self,
+        approver: impl Into<String>,
+        effective_from: DateTime<Utc>
+    ) -> IclResult<BackdatingApproval> {
+        let approver = approver.into();
+        if approver.is_empty() {
+            return Err(IclError::Unauthorized("Backdating approvals need an approver".into()));
+        }
+        self.ensure_open_period(effective_from)
+            .map_err(|reason| IclError::InvalidEvent(format!("Cannot approve backdating: {}", reason)))?;
+        let approval = BackdatingApproval {
+            token: self.next_id(),
+            approver,
+            effective_from,
+            issued_at: self.now(),
+            used_by: None,
+        };
+        self.log_settings_write();
+        self.backdating_approvals.insert(approval.token, approval.clone());
+        Ok(approval)
+    }
+
+    /// Issued backdating approvals, spent or not, in token order
+    pub fn backdating_approvals(&self) -> impl Iterator<Item = &BackdatingApproval> {
+        self.backdating_approvals.values()
+    }
+
+    fn ensure_open_period(&self, effective_date: DateTime<Utc>) -> Result<(), String> {
+        match self.closed_until.filter(|closed_until| effective_date < *closed_until) {
+            Some(closed_until) => Err(format!(
+                "effective date {} falls in a period closed until {}",
+                effective_date.to_rfc3339(), closed_until.to_rfc3339()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn check_backdating(&self, event: &CapitalEvent, origin: PostingOrigin) -> IclResult<Option<Uuid>> {
+        let effective_date = event.effective_date();
+        self.ensure_open_period(effective_date).map_err(IclError::InvalidEvent)?;
+        if origin == PostingOrigin::Ledger || !self.backdating_policy.requires_approval(effective_date) {
+            return Ok(None);
+        }
+
+        let token = event.details.get(BACKDATING_APPROVAL_DETAIL)
+            .and_then(|v| v.as_str())
+            .and_then(|token| Uuid::parse_str(token).ok())
+            .ok_or_else(|| IclError::Unauthorized(format!(
+                "Posting effective {} requires a backdating approval", effective_date.to_rfc3339()
+            )))?;
+        let approval = self.backdating_approvals.get(&token)
+            .ok_or_else(|| IclError::Unauthorized(format!("Unknown backdating approval {}", token)))?;
+        if let Some(event_id) = approval.used_by {
+            return Err(IclError::Unauthorized(format!(
+                "Backdating approval {} was already used by event {}", token, event_id
+            )));
+        }
+        if effective_date < approval.effective_from {
+            return Err(IclError::Unauthorized(format!(
+                "Backdating approval {} only covers postings effective from {}",
+                token, approval.effective_from.to_rfc3339()
+            )));
+        }
+        Ok(Some(token))
+    }
+
+    fn check_journal_backdating(&self, journal_entry: &JournalEntry, origin: PostingOrigin) -> IclResult<()> {
+        let effective_date = journal_entry.effective_date();
+        self.ensure_open_period(effective_date).map_err(IclError::InvalidEntry)?;
+        let approved = origin == PostingOrigin::Ledger || self.get_event(journal_entry.event_id)
+            .is_some_and(|event| event.details.contains_key(APPROVED_BY_DETAIL));
+        if self.backdating_policy.requires_approval(effective_date) && !approved {
+            return Err(IclError::Unauthorized(format!(
+                "Journal entry effective {} requires a backdated event approval", effective_date.to_rfc3339()
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_event_on_write(&self, event: &CapitalEvent) -> IclResult<()> {
+        let checker = IntegrityChecker::new(self);
+        let config = &self.validation;
+        if config.unknown_event_types == ValidationMode::Strict && !config.is_known_event_type(&event.event_type) {
+            return Err(IclError::InvalidEvent(format!("Unknown event type {}", event.event_type)));
+        }
+        if config.currency_checks == ValidationMode::Strict {
+            if let Some(currency) = event.details.get(CURRENCY_DETAIL).and_then(|v| v.as_str()) {
+                if currency != config.currency {
+                    return Err(IclError::InvalidEvent(format!(
+                        "Event currency {} does not match ledger currency {}", currency, config.currency
+                    )));
+                }
+            }
+        }
+        if config.retroactive_timestamps == ValidationMode::Strict {
+            checker.ensure_no_retroactive_modification(event)?;
+        }
+        if config.events != ValidationMode::Strict {
+            return Ok(());
+        }
+        checker.validate_event(event)?;
+        for rule in self.rules.iter() {
+            rule.check_event(self, event)?;
+        }
+        Ok(())
+    }
+
+    fn validate_journal_entry_on_write(&self, journal_entry: &JournalEntry) -> IclResult<()> {
+        if self.validation.events != ValidationMode::Strict {
+            return Ok(());
+        }
+        if !self._event_index.contains_key(&journal_entry.event_id) {
+            return Err(IclError::InvalidEntry(format!(
+                "Journal entry references unknown event {}", journal_entry.event_id
+            )));
+        }
+        for rule in self.rules.iter() {
+            rule.check_journal_entry(self, journal_entry)?;
+        }
+        Ok(())
+    }
+
+    pub fn create_asset(
+        &mut self,
+        asset_id: AssetId,
+        owner: impl Into<OwnerId>,
+        initial_value: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32
+    ) -> IclResult<IntelligenceAsset> {
+        self.create_asset_in_org(OrgId::default(), asset_id, owner, initial_value, depreciation_method, useful_life_months)
+    }
+
+    /// Create an asset in a tenant namespace; its events, journal entries, and proofs stay in it
+    pub fn create_asset_in_org(
+        &mut self,
+        org_id: OrgId,
+        asset_id: AssetId,
+        owner: impl Into<OwnerId>,
+        initial_value: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32
+    ) -> IclResult<IntelligenceAsset> {
+        let mut asset = IntelligenceAsset::new(asset_id, owner, initial_value, depreciation_method, useful_life_months, self.now());
+        asset.org_id = org_id;
+        self.insert_new_asset(asset)
+    }
+
+    pub(crate) fn insert_new_asset(&mut self, asset: IntelligenceAsset) -> IclResult<IntelligenceAsset> {
+        if asset.org_id.as_str().is_empty() {
+            return Err(IclError::InvalidAsset("Namespace cannot be empty".into()));
+        }
+
+        if self.assets.contains_key(&asset.asset_id) {
+            return Err(IclError::AssetAlreadyExists(asset.asset_id));
+        }
+
+        self.validate_owner(&asset.owner)?;
+
+        if asset.initial_value <= 0.0 {
+            return Err(IclError::InvalidAsset("Initial value must be positive".into()));
+        }
+
+        asset.validate_schedule().map_err(IclError::InvalidAsset)?;
+        
+        self.log_asset_write(asset.asset_id);
+        self.assets.insert(asset.asset_id, asset.clone());
+        Ok(asset)
+    }
+
+    pub(crate) fn remove_uncapitalized_asset(&mut self, asset_id: AssetId) {
+        if self._events_by_asset.get(&asset_id).is_none_or(|events| events.is_empty()) {
+            self.log_asset_write(asset_id);
+            self.assets.remove(&asset_id);
+        }
+    }
+
+    /// Validate and capitalize many assets from `csv` or `json` input, reporting per row whether
+    /// the asset was created, skipped as a duplicate, or rejected
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(format, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn import_assets<R: std::io::Read>(
+        &mut self,
+        mut reader: R,
+        format: &str,
+        mode: ImportMode
+    ) -> IclResult<AssetImportReport> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        let rows = parse_asset_rows(&input, format)?;
+
+        let checkpoint = match mode {
+            ImportMode::AllOrNothing => Some(self.checkpoint()),
+            ImportMode::BestEffort => None,
+        };
+
+        let mut report = AssetImportReport::default();
+        for (index, row) in rows.into_iter().enumerate() {
+            let row_number = index + 1;
+            let row = match row {
+                Ok(row) => row,
+                Err(message) => {
+                    report.push(row_number, None, AssetImportOutcome::ValidationError(message));
+                    continue;
+                },
+            };
+            let asset_id = row.asset_id.unwrap_or_else(|| self.next_id().into());
+            if self.assets.contains_key(&asset_id) {
+                report.push(row_number, Some(asset_id), AssetImportOutcome::SkippedDuplicate);
+                continue;
+            }
+            let result = IntelligenceCapitalLifecycle::new(self).capitalize(
+                asset_id,
+                row.owner,
+                row.initial_value,
+                row.depreciation_method,
+                row.useful_life_months,
+            );
+            let outcome = match result {
+                Ok(_) => AssetImportOutcome::Created,
+                Err(e) => AssetImportOutcome::ValidationError(e.to_string()),
+            };
+            report.push(row_number, Some(asset_id), outcome);
+        }
+
+        if let Some(checkpoint) = checkpoint {
+            if report.failed > 0 {
+                self.rollback(checkpoint);
+                report.roll_back();
+            } else {
+                self.commit(checkpoint);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Append an event, sealing it (and its derived ledger entry) into the record hash chain
+    pub fn record_event(&mut self, event: CapitalEvent) -> IclResult<CapitalEvent> {
+        self.record_event_from(event, PostingOrigin::Caller)
+    }
+
+    pub(crate) fn record_ledger_event(&mut self, event: CapitalEvent) -> IclResult<CapitalEvent> {
+        self.record_event_from(event, PostingOrigin::Ledger)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "record_event", level = "debug", skip_all, fields(
+        event_id = %event.event_id,
+        asset_id = %event.asset_id,
+        event_type = %event.event_type,
+        amount = event.details.get("amount").and_then(|v| v.as_f64()),
+        duration_us = tracing::field::Empty
+    ), err))]
+    fn record_event_from(&mut self, event: CapitalEvent, origin: PostingOrigin) -> IclResult<CapitalEvent> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let started = std::time::Instant::now();
+        let result = self.append_event(event, origin);
+        match &result {
+            Ok(event) => self.metrics.event_recorded(&event.event_type, event.recorded_at(), started),
+            Err(_) => self.metrics.write_rejected("event"),
+        }
+        result
+    }
+
+    fn append_event(&mut self, mut event: CapitalEvent, origin: PostingOrigin) -> IclResult<CapitalEvent> {
+        // Only the ledger records events about itself
+        if event.asset_id != LEDGER_SUBJECT || origin != PostingOrigin::Ledger {
+            let asset = self.assets.get(&event.asset_id).ok_or(IclError::AssetNotFound(event.asset_id))?;
+            if event.org_id.is_default() {
+                event.org_id = asset.org_id.clone();
+            } else if event.org_id != asset.org_id {
+                return Err(IclError::InvalidEvent(format!(
+                    "Event namespace {} does not match asset namespace {}", event.org_id, asset.org_id
+                )));
+            }
+
+            for (name, value) in &asset.dimensions {
+                if self.dimensions.contains_key(name) && !event.dimensions.contains_key(name) {
+                    event.dimensions.insert(name.clone(), value.clone());
+                }
+            }
+        }
+        check_dimensions(&self.dimensions, &event.dimensions).map_err(IclError::InvalidEvent)?;
+
+        if event.event_type.is_empty() {
+            return Err(IclError::InvalidEvent("Event type cannot be empty".into()));
+        }
+
+        if self._event_index.contains_key(&event.event_id) {
+            return Err(IclError::DuplicateEvent(event.event_id));
+        }
+
+        event.recorded_at = Some(self.now());
+        let approval = self.check_backdating(&event, origin)?;
+        self.guard_duplicate_depreciation(&event)?;
+        self.guard_cumulative_depreciation(&event)?;
+        self.validate_event_on_write(&event)?;
+
+        if approval.is_some() {
+            self.log_settings_write();
+        }
+        if let Some(approval) = approval.and_then(|token| self.backdating_approvals.get_mut(&token)) {
+            approval.used_by = Some(event.event_id);
+            event.details.insert(APPROVED_BY_DETAIL.to_string(), serde_json::Value::String(approval.approver.clone()));
+        }
+
+        event.sequence = self.next_sequence;
+        event.previous_hash = self.events.last().and_then(|e| e.record_hash.clone());
+        event.record_hash = None;
+        event.record_hash = Some(record_chain_hash(self.record_hash_algorithm, &event)?);
+
+        let mut entry = LedgerEntry {
+            entry_id: self.next_id().into(),
+            event_id: event.event_id,
+            asset_id: event.asset_id,
+            timestamp: event.timestamp,
+            amount: event.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            description: event.event_type.clone(),
+            metadata: event.details.clone(),
+            sequence: event.sequence,
+            previous_hash: self.entries.last().and_then(|e| e.record_hash.clone()),
+            record_hash: None,
+            actor: event.actor.clone(),
+            effective_date: event.effective_date,
+            recorded_at: event.recorded_at,
+        };
+        entry.record_hash = Some(record_chain_hash(self.record_hash_algorithm, &entry)?);
+
+        self.next_sequence += 1;
+        self.record_chain_heads.events.extend(&event.record_hash);
+        self.record_chain_heads.entries.extend(&entry.record_hash);
+        self.events.push(event.clone());
+        self.index_event(self.events.len() - 1);
+
+        self._entries_by_asset.entry(event.asset_id).or_default().push(self.entries.len());
+        self.entries.push(entry);
+
+        Ok(event)
+    }
+
+    /// Append a journal entry, sealing it into the journal hash chain
+    pub fn record_journal_entry(&mut self, journal_entry: JournalEntry) -> IclResult<JournalEntry> {
+        self.record_journal_entry_from(journal_entry, PostingOrigin::Caller)
+    }
+
+    pub(crate) fn record_ledger_journal_entry(&mut self, journal_entry: JournalEntry) -> IclResult<JournalEntry> {
+        self.record_journal_entry_from(journal_entry, PostingOrigin::Ledger)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "record_journal_entry", level = "debug", skip_all, fields(
+        entry_id = %journal_entry.entry_id,
+        event_id = %journal_entry.event_id,
+        amount = journal_entry.amount,
+        duration_us = tracing::field::Empty
+    ), err))]
+    fn record_journal_entry_from(&mut self, journal_entry: JournalEntry, origin: PostingOrigin) -> IclResult<JournalEntry> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let started = std::time::Instant::now();
+        let result = self.append_journal_entry(journal_entry, origin);
+        match &result {
+            Ok(_) => self.metrics.journal_entry_posted(started),
+            Err(_) => self.metrics.write_rejected("journal_entry"),
+        }
+        result
+    }
+
+    fn append_journal_entry(&mut self, mut journal_entry: JournalEntry, origin: PostingOrigin) -> IclResult<JournalEntry> {
+        if self.validation.rejects_amount(journal_entry.amount) {
+            return Err(IclError::InvalidEntry("Journal entry amount must be positive".into()));
+        }
+
+        if self._journal_entry_index.contains_key(&journal_entry.entry_id) {
+            return Err(IclError::DuplicateEntry(journal_entry.entry_id));
+        }
+
+        if let Some(event) = self.get_event(journal_entry.event_id) {
+            if journal_entry.actor.is_none() {
+                journal_entry.actor = event.actor.clone();
+            }
+            if journal_entry.effective_date.is_none() {
+                journal_entry.effective_date = event.effective_date;
+            }
+            if journal_entry.dimensions.is_empty() {
+                journal_entry.dimensions = event.dimensions.clone();
+            }
+            if journal_entry.org_id.is_default() {
+                journal_entry.org_id = event.org_id.clone();
+            } else if journal_entry.org_id != event.org_id {
+                return Err(IclError::InvalidEntry(format!(
+                    "Journal entry namespace {} does not match event namespace {}",
+                    journal_entry.org_id, event.org_id
+                )));
+            }
+        }
+
+        check_dimensions(&self.dimensions, &journal_entry.dimensions).map_err(IclError::InvalidEntry)?;
+
+        journal_entry.recorded_at = Some(self.now());
+        self.check_journal_backdating(&journal_entry, origin)?;
+        self.validate_journal_entry_on_write(&journal_entry)?;
+
+        journal_entry.previous_hash = self.journal_entries.last().and_then(|j| j.record_hash.clone());
+        journal_entry.record_hash = None;
+        journal_entry.record_hash = Some(record_chain_hash(self.record_hash_algorithm, &journal_entry)?);
+
+        self.record_chain_heads.journal_entries.extend(&journal_entry.record_hash);
+        self.journal_entries.push(journal_entry.clone());
+        self.index_journal_entry(self.journal_entries.len() - 1);
+        Ok(journal_entry)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(asset_id = %asset_id, event_id = ?event_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn generate_proof(&mut self, asset_id: AssetId, event_id: Option<EventId>) -> IclResult<CapitalProof> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let previous_hash = self.latest_proof_for_asset(asset_id)
+            .map(|p| p.proof_hash.clone().unwrap_or_default());
+
+        let asset = self.assets.get(&asset_id).ok_or(IclError::AssetNotFound(asset_id))?;
+        let mut content = asset.proof_content();
+        // A proof of an event also commits to who performed it and to the event itself
+        if let Some(event_id) = event_id {
+            let event = self.get_event(event_id).ok_or(IclError::EventNotFound(event_id))?;
+            if event.asset_id != asset_id {
+                return Err(IclError::InvalidEvent(format!(
+                    "Event {} belongs to asset {}, not {}", event_id, event.asset_id, asset_id
+                )));
+            }
+            if let Some(actor) = &event.actor {
+                content.insert("actor".to_string(), serde_json::Value::String(actor.clone()));
+            }
+            content.insert(EVENT_DIGEST_CONTENT.to_string(), serde_json::json!(self.hash_algorithm.digest_record(event)?));
+        }
+
+        let proof = CapitalProof {
+            proof_id: self.next_id(),
+            asset_id,
+            event_id,
+            timestamp: self.now(),
+            origin: "ICL".to_string(),
+            previous_proof_hash: previous_hash.clone(),
+            content,
+            proof_hash: None,
+            hash_version: PROOF_HASH_VERSION,
+            hash_algorithm: self.hash_algorithm,
+            redacted: BTreeMap::new(),
+            legacy_content_order: Vec::new(),
+        };
+        
+        let computed_hash = proof.compute_hash()?;
+        let mut updated_proof = proof;
+        updated_proof.proof_hash = Some(computed_hash);
+        
+        self.push_proof(updated_proof.clone());
+        self.metrics.proof_generated("asset");
+        Ok(updated_proof)
+    }
+
+    fn push_proof(&mut self, proof: CapitalProof) {
+        self._proofs_by_asset.entry(proof.asset_id).or_default().push(self.proofs.len());
+        self.proofs.push(proof);
+    }
+
+    pub fn get_proofs_for_asset(&self, asset_id: AssetId) -> Vec<&CapitalProof> {
+        self._proofs_by_asset.get(&asset_id)
+            .map_or_else(Vec::new, |indices| indices.iter().map(|&i| &self.proofs[i]).collect())
+    }
+
+    /// Most recent proof in the asset's chain, found in constant time
+    pub fn latest_proof_for_asset(&self, asset_id: AssetId) -> Option<&CapitalProof> {
+        self._proofs_by_asset.get(&asset_id)
+            .and_then(|indices| indices.last())
+            .map(|&i| &self.proofs[i])
+    }
+
+    pub fn proof_count_for_asset(&self, asset_id: AssetId) -> usize {
+        self._proofs_by_asset.get(&asset_id).map_or(0, |indices| indices.len())
+    }
+
+    /// Commit the state of many assets for a period in one Merkle-rooted proof
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(assets = asset_ids.len(), duration_us = tracing::field::Empty), err
+    ))]
+    pub fn generate_portfolio_proof(
+        &mut self,
+        asset_ids: &[AssetId],
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>
+    ) -> IclResult<PortfolioProof> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        if period_start >= period_end {
+            return Err(IclError::InvalidDateRange {
+                start: period_start.to_rfc3339(),
+                end: period_end.to_rfc3339(),
+            });
+        }
+
+        let mut ids: Vec<AssetId> = asset_ids.to_vec();
+        ids.sort();
+        ids.dedup();
+        if ids.is_empty() {
+            return Err(IclError::InvalidAsset("Portfolio proof requires at least one asset".into()));
+        }
+
+        let org_id = self.assets.get(&ids[0]).ok_or(IclError::AssetNotFound(ids[0]))?.org_id.clone();
+        let mut leaves = Vec::with_capacity(ids.len());
+        for asset_id in ids {
+            let asset = self.assets.get(&asset_id).ok_or(IclError::AssetNotFound(asset_id))?;
+            if asset.org_id != org_id {
+                return Err(IclError::InvalidAsset(format!(
+                    "Portfolio proof cannot span namespaces {} and {}", org_id, asset.org_id
+                )));
+            }
+            let period_depreciation: f64 = self.get_events_for_asset(asset_id).iter()
+                .filter(|e| e.event_type == "depreciation" && !self.is_voided(e.event_id))
+                .filter(|e| e.effective_date() >= period_start && e.effective_date() < period_end)
+                .filter_map(|e| e.details.get("amount").and_then(|v| v.as_f64()))
+                .sum();
+            let mut state = asset.proof_content();
+            state.insert("period_depreciation".to_string(), serde_json::json!(period_depreciation));
+            leaves.push(PortfolioLeaf {
+                asset_id,
+                state_hash: self.hash_algorithm.digest_record(&state)?,
+            });
+        }
+
+        let leaf_values: Vec<String> = leaves.iter().map(|l| format!("{}:{}", l.asset_id, l.state_hash)).collect();
+        let merkle_root = merkle_root(self.hash_algorithm, &leaf_values).unwrap_or_default();
+
+        let mut proof = PortfolioProof {
+            proof_id: self.next_id(),
+            timestamp: self.now(),
+            origin: "ICL".to_string(),
+            period_start,
+            period_end,
+            leaves,
+            merkle_root,
+            previous_proof_hash: self.latest_portfolio_proof(&org_id).and_then(|p| p.proof_hash.clone()),
+            proof_hash: None,
+            hash_algorithm: self.hash_algorithm,
+            org_id,
+        };
+        proof.proof_hash = Some(proof.compute_hash()?);
+
+        self.portfolio_proofs.push(proof.clone());
+        self.metrics.proof_generated("portfolio");
+        Ok(proof)
+    }
+
+    /// Most recent portfolio proof in a namespace's chain
+    pub fn latest_portfolio_proof(&self, org_id: &OrgId) -> Option<&PortfolioProof> {
+        self.portfolio_proofs.iter().rev().find(|p| &p.org_id == org_id)
+    }
+
+    /// Commit the most recent proof hash to an external notary and store the receipt
+    pub fn anchor_latest_proof(&mut self, anchor: &dyn Anchor) -> IclResult<AnchorReceipt> {
+        let proof = self.proofs.last()
+            .ok_or_else(|| IclError::IntegrationError("No proofs available to anchor".into()))?;
+        let hash = proof.proof_hash.clone()
+            .ok_or_else(|| IclError::IntegrityViolation(format!("Proof {} has no hash", proof.proof_id)))?;
+
+        let commitment = anchor.anchor(&hash)?;
+        let receipt = AnchorReceipt {
+            anchor_id: self.next_id(),
+            proof_id: proof.proof_id,
+            anchored_hash: hash,
+            provider: anchor.name().to_string(),
+            reference: commitment.reference,
+            anchored_at: commitment.anchored_at,
+            receipt: commitment.receipt,
+        };
+
+        self.anchor_receipts.push(receipt.clone());
+        Ok(receipt)
+    }
+
+    pub fn get_anchor_receipts(&self, proof_id: Uuid) -> Vec<&AnchorReceipt> {
+        self.anchor_receipts.iter().filter(|r| r.proof_id == proof_id).collect()
+    }
+
+    /// Re-seal every proof under the current hash scheme, relinking each asset's chain in order
+    pub fn migrate_proof_hashes(&mut self) -> IclResult<usize> {
+        let mut mismatched = Vec::new();
+        for proof in &self.proofs {
+            if !proof.verify_hash()? {
+                mismatched.push(proof.proof_id.to_string());
+            }
+        }
+        if !mismatched.is_empty() {
+            return Err(IclError::IntegrityViolation(format!(
+                "Proofs do not match their stored hashes and cannot be re-sealed: {}", mismatched.join(", ")
+            )));
+        }
+
+        let mut upgraded = 0;
+        let mut latest_hash_by_asset: HashMap<AssetId, String> = HashMap::new();
+        for i in 0..self.proofs.len() {
+            let previous_hash = latest_hash_by_asset.get(&self.proofs[i].asset_id).cloned();
+            let proof = self.proof_mut(i);
+            if proof.hash_version != PROOF_HASH_VERSION {
+                upgraded += 1;
+            }
+            proof.reseal(previous_hash)?;
+            if let Some(hash) = &proof.proof_hash {
+                latest_hash_by_asset.insert(proof.asset_id, hash.clone());
+            }
+        }
+
+        Ok(upgraded)
+    }
+
+    /// Version of the data model the ledger's records are in: `LEDGER_DATA_MODEL_VERSION` for
+    /// new ledgers, older for ledgers deserialized from older snapshots until `upgrade` runs
+    pub fn data_model_version(&self) -> u32 {
+        self.data_model_version
+    }
+
+    pub fn needs_upgrade(&self) -> bool {
+        self.data_model_version < LEDGER_DATA_MODEL_VERSION
+    }
+
+    /// Bring the ledger's records up to `LEDGER_DATA_MODEL_VERSION` in place, applying each
+    /// migration from its current version in turn
+    pub fn upgrade(&mut self) -> IclResult<UpgradeReport> {
+        self.atomically(|ledger| ledger.apply_migrations(false))
+    }
+
+    pub fn upgrade_dry_run(&mut self) -> IclResult<UpgradeReport> {
+        let checkpoint = self.checkpoint();
+        let report = self.apply_migrations(true);
+        self.rollback(checkpoint);
+        report
+    }
+
+    fn apply_migrations(&mut self, dry_run: bool) -> IclResult<UpgradeReport> {
+        let mut report = UpgradeReport {
+            from_version: self.data_model_version,
+            to_version: self.data_model_version,
+            dry_run,
+            steps: Vec::new(),
+        };
+        let from_version = self.data_model_version;
+        for migration in LEDGER_MIGRATIONS.iter().filter(|m| m.from_version >= from_version) {
+            if migration.from_version != self.data_model_version {
+                return Err(IclError::UnsupportedFormat(format!(
+                    "ledger data model version {} has no migration to {}", self.data_model_version, LEDGER_DATA_MODEL_VERSION
+                )));
+            }
+            let records_changed = (migration.apply)(self)
+                .with_context(|| format!("upgrade ledger: {}", migration.name), None)?;
+            self.log_settings_write();
+            self.data_model_version = migration.to_version;
+            report.to_version = migration.to_version;
+            report.steps.push(UpgradeStep {
+                name: migration.name.into(),
+                from_version: migration.from_version,
+                to_version: migration.to_version,
+                description: migration.description.into(),
+                records_changed,
+            });
+        }
+        Ok(report)
+    }
+
+    pub fn quarantine_record(&mut self, record_id: impl Into<Uuid>) {
+        self.log_settings_write();
+        self.quarantine.insert(record_id.into());
+    }
+
+    pub fn release_record(&mut self, record_id: impl Into<Uuid>) -> bool {
+        self.log_settings_write();
+        self.quarantine.remove(&record_id.into())
+    }
+
+    pub fn is_quarantined(&self, record_id: impl Into<Uuid>) -> bool {
+        self.quarantine.contains(&record_id.into())
+    }
+
+    pub fn quarantined_records(&self) -> impl Iterator<Item = &Uuid> {
+        self.quarantine.iter()
+    }
+
+    pub fn quarantine_orphans(&mut self) -> OrphanReport {
+        let orphans = IntegrityChecker::new(self).find_orphans();
+        self.log_settings_write();
+        self.quarantine.extend(orphans.record_ids());
+        orphans
+    }
+
+    /// Void an erroneous event: record a `void` event naming it, performed by `actor` for `reason`,
+    /// and post a compensating reversal of each of its journal entries against the void
+    pub fn void_event(&mut self, event_id: EventId, reason: impl Into<String>, actor: impl Into<String>) -> IclResult<CapitalEvent> {
+        let (reason, actor) = (reason.into(), actor.into());
+        if reason.trim().is_empty() || actor.trim().is_empty() {
+            return Err(IclError::InvalidEvent("A void requires a reason and an actor".into()));
+        }
+        let voided = self.get_event(event_id).cloned().ok_or(IclError::EventNotFound(event_id))?;
+        if let Some(void) = self.void_of(event_id) {
+            return Err(IclError::IntegrityViolation(format!(
+                "Event {} is already voided by event {}", event_id, void.event_id
+            )));
+        }
+        if !VOIDABLE_EVENT_TYPES.contains(&voided.event_type.as_str()) {
+            return Err(IclError::InvalidEvent(format!("{} events cannot be voided", voided.event_type)));
+        }
+        let restored_value = match voided.event_type.as_str() {
+            "depreciation" | IMPAIRMENT_EVENT => voided.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            _ => 0.0,
+        };
+        if restored_value > 0.0 {
+            let later = self.get_events_for_asset(voided.asset_id).into_iter().find(|e| {
+                e.sequence > voided.sequence
+                    && VALUE_CHANGING_EVENT_TYPES.contains(&e.event_type.as_str())
+                    && !self.is_voided(e.event_id)
+            });
+            if let Some(later) = later {
+                return Err(IclError::IntegrityViolation(format!(
+                    "{} event {} changed the value of asset {} after event {} and must be voided first",
+                    later.event_type, later.event_id, voided.asset_id, event_id
+                )));
+            }
+        }
+
+        let now = self.now();
+        let mut details = HashMap::new();
+        details.insert(VOIDED_EVENT_DETAIL.to_string(), serde_json::Value::String(event_id.to_string()));
+        details.insert("voided_event_type".to_string(), serde_json::Value::String(voided.event_type.clone()));
+        details.insert("reason".to_string(), serde_json::Value::String(reason));
+        let mut void = CapitalEvent::new(voided.asset_id, VOID_EVENT, now, details).with_id(self.next_id());
+        void.actor = Some(actor);
+        // A rejected reversal or restore takes the void back out, so the event can be voided again
+        self.atomically(|ledger| {
+            let void = ledger.record_event(void)?;
+
+            let reversals: Vec<JournalEntry> = ledger.journal_entries_for_event(event_id).into_iter()
+                .map(|j| JournalEntry::new(
+                    void.event_id,
+                    now,
+                    j.credit_account,
+                    j.debit_account,
+                    j.amount,
+                    format!("Void of {}", j.description),
+                    HashMap::from([(REVERSED_ENTRY_METADATA.to_string(), serde_json::Value::String(j.entry_id.to_string()))]),
+                ).with_id(ledger.next_id()))
+                .collect();
+            for reversal in reversals {
+                ledger.record_journal_entry(reversal).with_context(|| "post void reversal", Some(voided.asset_id))?;
+            }
+
+            if restored_value > 0.0 {
+                ledger.update_asset(voided.asset_id, |asset| {
+                    asset.current_value = Some(asset.current_value.unwrap_or(asset.initial_value) + restored_value);
+                    if asset.status == AssetStatus::Depreciated {
+                        asset.status = AssetStatus::Active;
+                    }
+                }).with_context(|| "restore book value", Some(voided.asset_id))?;
+            }
+            Ok(void)
+        })
+    }
+
+    pub fn is_voided(&self, event_id: EventId) -> bool {
+        self._voids.contains_key(&event_id)
+    }
+
+    pub fn void_of(&self, event_id: EventId) -> Option<&CapitalEvent> {
+        self._voids.get(&event_id).map(|&i| &self.events[i])
+    }
+
+    pub fn voided_events(&self) -> impl Iterator<Item = &CapitalEvent> {
+        self.events_of_type(VOID_EVENT).filter_map(|void| voided_event_id(void).and_then(|id| self.get_event(id)))
+    }
+
+    /// Move every event effective in `period`, with its ledger and journal entries, out of the
+    /// ledger into a compressed, hash-sealed archive for cold storage
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(period = %period, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn archive_period(&mut self, period: FiscalPeriod) -> IclResult<ArchiveFile> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        period.validate()?;
+        if self.closed_until.is_none_or(|closed_until| closed_until < period.end) {
+            return Err(IclError::IntegrityViolation(format!("Period {} must be closed before it is archived", period)));
+        }
+        if let Some(archive) = self.archives.iter().find(|a| a.period.overlaps(period.start, period.end)) {
+            return Err(IclError::IntegrityViolation(format!(
+                "Period {} overlaps archived period {}", period, archive.period
+            )));
+        }
+
+        let effective_dates: HashMap<EventId, DateTime<Utc>> = self.events.iter()
+            .filter(|e| period.contains(e.effective_date()) && e.event_type != ARCHIVE_EVENT && e.asset_id != LEDGER_SUBJECT)
+            .map(|e| (e.event_id, e.effective_date()))
+            .collect();
+        let archived: HashSet<EventId> = effective_dates.keys().copied().collect();
+        if archived.is_empty() {
+            return Err(IclError::IntegrityViolation(format!("Period {} has no records to archive", period)));
+        }
+        for void in self.events_of_type(VOID_EVENT) {
+            if let Some(voided) = voided_event_id(void).filter(|id| archived.contains(id) != archived.contains(&void.event_id)) {
+                return Err(IclError::IntegrityViolation(format!(
+                    "Void {} and voided event {} fall on different sides of period {}", void.event_id, voided, period
+                )));
+            }
+        }
+        // Later records link to the latest one, so it must stay in the ledger to keep the chains whole
+        let latest_archived = self.events.last().is_some_and(|e| archived.contains(&e.event_id))
+            || self.entries.last().is_some_and(|e| archived.contains(&e.event_id))
+            || self.journal_entries.last().is_some_and(|j| archived.contains(&j.event_id));
+        if latest_archived {
+            return Err(IclError::IntegrityViolation(format!(
+                "The latest records belong to period {}; it can be archived once later activity is recorded", period
+            )));
+        }
+
+        let period_end = period.end - Duration::nanoseconds(1);
+        let mut assets: BTreeMap<AssetId, ArchivedAssetSummary> = BTreeMap::new();
+        let new_summary = |asset_id| ArchivedAssetSummary {
+            closing_position: self.asset_position(asset_id, period_end),
+            book_value_change: 0.0,
+            improvements: 0.0,
+            salvage_value: 0.0,
+            pool_depreciation: 0.0,
+            totals: AssetTotals::default(),
+        };
+        for event in self.events.iter().filter(|e| archived.contains(&e.event_id)) {
+            let summary = assets.entry(event.asset_id).or_insert_with(|| new_summary(event.asset_id));
+            summary.totals.add_event(event);
+            if self.is_voided(event.event_id) {
+                summary.totals.remove_voided(event);
+            } else if !self.is_quarantined(event.event_id) {
+                summary.book_value_change += book_value_change(event, event.asset_id).unwrap_or(0.0);
+            }
+            match event.event_type.as_str() {
+                IMPROVEMENT_EVENT => summary.improvements += event.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                "depreciation" => {
+                    let salvage_value = event.details.get("salvage_value").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                    summary.salvage_value = summary.salvage_value.max(salvage_value);
+                },
+                _ => {},
+            }
+            // Pool depreciation lands on each member by its share; the lead's is counted above
+            if event.event_type == POOL_DEPRECIATION_EVENT && !self.is_quarantined(event.event_id) {
+                for (asset_id, share) in pool_allocations(event) {
+                    let summary = assets.entry(asset_id).or_insert_with(|| new_summary(asset_id));
+                    summary.pool_depreciation += share;
+                    if asset_id != event.asset_id {
+                        summary.book_value_change -= share;
+                    }
+                }
+            }
+        }
+        let mut account_balances = HashMap::new();
+        for journal_entry in self.journal_entries.iter().filter(|j| archived.contains(&j.event_id)) {
+            if let Some(summary) = self.get_event(journal_entry.event_id).and_then(|e| assets.get_mut(&e.asset_id)) {
+                summary.totals.journal_entry_count += 1;
+            }
+            if !self.is_quarantined(journal_entry.entry_id) {
+                *account_balances.entry(journal_entry.debit_account).or_default() += journal_entry.amount;
+                *account_balances.entry(journal_entry.credit_account).or_default() -= journal_entry.amount;
+            }
+        }
+
+        let records = ArchivedRecords {
+            events: self.events.iter().filter(|e| archived.contains(&e.event_id)).cloned().collect(),
+            entries: self.entries.iter().filter(|e| archived.contains(&e.event_id)).cloned().collect(),
+            journal_entries: self.journal_entries.iter().filter(|j| archived.contains(&j.event_id)).cloned().collect(),
+        };
+        let (event_count, entry_count, journal_entry_count) = (records.events.len(), records.entries.len(), records.journal_entries.len());
+        // Archived retention purges take the links of the records they purged with them
+        let archived_purges: Vec<_> = records.events.iter().filter_map(RetentionPurge::from_event).collect();
+        let link = |event_id: EventId, previous_hash: &Option<String>, record_hash: &Option<String>| {
+            ChainLink::new(previous_hash, record_hash).with_effective_date(effective_dates[&event_id])
+        };
+        let event_links = records.events.iter().map(|e| link(e.event_id, &e.previous_hash, &e.record_hash))
+            .chain(archived_purges.iter().flat_map(|p| p.event_links.iter().cloned()))
+            .collect();
+        let entry_links = records.entries.iter().map(|e| link(e.event_id, &e.previous_hash, &e.record_hash))
+            .chain(archived_purges.iter().flat_map(|p| p.entry_links.iter().cloned()))
+            .collect();
+        let journal_links = records.journal_entries.iter().map(|j| link(j.event_id, &j.previous_hash, &j.record_hash)).collect();
+        // Nothing is removed unless the archive event is recorded
+        self.atomically(|ledger| {
+            let archive_id = ledger.next_id();
+            let (file, seal) = ArchiveFile::write(archive_id, period, ledger.hash_algorithm, records)
+                .with_context(|| format!("archive period {}", period), None)?;
+            let stub = ArchiveStub {
+                archive_id,
+                period,
+                archived_at: ledger.now(),
+                seal,
+                hash_algorithm: ledger.hash_algorithm,
+                event_count,
+                entry_count,
+                journal_entry_count,
+                assets,
+                account_balances,
+                event_links,
+                entry_links,
+                journal_links,
+            };
+
+            let mut details = HashMap::new();
+            details.insert("archive_id".to_string(), serde_json::Value::String(archive_id.to_string()));
+            details.insert("period_start".to_string(), serde_json::Value::String(period.start.to_rfc3339()));
+            details.insert("period_end".to_string(), serde_json::Value::String(period.end.to_rfc3339()));
+            details.insert("seal".to_string(), serde_json::Value::String(stub.seal.clone()));
+            details.insert(ARCHIVE_DIGEST_DETAIL.to_string(), serde_json::Value::String(stub.digest()?));
+            let now = ledger.now();
+            let mut event = CapitalEvent::new(LEDGER_SUBJECT, ARCHIVE_EVENT, now, details).with_id(ledger.next_id());
+            // Nothing can be posted into the closed, archived period
+            if let Some(closed_until) = ledger.closed_until.filter(|closed_until| *closed_until > now) {
+                event = event.with_effective_date(closed_until);
+            }
+            ledger.record_ledger_event(event).with_context(|| format!("archive period {}: record archive event", period), None)?;
+
+            ledger.remove_records(&archived);
+            ledger.archives.push(stub);
+            ledger.rebuild_indexes().with_context(|| format!("archive period {}: rebuild indexes", period), None)?;
+            Ok(file)
+        })
+    }
+
+    /// Bring the records of an archive written by `archive_period` back into the ledger, each in
+    /// its place in the hash chains, and drop its stub
+    pub fn load_archive(&mut self, file: &ArchiveFile) -> IclResult<ArchiveStub> {
+        let (archive_id, seal, records) = file.open()?;
+        let position = self.archives.iter().position(|a| a.archive_id == archive_id).ok_or_else(|| {
+            IclError::IntegrityViolation(format!("Archive {} was not archived from this ledger or is already loaded", archive_id))
+        })?;
+        if self.archives[position].seal != seal {
+            return Err(IclError::IntegrityViolation(format!("Archive {} does not match the seal kept by the ledger", archive_id)));
+        }
+        if let Some(event) = records.events.iter().find(|e| self._event_index.contains_key(&e.event_id)) {
+            return Err(IclError::DuplicateEvent(event.event_id));
+        }
+        if let Some(journal_entry) = records.journal_entries.iter().find(|j| self._journal_entry_index.contains_key(&j.entry_id)) {
+            return Err(IclError::DuplicateEntry(journal_entry.entry_id));
+        }
+
+        let stub = self.archives.remove(position);
+        self.log_record_undo(|_| RecordUndo::RemovedArchive(position, stub.clone()));
+        let loaded: HashSet<EventId> = records.events.iter().map(|e| e.event_id).collect();
+        self.events = splice(std::mem::take(&mut self.events), records.events, |e| ChainLink::new(&e.previous_hash, &e.record_hash));
+        self.entries = splice(std::mem::take(&mut self.entries), records.entries, |e| ChainLink::new(&e.previous_hash, &e.record_hash));
+        self.journal_entries = splice(
+            std::mem::take(&mut self.journal_entries),
+            records.journal_entries,
+            |j| ChainLink::new(&j.previous_hash, &j.record_hash),
+        );
+        self.log_record_undo(|ledger| RecordUndo::Inserted {
+            events: positions_where(&ledger.events, |e| loaded.contains(&e.event_id)),
+            entries: positions_where(&ledger.entries, |e| loaded.contains(&e.event_id)),
+            journal_entries: positions_where(&ledger.journal_entries, |j| loaded.contains(&j.event_id)),
+        });
+        self.rebuild_indexes().with_context(|| format!("load archive {}: rebuild indexes", archive_id), None)?;
+        Ok(stub)
+    }
+
+    pub fn year_end_closes(&self) -> &[YearEndClose] {
+        &self.year_end_closes
+    }
+
+    /// Balances carried into a fiscal year from the close of the year ending when it starts
+    pub fn opening_balances(&self, fiscal_year: FiscalPeriod) -> Option<&HashMap<AccountType, f64>> {
+        self.year_end_closes.iter()
+            .find(|close| close.fiscal_year.end == fiscal_year.start)
+            .map(|close| &close.opening_balances)
+    }
+
+    pub(crate) fn push_year_end_close(&mut self, close: YearEndClose) {
+        self.year_end_closes.push(close);
+    }
+
+    pub fn archives(&self) -> &[ArchiveStub] {
+        &self.archives
+    }
+
+    pub(crate) fn archived_summaries(&self, asset_id: AssetId, as_of: DateTime<Utc>) -> impl Iterator<Item = &ArchivedAssetSummary> {
+        self.archives.iter()
+            .filter(move |a| a.period.has_ended_by(as_of))
+            .filter_map(move |a| a.assets.get(&asset_id))
+    }
+
+    /// Register a dimension events and journal entries may carry, replacing any definition of the
+    /// same name
+    pub fn register_dimension(&mut self, definition: DimensionDefinition) -> IclResult<()> {
+        if definition.name.trim().is_empty() {
+            return Err(IclError::InvalidEvent("Dimension name cannot be empty".into()));
+        }
+        if let Some(value) = definition.allowed_values.iter().find(|v| v.trim().is_empty()) {
+            return Err(IclError::InvalidEvent(format!("Dimension {} has an empty allowed value {:?}", definition.name, value)));
+        }
+        self.log_settings_write();
+        self.dimensions.insert(definition.name.clone(), definition);
+        Ok(())
+    }
+
+    /// Stop accepting a dimension on new records
+    pub fn unregister_dimension(&mut self, name: &str) -> Option<DimensionDefinition> {
+        self.log_settings_write();
+        self.dimensions.remove(name)
+    }
+
+    pub fn dimensions(&self) -> impl Iterator<Item = &DimensionDefinition> {
+        self.dimensions.values()
+    }
+
+    /// Register a journal template, replacing any template of the same name
+    pub fn register_journal_template(&mut self, template: JournalTemplate) -> IclResult<()> {
+        template.validate()?;
+        self.log_settings_write();
+        self.journal_templates.insert(template.name.clone(), template);
+        Ok(())
+    }
+
+    pub fn remove_journal_template(&mut self, name: &str) -> Option<JournalTemplate> {
+        self.log_settings_write();
+        self.journal_templates.remove(name)
+    }
+
+    pub fn journal_template(&self, name: &str) -> Option<&JournalTemplate> {
+        self.journal_templates.get(name)
+    }
+
+    pub fn journal_templates(&self) -> impl Iterator<Item = &JournalTemplate> {
+        self.journal_templates.values()
+    }
+
+    /// Post the entries of a registered journal template for a recorded event, at the event's
+    /// timestamp
+    pub fn post_template(
+        &mut self,
+        name: &str,
+        event_id: EventId,
+        values: &HashMap<String, serde_json::Value>,
+    ) -> IclResult<Vec<JournalEntry>> {
+        let template = self.journal_templates.get(name)
+            .cloned()
+            .ok_or_else(|| IclError::InvalidEntry(format!("Journal template {} is not registered", name)))?;
+        self.post_journal_template(&template, event_id, values)
+    }
+
+    pub(crate) fn post_journal_template(
+        &mut self,
+        template: &JournalTemplate,
+        event_id: EventId,
+        values: &HashMap<String, serde_json::Value>,
+    ) -> IclResult<Vec<JournalEntry>> {
+        let timestamp = self.get_event(event_id)
+            .map(|e| e.timestamp)
+            .ok_or(IclError::EventNotFound(event_id))?;
+        let entries = template.instantiate(event_id, timestamp, values)?;
+        entries.into_iter()
+            .map(|entry| {
+                let entry = entry.with_id(self.next_id());
+                self.record_journal_entry(entry)
+            })
+            .collect()
+    }
+
+    pub fn set_retention_rule(&mut self, rule: RetentionRule) -> IclResult<()> {
+        if rule.event_type.trim().is_empty() {
+            return Err(IclError::InvalidEvent("Retention rule event type cannot be empty".into()));
+        }
+        if RETENTION_PROTECTED_EVENT_TYPES.contains(&rule.event_type.as_str()) {
+            return Err(IclError::InvalidEvent(format!("{} events cannot be purged by retention", rule.event_type)));
+        }
+        if rule.retain_months == 0 {
+            return Err(IclError::InvalidEvent("Retention period must be at least one month".into()));
+        }
+        self.log_settings_write();
+        self.retention_rules.insert(rule.event_type.clone(), rule);
+        Ok(())
+    }
+
+    pub fn remove_retention_rule(&mut self, event_type: &str) -> Option<RetentionRule> {
+        self.log_settings_write();
+        self.retention_rules.remove(event_type)
+    }
+
+    pub fn retention_rules(&self) -> impl Iterator<Item = &RetentionRule> {
+        self.retention_rules.values()
+    }
+
+    /// Purge the events each retention rule has outlived, with their ledger entries
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(duration_us = tracing::field::Empty), err
+    ))]
+    pub fn apply_retention(&mut self) -> IclResult<RetentionReport> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let now = self.now();
+        // Proven events stay, so their proofs can still be bundled and verified
+        let proven: HashSet<EventId> = self.proofs.iter().filter_map(|p| p.event_id).collect();
+        let mut purges: BTreeMap<(String, AssetId), RetentionPurge> = BTreeMap::new();
+        for rule in self.retention_rules.values() {
+            let cutoff = rule.cutoff(now);
+            let expired = self.events_of_type(&rule.event_type).filter(|e| {
+                e.effective_date() < cutoff
+                    && !self.is_voided(e.event_id)
+                    && !proven.contains(&e.event_id)
+                    && self._journal_entries_by_event.get(&e.event_id).is_none_or(|j| j.is_empty())
+            });
+            for event in expired {
+                let purge = purges.entry((rule.event_type.clone(), event.asset_id)).or_insert_with(|| RetentionPurge {
+                    purged_event_type: rule.event_type.clone(),
+                    retain_months: rule.retain_months,
+                    cutoff,
+                    purged_event_ids: Vec::new(),
+                    purged_totals: AssetTotals::default(),
+                    event_links: Vec::new(),
+                    entry_links: Vec::new(),
+                });
+                purge.purged_event_ids.push(event.event_id);
+                purge.purged_totals.add_event(event);
+                purge.event_links.push(ChainLink::new(&event.previous_hash, &event.record_hash));
+            }
+        }
+        for purge in purges.values_mut() {
+            let purged: HashSet<EventId> = purge.purged_event_ids.iter().copied().collect();
+            purge.entry_links = self.entries.iter()
+                .filter(|e| purged.contains(&e.event_id))
+                .map(|e| ChainLink::new(&e.previous_hash, &e.record_hash))
+                .collect();
+        }
+
+        // Nothing is purged unless every purge is recorded
+        self.atomically(|ledger| {
+            let mut report = RetentionReport { applied_at: now, purge_events: Vec::new(), purged_event_count: 0 };
+            let mut purged = HashSet::new();
+            for ((_, asset_id), purge) in purges {
+                let event = CapitalEvent::new(asset_id, RETENTION_PURGE_EVENT, now, purge.to_details()?).with_id(ledger.next_id());
+                let event = ledger.record_event(event).with_context(|| "record retention purge", Some(asset_id))?;
+                report.purged_event_count += purge.purged_event_ids.len();
+                purged.extend(purge.purged_event_ids);
+                report.purge_events.push(event);
+            }
+            ledger.remove_records(&purged);
+            ledger.rebuild_indexes().with_context(|| "apply retention: rebuild indexes", None)?;
+            Ok(report)
+        })
+    }
+
+    pub fn retention_purges(&self) -> impl Iterator<Item = RetentionPurge> + '_ {
+        self.events_of_type(RETENTION_PURGE_EVENT).filter_map(RetentionPurge::from_event)
+    }
+
+    pub fn owner_rewrites(&self) -> impl Iterator<Item = OwnerRewrite> + '_ {
+        self.events_of_type(OWNER_ANONYMIZED_EVENT).filter_map(OwnerRewrite::from_event)
+    }
+
+    pub(crate) fn ensure_unarchived(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> IclResult<()> {
+        match self.archives.iter().find(|a| a.period.overlaps(from, to)) {
+            Some(archive) => Err(IclError::IntegrityViolation(format!(
+                "Period {} is archived; load archive {} to report on it", archive.period, archive.archive_id
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get_event(&self, event_id: EventId) -> Option<&CapitalEvent> {
+        self._event_index.get(&event_id).map(|&i| &self.events[i])
+    }
+
+    pub fn get_journal_entry(&self, entry_id: EntryId) -> Option<&JournalEntry> {
+        self._journal_entry_index.get(&entry_id).map(|&i| &self.journal_entries[i])
+    }
+
+    pub fn get_asset(&self, asset_id: AssetId) -> Option<&IntelligenceAsset> {
+        self.assets.get(&asset_id)
+    }
+
+    /// Change an asset's reporting dimensions, the one attribute no event records
+    pub fn update_asset_metadata(
+        &mut self,
+        asset_id: AssetId,
+        change: impl FnOnce(&mut IntelligenceAsset)
+    ) -> IclResult<IntelligenceAsset> {
+        let current = self.assets.get(&asset_id).ok_or(IclError::AssetNotFound(asset_id))?;
+        let mut updated = current.clone();
+        change(&mut updated);
+        let mut unrecorded = updated.clone();
+        unrecorded.dimensions = current.dimensions.clone();
+        if serde_json::to_value(&unrecorded)? != serde_json::to_value(current)? {
+            return Err(IclError::InvalidAsset(
+                "Only an asset's dimensions can be changed outside the lifecycle operations".into()
+            ));
+        }
+        self.update_asset(asset_id, |asset| asset.dimensions = updated.dimensions)
+    }
+
+    pub(crate) fn update_asset(
+        &mut self,
+        asset_id: AssetId,
+        change: impl FnOnce(&mut IntelligenceAsset)
+    ) -> IclResult<IntelligenceAsset> {
+        let mut updated = self.assets.get(&asset_id)
+            .cloned()
+            .ok_or(IclError::AssetNotFound(asset_id))?;
+        change(&mut updated);
+
+        if updated.asset_id != asset_id {
+            return Err(IclError::InvalidAsset("Asset ID cannot be changed".into()));
+        }
+        if updated.org_id != self.assets[&asset_id].org_id {
+            return Err(IclError::InvalidAsset("Asset namespace cannot be changed".into()));
+        }
+        if updated.parent_asset_id != self.assets[&asset_id].parent_asset_id {
+            return Err(IclError::InvalidAsset("Parent asset cannot be changed".into()));
+        }
+        if updated.owner != self.assets[&asset_id].owner {
+            self.validate_owner(&updated.owner)?;
+        }
+        if updated.initial_value <= 0.0 {
+            return Err(IclError::InvalidAsset("Initial value must be positive".into()));
+        }
+        updated.validate_schedule().map_err(IclError::InvalidAsset)?;
+        if updated.current_value.is_some_and(|v| v < -VALUE_TOLERANCE) {
+            return Err(IclError::InvalidAsset("Current value cannot be negative".into()));
+        }
+
+        self.log_asset_write(asset_id);
+        self.assets.insert(asset_id, updated.clone());
+        Ok(updated)
+    }
+
+    /// Set the threshold for one metric, replacing any existing threshold for it
+    pub fn set_impairment_threshold(&mut self, threshold: ImpairmentThreshold) -> IclResult<()> {
+        if threshold.metric.trim().is_empty() {
+            return Err(IclError::InvalidAsset("Impairment threshold metric cannot be empty".into()));
+        }
+        if !threshold.limit.is_finite() {
+            return Err(IclError::InvalidAsset("Impairment threshold limit must be finite".into()));
+        }
+        if threshold.draft_fraction.is_some_and(|f| !(f > 0.0 && f <= 1.0)) {
+            return Err(IclError::InvalidAsset("Draft impairment fraction must be in (0, 1]".into()));
+        }
+        self.log_settings_write();
+        self.impairment_thresholds.insert(threshold.metric.clone(), threshold);
+        Ok(())
+    }
+
+    pub fn remove_impairment_threshold(&mut self, metric: &str) -> Option<ImpairmentThreshold> {
+        self.log_settings_write();
+        self.impairment_thresholds.remove(metric)
+    }
+
+    pub fn impairment_thresholds(&self) -> impl Iterator<Item = &ImpairmentThreshold> {
+        self.impairment_thresholds.values()
+    }
+
+    /// Take in a quality or drift measurement from a monitoring system
+    pub fn record_impairment_indicator(&mut self, mut indicator: ImpairmentIndicator) -> IclResult<Option<ImpairmentReview>> {
+        let asset = self.assets.get(&indicator.asset_id).ok_or(IclError::AssetNotFound(indicator.asset_id))?;
+        if asset.status == AssetStatus::Retired {
+            return Err(IclError::AssetRetired(indicator.asset_id));
+        }
+        if !indicator.value.is_finite() {
+            return Err(IclError::InvalidEvent(format!("Indicator {} must be finite", indicator.metric)));
+        }
+        let book_value = asset.current_value.unwrap_or(asset.initial_value);
+        let now = self.now();
+        indicator.observed_at.get_or_insert(now);
+        self.log_settings_write();
+        self.impairment_indicators.entry(indicator.asset_id).or_default()
+            .insert(indicator.metric.clone(), indicator.clone());
+
+        let Some(threshold) = self.impairment_thresholds.get(&indicator.metric) else {
+            return Ok(None);
+        };
+        if !threshold.is_breached_by(indicator.value) {
+            return Ok(None);
+        }
+        if let Some(review) = self.impairment_reviews.values_mut()
+            .find(|review| review.asset_id == indicator.asset_id && review.is_open()) {
+            review.indicators.push(indicator);
+            return Ok(Some(review.clone()));
+        }
+
+        let draft = threshold.draft_fraction
+            .filter(|_| book_value > VALUE_TOLERANCE)
+            .map(|fraction| DraftImpairment {
+                amount: book_value * fraction,
+                book_value_before: book_value,
+                book_value_after: book_value * (1.0 - fraction),
+            });
+        let review = ImpairmentReview {
+            review_id: self.next_id(),
+            asset_id: indicator.asset_id,
+            flagged_at: now,
+            indicators: vec![indicator],
+            draft,
+            status: ReviewStatus::Open,
+            resolved_at: None,
+            resolution_note: None,
+        };
+        self.impairment_reviews.insert(review.review_id, review.clone());
+        Ok(Some(review))
+    }
+
+    /// Latest measurement of each metric posted for an asset
+    pub fn latest_impairment_indicators(&self, asset_id: AssetId) -> impl Iterator<Item = &ImpairmentIndicator> {
+        self.impairment_indicators.get(&asset_id).into_iter().flat_map(|by_metric| by_metric.values())
+    }
+
+    pub fn impairment_review(&self, review_id: Uuid) -> Option<&ImpairmentReview> {
+        self.impairment_reviews.get(&review_id)
+    }
+
+    /// Reviews awaiting a decision, i.e. the assets currently flagged for impairment review
+    pub fn open_impairment_reviews(&self) -> impl Iterator<Item = &ImpairmentReview> {
+        self.impairment_reviews.values().filter(|review| review.is_open())
+    }
+
+    /// Close a review without impairing the asset
+    pub fn dismiss_impairment_review(&mut self, review_id: Uuid, note: impl Into<String>) -> IclResult<ImpairmentReview> {
+        self.resolve_impairment_review(review_id, ReviewStatus::Dismissed, Some(note.into()))
+    }
+
+    pub(crate) fn resolve_impairment_review(
+        &mut self,
+        review_id: Uuid,
+        status: ReviewStatus,
+        note: Option<String>
+    ) -> IclResult<ImpairmentReview> {
+        let now = self.now();
+        self.log_settings_write();
+        let review = self.impairment_reviews.get_mut(&review_id)
+            .ok_or_else(|| IclError::InvalidEvent(format!("Unknown impairment review {}", review_id)))?;
+        if !review.is_open() {
+            return Err(IclError::InvalidEvent(format!("Impairment review {} is {}", review_id, review.status)));
+        }
+        review.status = status;
+        review.resolved_at = Some(now);
+        review.resolution_note = note;
+        Ok(review.clone())
+    }
+
+    pub fn license(&self, license_id: Uuid) -> Option<&License> {
+        self.licenses.get(&license_id)
+    }
+
+    pub fn licenses_for_asset(&self, asset_id: AssetId) -> Vec<&License> {
+        let mut licenses: Vec<&License> = self.licenses.values().filter(|l| l.asset_id == asset_id).collect();
+        licenses.sort_by_key(|l| l.granted_at);
+        licenses
+    }
+
+    pub(crate) fn ensure_licensable(&self, asset_id: AssetId, terms: &LicenseTerms) -> IclResult<()> {
+        let conflict = self.licenses.values()
+            .filter(|l| l.asset_id == asset_id && (terms.exclusive || l.terms.exclusive))
+            .find(|l| l.terms.overlaps(terms));
+        match conflict {
+            Some(existing) => Err(IclError::InvalidEvent(format!(
+                "License overlaps {}license {} to {}",
+                if existing.terms.exclusive { "exclusive " } else { "" }, existing.license_id, existing.licensee
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn insert_license(&mut self, license: License) {
+        self.log_settings_write();
+        self.licenses.insert(license.license_id, license);
+    }
+
+    /// Attach a recurring royalty or cost-sharing obligation to an asset
+    pub fn attach_obligation(&mut self, mut obligation: RecurringObligation) -> IclResult<RecurringObligation> {
+        let asset = self.assets.get(&obligation.asset_id).ok_or(IclError::AssetNotFound(obligation.asset_id))?;
+        if asset.status == AssetStatus::Retired {
+            return Err(IclError::AssetRetired(obligation.asset_id));
+        }
+        if obligation.counterparty.trim().is_empty() {
+            return Err(IclError::InvalidEntry("Obligation counterparty is required".into()));
+        }
+        let valid_basis = match obligation.basis {
+            ObligationBasis::Fixed(amount) => amount > 0.0 && amount.is_finite(),
+            ObligationBasis::RevenueShare(rate) => rate > 0.0 && rate <= 1.0,
+        };
+        if !valid_basis {
+            return Err(IclError::InvalidEntry(format!("Invalid obligation basis {}", obligation.basis)));
+        }
+        if let Some(ends_at) = obligation.ends_at.filter(|end| *end <= obligation.starts_at) {
+            return Err(IclError::InvalidDateRange {
+                start: obligation.starts_at.to_rfc3339(),
+                end: ends_at.to_rfc3339(),
+            });
+        }
+        obligation.obligation_id = self.next_id();
+        obligation.accrued = 0.0;
+        obligation.accrued_through = None;
+        self.log_settings_write();
+        self.obligations.insert(obligation.obligation_id, obligation.clone());
+        Ok(obligation)
+    }
+
+    pub fn obligation(&self, obligation_id: Uuid) -> Option<&RecurringObligation> {
+        self.obligations.get(&obligation_id)
+    }
+
+    pub fn obligations_for_asset(&self, asset_id: AssetId) -> Vec<&RecurringObligation> {
+        let mut obligations: Vec<&RecurringObligation> = self.obligations.values().filter(|o| o.asset_id == asset_id).collect();
+        obligations.sort_by_key(|o| (o.starts_at, o.obligation_id));
+        obligations
+    }
+
+    pub fn obligations_iter(&self) -> impl Iterator<Item = &RecurringObligation> {
+        self.obligations.values()
+    }
+
+    pub(crate) fn mark_obligation_accrued(&mut self, obligation_id: Uuid, through: DateTime<Utc>, amount: f64) {
+        self.log_settings_write();
+        if let Some(obligation) = self.obligations.get_mut(&obligation_id) {
+            obligation.accrued += amount;
+            obligation.accrued_through = Some(through);
+        }
+    }
+
+    pub(crate) fn set_parent_asset(&mut self, asset_id: AssetId, parent_asset_id: AssetId) -> IclResult<IntelligenceAsset> {
+        self.log_asset_write(asset_id);
+        let asset = self.assets.get_mut(&asset_id).ok_or(IclError::AssetNotFound(asset_id))?;
+        asset.parent_asset_id = Some(parent_asset_id);
+        Ok(asset.clone())
+    }
+
+    /// Open a construction-in-progress account to accumulate the costs of an asset before it is
+    /// placed in service
+    pub fn open_work_in_progress(
+        &mut self,
+        name: impl Into<String>,
+        owner: impl Into<OwnerId>
+    ) -> IclResult<CapitalWorkInProgress> {
+        self.open_work_in_progress_in_org(OrgId::default(), name, owner)
+    }
+
+    /// Open a construction-in-progress account in a tenant namespace; the asset it becomes is
+    /// created in the same namespace
+    pub fn open_work_in_progress_in_org(
+        &mut self,
+        org_id: OrgId,
+        name: impl Into<String>,
+        owner: impl Into<OwnerId>
+    ) -> IclResult<CapitalWorkInProgress> {
+        let owner = owner.into();
+        self.validate_owner(&owner)?;
+        if org_id.as_str().is_empty() {
+            return Err(IclError::InvalidAsset("Namespace cannot be empty".into()));
+        }
+        let wip = CapitalWorkInProgress {
+            wip_id: self.next_id(),
+            name: name.into(),
+            owner,
+            org_id,
+            opened_at: self.now(),
+            status: WipStatus::Open,
+            costs: Vec::new(),
+            placed_in_service_at: None,
+        };
+        self.log_settings_write();
+        self.work_in_progress.insert(wip.wip_id, wip.clone());
+        Ok(wip)
+    }
+
+    /// Accrue a cost to an open construction-in-progress account
+    pub fn accrue_cost(&mut self, wip_id: Uuid, mut cost: WipCost) -> IclResult<WipCost> {
+        if !(cost.amount > 0.0 && cost.amount.is_finite()) {
+            return Err(IclError::InvalidEntry("Accrued cost must be positive".into()));
+        }
+        let now = self.now();
+        let incurred_at = *cost.incurred_at.get_or_insert(now);
+        self.ensure_open_period(incurred_at)
+            .map_err(|reason| IclError::InvalidEntry(format!("Cannot accrue cost: {}", reason)))?;
+        cost.cost_id = self.next_id();
+
+        self.log_settings_write();
+        let wip = self.work_in_progress.get_mut(&wip_id)
+            .ok_or_else(|| IclError::InvalidAsset(format!("Unknown work in progress {}", wip_id)))?;
+        if !wip.is_open() {
+            return Err(IclError::InvalidAsset(format!("Work in progress {} is {}", wip_id, wip.status)));
+        }
+        wip.costs.push(cost.clone());
+        Ok(cost)
+    }
+
+    pub fn work_in_progress(&self, wip_id: Uuid) -> Option<&CapitalWorkInProgress> {
+        self.work_in_progress.get(&wip_id)
+    }
+
+    pub fn work_in_progress_iter(&self) -> impl Iterator<Item = &CapitalWorkInProgress> {
+        self.work_in_progress.values()
+    }
+
+    pub fn depreciation_pool(&self, pool_id: Uuid) -> Option<&DepreciationPool> {
+        self.depreciation_pools.get(&pool_id)
+    }
+
+    pub fn depreciation_pools(&self) -> impl Iterator<Item = &DepreciationPool> {
+        self.depreciation_pools.values()
+    }
+
+    pub(crate) fn insert_depreciation_pool(&mut self, pool: DepreciationPool) {
+        self.log_settings_write();
+        self.depreciation_pools.insert(pool.pool_id, pool);
+    }
+
+    pub(crate) fn update_depreciation_pool(&mut self, pool_id: Uuid, update: impl FnOnce(&mut DepreciationPool)) {
+        self.log_settings_write();
+        if let Some(pool) = self.depreciation_pools.get_mut(&pool_id) {
+            update(pool);
+        }
+    }
+
+    pub(crate) fn close_work_in_progress(&mut self, wip_id: Uuid, asset_id: AssetId) {
+        let now = self.now();
+        self.log_settings_write();
+        if let Some(wip) = self.work_in_progress.get_mut(&wip_id) {
+            wip.status = WipStatus::PlacedInService(asset_id);
+            wip.placed_in_service_at = Some(now);
+        }
+    }
+
+    /// Link an asset to the model artifact it was capitalized for, recording an `artifact_linked`
+    /// event
+    pub fn link_artifact(&mut self, asset_id: AssetId, artifact: ModelArtifact) -> IclResult<CapitalEvent> {
+        if artifact.model_name.trim().is_empty() || artifact.weights_hash.trim().is_empty() {
+            return Err(IclError::InvalidAsset("Model artifact requires a model name and weights hash".into()));
+        }
+        let asset = self.assets.get(&asset_id).ok_or(IclError::AssetNotFound(asset_id))?;
+        if let Some(linked) = self.find_asset_by_artifact_hash(&artifact.weights_hash).filter(|a| a.asset_id != asset_id) {
+            return Err(IclError::InvalidAsset(format!(
+                "Weights hash {} is already linked to asset {}", artifact.weights_hash, linked.asset_id
+            )));
+        }
+
+        let mut details = HashMap::new();
+        details.insert("model_name".to_string(), serde_json::json!(artifact.model_name));
+        details.insert("version".to_string(), serde_json::json!(artifact.version));
+        details.insert("weights_hash".to_string(), serde_json::json!(artifact.weights_hash));
+        if let Some(registry_uri) = &artifact.registry_uri {
+            details.insert("registry_uri".to_string(), serde_json::json!(registry_uri));
+        }
+        if let Some(previous) = &asset.artifact {
+            details.insert("previous_weights_hash".to_string(), serde_json::json!(previous.weights_hash));
+        }
+        let event = CapitalEvent::new(asset_id, ARTIFACT_LINKED_EVENT, self.now(), details).with_id(self.next_id());
+        let event = self.record_event(event)?;
+        self.log_asset_write(asset_id);
+        if let Some(asset) = self.assets.get_mut(&asset_id) {
+            asset.artifact = Some(artifact);
+        }
+        Ok(event)
+    }
+
+    /// Asset linked to the artifact with this weights hash, compared case-insensitively
+    pub fn find_asset_by_artifact_hash(&self, weights_hash: &str) -> Option<&IntelligenceAsset> {
+        let weights_hash = weights_hash.trim();
+        self.assets.values().find(|asset| {
+            asset.artifact.as_ref().is_some_and(|a| a.weights_hash.eq_ignore_ascii_case(weights_hash))
+        })
+    }
+
+    /// Add an owner to the registry
+    pub fn register_owner(&mut self, owner: Owner) -> IclResult<Owner> {
+        self.log_settings_write();
+        self.owners.register(owner.clone())?;
+        Ok(owner)
+    }
+
+    pub fn owner(&self, owner_id: &OwnerId) -> Option<&Owner> {
+        self.owners.get(owner_id)
+    }
+
+    pub fn owners(&self) -> &OwnerRegistry {
+        &self.owners
+    }
+
+    pub fn validate_owner(&self, owner: &OwnerId) -> IclResult<()> {
+        if owner.is_empty() {
+            return Err(IclError::InvalidAsset("Owner cannot be empty".into()));
+        }
+        if !self.owners.is_empty() && !self.owners.contains(owner) {
+            return Err(IclError::OwnerNotFound(owner.clone()));
+        }
+        Ok(())
+    }
+
+    /// Move free-text owners onto the registry
+    pub fn migrate_owners(
+        &mut self,
+        resolve: impl Fn(&str) -> OwnerId,
+        kind: OwnerKind
+    ) -> IclResult<OwnerMigration> {
+        let mut assets: Vec<&IntelligenceAsset> = self.assets.values().collect();
+        assets.sort_by_key(|a| (a.created_at, a.asset_id));
+        let mut raw_owners: Vec<String> = assets.iter().map(|a| a.owner.to_string()).collect();
+        for event in &self.events {
+            for key in ["owner", "from_owner", "to_owner"] {
+                if let Some(owner) = event.details.get(key).and_then(|v| v.as_str()) {
+                    raw_owners.push(owner.to_string());
+                }
+            }
+        }
+
+        let mut migration = OwnerMigration::default();
+        let mut registry = self.owners.clone();
+        for raw in raw_owners {
+            if migration.mapping.contains_key(&raw) {
+                continue;
+            }
+            let owner_id = match registry.aliases().get(&raw) {
+                Some(owner_id) => owner_id.clone(),
+                None if registry.contains(&OwnerId::new(raw.as_str())) => OwnerId::new(raw.as_str()),
+                None => resolve(&raw),
+            };
+            if owner_id.is_empty() {
+                return Err(IclError::InvalidAsset(format!("Owner '{}' does not resolve to an owner ID", raw)));
+            }
+            if !registry.contains(&owner_id) {
+                registry.register(Owner::new(owner_id.clone(), raw.clone(), kind))?;
+                migration.registered.push(owner_id.clone());
+            }
+            if raw != owner_id.as_str() {
+                registry.add_alias(raw.clone(), owner_id.clone())?;
+            }
+            migration.mapping.insert(raw, owner_id);
+        }
+        migration.registered.sort();
+        self.log_settings_write();
+        self.owners = registry;
+
+        let renames: Vec<(AssetId, OwnerId)> = self.assets.values()
+            .filter_map(|a| {
+                let owner_id = &migration.mapping[a.owner.as_str()];
+                (owner_id != &a.owner).then(|| (a.asset_id, owner_id.clone()))
+            })
+            .collect();
+        for (asset_id, owner_id) in renames {
+            self.update_asset(asset_id, |asset| asset.owner = owner_id)?;
+            migration.assets_updated += 1;
+        }
+        Ok(migration)
+    }
+
+    /// Replace an owner with a stable pseudonym on assets (owner and dimensions), event details,
+    /// ledger and journal entry metadata, and proof content, e.g. to honour a data-deletion request
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(duration_us = tracing::field::Empty), err
+    ))]
+    pub fn anonymize_owner(&mut self, owner_id: &OwnerId) -> IclResult<OwnerAnonymization> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        // Archived records cannot be rewritten and would keep naming the owner
+        if let Some(archive) = self.archives.first() {
+            return Err(IclError::IntegrityViolation(format!(
+                "Load archived period {} before anonymizing owner {}", archive.period, owner_id
+            )));
+        }
+        let mut originals: BTreeSet<String> = self.owners.aliases().iter()
+            .filter(|(_, target)| *target == owner_id)
+            .map(|(alias, _)| alias.clone())
+            .collect();
+        originals.insert(owner_id.to_string());
+        let is_original = |value: &serde_json::Value| value.as_str().is_some_and(|s| originals.contains(s));
+
+        let known = self.owners.contains(owner_id)
+            || self.assets.values().any(|a| originals.contains(a.owner.as_str()))
+            || self.events.iter().any(|e| e.details.values().any(is_original));
+        if owner_id.is_empty() || !known {
+            return Err(IclError::OwnerNotFound(owner_id.clone()));
+        }
+        // Nothing is rewritten unless the rewrite is recorded and every proof re-attested
+        self.atomically(|ledger| ledger.rewrite_owner(owner_id, &originals))
+    }
+
+    fn rewrite_owner(&mut self, owner_id: &OwnerId, originals: &BTreeSet<String>) -> IclResult<OwnerAnonymization> {
+        let is_original = |value: &serde_json::Value| value.as_str().is_some_and(|s| originals.contains(s));
+        // Legacy proofs hash their content as a whole, so they cannot be redacted field by field
+        if self.proofs.iter().any(|p| p.hash_version == PROOF_HASH_VERSION_LEGACY && p.content.values().any(is_original)) {
+            self.migrate_proof_hashes()?;
+        }
+
+        let salt = self.next_id().simple().to_string();
+        let commitment = OwnerPseudonym::commit(owner_id.as_str(), &salt);
+        let pseudonym = OwnerPseudonym {
+            pseudonym: OwnerId::new(format!("anon-{}", &commitment[..16])),
+            commitment,
+            anonymized_at: self.now(),
+        };
+        let replacement = serde_json::Value::String(pseudonym.pseudonym.to_string());
+        let mut anonymization = OwnerAnonymization {
+            pseudonym: pseudonym.clone(),
+            salt,
+            assets_updated: 0,
+            events_updated: 0,
+            proofs_redacted: 0,
+            proofs_reattested: 0,
+        };
+
+        let mentioned: Vec<AssetId> = self.assets.values()
+            .filter(|a| originals.contains(a.owner.as_str()) || a.dimensions.values().any(|v| originals.contains(v.as_str())))
+            .map(|a| a.asset_id)
+            .collect();
+        for asset_id in mentioned {
+            self.log_asset_write(asset_id);
+            let Some(asset) = self.assets.get_mut(&asset_id) else {
+                continue;
+            };
+            let mut updated = false;
+            if originals.contains(asset.owner.as_str()) {
+                asset.owner = pseudonym.pseudonym.clone();
+                updated = true;
+            }
+            for value in asset.dimensions.values_mut().filter(|v| originals.contains(v.as_str())) {
+                *value = pseudonym.pseudonym.to_string();
+                updated = true;
+            }
+            anonymization.assets_updated += usize::from(updated);
+        }
+
+        // Rewritten records keep their chain hashes; the rewrite records what they hash to now
+        let algorithm = self.record_hash_algorithm;
+        let mut rewritten_records = BTreeMap::new();
+        let mut rewritten_events = Vec::new();
+        for i in 0..self.events.len() {
+            if self.events[i].details.values().any(is_original) {
+                let event = self.event_mut(i);
+                replace_values(&mut event.details, originals, &replacement);
+                if event.record_hash.is_some() {
+                    rewritten_records.insert(event.event_id.into(), record_chain_hash(algorithm, &*event)?);
+                }
+                rewritten_events.push(event.event_id);
+            }
+        }
+        anonymization.events_updated = rewritten_events.len();
+        for i in 0..self.entries.len() {
+            if self.entries[i].metadata.values().any(is_original) {
+                let entry = self.entry_mut(i);
+                replace_values(&mut entry.metadata, originals, &replacement);
+                if entry.record_hash.is_some() {
+                    rewritten_records.insert(entry.entry_id.into(), record_chain_hash(algorithm, &*entry)?);
+                }
+            }
+        }
+        for i in 0..self.journal_entries.len() {
+            if self.journal_entries[i].metadata.values().any(is_original) {
+                let journal_entry = self.journal_entry_mut(i);
+                replace_values(&mut journal_entry.metadata, originals, &replacement);
+                if journal_entry.record_hash.is_some() {
+                    rewritten_records.insert(journal_entry.entry_id.into(), record_chain_hash(algorithm, &*journal_entry)?);
+                }
+            }
+        }
+        for i in 0..self.proofs.len() {
+            let keys: Vec<String> = self.proofs[i].content.iter()
+                .filter(|(_, value)| is_original(value))
+                .map(|(key, _)| key.clone())
+                .collect();
+            if keys.is_empty() {
+                continue;
+            }
+            let proof = self.proof_mut(i);
+            for key in &keys {
+                proof.redact_field(key, replacement.clone());
+            }
+            anonymization.proofs_redacted += 1;
+        }
+
+        self.log_settings_write();
+        if let Some(owner) = self.owners.remove(owner_id) {
+            let pseudonym_id = pseudonym.pseudonym.clone();
+            self.owners.register(Owner::new(pseudonym_id.clone(), pseudonym_id.to_string(), owner.kind))?;
+        }
+        self.owner_pseudonyms.insert(pseudonym.pseudonym.clone(), pseudonym.clone());
+
+        let rewrite = OwnerRewrite { pseudonym, rewritten_records };
+        let now = self.now();
+        let mut event = CapitalEvent::new(LEDGER_SUBJECT, OWNER_ANONYMIZED_EVENT, now, rewrite.to_details()?).with_id(self.next_id());
+        if let Some(closed_until) = self.closed_until.filter(|closed_until| *closed_until > now) {
+            event = event.with_effective_date(closed_until);
+        }
+        self.record_ledger_event(event).with_context(|| format!("anonymize owner {}: record rewrite", owner_id), None)?;
+        self.rebuild_indexes().with_context(|| format!("anonymize owner {}: rebuild indexes", owner_id), None)?;
+
+        // Proofs of rewritten events committed to their original contents; a new proof commits to each as it is now
+        let attested: Vec<(AssetId, EventId)> = rewritten_events.iter()
+            .filter(|event_id| self.proofs.iter().any(|p| p.event_id == Some(**event_id)))
+            .filter_map(|event_id| self.get_event(*event_id).map(|e| (e.asset_id, e.event_id)))
+            .collect();
+        for (asset_id, event_id) in attested {
+            self.generate_proof(asset_id, Some(event_id))
+                .with_context(|| format!("anonymize owner {}: re-attest event {}", owner_id, event_id), Some(asset_id))?;
+            anonymization.proofs_reattested += 1;
+        }
+        Ok(anonymization)
+    }
+
+    pub fn owner_pseudonyms(&self) -> &BTreeMap<OwnerId, OwnerPseudonym> {
+        &self.owner_pseudonyms
+    }
+
+    pub fn record_chain_heads(&self) -> &RecordChainHeads {
+        &self.record_chain_heads
+    }
+
+    fn derived_chain_heads(&self) -> RecordChainHeads {
+        let purges: Vec<_> = self.retention_purges().collect();
+        let head = |last: Option<&Option<String>>, links: usize, held: usize| ChainHead {
+            head: last.cloned().flatten(),
+            length: held + links,
+        };
+        RecordChainHeads {
+            events: head(
+                self.events.last().map(|e| &e.record_hash),
+                self.archives.iter().map(|a| a.event_links.len()).chain(purges.iter().map(|p| p.event_links.len())).sum(),
+                self.events.len(),
+            ),
+            entries: head(
+                self.entries.last().map(|e| &e.record_hash),
+                self.archives.iter().map(|a| a.entry_links.len()).chain(purges.iter().map(|p| p.entry_links.len())).sum(),
+                self.entries.len(),
+            ),
+            journal_entries: head(
+                self.journal_entries.last().map(|j| &j.record_hash),
+                self.archives.iter().map(|a| a.journal_links.len()).sum(),
+                self.journal_entries.len(),
+            ),
+        }
+    }
+
+    pub fn assets(&self) -> &HashMap<AssetId, IntelligenceAsset> {
+        &self.assets
+    }
+
+    pub fn events(&self) -> &[CapitalEvent] {
+        &self.events
+    }
+
+    pub fn entries(&self) -> &[LedgerEntry] {
+        &self.entries
+    }
+
+    pub fn journal_entries(&self) -> &[JournalEntry] {
+        &self.journal_entries
+    }
 
-        if initial_value <= 0.0 {
-            return Err(IclError::InvalidAsset("Initial value must be positive".into()));
-        }
+    pub fn proofs(&self) -> &[CapitalProof] {
+        &self.proofs
+    }
 
-        if useful_life_months <= 0 {
-            return Err(IclError::InvalidAsset("Useful life must be positive".into()));
-        }
+    pub fn anchor_receipts(&self) -> &[AnchorReceipt] {
+        &self.anchor_receipts
+    }
 
-        let asset = IntelligenceAsset {
-            asset_id,
-            owner,
-            initial_value,
-            depreciation_method,
-            useful_life_months,
-            created_at: Utc::now(),
-            status: AssetStatus::Active,
-            current_value: Some(initial_value),
-        };
-        
-        self.assets.insert(asset_id, asset.clone());
-        Ok(asset)
+    pub fn portfolio_proofs(&self) -> &[PortfolioProof] {
+        &self.portfolio_proofs
     }
 
-    pub fn record_event(&mut self, event: CapitalEvent) -> IclResult<()> {
-        if !self.assets.contains_key(&event.asset_id) {
-            return Err(IclError::AssetNotFound(event.asset_id));
-        }
+    pub fn assets_iter(&self) -> impl Iterator<Item = &IntelligenceAsset> {
+        self.assets.values()
+    }
 
-        if event.event_type.is_empty() {
-            return Err(IclError::InvalidEvent("Event type cannot be empty".into()));
-        }
+    pub fn assets_by_status(&self, status: AssetStatus) -> impl Iterator<Item = &IntelligenceAsset> {
+        self.assets_iter().filter(move |a| a.status == status)
+    }
 
-        self.events.push(event.clone());
+    pub fn assets_by_owner<'a>(&'a self, owner: &'a OwnerId) -> impl Iterator<Item = &'a IntelligenceAsset> {
+        self.assets_iter().filter(move |a| &a.owner == owner)
+    }
 
-        self._events_by_asset.entry(event.asset_id).or_insert_with(Vec::new).push(event.clone());
+    pub fn assets_in_org<'a>(&'a self, org_id: &'a OrgId) -> impl Iterator<Item = &'a IntelligenceAsset> {
+        self.assets_iter().filter(move |a| &a.org_id == org_id)
+    }
 
-        let entry = LedgerEntry {
-            entry_id: Uuid::new_v4(),
-            event_id: event.event_id,
-            asset_id: event.asset_id,
-            timestamp: event.timestamp,
-            amount: event.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0),
-            description: event.event_type.clone(),
-            metadata: event.details.clone(),
-        };
-        
-        self.entries.push(entry.clone());
-        self._entries_by_asset.entry(event.asset_id).or_insert_with(Vec::new).push(entry);
+    pub fn orgs(&self) -> BTreeSet<&OrgId> {
+        self.assets_iter().map(|a| &a.org_id).collect()
+    }
 
-        Ok(())
+    /// Assets created in the half-open range `[start, end)`
+    pub fn assets_created_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>
+    ) -> impl Iterator<Item = &IntelligenceAsset> {
+        self.assets_iter().filter(move |a| a.created_at >= start && a.created_at < end)
     }
 
-    pub fn record_journal_entry(&mut self, journal_entry: JournalEntry) -> IclResult<()> {
-        if journal_entry.amount <= 0.0 {
-            return Err(IclError::InvalidEntry("Journal entry amount must be positive".into()));
+    pub fn get_events_for_asset(&self, asset_id: AssetId) -> Vec<&CapitalEvent> {
+        self._events_by_asset.get(&asset_id)
+            .map_or_else(Vec::new, |indices| indices.iter().map(|&i| &self.events[i]).collect())
+    }
+
+    pub(crate) fn valuation_events(&self, asset_id: AssetId) -> Vec<&CapitalEvent> {
+        let mut events = self.get_events_for_asset(asset_id);
+        let pooled = self.asset_totals(asset_id).is_some_and(|t| t.amount_by_type.contains_key(POOL_ADDITION_EVENT));
+        if pooled {
+            events.retain(|e| e.event_type != POOL_DEPRECIATION_EVENT);
+            events.extend(self.pool_depreciation_charging(asset_id));
+            events.sort_by_key(|e| e.sequence);
         }
+        events
+    }
 
-        self.journal_entries.push(journal_entry.clone());
-        self._journal_entries_by_asset
-            .entry(journal_entry.event_id)
-            .or_insert_with(Vec::new)
-            .push(journal_entry);
-        Ok(())
+    /// Events of one type, e.g. `depreciation`, in recording order
+    pub fn events_of_type<'a>(&'a self, event_type: &str) -> impl Iterator<Item = &'a CapitalEvent> + 'a {
+        self._events_by_type.get(event_type).into_iter().flatten().map(|&i| &self.events[i])
     }
 
-    pub fn generate_proof(&mut self, asset_id: Uuid, event_id: Option<Uuid>) -> IclResult<CapitalProof> {
-        if !self.assets.contains_key(&asset_id) {
-            return Err(IclError::AssetNotFound(asset_id));
-        }
+    pub(crate) fn journal_entries_for_event(&self, event_id: EventId) -> Vec<&JournalEntry> {
+        self._journal_entries_by_event.get(&event_id)
+            .map_or_else(Vec::new, |indices| indices.iter().map(|&i| &self.journal_entries[i]).collect())
+    }
 
-        let asset_proofs: Vec<&CapitalProof> = self.proofs.iter()
-            .filter(|p| p.asset_id == asset_id)
-            .collect();
-        
-        let previous_hash = if !asset_proofs.is_empty() {
-            Some(asset_proofs.last().unwrap().proof_hash.clone().unwrap_or_default())
-        } else {
-            None
-        };
+    pub fn get_entries_for_asset(&self, asset_id: AssetId) -> Vec<&LedgerEntry> {
+        self._entries_by_asset.get(&asset_id)
+            .map_or_else(Vec::new, |indices| indices.iter().map(|&i| &self.entries[i]).collect())
+    }
+    
+    pub fn get_journal_entries_for_asset(&self, asset_id: AssetId) -> Vec<&JournalEntry> {
+        self._journal_entries_by_asset.get(&asset_id)
+            .map_or_else(Vec::new, |indices| indices.iter().map(|&i| &self.journal_entries[i]).collect())
+    }
 
-        let asset = self.assets.get(&asset_id).unwrap();
-        let mut content: HashMap<String, serde_json::Value> = HashMap::new();
-        content.insert("asset_id".to_string(), serde_json::Value::String(asset.asset_id.to_string()));
-        content.insert("owner".to_string(), serde_json::Value::String(asset.owner.clone()));
-        content.insert("initial_value".to_string(), serde_json::json!(asset.initial_value));
-        content.insert("depreciation_method".to_string(), serde_json::Value::String(asset.depreciation_method.to_string()));
-        content.insert("useful_life_months".to_string(), serde_json::Value::Number(serde_json::Number::from(asset.useful_life_months)));
-        content.insert("status".to_string(), serde_json::Value::String(asset.status.to_string()));
-        content.insert("current_value".to_string(), serde_json::json!(asset.current_value.unwrap_or_default()));
+    /// Asset of the event a journal entry was posted for; `None` for ledger-level events
+    pub fn journal_entry_asset(&self, entry: &JournalEntry) -> Option<&IntelligenceAsset> {
+        self.get_event(entry.event_id).and_then(|event| self.get_asset(event.asset_id))
+    }
 
-        let proof = CapitalProof {
-            proof_id: Uuid::new_v4(),
-            asset_id,
-            event_id,
-            timestamp: Utc::now(),
-            origin: "ICL".to_string(),
-            previous_proof_hash: previous_hash.clone(),
-            content,
-            proof_hash: None,
-        };
-        
-        let computed_hash = proof.compute_hash();
-        let mut updated_proof = proof;
-        updated_proof.proof_hash = Some(computed_hash);
-        
-        self.proofs.push(updated_proof.clone());
-        Ok(updated_proof)
+    /// Index of reclassifications for resolving the category each journal entry posted under;
+    /// build it once per export rather than once per entry
+    pub fn posting_assets(&self) -> PostingAssets<'_> {
+        let mut reclassifications: HashMap<AssetId, Vec<(u64, Option<String>)>> = HashMap::new();
+        for event in self.events_of_type(RECLASSIFICATION_EVENT) {
+            let from_category = event.details.get("from_category").and_then(|v| v.as_str()).map(str::to_string);
+            reclassifications.entry(event.asset_id).or_default().push((event.sequence, from_category));
+        }
+        for asset_reclassifications in reclassifications.values_mut() {
+            asset_reclassifications.sort_by_key(|(sequence, _)| *sequence);
+        }
+        PostingAssets { ledger: self, reclassifications }
     }
 
-    pub fn get_asset(&self, asset_id: Uuid) -> Option<&IntelligenceAsset> {
-        self.assets.get(&asset_id)
+    /// Whether a journal entry moves a balance between the categories of one account: a
+    /// reclassification event's entry naming different categories for its two sides
+    pub fn is_reclassification_entry(&self, entry: &JournalEntry) -> bool {
+        if self.get_event(entry.event_id).is_none_or(|e| e.event_type != RECLASSIFICATION_EVENT) {
+            return false;
+        }
+        match (entry.metadata.get(DEBIT_CATEGORY_METADATA), entry.metadata.get(CREDIT_CATEGORY_METADATA)) {
+            (Some(debit), Some(credit)) => debit != credit,
+            _ => false,
+        }
     }
 
-    pub fn get_asset_mut(&mut self, asset_id: Uuid) -> Option<&mut IntelligenceAsset> {
-        self.assets.get_mut(&asset_id)
+    /// Service an asset delivered in utilization events effective in `[start, end)`, leaving
+    /// out quarantined and voided events
+    pub fn usage_between(&self, asset_id: AssetId, start: DateTime<Utc>, end: DateTime<Utc>) -> Usage {
+        let mut usage = Usage::default();
+        for event in self.get_events_for_asset(asset_id) {
+            let effective = event.effective_date();
+            if event.event_type == "utilization"
+                && effective >= start
+                && effective < end
+                && !self.is_quarantined(event.event_id)
+                && !self.is_voided(event.event_id)
+            {
+                usage.add(&Usage::from_details(&event.details));
+            }
+        }
+        usage
     }
 
-    pub fn get_events_for_asset(&self, asset_id: Uuid) -> Vec<&CapitalEvent> {
-        self._events_by_asset.get(&asset_id).map_or_else(Vec::new, |v| v.iter().collect())
+    /// Revenue recorded against an asset and effective in `[start, end)`
+    pub fn revenue_between(&self, asset_id: AssetId, start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+        self.get_events_for_asset(asset_id).into_iter()
+            .filter(|e| e.event_type == REVENUE_EVENT && !self.is_quarantined(e.event_id) && !self.is_voided(e.event_id))
+            .filter(|e| e.effective_date() >= start && e.effective_date() < end)
+            .filter_map(|e| e.details.get("amount").and_then(|v| v.as_f64()))
+            .sum()
     }
 
-    pub fn get_entries_for_asset(&self, asset_id: Uuid) -> Vec<&LedgerEntry> {
-        self._entries_by_asset.get(&asset_id).map_or_else(Vec::new, |v| v.iter().collect())
+    /// Component improvements capitalized against an asset, such as fine-tunes of a base model,
+    /// in the order they were capitalized
+    pub fn components_of(&self, parent_asset_id: AssetId) -> Vec<&IntelligenceAsset> {
+        self._components_by_parent.get(&parent_asset_id)
+            .map_or_else(Vec::new, |ids| ids.iter().filter_map(|id| self.assets.get(id)).collect())
     }
-    
-    pub fn get_journal_entries_for_asset(&self, asset_id: Uuid) -> Vec<&JournalEntry> {
-        let asset_events = self.get_events_for_asset(asset_id);
-        let event_ids: std::collections::HashSet<Uuid> = asset_events.iter().map(|e| e.event_id).collect();
-        
-        self.journal_entries.iter()
-            .filter(|entry| event_ids.contains(&entry.event_id))
-            .collect()
+
+    /// Running totals of an asset's events and journal entries, in constant time
+    pub fn asset_totals(&self, asset_id: AssetId) -> Option<&AssetTotals> {
+        self._asset_totals.get(&asset_id)
+    }
+
+    pub(crate) fn proof_chains(&self) -> impl Iterator<Item = (AssetId, Vec<&CapitalProof>)> {
+        self._proofs_by_asset.iter()
+            .map(|(asset_id, indices)| (*asset_id, indices.iter().map(|&i| &self.proofs[i]).collect()))
     }
     
     pub fn verify_journal_balance(&self) -> bool {
         self.journal_entries.iter().all(|entry| entry.amount > 0.0)
     }
     
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(format, duration_us = tracing::field::Empty), err
+    ))]
     pub fn export_audit_trail(&self, format: &str) -> IclResult<String> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
         match format {
             "json" => {
                 let data = serde_json::json!({
-                    "version": "1.0.0",
-                    "exported_at": Utc::now().to_rfc3339(),
+                    "version": AUDIT_TRAIL_VERSION,
+                    "exported_at": self.now().to_rfc3339(),
                     "assets": self.assets.values().collect::<Vec<_>>(),
                     "events": &self.events,
-                    "entries": &self.entries,
-                    "journal_entries": &self.journal_entries,
-                    "proofs": &self.proofs,
+                    "entries": self.entries.iter().filter(|e| !self.is_quarantined(e.entry_id)).collect::<Vec<_>>(),
+                    "journal_entries": self.journal_entries.iter().filter(|j| !self.is_quarantined(j.entry_id)).collect::<Vec<_>>(),
+                    "proofs": self.proofs.iter().filter(|p| !self.is_quarantined(p.proof_id)).collect::<Vec<_>>(),
+                    "archives": &self.archives,
+                    "record_chain_heads": &self.record_chain_heads,
+                    "record_hash_algorithm": self.record_hash_algorithm,
                 });
                 serde_json::to_string_pretty(&data).map_err(IclError::from)
             },
             "csv" => {
-                let mut csv = String::from("entry_id,event_id,asset_id,timestamp,amount,description\n");
-                for entry in &self.entries {
+                let mut csv = String::from("entry_id,event_id,asset_id,timestamp,amount,description,actor\n");
+                for entry in self.entries.iter().filter(|e| !self.is_quarantined(e.entry_id)) {
                     csv.push_str(&format!(
-                        "{},{},{},{},{},{}\n",
+                        "{},{},{},{},{},{},{}\n",
                         entry.entry_id,
                         entry.event_id,
                         entry.asset_id,
                         entry.timestamp.to_rfc3339(),
                         entry.amount,
-                        entry.description.replace(',', ";")
+                        csv_field(&entry.description),
+                        csv_field(entry.actor.as_deref().unwrap_or_default())
                     ));
                 }
                 Ok(csv)
             },
+            // Binary, multi-file output cannot be returned as a string
+            "parquet" => Err(IclError::UnsupportedFormat(
+                "parquet (use export_audit_trail_parquet with the parquet feature)".into()
+            )),
             _ => Err(IclError::UnsupportedFormat(format.to_string())),
         }
     }
 
+    /// Stream the audit trail to `writer` as `jsonl` (one `{"record_type", "data"}` object per
+    /// line: assets, events, entries, journal entries, proofs, then archive stubs) or `csv` (ledger
+    /// entries, as in `export_audit_trail`), keeping only records the filter selects
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(format, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn export_audit_trail_to<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        format: &str,
+        filter: &AuditTrailFilter
+    ) -> IclResult<usize> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let entries = filter.query(Query::entries()).run(self);
+        let mut written = 0;
+        match format {
+            "jsonl" => {
+                let mut assets: Vec<&IntelligenceAsset> = self.assets_iter()
+                    .filter(|a| filter.selects_asset(a))
+                    .collect();
+                assets.sort_by_key(|a| (a.created_at, a.asset_id));
+                for asset in assets {
+                    written += write_jsonl_record(&mut writer, "asset", asset)?;
+                }
+                for event in filter.query(Query::events()).run(self) {
+                    written += write_jsonl_record(&mut writer, "event", event)?;
+                }
+                for entry in entries {
+                    written += write_jsonl_record(&mut writer, "entry", entry)?;
+                }
+                for journal_entry in filter.query(Query::journal_entries()).run(self) {
+                    written += write_jsonl_record(&mut writer, "journal_entry", journal_entry)?;
+                }
+                let proofs = self.proofs().iter()
+                    .filter(|p| !self.is_quarantined(p.proof_id))
+                    .filter(|p| filter.selects(Some(p.asset_id), p.timestamp));
+                for proof in proofs {
+                    written += write_jsonl_record(&mut writer, "proof", proof)?;
+                }
+                for archive in &self.archives {
+                    written += write_jsonl_record(&mut writer, "archive", archive)?;
+                }
+            },
+            "csv" => {
+                writer.write_all(b"entry_id,event_id,asset_id,timestamp,amount,description,actor\n")?;
+                for entry in entries {
+                    writeln!(
+                        writer,
+                        "{},{},{},{},{},{},{}",
+                        entry.entry_id,
+                        entry.event_id,
+                        entry.asset_id,
+                        entry.timestamp.to_rfc3339(),
+                        entry.amount,
+                        csv_field(&entry.description),
+                        csv_field(entry.actor.as_deref().unwrap_or_default())
+                    )?;
+                    written += 1;
+                }
+            },
+            _ => return Err(IclError::UnsupportedFormat(format.to_string())),
+        }
+        writer.flush()?;
+        Ok(written)
+    }
+
+    /// Rebuild a ledger from an `export_audit_trail` `json` export or an `export_audit_trail_to`
+    /// `jsonl` export
+    pub fn from_audit_trail(data: &str, format: &str) -> IclResult<Self> {
+        Self::from_audit_trail_with(data, format, &AuditTrailMigrations::default())
+    }
+
+    /// Rebuild a ledger from an audit trail export as `from_audit_trail` does, migrating `json`
+    /// exports with `migrations`
+    pub fn from_audit_trail_with(data: &str, format: &str, migrations: &AuditTrailMigrations) -> IclResult<Self> {
+        let mut ledger = Self::new();
+        let mut assets = Vec::new();
+        match format {
+            "json" => {
+                let trail: serde_json::Value = serde_json::from_str(data)?;
+                let from_version = audit_trail_version(&trail).to_string();
+                let trail: AuditTrail = serde_json::from_value(migrations.migrate(trail, &from_version)?)?;
+                assets = trail.assets;
+                ledger.events = trail.events;
+                ledger.entries = trail.entries;
+                ledger.journal_entries = trail.journal_entries;
+                ledger.proofs = trail.proofs;
+                ledger.archives = trail.archives;
+                ledger.record_chain_heads = trail.record_chain_heads.unwrap_or_default();
+            },
+            "jsonl" => {
+                for line in data.lines().filter(|line| !line.trim().is_empty()) {
+                    let line: JsonlLine = serde_json::from_str(line)?;
+                    match line.record_type.as_str() {
+                        "asset" => assets.push(serde_json::from_value(line.data)?),
+                        "event" => ledger.events.push(serde_json::from_value(line.data)?),
+                        "entry" => ledger.entries.push(serde_json::from_value(line.data)?),
+                        "journal_entry" => ledger.journal_entries.push(serde_json::from_value(line.data)?),
+                        "proof" => ledger.proofs.push(serde_json::from_value(line.data)?),
+                        "archive" => ledger.archives.push(serde_json::from_value(line.data)?),
+                        other => return Err(IclError::SerializationError(
+                            format!("Unknown audit trail record type: {}", other)
+                        )),
+                    }
+                }
+            },
+            _ => return Err(IclError::UnsupportedFormat(format.to_string())),
+        }
+        for asset in assets {
+            let asset_id = asset.asset_id;
+            if ledger.assets.insert(asset_id, asset).is_some() {
+                return Err(IclError::AssetAlreadyExists(asset_id));
+            }
+        }
+        ledger.rebuild_indexes().with_context(|| "load audit trail: rebuild indexes", None)?;
+        // `jsonl` trails and older `json` trails carry no chain heads
+        if ledger.record_chain_heads == RecordChainHeads::default() {
+            ledger.record_chain_heads = ledger.derived_chain_heads();
+        }
+        // Trails carry no data model version; legacy proofs date them to before versioning
+        if ledger.proofs.iter().any(|p| p.hash_version != PROOF_HASH_VERSION) {
+            ledger.data_model_version = UNVERSIONED_DATA_MODEL;
+        }
+        Ok(ledger)
+    }
+
+    pub fn export_journal(&self, format: &str) -> IclResult<String> {
+        JournalExporter::new(self).export_journal(format)
+    }
+
+    pub fn emission_outbox(&self) -> &Outbox {
+        &self.emissions
+    }
+
+    pub fn emission_outbox_mut(&mut self) -> &mut Outbox {
+        &mut self.emissions
+    }
+
+    pub fn set_emission_outbox(&mut self, outbox: Outbox) {
+        self.emissions = outbox.with_clock(self.clock.clone());
+    }
+
+    pub(crate) fn replace_emission_outbox(&mut self, outbox: Outbox) -> Outbox {
+        std::mem::replace(&mut self.emissions, outbox.with_clock(self.clock.clone()))
+    }
+
+    pub fn pending_emissions(&self) -> Vec<&OutboxMessage> {
+        self.emissions.pending().collect()
+    }
+
+    pub fn mark_delivered(&mut self, message_id: Uuid) -> IclResult<bool> {
+        self.emissions.mark_delivered(message_id)
+    }
+
+    /// Read-only view of the ledger as of `timestamp`, for regenerating period-end figures
+    /// exactly after later activity has been recorded
+    pub fn state_as_of(&self, timestamp: DateTime<Utc>) -> LedgerView<'_> {
+        LedgerView::new(self, timestamp)
+    }
+
+    /// Owner, status, and valuation of an asset at `as_of`, reconstructed from its events and the
+    /// closing positions of archived periods
+    pub fn asset_position(&self, asset_id: AssetId, as_of: DateTime<Utc>) -> Option<AssetPosition> {
+        let asset = self.assets.get(&asset_id).filter(|a| a.created_at <= as_of)?;
+        let mut position = AssetPosition {
+            asset_id,
+            owner: asset.owner.clone(),
+            status: AssetStatus::Active,
+            book_value: asset.initial_value,
+            accumulated_depreciation: 0.0,
+        };
+
+        let events = self.valuation_events(asset_id);
+        let detail = |event: &CapitalEvent, key: &str| event.details.get(key).and_then(|v| v.as_f64());
+        let transfer_owner = |event: &CapitalEvent, key: &str| {
+            event.details.get(key).and_then(|v| v.as_str()).map(|owner| self.owners.resolve(owner))
+        };
+        // Archived events are replayed from the closing position of the latest archive
+        let archived = self.archives.iter()
+            .filter(|a| a.period.has_ended_by(as_of))
+            .filter_map(|a| Some((a.period.end, a.assets.get(&asset_id)?.closing_position.clone()?)))
+            .max_by_key(|(end, _)| *end);
+        let replay_from = archived.as_ref().map(|(end, _)| *end);
+        // The current owner may be the result of a later transfer
+        let later = events.iter()
+            .filter(|e| e.event_type == "allocation" && e.effective_date() > as_of)
+            .min_by_key(|e| (e.effective_date(), e.sequence));
+        if let Some((_, closing_position)) = archived {
+            position = closing_position;
+        } else if let Some(later) = later {
+            position.owner = transfer_owner(later, "from_owner").unwrap_or(position.owner);
+        }
+        let replayed = events.iter().filter(|e| {
+            e.effective_date() <= as_of
+                && replay_from.is_none_or(|from| e.effective_date() >= from)
+                && !self.is_voided(e.event_id)
+        });
+        for event in replayed {
+            match event.event_type.as_str() {
+                "allocation" => position.owner = transfer_owner(event, "to_owner").unwrap_or(position.owner),
+                "depreciation" => {
+                    position.accumulated_depreciation += detail(event, "amount").unwrap_or(0.0);
+                    position.book_value = detail(event, "new_value").unwrap_or(position.book_value);
+                    if position.book_value <= detail(event, "salvage_value").unwrap_or(0.0) {
+                        position.status = AssetStatus::Depreciated;
+                    }
+                },
+                "retirement" | INTERCOMPANY_DISPOSAL_EVENT | POOL_DISPOSAL_EVENT => {
+                    position.status = AssetStatus::Retired;
+                    position.book_value = 0.0;
+                },
+                IMPROVEMENT_EVENT => position.book_value += detail(event, "amount").unwrap_or(0.0),
+                IMPAIRMENT_EVENT => position.book_value -= detail(event, "amount").unwrap_or(0.0),
+                POOL_DEPRECIATION_EVENT => {
+                    let share = pool_share(event, asset_id).unwrap_or(0.0);
+                    position.accumulated_depreciation += share;
+                    position.book_value -= share;
+                    if position.book_value <= VALUE_TOLERANCE {
+                        position.status = AssetStatus::Depreciated;
+                    }
+                },
+                EXPENSED_EVENT => {
+                    position.status = AssetStatus::Depreciated;
+                    position.book_value = 0.0;
+                },
+                _ => {},
+            }
+        }
+        Some(position)
+    }
+
+    /// Aggregate book value, depreciation, and asset counts over every asset that existed at
+    /// `as_of`, reconstructing each asset's state at that time from its events
+    pub fn portfolio_summary(&self, as_of: DateTime<Utc>) -> PortfolioSummary {
+        let mut summary = PortfolioSummary {
+            as_of,
+            asset_count: 0,
+            total_initial_value: 0.0,
+            total_book_value: 0.0,
+            accumulated_depreciation: 0.0,
+            assets_by_status: HashMap::new(),
+            assets_by_owner: BTreeMap::new(),
+            value_by_category: BTreeMap::new(),
+        };
+
+        for asset in self.assets_iter() {
+            let Some(position) = self.asset_position(asset.asset_id, as_of) else {
+                continue;
+            };
+            summary.asset_count += 1;
+            summary.total_initial_value += asset.initial_value;
+            summary.total_book_value += position.book_value;
+            summary.accumulated_depreciation += position.accumulated_depreciation;
+            *summary.assets_by_status.entry(position.status).or_default() += 1;
+            *summary.assets_by_owner.entry(position.owner).or_default() += 1;
+            *summary.value_by_category
+                .entry(asset.category.clone().unwrap_or_else(|| UNCATEGORIZED.to_string()))
+                .or_default() += position.book_value;
+        }
+        summary
+    }
+
     pub fn asset_count(&self) -> usize {
         self.assets.len()
     }
@@ -235,4 +3863,634 @@ impl IntelligenceCapitalLedger {
     pub fn event_count(&self) -> usize {
         self.events.len()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_proof_rejects_unknown_and_foreign_events() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let (asset_id, other_id) = (AssetId::random(), AssetId::random());
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.capitalize(other_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let event = lifecycle.utilize(asset_id, 50.0).unwrap();
+
+        let unknown = EventId::random();
+        assert!(matches!(ledger.generate_proof(asset_id, Some(unknown)), Err(IclError::EventNotFound(id)) if id == unknown));
+        assert!(matches!(ledger.generate_proof(other_id, Some(event.event_id)), Err(IclError::InvalidEvent(_))));
+        assert!(ledger.proofs().is_empty());
+
+        let proof = ledger.generate_proof(asset_id, Some(event.event_id)).unwrap();
+        assert!(proof.content.contains_key(EVENT_DIGEST_CONTENT));
+    }
+
+    #[test]
+    fn test_rejected_backdated_capitalization_leaves_no_asset() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let now = ledger.now();
+        let asset_id = AssetId::random();
+        ledger.close_period(now - Duration::days(10)).unwrap();
+        let closed = IntelligenceCapitalLifecycle::new(&mut ledger)
+            .effective_on(now - Duration::days(30))
+            .capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12);
+        assert!(closed.is_err());
+        assert_eq!((ledger.asset_count(), ledger.event_count()), (0, 0));
+
+        ledger.set_backdating_policy(BackdatingPolicy::allow_backdated_until(now - Duration::days(1)));
+        let unapproved = IntelligenceCapitalLifecycle::new(&mut ledger)
+            .effective_on(now - Duration::days(5))
+            .capitalize_or_expense(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12);
+        assert!(matches!(unapproved.unwrap_err().root(), IclError::Unauthorized(_)));
+        assert_eq!((ledger.asset_count(), ledger.event_count()), (0, 0));
+
+        let approval = ledger.approve_backdating("Controller", now - Duration::days(5)).unwrap();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .effective_on(now - Duration::days(5))
+            .with_backdating_approval(approval.token)
+            .capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        assert_eq!(ledger.get_asset(asset_id).unwrap().created_at, now - Duration::days(5));
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_rollback_restores_records_indexes_and_assets() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let (asset_id, other_id) = (AssetId::random(), AssetId::random());
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.utilize(asset_id, 50.0).unwrap();
+        ledger.generate_proof(asset_id, None).unwrap();
+        let before = serde_json::to_value(&ledger).unwrap();
+
+        let checkpoint = ledger.checkpoint();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(other_id, "Team B", 600.0, DepreciationMethod::Linear, 6).unwrap();
+        lifecycle.utilize(asset_id, 25.0).unwrap();
+        let now = ledger.now();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .depreciate(asset_id, now - Duration::days(30), now, 0.0, 1.0)
+            .unwrap();
+        ledger.generate_proof(asset_id, None).unwrap();
+        let nested = ledger.checkpoint();
+        // In-place rewrites inside a committed checkpoint are undone by the outer one
+        ledger.migrate_proof_hashes().unwrap();
+        ledger.commit(nested);
+        IntelligenceCapitalLifecycle::new(&mut ledger).utilize(other_id, 10.0).unwrap();
+        ledger.rollback(checkpoint);
+
+        assert_eq!(serde_json::to_value(&ledger).unwrap(), before);
+        assert!(ledger.get_asset(other_id).is_none());
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    struct RejectReversals(Arc<std::sync::atomic::AtomicBool>);
+
+    impl Rule for RejectReversals {
+        fn name(&self) -> &str {
+            "reject_reversals"
+        }
+
+        fn check_journal_entry(&self, _ledger: &IntelligenceCapitalLedger, entry: &JournalEntry) -> IclResult<()> {
+            if self.0.load(std::sync::atomic::Ordering::SeqCst) && entry.metadata.contains_key(REVERSED_ENTRY_METADATA) {
+                return Err(IclError::InvalidEntry("Reversals are frozen".into()));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_rejected_void_reversal_leaves_event_unvoided() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        let frozen = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        ledger.register_rule(RejectReversals(frozen.clone()));
+        ledger.set_validation_mode(ValidationMode::Strict);
+        let now = ledger.now();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let depreciation = lifecycle.depreciate(asset_id, now - Duration::days(30), now, 0.0, 1.0).unwrap();
+        let book_value = ledger.get_asset(asset_id).unwrap().current_value;
+        let event_count = ledger.event_count();
+        assert!(!ledger.journal_entries_for_event(depreciation.event_id).is_empty());
+
+        assert!(ledger.void_event(depreciation.event_id, "Posted twice", "Controller").is_err());
+        assert!(!ledger.is_voided(depreciation.event_id));
+        assert_eq!(ledger.event_count(), event_count);
+        assert_eq!(ledger.get_asset(asset_id).unwrap().current_value, book_value);
+        assert!(ledger.assert_invariants().is_ok());
+
+        frozen.store(false, std::sync::atomic::Ordering::SeqCst);
+        let void = ledger.void_event(depreciation.event_id, "Posted twice", "Controller").unwrap();
+        assert_eq!(ledger.void_of(depreciation.event_id).map(|v| v.event_id), Some(void.event_id));
+        assert_eq!(ledger.get_asset(asset_id).unwrap().current_value, Some(1200.0));
+        assert!(IntegrityChecker::new(&ledger).check_all_integrity().is_clean());
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_backdated_posting_orders_by_recording_and_reports_by_effective_date() {
+        let start = Utc::now();
+        let clock = Arc::new(crate::core::clock::ManualClock::new(start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        ledger.set_validation_mode(ValidationMode::Strict);
+        let asset_id = AssetId::random();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+
+        // Depreciation to the end of the last period, booked a month later
+        clock.advance(Duration::days(92));
+        let now = ledger.now();
+        let month_end = start + Duration::days(62);
+        let depreciation = IntelligenceCapitalLifecycle::new(&mut ledger)
+            .effective_on(month_end)
+            .depreciate(asset_id, start, month_end, 0.0, 1.0)
+            .unwrap();
+        assert_eq!((depreciation.effective_date(), depreciation.recorded_at()), (month_end, now));
+        let journal_entries = ledger.journal_entries_for_event(depreciation.event_id);
+        assert!(!journal_entries.is_empty());
+        assert!(journal_entries.iter().all(|j| j.effective_date() == month_end && j.recorded_at() == now));
+
+        let before = ledger.asset_position(asset_id, month_end - Duration::days(1)).unwrap();
+        let after = ledger.asset_position(asset_id, month_end).unwrap();
+        assert_eq!(before.book_value, 1200.0);
+        assert!(after.book_value < 1200.0);
+
+        // A write recorded before the latest one is out of order, whatever its effective date
+        clock.set(now - Duration::days(1));
+        let rewound = IntelligenceCapitalLifecycle::new(&mut ledger).utilize(asset_id, 5.0);
+        assert!(matches!(rewound.unwrap_err().root(), IclError::IntegrityViolation(_)));
+        clock.set(now);
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .effective_on(start + Duration::days(1))
+            .utilize(asset_id, 5.0)
+            .unwrap();
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_backdating_approval_is_single_use_and_dated() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let now = ledger.now();
+        let asset_id = AssetId::random();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .effective_on(now - Duration::days(40))
+            .capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        ledger.set_backdating_policy(BackdatingPolicy::allow_backdated_until(now - Duration::days(1)));
+        let approval = ledger.approve_backdating("Controller", now - Duration::days(10)).unwrap();
+
+        let too_early = IntelligenceCapitalLifecycle::new(&mut ledger)
+            .effective_on(now - Duration::days(20))
+            .with_backdating_approval(approval.token)
+            .utilize(asset_id, 5.0);
+        assert!(matches!(too_early.unwrap_err().root(), IclError::Unauthorized(_)));
+
+        let event = IntelligenceCapitalLifecycle::new(&mut ledger)
+            .effective_on(now - Duration::days(5))
+            .with_backdating_approval(approval.token)
+            .utilize(asset_id, 5.0)
+            .unwrap();
+        assert_eq!(event.details.get(APPROVED_BY_DETAIL), Some(&serde_json::json!("Controller")));
+        let spent = ledger.backdating_approvals().find(|a| a.token == approval.token).unwrap();
+        assert_eq!(spent.used_by, Some(event.event_id));
+
+        let event_count = ledger.event_count();
+        let reused = IntelligenceCapitalLifecycle::new(&mut ledger)
+            .effective_on(now - Duration::days(5))
+            .with_backdating_approval(approval.token)
+            .utilize(asset_id, 5.0);
+        assert!(matches!(reused.unwrap_err().root(), IclError::Unauthorized(_)));
+        assert_eq!(ledger.event_count(), event_count);
+    }
+
+    #[test]
+    fn test_indexes_and_totals_stay_consistent_through_void_and_archive() {
+        let start = Utc::now();
+        let clock = Arc::new(crate::core::clock::ManualClock::new(start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        let (asset_id, other_id) = (AssetId::random(), AssetId::random());
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.capitalize(other_id, "Team B", 600.0, DepreciationMethod::Linear, 6).unwrap();
+        let duplicate = lifecycle.utilize(asset_id, 50.0).unwrap();
+        lifecycle.utilize(asset_id, 20.0).unwrap();
+        ledger.void_event(duplicate.event_id, "Posted twice", "Controller").unwrap();
+        assert!(ledger.assert_invariants().is_ok());
+        let totals = ledger.asset_totals(asset_id).cloned().unwrap();
+        assert_eq!(totals.event_count, 4);
+        assert_eq!(totals.amount_by_type.get("utilization"), Some(&20.0));
+
+        clock.advance(Duration::days(70));
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .depreciate(other_id, start, start + Duration::days(62), 0.0, 1.0)
+            .unwrap();
+        let before_archive = [asset_id, other_id].map(|id| ledger.asset_totals(id).cloned().unwrap());
+        ledger.close_period(start + Duration::days(30)).unwrap();
+        let file = ledger.archive_period(FiscalPeriod::new(start - Duration::days(1), start + Duration::days(30))).unwrap();
+        assert!(ledger.get_event(duplicate.event_id).is_none());
+        assert!(ledger.assert_invariants().is_ok());
+        let archived = [asset_id, other_id].map(|id| ledger.asset_totals(id).cloned().unwrap());
+        for (before, after) in before_archive.iter().zip(&archived) {
+            assert_eq!(before.amount_by_type, after.amount_by_type);
+        }
+        // The archive event is recorded against the ledger, not either asset
+        for (before, after) in before_archive.iter().zip(&archived) {
+            assert_eq!(before.event_count, after.event_count);
+        }
+        assert_eq!(ledger.get_events_for_asset(LEDGER_SUBJECT).len(), 1);
+
+        ledger.load_archive(&file).unwrap();
+        assert!(ledger.is_voided(duplicate.event_id));
+        assert!(ledger.assert_invariants().is_ok());
+        for (asset_id, totals) in [asset_id, other_id].iter().zip(&archived) {
+            assert_eq!(ledger.asset_totals(*asset_id), Some(totals));
+            assert_eq!(ledger.get_events_for_asset(*asset_id).len(), totals.event_count);
+        }
+    }
+
+    fn archivable_ledger() -> (IntelligenceCapitalLedger, AssetId, FiscalPeriod) {
+        let start = Utc::now();
+        let clock = Arc::new(crate::core::clock::ManualClock::new(start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        let asset_id = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.utilize(asset_id, 50.0).unwrap();
+        clock.advance(Duration::days(70));
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .depreciate(asset_id, start, start + Duration::days(62), 0.0, 1.0)
+            .unwrap();
+        ledger.close_period(start + Duration::days(30)).unwrap();
+        (ledger, asset_id, FiscalPeriod::new(start - Duration::days(1), start + Duration::days(30)))
+    }
+
+    #[test]
+    fn test_archive_round_trip_keeps_record_chain_verified() {
+        let (mut ledger, asset_id, period) = archivable_ledger();
+        let events = ledger.events().to_vec();
+
+        let file = ledger.archive_period(period).unwrap();
+        assert_eq!(ledger.get_events_for_asset(asset_id).len(), 1);
+        let archive_events = ledger.get_events_for_asset(LEDGER_SUBJECT);
+        assert_eq!(archive_events.len(), 1);
+        assert_eq!(archive_events[0].event_type, ARCHIVE_EVENT);
+        assert!(IntegrityChecker::new(&ledger).verify_record_chain().is_empty());
+        assert!(!IntegrityChecker::new(&ledger).check_all_integrity().has_errors());
+
+        ledger.load_archive(&file).unwrap();
+        assert!(ledger.archives().is_empty());
+        let restored: Vec<_> = ledger.events().iter().filter(|e| e.asset_id != LEDGER_SUBJECT).cloned().collect();
+        assert_eq!(serde_json::to_value(&restored).unwrap(), serde_json::to_value(&events).unwrap());
+        assert!(IntegrityChecker::new(&ledger).verify_record_chain().is_empty());
+        assert!(!IntegrityChecker::new(&ledger).check_all_integrity().has_errors());
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    struct RejectArchives;
+
+    impl Rule for RejectArchives {
+        fn name(&self) -> &str {
+            "reject_archives"
+        }
+
+        fn check_event(&self, _ledger: &IntelligenceCapitalLedger, event: &CapitalEvent) -> IclResult<()> {
+            if event.event_type == ARCHIVE_EVENT {
+                return Err(IclError::InvalidEvent("Archiving is frozen".into()));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_rejected_archive_event_keeps_records() {
+        let (mut ledger, _, period) = archivable_ledger();
+        ledger.register_rule(RejectArchives);
+        ledger.set_validation_mode(ValidationMode::Strict);
+        let before = serde_json::to_value(&ledger).unwrap();
+
+        assert!(ledger.archive_period(period).is_err());
+        assert_eq!(serde_json::to_value(&ledger).unwrap(), before);
+        assert!(ledger.archives().is_empty());
+    }
+
+    #[test]
+    fn test_ledger_subject_is_reserved_for_ledger_events() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let event = CapitalEvent::new(LEDGER_SUBJECT, ARCHIVE_EVENT, ledger.now(), HashMap::new());
+        assert!(matches!(ledger.record_event(event), Err(IclError::AssetNotFound(_))));
+        assert_eq!(ledger.event_count(), 0);
+    }
+
+    #[test]
+    fn test_portfolio_proof_hash_keeps_its_envelope() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_ids = [AssetId::random(), AssetId::random()];
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        for asset_id in asset_ids {
+            lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        }
+        let now = ledger.now();
+        let mut proof = ledger.generate_portfolio_proof(&asset_ids, now - Duration::days(30), now).unwrap();
+        assert!(proof.verify());
+
+        // Proofs sealed before the envelope was typed must still verify
+        let envelope = serde_json::json!({
+            "hash_algorithm": proof.hash_algorithm.to_string(),
+            "proof_id": proof.proof_id.to_string(),
+            "timestamp": proof.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
+            "origin": &proof.origin,
+            "period_start": proof.period_start.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
+            "period_end": proof.period_end.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
+            "merkle_root": &proof.merkle_root,
+            "previous_proof_hash": &proof.previous_proof_hash,
+        });
+        assert_eq!(proof.compute_hash().unwrap(), proof.hash_algorithm.digest_hex(crate::canonical_json(&envelope).as_bytes()));
+
+        proof.origin = "Elsewhere".to_string();
+        assert!(!proof.verify());
+    }
+
+    #[test]
+    fn test_audit_trail_csv_quotes_free_text() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        let description = "review, \"final\"\nround";
+        let mut event = CapitalEvent::new(asset_id, description, ledger.now(), HashMap::new());
+        event.actor = Some("Doe, Jane".to_string());
+        ledger.record_event(event).unwrap();
+
+        let mut streamed = Vec::new();
+        ledger.export_audit_trail_to(&mut streamed, "csv", &AuditTrailFilter::new()).unwrap();
+        for csv in [ledger.export_audit_trail("csv").unwrap(), String::from_utf8(streamed).unwrap()] {
+            let rows: Vec<csv::StringRecord> = csv::Reader::from_reader(csv.as_bytes()).records().map(Result::unwrap).collect();
+            assert_eq!(rows.len(), 2);
+            assert!(rows.iter().all(|row| row.len() == 7));
+            assert_eq!(&rows[1][5], description);
+            assert_eq!(&rows[1][6], "Doe, Jane");
+        }
+    }
+
+    fn anonymizable_ledger() -> (IntelligenceCapitalLedger, AssetId, EventId) {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_id_generator(Arc::new(crate::core::ids::SeededIdGenerator::new(7)));
+        let asset_id = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.utilize(asset_id, 50.0).unwrap();
+        let capitalization = ledger.get_events_for_asset(asset_id)[0].event_id;
+        ledger.generate_proof(asset_id, Some(capitalization)).unwrap();
+        (ledger, asset_id, capitalization)
+    }
+
+    #[test]
+    fn test_anonymize_owner_keeps_record_hashes_and_records_the_rewrite() {
+        let (mut ledger, asset_id, capitalization) = anonymizable_ledger();
+        let sealed: Vec<_> = ledger.events().iter().map(|e| e.record_hash.clone()).collect();
+        let heads = ledger.record_chain_heads().clone();
+
+        let anonymization = ledger.anonymize_owner(&OwnerId::new("Team A")).unwrap();
+        let pseudonym = anonymization.pseudonym.pseudonym.to_string();
+        assert!(anonymization.pseudonym.verify("Team A", &anonymization.salt));
+        assert!(!anonymization.pseudonym.verify("Team B", &anonymization.salt));
+        assert_eq!(ledger.get_asset(asset_id).unwrap().owner.as_str(), pseudonym);
+        assert_eq!(ledger.get_event(capitalization).unwrap().details["owner"], serde_json::json!(pseudonym));
+        assert!(ledger.entries().iter().all(|e| e.metadata.get("owner").is_none_or(|v| *v != "Team A")));
+
+        let resealed: Vec<_> = ledger.events().iter().take(sealed.len()).map(|e| e.record_hash.clone()).collect();
+        assert_eq!(resealed, sealed);
+        assert_eq!(ledger.record_chain_heads().events.length, heads.events.length + 1);
+        let rewrites: Vec<_> = ledger.owner_rewrites().collect();
+        assert_eq!(rewrites.len(), 1);
+        assert!(rewrites[0].rewritten_records.contains_key(&capitalization.into()));
+        assert_eq!(ledger.get_events_for_asset(LEDGER_SUBJECT)[0].event_type, OWNER_ANONYMIZED_EVENT);
+        assert!(IntegrityChecker::new(&ledger).verify_record_chain().is_empty());
+        assert!(!IntegrityChecker::new(&ledger).check_all_integrity().has_errors());
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_anonymized_owner_proofs_verify_without_redacting_event_digests() {
+        let (mut ledger, asset_id, capitalization) = anonymizable_ledger();
+        let anonymization = ledger.anonymize_owner(&OwnerId::new("Team A")).unwrap();
+        assert_eq!(anonymization.proofs_redacted, 1);
+        assert_eq!(anonymization.proofs_reattested, 1);
+
+        let proofs = ledger.get_proofs_for_asset(asset_id);
+        assert!(proofs.iter().all(|p| !p.redacted.contains_key(EVENT_DIGEST_CONTENT)));
+        assert!(proofs.iter().all(|p| p.verify_hash().unwrap()));
+        let latest = proofs.last().unwrap();
+        assert_eq!(latest.event_id, Some(capitalization));
+        assert!(latest.content["owner"] == anonymization.pseudonym.pseudonym.as_str());
+
+        let bytes = crate::core::proofs::CapitalProofGenerator::new(&ledger).export_proof_bundle(asset_id).unwrap();
+        assert!(crate::core::proofs::verify_proof_bundle(&bytes).is_ok());
+    }
+
+    #[test]
+    fn test_anonymization_salt_comes_from_the_ledger_id_generator() {
+        let salts: Vec<String> = (0..2)
+            .map(|_| anonymizable_ledger().0.anonymize_owner(&OwnerId::new("Team A")).unwrap().salt)
+            .collect();
+        assert_eq!(salts[0], salts[1]);
+    }
+
+    fn retention_ledger() -> (IntelligenceCapitalLedger, [AssetId; 2], Vec<EventId>) {
+        let start = Utc::now();
+        let clock = Arc::new(crate::core::clock::ManualClock::new(start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        let asset_ids = [AssetId::random(), AssetId::random()];
+        let mut utilizations = Vec::new();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        for asset_id in asset_ids {
+            lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+            utilizations.push(lifecycle.utilize(asset_id, 50.0).unwrap().event_id);
+            utilizations.push(lifecycle.utilize(asset_id, 20.0).unwrap().event_id);
+        }
+        clock.advance(Duration::days(70));
+        ledger.set_retention_rule(RetentionRule::new("utilization", 1)).unwrap();
+        (ledger, asset_ids, utilizations)
+    }
+
+    #[test]
+    fn test_retention_purges_expired_events_and_keeps_chain_verified() {
+        let (mut ledger, asset_ids, utilizations) = retention_ledger();
+
+        let report = ledger.apply_retention().unwrap();
+        assert_eq!(report.purged_event_count, 4);
+        assert_eq!(report.purge_events.len(), 2);
+        assert!(utilizations.iter().all(|id| ledger.get_event(*id).is_none()));
+        for asset_id in asset_ids {
+            assert_eq!(ledger.asset_totals(asset_id).unwrap().amount_by_type.get("utilization"), Some(&70.0));
+        }
+        assert!(IntegrityChecker::new(&ledger).verify_record_chain().is_empty());
+        assert!(!IntegrityChecker::new(&ledger).check_all_integrity().has_errors());
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_retention_keeps_proven_events() {
+        let (mut ledger, asset_ids, utilizations) = retention_ledger();
+        ledger.generate_proof(asset_ids[0], Some(utilizations[0])).unwrap();
+
+        let report = ledger.apply_retention().unwrap();
+        assert_eq!(report.purged_event_count, 3);
+        assert!(ledger.get_event(utilizations[0]).is_some());
+        assert!(utilizations[1..].iter().all(|id| ledger.get_event(*id).is_none()));
+        let bytes = crate::core::proofs::CapitalProofGenerator::new(&ledger).export_proof_bundle(asset_ids[0]).unwrap();
+        assert!(crate::core::proofs::verify_proof_bundle(&bytes).is_ok());
+    }
+
+    struct RejectSecondPurge(std::sync::atomic::AtomicUsize);
+
+    impl Rule for RejectSecondPurge {
+        fn name(&self) -> &str {
+            "reject_second_purge"
+        }
+
+        fn check_event(&self, _ledger: &IntelligenceCapitalLedger, event: &CapitalEvent) -> IclResult<()> {
+            if event.event_type == RETENTION_PURGE_EVENT && self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 1 {
+                return Err(IclError::InvalidEvent("Purges are frozen".into()));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_failed_retention_purges_nothing() {
+        let (mut ledger, _, utilizations) = retention_ledger();
+        ledger.register_rule(RejectSecondPurge(std::sync::atomic::AtomicUsize::new(0)));
+        ledger.set_validation_mode(ValidationMode::Strict);
+        let before = serde_json::to_value(&ledger).unwrap();
+
+        assert!(ledger.apply_retention().is_err());
+        assert_eq!(serde_json::to_value(&ledger).unwrap(), before);
+        assert!(utilizations.iter().all(|id| ledger.get_event(*id).is_some()));
+        assert_eq!(ledger.retention_purges().count(), 0);
+    }
+
+    #[test]
+    fn test_export_refuses_proofs_of_purged_events() {
+        let start = Utc::now();
+        let clock = Arc::new(crate::core::clock::ManualClock::new(start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        let asset_id = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let event = lifecycle.utilize(asset_id, 50.0).unwrap();
+        ledger.generate_proof(asset_id, Some(event.event_id)).unwrap();
+        // Ledgers could purge proven events before retention learned to keep them
+        let proofs = std::mem::take(&mut ledger.proofs);
+        clock.advance(Duration::days(70));
+        ledger.set_retention_rule(RetentionRule::new("utilization", 1)).unwrap();
+        assert_eq!(ledger.apply_retention().unwrap().purged_event_count, 1);
+        ledger.proofs = proofs;
+        ledger.rebuild_indexes().unwrap();
+
+        let err = crate::core::proofs::CapitalProofGenerator::new(&ledger).export_proof_bundle(asset_id).unwrap_err();
+        assert!(err.to_string().contains("purged by retention"));
+    }
+
+    #[test]
+    fn test_export_refuses_legacy_proofs_without_event_digests() {
+        let (mut ledger, asset_id, _) = anonymizable_ledger();
+        let proof = &mut ledger.proofs[0];
+        proof.content.remove(EVENT_DIGEST_CONTENT);
+        proof.hash_version = PROOF_HASH_VERSION_LEGACY;
+        proof.proof_hash = Some(proof.compute_hash().unwrap());
+
+        let err = crate::core::proofs::CapitalProofGenerator::new(&ledger).export_proof_bundle(asset_id).unwrap_err();
+        assert!(matches!(err, IclError::UnsupportedFormat(_)));
+    }
+
+    #[test]
+    fn test_failed_anonymization_leaves_owner_in_place() {
+        let (mut ledger, asset_id, _) = anonymizable_ledger();
+        ledger.register_rule(RejectAnonymization);
+        ledger.set_validation_mode(ValidationMode::Strict);
+        let before = serde_json::to_value(&ledger).unwrap();
+
+        assert!(ledger.anonymize_owner(&OwnerId::new("Team A")).is_err());
+        assert_eq!(serde_json::to_value(&ledger).unwrap(), before);
+        assert_eq!(ledger.get_asset(asset_id).unwrap().owner.as_str(), "Team A");
+        assert!(ledger.owner_pseudonyms().is_empty());
+    }
+
+    struct RejectAnonymization;
+
+    impl Rule for RejectAnonymization {
+        fn name(&self) -> &str {
+            "reject_anonymization"
+        }
+
+        fn check_event(&self, _ledger: &IntelligenceCapitalLedger, event: &CapitalEvent) -> IclResult<()> {
+            if event.event_type == OWNER_ANONYMIZED_EVENT {
+                return Err(IclError::InvalidEvent("Anonymization is frozen".into()));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_rollback_undoes_archive_load_and_anonymization() {
+        let (mut ledger, asset_id, period) = archivable_ledger();
+        ledger.generate_proof(asset_id, None).unwrap();
+        let before = serde_json::to_value(&ledger).unwrap();
+
+        let checkpoint = ledger.checkpoint();
+        let file = ledger.archive_period(period).unwrap();
+        ledger.load_archive(&file).unwrap();
+        ledger.anonymize_owner(&OwnerId::new("Team A")).unwrap();
+        ledger.rollback(checkpoint);
+
+        assert_eq!(serde_json::to_value(&ledger).unwrap(), before);
+        assert_eq!(ledger.get_asset(asset_id).unwrap().owner.as_str(), "Team A");
+        assert!(ledger.owner_pseudonyms().is_empty());
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_void_restores_depreciation_latest_first() {
+        let start = Utc::now();
+        let clock = Arc::new(crate::core::clock::ManualClock::new(start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        let asset_id = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        clock.advance(Duration::days(130));
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        let first = lifecycle.depreciate(asset_id, start, start + Duration::days(62), 0.0, 1.0).unwrap();
+        let after_first = lifecycle.ledger.get_asset(asset_id).unwrap().current_value;
+        assert!(after_first < Some(1200.0));
+        let second = lifecycle.depreciate(asset_id, start + Duration::days(62), start + Duration::days(124), 0.0, 1.0).unwrap();
+        assert!(ledger.get_asset(asset_id).unwrap().current_value < after_first);
+
+        // The later depreciation changed the value since, so it has to go first
+        let out_of_order = ledger.void_event(first.event_id, "Wrong period", "Controller");
+        assert!(matches!(out_of_order, Err(IclError::IntegrityViolation(_))));
+        assert!(!ledger.is_voided(first.event_id));
+
+        ledger.void_event(second.event_id, "Wrong period", "Controller").unwrap();
+        assert_eq!(ledger.get_asset(asset_id).unwrap().current_value, after_first);
+        ledger.void_event(first.event_id, "Wrong period", "Controller").unwrap();
+        let asset = ledger.get_asset(asset_id).unwrap();
+        assert_eq!((asset.current_value, asset.status), (Some(1200.0), AssetStatus::Active));
+        for voided in [&first, &second] {
+            let reversals = ledger.journal_entries_for_event(ledger.void_of(voided.event_id).unwrap().event_id);
+            assert_eq!(reversals.len(), ledger.journal_entries_for_event(voided.event_id).len());
+        }
+        assert!(IntegrityChecker::new(&ledger).check_all_integrity().is_clean());
+    }
+}
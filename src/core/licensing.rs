@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::types::{AssetId, EventId};
+
+/// Event type of licenses granted over an asset
+pub const LICENSE_EVENT: &str = "license";
+
+/// Event type of revenue earned by an asset
+pub const REVENUE_EVENT: &str = "revenue";
+
+/// Terms under which an asset is licensed to a third party
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LicenseTerms {
+    pub starts_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub exclusive: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+}
+
+impl LicenseTerms {
+    pub fn new(starts_at: DateTime<Utc>) -> Self {
+        Self { starts_at, ends_at: None, exclusive: false, reference: None }
+    }
+
+    pub fn ending_at(mut self, ends_at: DateTime<Utc>) -> Self {
+        self.ends_at = Some(ends_at);
+        self
+    }
+
+    pub fn exclusive(mut self) -> Self {
+        self.exclusive = true;
+        self
+    }
+
+    pub fn with_reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    pub fn overlaps(&self, other: &LicenseTerms) -> bool {
+        self.ends_at.is_none_or(|end| other.starts_at < end) && other.ends_at.is_none_or(|end| self.starts_at < end)
+    }
+}
+
+/// A license of one asset to a licensee, as granted by `IntelligenceCapitalLifecycle::license`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct License {
+    pub license_id: Uuid,
+    pub asset_id: AssetId,
+    pub licensee: String,
+    pub terms: LicenseTerms,
+    pub granted_at: DateTime<Utc>,
+    pub event_id: EventId,
+}
+
+impl License {
+    pub fn is_active_at(&self, at: DateTime<Utc>) -> bool {
+        self.terms.starts_at <= at && self.terms.ends_at.is_none_or(|end| at < end)
+    }
+}
+
+/// Period `[start, end)` in which revenue was earned
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RevenuePeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl RevenuePeriod {
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self { start, end }
+    }
+}
@@ -1,157 +1,708 @@
+use std::collections::BTreeMap;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 
 use crate::core::types::*;
-use crate::core::ledger::IntelligenceCapitalLedger;
-use crate::core::depreciation::calculate_depreciation;
+use crate::core::ledger::{
+    IntelligenceCapitalLedger, LedgerCheckpoint, ValidationMode, CREDIT_CATEGORY_METADATA, DEBIT_CATEGORY_METADATA,
+    PARENT_ASSET_DETAIL, RECLASSIFICATION_EVENT, REVERSED_ENTRY_METADATA,
+};
+use crate::core::depreciation::{calculate_depreciation, calculate_units_of_production_depreciation, months_between};
+use crate::core::outbox::Outbox;
+use crate::core::authorization::{LifecycleOperation, Principal};
+use crate::core::integrity::{BACKDATING_APPROVAL_DETAIL, VALUE_TOLERANCE};
+use crate::core::reports::IMPAIRMENT_EVENT;
+use crate::core::impairment::ReviewStatus;
+use crate::core::licensing::{License, LicenseTerms, RevenuePeriod, LICENSE_EVENT, REVENUE_EVENT};
+use crate::core::obligations::{ObligationBalance, ObligationBasis, ObligationKind, OBLIGATION_EVENT};
+use crate::core::intercompany::{Elimination, IntercompanyTransfer, ELIMINATION_DETAIL, INTERCOMPANY_DISPOSAL_EVENT};
+use crate::core::accruals::{Accrual, AccrualSpec, ACCRUAL_EVENT, ACCRUAL_REVERSAL_EVENT, REVERSED_ACCRUAL_DETAIL};
+use crate::core::journal_templates::{JournalTemplate, LegAmount};
+use crate::core::capitalization::{CapitalizationOutcome, EXPENSED_EVENT};
+use crate::core::pools::{POOL_DETAIL, POOL_DISPOSAL_EVENT};
 use crate::core::error::*;
 
+/// How one asset would change under a previewed operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetChange {
+    pub before: Option<IntelligenceAsset>,
+    pub after: IntelligenceAsset,
+}
+
+/// Records and asset changes a lifecycle operation would produce, without having been committed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecyclePreview<T> {
+    pub result: T,
+    pub events: Vec<CapitalEvent>,
+    pub journal_entries: Vec<JournalEntry>,
+    pub asset_changes: Vec<AssetChange>,
+}
+
+/// Activity and valuation of one asset, as returned by `get_asset_summary`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetSummary {
+    pub asset: IntelligenceAsset,
+    pub event_count: usize,
+    pub journal_entry_count: usize,
+    pub total_depreciation: f64,
+    pub accumulated_by_type: BTreeMap<String, f64>,
+    pub book_value: f64,
+    pub last_event_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub total_revenue: f64,
+    #[serde(default)]
+    pub usage: Usage,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub obligations: Vec<ObligationBalance>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<AssetSummary>,
+    pub rolled_up_book_value: f64,
+    pub rolled_up_depreciation: f64,
+}
+
+/// Runs lifecycle operations and rolls them back, so their effects can be reviewed before
+/// committing them for real
+#[derive(Debug)]
+pub struct DryRun<'a> {
+    ledger: &'a mut IntelligenceCapitalLedger,
+}
+
+impl<'a> DryRun<'a> {
+    pub fn new(ledger: &'a mut IntelligenceCapitalLedger) -> Self {
+        Self { ledger }
+    }
+
+    pub fn capitalize(
+        &mut self,
+        asset_id: AssetId,
+        owner: impl Into<OwnerId>,
+        initial_value: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32
+    ) -> IclResult<LifecyclePreview<IntelligenceAsset>> {
+        self.run(|lifecycle| lifecycle.capitalize(asset_id, owner, initial_value, depreciation_method, useful_life_months))
+    }
+
+    pub fn capitalize_or_expense(
+        &mut self,
+        asset_id: AssetId,
+        owner: impl Into<OwnerId>,
+        cost: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32
+    ) -> IclResult<LifecyclePreview<CapitalizationOutcome>> {
+        self.run(|lifecycle| lifecycle.capitalize_or_expense(asset_id, owner, cost, depreciation_method, useful_life_months))
+    }
+
+    pub fn place_in_service(
+        &mut self,
+        wip_id: Uuid,
+        asset_id: AssetId,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32
+    ) -> IclResult<LifecyclePreview<IntelligenceAsset>> {
+        self.run(|lifecycle| lifecycle.place_in_service(wip_id, asset_id, depreciation_method, useful_life_months))
+    }
+
+    pub fn capitalize_improvement(
+        &mut self,
+        parent_asset_id: AssetId,
+        asset_id: AssetId,
+        cost: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32
+    ) -> IclResult<LifecyclePreview<IntelligenceAsset>> {
+        self.run(|lifecycle| lifecycle.capitalize_improvement(parent_asset_id, asset_id, cost, depreciation_method, useful_life_months))
+    }
+
+    pub fn allocate(&mut self, asset_id: AssetId, target_owner: impl Into<OwnerId>) -> IclResult<LifecyclePreview<CapitalEvent>> {
+        self.run(|lifecycle| lifecycle.allocate(asset_id, target_owner))
+    }
+
+    pub fn depreciate(
+        &mut self,
+        asset_id: AssetId,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+        salvage_value: f64,
+        rate_multiplier: f64
+    ) -> IclResult<LifecyclePreview<CapitalEvent>> {
+        self.run(|lifecycle| lifecycle.depreciate(asset_id, start_date, end_date, salvage_value, rate_multiplier))
+    }
+
+    pub fn impair(&mut self, asset_id: AssetId, amount: f64, reason: impl Into<String>) -> IclResult<LifecyclePreview<CapitalEvent>> {
+        self.run(|lifecycle| lifecycle.impair(asset_id, amount, reason))
+    }
+
+    pub fn approve_impairment_review(&mut self, review_id: Uuid, amount: Option<f64>) -> IclResult<LifecyclePreview<CapitalEvent>> {
+        self.run(|lifecycle| lifecycle.approve_impairment_review(review_id, amount))
+    }
+
+    pub fn license(
+        &mut self,
+        asset_id: AssetId,
+        licensee: impl Into<String>,
+        terms: LicenseTerms
+    ) -> IclResult<LifecyclePreview<License>> {
+        self.run(|lifecycle| lifecycle.license(asset_id, licensee, terms))
+    }
+
+    pub fn record_revenue(&mut self, asset_id: AssetId, amount: f64, period: RevenuePeriod) -> IclResult<LifecyclePreview<CapitalEvent>> {
+        self.run(|lifecycle| lifecycle.record_revenue(asset_id, amount, period))
+    }
+
+    pub fn accrue_obligations(&mut self, asset_id: AssetId, as_of: DateTime<Utc>) -> IclResult<LifecyclePreview<Vec<CapitalEvent>>> {
+        self.run(|lifecycle| lifecycle.accrue_obligations(asset_id, as_of))
+    }
+
+    pub fn accrue(&mut self, period: FiscalPeriod, spec: AccrualSpec) -> IclResult<LifecyclePreview<Accrual>> {
+        self.run(|lifecycle| lifecycle.accrue(period, spec))
+    }
+
+    pub fn transfer_to_org(
+        &mut self,
+        asset_id: AssetId,
+        buyer_org: OrgId,
+        buyer_asset_id: AssetId,
+        buyer_owner: impl Into<OwnerId>,
+        transfer_price: f64
+    ) -> IclResult<LifecyclePreview<IntercompanyTransfer>> {
+        self.run(|lifecycle| lifecycle.transfer_to_org(asset_id, buyer_org, buyer_asset_id, buyer_owner, transfer_price))
+    }
+
+    pub fn reclassify(&mut self, asset_id: AssetId, new_category: impl Into<String>) -> IclResult<LifecyclePreview<CapitalEvent>> {
+        self.run(|lifecycle| lifecycle.reclassify(asset_id, new_category))
+    }
+
+    pub fn retire(&mut self, asset_id: AssetId) -> IclResult<LifecyclePreview<CapitalEvent>> {
+        self.run(|lifecycle| lifecycle.retire(asset_id))
+    }
+
+    pub fn dispose_from_pool(&mut self, asset_id: AssetId) -> IclResult<LifecyclePreview<CapitalEvent>> {
+        self.run(|lifecycle| lifecycle.dispose_from_pool(asset_id))
+    }
+
+    /// Preview any sequence of lifecycle operations, e.g. a full quarter-end depreciation run
+    pub fn run<T>(
+        &mut self,
+        operations: impl FnOnce(&mut IntelligenceCapitalLifecycle) -> IclResult<T>
+    ) -> IclResult<LifecyclePreview<T>> {
+        let (event_count, journal_entry_count) = (self.ledger.events().len(), self.ledger.journal_entries().len());
+        let checkpoint = self.ledger.checkpoint();
+        // Never let a preview touch a file-backed outbox
+        let outbox = self.ledger.replace_emission_outbox(Outbox::new());
+        let preview = Self::preview(self.ledger, &checkpoint, event_count, journal_entry_count, operations);
+        self.ledger.rollback(checkpoint);
+        self.ledger.replace_emission_outbox(outbox);
+        preview
+    }
+
+    fn preview<T>(
+        ledger: &mut IntelligenceCapitalLedger,
+        checkpoint: &LedgerCheckpoint,
+        event_count: usize,
+        journal_entry_count: usize,
+        operations: impl FnOnce(&mut IntelligenceCapitalLifecycle) -> IclResult<T>
+    ) -> IclResult<LifecyclePreview<T>> {
+        let result = operations(&mut IntelligenceCapitalLifecycle::new(ledger))?;
+
+        let mut asset_changes = Vec::new();
+        for (before, after) in ledger.changed_assets(checkpoint) {
+            let unchanged = match &before {
+                Some(before) => serde_json::to_value(before)? == serde_json::to_value(after)?,
+                None => false,
+            };
+            if !unchanged {
+                asset_changes.push(AssetChange { before, after: after.clone() });
+            }
+        }
+        asset_changes.sort_by_key(|change| change.after.asset_id);
+
+        Ok(LifecyclePreview {
+            result,
+            events: ledger.events().get(event_count..).unwrap_or_default().to_vec(),
+            journal_entries: ledger.journal_entries().get(journal_entry_count..).unwrap_or_default().to_vec(),
+            asset_changes,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub struct IntelligenceCapitalLifecycle<'a> {
     pub ledger: &'a mut IntelligenceCapitalLedger,
+    org_id: Option<OrgId>,
+    principal: Option<Principal>,
+    effective_date: Option<DateTime<Utc>>,
+    backdating_approval: Option<Uuid>,
+    kind: AssetKind,
+    category: Option<String>,
 }
 
 impl<'a> IntelligenceCapitalLifecycle<'a> {
     pub fn new(ledger: &'a mut IntelligenceCapitalLedger) -> Self {
-        Self { ledger }
+        Self {
+            ledger,
+            org_id: None,
+            principal: None,
+            effective_date: None,
+            backdating_approval: None,
+            kind: AssetKind::default(),
+            category: None,
+        }
+    }
+
+    /// Scope operations to one namespace: new assets are created in it and assets outside it
+    /// are reported as not found
+    pub fn in_org(mut self, org_id: OrgId) -> Self {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn of_kind(mut self, kind: AssetKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn in_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
     }
 
+    /// Act on behalf of `principal`: the ledger's authorizer is asked about each operation and
+    /// the principal's ID is recorded as the `actor` of the resulting events and journal entries
+    pub fn as_principal(mut self, principal: Principal) -> Self {
+        self.principal = Some(principal);
+        self
+    }
+
+    /// Post the resulting events and journal entries as effective on `effective_date` rather than
+    /// when they are recorded, e.g. to book last month's depreciation today
+    pub fn effective_on(mut self, effective_date: DateTime<Utc>) -> Self {
+        self.effective_date = Some(effective_date);
+        self
+    }
+
+    /// Post under a backdating approval from `IntelligenceCapitalLedger::approve_backdating`,
+    /// needed when the effective date is earlier than the ledger's `BackdatingPolicy` allows
+    pub fn with_backdating_approval(mut self, token: Uuid) -> Self {
+        self.backdating_approval = Some(token);
+        self
+    }
+
+    fn authorize(&self, operation: LifecycleOperation, asset_id: AssetId) -> IclResult<()> {
+        self.ledger.authorizer().authorize(self.ledger, self.principal.as_ref(), operation, asset_id)
+    }
+
+    fn attributed(&self, mut event: CapitalEvent) -> CapitalEvent {
+        event.actor = self.principal.as_ref().map(|p| p.id.clone());
+        event.effective_date = self.effective_date;
+        if let Some(token) = self.backdating_approval {
+            event.details.insert(BACKDATING_APPROVAL_DETAIL.to_string(), serde_json::Value::String(token.to_string()));
+        }
+        event
+    }
+
+    /// Run `operation` so that a failure part way through leaves nothing of it in the ledger
+    fn atomically<T>(&mut self, operation: impl FnOnce(&mut Self) -> IclResult<T>) -> IclResult<T> {
+        let checkpoint = self.ledger.checkpoint();
+        let result = operation(self);
+        match result {
+            Ok(_) => self.ledger.commit(checkpoint),
+            Err(_) => self.ledger.rollback(checkpoint),
+        }
+        result
+    }
+
+    fn scoped_asset(&self, asset_id: AssetId) -> IclResult<&IntelligenceAsset> {
+        self.ledger.get_asset(asset_id)
+            .filter(|asset| self.org_id.as_ref().is_none_or(|org| &asset.org_id == org))
+            .ok_or(IclError::AssetNotFound(asset_id))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, amount = initial_value, duration_us = tracing::field::Empty), err
+    ))]
     pub fn capitalize(
         &mut self,
-        asset_id: Uuid,
-        owner: String,
+        asset_id: AssetId,
+        owner: impl Into<OwnerId>,
         initial_value: f64,
         depreciation_method: DepreciationMethod,
         useful_life_months: i32
     ) -> IclResult<IntelligenceAsset> {
-        let asset = self.ledger.create_asset(
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        self.capitalize_with_details(
             asset_id,
             owner,
             initial_value,
             depreciation_method,
-            useful_life_months
+            useful_life_months,
+            std::collections::HashMap::new()
+        )
+    }
+
+    /// Capitalize a cost, or expense it if it is below the ledger's `CapitalizationPolicy`
+    /// threshold
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, amount = cost, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn capitalize_or_expense(
+        &mut self,
+        asset_id: AssetId,
+        owner: impl Into<OwnerId>,
+        cost: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32
+    ) -> IclResult<CapitalizationOutcome> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let policy = self.ledger.capitalization_policy();
+        if !policy.expenses(cost) {
+            return self.capitalize_with_details(
+                asset_id,
+                owner,
+                cost,
+                depreciation_method,
+                useful_life_months,
+                std::collections::HashMap::new()
+            ).map(CapitalizationOutcome::Capitalized);
+        }
+
+        self.atomically(|lifecycle| {
+            let asset = lifecycle.insert_asset(asset_id, owner, cost, depreciation_method, useful_life_months, |asset| {
+                asset.status = AssetStatus::Depreciated;
+                asset.current_value = Some(0.0);
+            })?;
+
+            let mut details = std::collections::HashMap::new();
+            details.insert("amount".to_string(), serde_json::json!(cost));
+            details.insert("threshold".to_string(), serde_json::json!(policy.threshold));
+            details.insert("owner".to_string(), serde_json::Value::String(asset.owner.to_string()));
+            details.insert("expense_account".to_string(), serde_json::Value::String(policy.expense_account.to_string()));
+            let event = CapitalEvent::new(asset_id, EXPENSED_EVENT, lifecycle.ledger.now(), details)
+                .with_id(lifecycle.ledger.next_id());
+            let event = lifecycle.record_capitalization(event).with_context(|| "capitalize or expense: record event", Some(asset_id))?;
+
+            let journal_entry = JournalEntry::new(
+                event.event_id,
+                event.timestamp,
+                policy.expense_account,
+                AccountType::AccountsPayable,
+                cost,
+                "Cost expensed below capitalization threshold",
+                {
+                    let mut map = std::collections::HashMap::new();
+                    map.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
+                    map.insert("owner".to_string(), serde_json::Value::String(asset.owner.to_string()));
+                    map
+                }
+            ).with_id(lifecycle.ledger.next_id());
+            lifecycle.ledger.record_journal_entry(journal_entry)
+                .with_context(|| "capitalize or expense: post journal entry", Some(asset_id))?;
+
+            Ok(CapitalizationOutcome::Expensed(event))
+        })
+    }
+
+    /// Capitalize the total accumulated on a construction-in-progress account as `asset_id`
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, wip_id = %wip_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn place_in_service(
+        &mut self,
+        wip_id: Uuid,
+        asset_id: AssetId,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32
+    ) -> IclResult<IntelligenceAsset> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let wip = self.ledger.work_in_progress(wip_id)
+            .filter(|wip| wip.org_id == self.org_id.clone().unwrap_or_default())
+            .ok_or_else(|| IclError::InvalidAsset(format!("Unknown work in progress {}", wip_id)))?
+            .clone();
+        if !wip.is_open() {
+            return Err(IclError::InvalidAsset(format!("Work in progress {} is {}", wip_id, wip.status)));
+        }
+        if wip.costs.is_empty() {
+            return Err(IclError::InvalidAsset(format!("Work in progress {} has no accrued costs", wip_id)));
+        }
+
+        let mut details = std::collections::HashMap::new();
+        details.insert("work_in_progress_id".to_string(), serde_json::Value::String(wip_id.to_string()));
+        details.insert("work_in_progress_name".to_string(), serde_json::Value::String(wip.name.clone()));
+        details.insert("cost_history".to_string(), serde_json::to_value(&wip.costs)?);
+        details.insert("cost_by_category".to_string(), serde_json::to_value(wip.cost_by_category())?);
+        let asset = self.capitalize_with_details(
+            asset_id,
+            wip.owner.clone(),
+            wip.total_cost(),
+            depreciation_method,
+            useful_life_months,
+            details
         )?;
+        self.ledger.close_work_in_progress(wip_id, asset_id);
+        Ok(asset)
+    }
+
+    /// Capitalize an improvement to an existing asset, such as a fine-tune of a base model, as a
+    /// component with its own useful life and depreciation stream
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, parent_asset_id = %parent_asset_id, amount = cost, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn capitalize_improvement(
+        &mut self,
+        parent_asset_id: AssetId,
+        asset_id: AssetId,
+        cost: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32
+    ) -> IclResult<IntelligenceAsset> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let parent = self.scoped_asset(parent_asset_id)?;
+        if parent.status == AssetStatus::Retired {
+            return Err(IclError::AssetRetired(parent_asset_id));
+        }
+        if parent.parent_asset_id.is_some() {
+            return Err(IclError::InvalidAsset(format!(
+                "Asset {} is itself a component and cannot take improvements", parent_asset_id
+            )));
+        }
+        // Components are created in the lifecycle's namespace, which must be the parent's
+        if parent.org_id != self.org_id.clone().unwrap_or_default() {
+            return Err(IclError::InvalidAsset(format!(
+                "Asset {} is in namespace {}; capitalize its improvements through a lifecycle scoped to it",
+                parent_asset_id, parent.org_id
+            )));
+        }
+        let owner = parent.owner.clone();
+
+        let mut details = std::collections::HashMap::new();
+        details.insert(PARENT_ASSET_DETAIL.to_string(), serde_json::Value::String(parent_asset_id.to_string()));
+        self.capitalize_with_details(asset_id, owner, cost, depreciation_method, useful_life_months, details)?;
+        self.ledger.set_parent_asset(asset_id, parent_asset_id)
+    }
+
+    fn insert_asset(
+        &mut self,
+        asset_id: AssetId,
+        owner: impl Into<OwnerId>,
+        initial_value: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32,
+        prepare: impl FnOnce(&mut IntelligenceAsset)
+    ) -> IclResult<IntelligenceAsset> {
+        self.authorize(LifecycleOperation::Capitalize, asset_id)?;
+        let mut asset = IntelligenceAsset::new(asset_id, owner, initial_value, depreciation_method, useful_life_months, self.ledger.now());
+        asset.org_id = self.org_id.clone().unwrap_or_default();
+        asset.kind = self.kind;
+        asset.category = self.category.clone();
+        prepare(&mut asset);
+        let asset = self.ledger.insert_new_asset(asset)?;
+        // A backdated capitalization puts the asset in service on its effective date
+        match self.effective_date {
+            Some(effective_date) => self.ledger.update_asset(asset_id, |asset| asset.created_at = effective_date),
+            None => Ok(asset),
+        }
+    }
 
-        let journal_entry = JournalEntry {
-            entry_id: Uuid::new_v4(),
-            event_id: Uuid::new_v4(),
-            timestamp: Utc::now(),
-            debit_account: AccountType::Asset,
-            credit_account: AccountType::AccumulatedDepreciation,
-            amount: initial_value,
-            description: "Asset capitalization".to_string(),
-            metadata: {
+    fn record_capitalization(&mut self, event: CapitalEvent) -> IclResult<CapitalEvent> {
+        let asset_id = event.asset_id;
+        let recorded = self.ledger.record_event(self.attributed(event));
+        if recorded.is_err() {
+            self.ledger.remove_uncapitalized_asset(asset_id);
+        }
+        recorded
+    }
+
+    fn capitalize_with_details(
+        &mut self,
+        asset_id: AssetId,
+        owner: impl Into<OwnerId>,
+        initial_value: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32,
+        details: std::collections::HashMap<String, serde_json::Value>
+    ) -> IclResult<IntelligenceAsset> {
+        let asset = self.insert_asset(asset_id, owner, initial_value, depreciation_method, useful_life_months, |_| {})?;
+
+        let event = CapitalEvent::new(
+            asset_id,
+            "capitalization",
+            self.ledger.now(),
+            {
+                let mut map = details;
+                map.insert("amount".to_string(), serde_json::json!(initial_value));
+                map.insert("owner".to_string(), serde_json::Value::String(asset.owner.to_string()));
+                map.insert("depreciation_method".to_string(), serde_json::Value::String(depreciation_method.to_string()));
+                map.insert("useful_life_months".to_string(), serde_json::json!(useful_life_months));
+                if !asset.kind.is_model() {
+                    map.insert("kind".to_string(), serde_json::Value::String(asset.kind.to_string()));
+                }
+                map
+            }
+        ).with_id(self.ledger.next_id());
+
+        let event = self.record_capitalization(event).with_context(|| "capitalize: record event", Some(asset_id))?;
+
+        let journal_entry = JournalEntry::new(
+            event.event_id,
+            event.timestamp,
+            AccountType::Asset,
+            AccountType::AccumulatedDepreciation,
+            initial_value,
+            "Asset capitalization",
+            {
                 let mut map = std::collections::HashMap::new();
                 map.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
-                map.insert("owner".to_string(), serde_json::Value::String(asset.owner.clone()));
+                map.insert("owner".to_string(), serde_json::Value::String(asset.owner.to_string()));
                 map.insert("initial_value".to_string(), serde_json::json!(initial_value));
                 map
             }
-        };
+        ).with_id(self.ledger.next_id());
         
-        self.ledger.record_journal_entry(journal_entry)?;
+        self.ledger.record_journal_entry(journal_entry)
+            .with_context(|| "capitalize: post journal entry", Some(asset_id))?;
         
         Ok(asset)
     }
 
-    pub fn allocate(&mut self, asset_id: Uuid, target_owner: String) -> IclResult<CapitalEvent> {
-        let asset = self.ledger.get_asset(asset_id)
-            .ok_or(IclError::AssetNotFound(asset_id))?;
+    /// Transfer an asset to another owner, which must be registered once the ledger's owner
+    /// registry is in use
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn allocate(&mut self, asset_id: AssetId, target_owner: impl Into<OwnerId>) -> IclResult<CapitalEvent> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let target_owner = target_owner.into();
+        let asset = self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::Allocate, asset_id)?;
         
         if asset.status == AssetStatus::Retired {
             return Err(IclError::AssetRetired(asset_id));
         }
+        self.ledger.validate_owner(&target_owner)?;
         
         let old_owner = asset.owner.clone();
         
-        let mut updated_asset = self.ledger.assets.get(&asset_id).unwrap().clone();
-        updated_asset.owner = target_owner.clone();
-        self.ledger.assets.insert(asset_id, updated_asset);
-        
-        let event = CapitalEvent {
-            event_id: Uuid::new_v4(),
+        let event = CapitalEvent::new(
             asset_id,
-            event_type: "allocation".to_string(),
-            timestamp: Utc::now(),
-            details: {
+            "allocation",
+            self.ledger.now(),
+            {
                 let mut map = std::collections::HashMap::new();
-                map.insert("from_owner".to_string(), serde_json::Value::String(old_owner));
-                map.insert("to_owner".to_string(), serde_json::Value::String(target_owner));
+                map.insert("from_owner".to_string(), serde_json::Value::String(old_owner.to_string()));
+                map.insert("to_owner".to_string(), serde_json::Value::String(target_owner.to_string()));
                 map
             }
-        };
+        ).with_id(self.ledger.next_id());
         
-        self.ledger.record_event(event.clone())?;
+        let event = self.ledger.record_event(self.attributed(event))?;
+
+        self.ledger.update_asset(asset_id, |asset| asset.owner = target_owner)?;
+
         Ok(event)
     }
 
-    pub fn utilize(&mut self, asset_id: Uuid, amount: f64) -> IclResult<CapitalEvent> {
-        if !self.ledger.assets.contains_key(&asset_id) {
-            return Err(IclError::AssetNotFound(asset_id));
-        }
+    /// Record use of an asset: the inference cost incurred, as a bare `f64` or `Utilization`, and
+    /// the tokens, requests, and GPU-hours served
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn utilize(&mut self, asset_id: AssetId, utilization: impl Into<Utilization>) -> IclResult<CapitalEvent> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let Utilization { cost, usage } = utilization.into();
+        self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::Utilize, asset_id)?;
         
-        if amount <= 0.0 {
+        if cost < 0.0 || !cost.is_finite() || !(usage.gpu_hours >= 0.0 && usage.gpu_hours.is_finite()) {
+            return Err(IclError::InvalidEvent("Utilization cannot be negative".into()));
+        }
+        if cost == 0.0 && usage.is_empty() && self.ledger.validation_config().zero_amounts == ValidationMode::Strict {
             return Err(IclError::InvalidEvent("Utilization amount must be positive".into()));
         }
 
-        let event = CapitalEvent {
-            event_id: Uuid::new_v4(),
+        let event = CapitalEvent::new(
             asset_id,
-            event_type: "utilization".to_string(),
-            timestamp: Utc::now(),
-            details: {
+            "utilization",
+            self.ledger.now(),
+            {
                 let mut map = std::collections::HashMap::new();
-                map.insert("amount".to_string(), serde_json::json!(amount));
+                map.insert("amount".to_string(), serde_json::json!(cost));
+                usage.insert_into(&mut map);
                 map
             }
-        };
+        ).with_id(self.ledger.next_id());
         
-        self.ledger.record_event(event.clone())?;
-        Ok(event)
+        self.ledger.record_event(self.attributed(event))
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, start = %start_date, end = %end_date, duration_us = tracing::field::Empty), err
+    ))]
     pub fn depreciate(
         &mut self,
-        asset_id: Uuid,
+        asset_id: AssetId,
         start_date: DateTime<Utc>,
         end_date: DateTime<Utc>,
         salvage_value: f64,
         rate_multiplier: f64
     ) -> IclResult<CapitalEvent> {
-        let asset = self.ledger.get_asset(asset_id)
-            .ok_or(IclError::AssetNotFound(asset_id))?;
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let asset = self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::Depreciate, asset_id)?;
         
         if asset.status == AssetStatus::Retired {
             return Err(IclError::AssetRetired(asset_id));
         }
+        if self.ledger.get_events_for_asset(asset_id).iter().any(|e| e.event_type == EXPENSED_EVENT) {
+            return Err(IclError::DepreciationError(format!(
+                "Asset {} was expensed below the capitalization threshold and has nothing to depreciate", asset_id
+            )));
+        }
+        if let Some(pool) = self.ledger.pool_of(asset_id) {
+            return Err(IclError::DepreciationError(format!(
+                "Asset {} is depreciated with pool {}; use depreciate_pool", asset_id, pool.name
+            )));
+        }
 
         use crate::core::integrity::IntegrityChecker;
-        let mut checker = IntegrityChecker::new(self.ledger);
+        let checker = IntegrityChecker::new(self.ledger);
         checker.validate_depreciation_period(asset_id, start_date, end_date)?;
 
+        let step = |step: &str| format!("depreciate {} to {}: {}", start_date.to_rfc3339(), end_date.to_rfc3339(), step);
         let previous_value = asset.current_value.unwrap_or(asset.initial_value);
-        let (depreciation_amount, new_value) = calculate_depreciation(
-            asset,
-            start_date,
-            end_date,
-            salvage_value,
-            rate_multiplier
-        )?;
-
-        let mut updated_asset = self.ledger.assets.get(&asset_id).unwrap().clone();
-        updated_asset.current_value = Some(new_value);
-        if new_value <= salvage_value {
-            updated_asset.status = AssetStatus::Depreciated;
-        }
-        self.ledger.assets.insert(asset_id, updated_asset);
+        let mut usage_details = std::collections::HashMap::new();
+        let (depreciation_amount, new_value) = match asset.depreciation_method {
+            DepreciationMethod::UnitsOfProduction { unit, .. } => {
+                if start_date >= end_date {
+                    return Err(IclError::InvalidDateRange {
+                        start: start_date.to_rfc3339(),
+                        end: end_date.to_rfc3339(),
+                    });
+                }
+                let units = self.ledger.usage_between(asset_id, start_date, end_date).quantity(unit);
+                usage_details.insert("units".to_string(), serde_json::json!(units));
+                usage_details.insert("unit".to_string(), serde_json::Value::String(unit.to_string()));
+                calculate_units_of_production_depreciation(asset, units, salvage_value)
+                    .with_context(|| step("calculate"), Some(asset_id))?
+            },
+            _ => calculate_depreciation(asset, start_date, end_date, salvage_value, rate_multiplier)
+                .with_context(|| step("calculate"), Some(asset_id))?,
+        };
 
-        let event = CapitalEvent {
-            event_id: Uuid::new_v4(),
+        let event = CapitalEvent::new(
             asset_id,
-            event_type: "depreciation".to_string(),
-            timestamp: Utc::now(),
-            details: {
-                let mut map = std::collections::HashMap::new();
+            "depreciation",
+            self.ledger.now(),
+            {
+                let mut map = usage_details;
                 map.insert("amount".to_string(), serde_json::json!(depreciation_amount));
                 map.insert("start_date".to_string(), serde_json::Value::String(start_date.to_rfc3339()));
                 map.insert("end_date".to_string(), serde_json::Value::String(end_date.to_rfc3339()));
@@ -161,105 +712,848 @@ impl<'a> IntelligenceCapitalLifecycle<'a> {
                 map.insert("new_value".to_string(), serde_json::json!(new_value));
                 map
             }
-        };
+        ).with_id(self.ledger.next_id());
+        
+        self.atomically(|lifecycle| {
+            let event = lifecycle.ledger.record_event(lifecycle.attributed(event))
+                .with_context(|| step("record event"), Some(asset_id))?;
+
+            lifecycle.ledger.update_asset(asset_id, |asset| {
+                asset.current_value = Some(new_value);
+                if new_value <= salvage_value {
+                    asset.status = AssetStatus::Depreciated;
+                }
+            }).with_context(|| step("update book value"), Some(asset_id))?;
         
-        self.ledger.record_event(event.clone())?;
+            let mut values = event.details.clone();
+            values.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
+            lifecycle.ledger.post_journal_template(&JournalTemplate::depreciation(), event.event_id, &values)
+                .with_context(|| step("post journal entry"), Some(asset_id))?;
         
-        if depreciation_amount > 0.0 {
-            let journal_entry = JournalEntry {
-                entry_id: Uuid::new_v4(),
-                event_id: event.event_id,
-                timestamp: Utc::now(),
-                debit_account: AccountType::DepreciationExpense,
-                credit_account: AccountType::AccumulatedDepreciation,
-                amount: depreciation_amount,
-                description: "Asset depreciation".to_string(),
-                metadata: {
+            Ok(event)
+        })
+    }
+
+    /// Write an asset down below its book value after an impairment test
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, amount, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn impair(&mut self, asset_id: AssetId, amount: f64, reason: impl Into<String>) -> IclResult<CapitalEvent> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        self.impair_with_details(asset_id, amount, reason.into(), std::collections::HashMap::new())
+    }
+
+    /// Approve an open impairment review and post its impairment: `amount` if given, otherwise
+    /// the review's draft
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(review_id = %review_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn approve_impairment_review(&mut self, review_id: Uuid, amount: Option<f64>) -> IclResult<CapitalEvent> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let review = self.ledger.impairment_review(review_id)
+            .ok_or_else(|| IclError::InvalidEvent(format!("Unknown impairment review {}", review_id)))?;
+        if !review.is_open() {
+            return Err(IclError::InvalidEvent(format!("Impairment review {} is {}", review_id, review.status)));
+        }
+        let amount = amount.or(review.draft.as_ref().map(|draft| draft.amount)).ok_or_else(|| {
+            IclError::InvalidEvent(format!("Impairment review {} has no draft; give an amount", review_id))
+        })?;
+        let (asset_id, reason) = (review.asset_id, review.reason());
+
+        let mut details = std::collections::HashMap::new();
+        details.insert("impairment_review_id".to_string(), serde_json::Value::String(review_id.to_string()));
+        let event = self.impair_with_details(asset_id, amount, reason, details)?;
+        self.ledger.resolve_impairment_review(review_id, ReviewStatus::Impaired(event.event_id), None)?;
+        Ok(event)
+    }
+
+    fn impair_with_details(
+        &mut self,
+        asset_id: AssetId,
+        amount: f64,
+        reason: String,
+        details: std::collections::HashMap<String, serde_json::Value>
+    ) -> IclResult<CapitalEvent> {
+        let asset = self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::Impair, asset_id)?;
+
+        if asset.status == AssetStatus::Retired {
+            return Err(IclError::AssetRetired(asset_id));
+        }
+        let previous_value = asset.current_value.unwrap_or(asset.initial_value);
+        if self.ledger.validation_config().rejects_amount(amount) {
+            return Err(IclError::InvalidEvent("Impairment amount must be positive".into()));
+        }
+        if amount > previous_value + VALUE_TOLERANCE {
+            return Err(IclError::InvalidEvent(format!(
+                "Impairment of {} exceeds book value {}", amount, previous_value
+            )));
+        }
+        let new_value = (previous_value - amount).max(0.0);
+
+        let event = CapitalEvent::new(
+            asset_id,
+            IMPAIRMENT_EVENT,
+            self.ledger.now(),
+            {
+                let mut map = details;
+                map.insert("amount".to_string(), serde_json::json!(amount));
+                map.insert("reason".to_string(), serde_json::Value::String(reason));
+                map.insert("previous_value".to_string(), serde_json::json!(previous_value));
+                map.insert("new_value".to_string(), serde_json::json!(new_value));
+                map
+            }
+        ).with_id(self.ledger.next_id());
+
+        self.atomically(|lifecycle| {
+            let event = lifecycle.ledger.record_event(lifecycle.attributed(event))
+                .with_context(|| "impair: record event", Some(asset_id))?;
+
+            lifecycle.ledger.update_asset(asset_id, |asset| {
+                asset.current_value = Some(new_value);
+                if new_value <= VALUE_TOLERANCE {
+                    asset.status = AssetStatus::Depreciated;
+                }
+            }).with_context(|| "impair: update book value", Some(asset_id))?;
+
+            let journal_entry = JournalEntry::new(
+                event.event_id,
+                lifecycle.ledger.now(),
+                AccountType::DepreciationExpense,
+                AccountType::AccumulatedDepreciation,
+                amount,
+                "Asset impairment",
+                {
                     let mut map = std::collections::HashMap::new();
                     map.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
                     map.insert("previous_value".to_string(), serde_json::json!(previous_value));
                     map.insert("new_value".to_string(), serde_json::json!(new_value));
-                    for (k, v) in &event.details {
-                        map.insert(k.clone(), v.clone());
+                    map
+                }
+            ).with_id(lifecycle.ledger.next_id());
+
+            lifecycle.ledger.record_journal_entry(journal_entry)
+                .with_context(|| "impair: post journal entry", Some(asset_id))?;
+
+            Ok(event)
+        })
+    }
+
+    /// License an asset to a third party, recording a `license` event
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn license(&mut self, asset_id: AssetId, licensee: impl Into<String>, terms: LicenseTerms) -> IclResult<License> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let licensee = licensee.into();
+        let asset = self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::License, asset_id)?;
+
+        if asset.status == AssetStatus::Retired {
+            return Err(IclError::AssetRetired(asset_id));
+        }
+        if licensee.trim().is_empty() {
+            return Err(IclError::InvalidEvent("Licensee is required".into()));
+        }
+        if terms.ends_at.is_some_and(|end| end <= terms.starts_at) {
+            return Err(IclError::InvalidDateRange {
+                start: terms.starts_at.to_rfc3339(),
+                end: terms.ends_at.map(|end| end.to_rfc3339()).unwrap_or_default(),
+            });
+        }
+        self.ledger.ensure_licensable(asset_id, &terms)?;
+
+        let license_id = self.ledger.next_id();
+        let event = CapitalEvent::new(
+            asset_id,
+            LICENSE_EVENT,
+            self.ledger.now(),
+            {
+                let mut map = std::collections::HashMap::new();
+                map.insert("license_id".to_string(), serde_json::Value::String(license_id.to_string()));
+                map.insert("licensee".to_string(), serde_json::Value::String(licensee.clone()));
+                map.insert("terms".to_string(), serde_json::to_value(&terms)?);
+                map
+            }
+        ).with_id(self.ledger.next_id());
+
+        let event = self.ledger.record_event(self.attributed(event))?;
+
+        let license = License {
+            license_id,
+            asset_id,
+            licensee,
+            terms,
+            granted_at: event.timestamp,
+            event_id: event.event_id,
+        };
+        self.ledger.insert_license(license.clone());
+        Ok(license)
+    }
+
+    /// Record revenue an asset earned over `period`, booked against accounts receivable, so
+    /// that returns can be reported next to the asset's cost
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, amount, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn record_revenue(&mut self, asset_id: AssetId, amount: f64, period: RevenuePeriod) -> IclResult<CapitalEvent> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::RecordRevenue, asset_id)?;
+
+        if self.ledger.validation_config().rejects_amount(amount) {
+            return Err(IclError::InvalidEvent("Revenue amount must be positive".into()));
+        }
+        if period.start >= period.end {
+            return Err(IclError::InvalidDateRange {
+                start: period.start.to_rfc3339(),
+                end: period.end.to_rfc3339(),
+            });
+        }
+
+        let event = CapitalEvent::new(
+            asset_id,
+            REVENUE_EVENT,
+            self.ledger.now(),
+            {
+                let mut map = std::collections::HashMap::new();
+                map.insert("amount".to_string(), serde_json::json!(amount));
+                map.insert("period_start".to_string(), serde_json::json!(period.start.to_rfc3339()));
+                map.insert("period_end".to_string(), serde_json::json!(period.end.to_rfc3339()));
+                map
+            }
+        ).with_id(self.ledger.next_id());
+
+        self.atomically(|lifecycle| {
+            let event = lifecycle.ledger.record_event(lifecycle.attributed(event))
+                .with_context(|| "record revenue: record event", Some(asset_id))?;
+
+            let journal_entry = JournalEntry::new(
+                event.event_id,
+                lifecycle.ledger.now(),
+                AccountType::AccountsReceivable,
+                AccountType::Revenue,
+                amount,
+                "Asset revenue",
+                {
+                    let mut map = std::collections::HashMap::new();
+                    map.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
+                    map
+                }
+            ).with_id(lifecycle.ledger.next_id());
+
+            lifecycle.ledger.record_journal_entry(journal_entry)
+                .with_context(|| "record revenue: post journal entry", Some(asset_id))?;
+
+            Ok(event)
+        })
+    }
+
+    /// Expense every period of the asset's royalty and cost-sharing obligations that has ended by
+    /// `as_of` and is not yet accrued
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn accrue_obligations(&mut self, asset_id: AssetId, as_of: DateTime<Utc>) -> IclResult<Vec<CapitalEvent>> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::AccrueObligations, asset_id)?;
+
+        let obligations: Vec<_> = self.ledger.obligations_for_asset(asset_id).into_iter().cloned().collect();
+        let mut events = Vec::new();
+        for obligation in obligations {
+            for (period_start, period_end) in obligation.due_periods(as_of) {
+                let step = |step: &str| format!(
+                    "accrue obligation {} for {} to {}: {}",
+                    obligation.obligation_id, period_start.to_rfc3339(), period_end.to_rfc3339(), step
+                );
+                let amount = match obligation.basis {
+                    ObligationBasis::Fixed(amount) => amount,
+                    ObligationBasis::RevenueShare(rate) => rate * self.ledger.revenue_between(asset_id, period_start, period_end),
+                };
+                if amount <= 0.0 {
+                    self.ledger.mark_obligation_accrued(obligation.obligation_id, period_end, 0.0);
+                    continue;
+                }
+
+                let event = CapitalEvent::new(
+                    asset_id,
+                    OBLIGATION_EVENT,
+                    self.ledger.now(),
+                    {
+                        let mut map = std::collections::HashMap::new();
+                        map.insert("amount".to_string(), serde_json::json!(amount));
+                        map.insert("obligation_id".to_string(), serde_json::Value::String(obligation.obligation_id.to_string()));
+                        map.insert("kind".to_string(), serde_json::Value::String(obligation.kind.to_string()));
+                        map.insert("counterparty".to_string(), serde_json::Value::String(obligation.counterparty.clone()));
+                        map.insert("basis".to_string(), serde_json::Value::String(obligation.basis.to_string()));
+                        map.insert("period_start".to_string(), serde_json::json!(period_start.to_rfc3339()));
+                        map.insert("period_end".to_string(), serde_json::json!(period_end.to_rfc3339()));
+                        map
+                    }
+                ).with_id(self.ledger.next_id());
+
+                let mut event = self.attributed(event);
+                event.effective_date = Some(period_end - Duration::seconds(1));
+                let event = self.ledger.record_event(event).with_context(|| step("record event"), Some(asset_id))?;
+
+                let journal_entry = JournalEntry::new(
+                    event.event_id,
+                    self.ledger.now(),
+                    AccountType::RoyaltyExpense,
+                    AccountType::AccountsPayable,
+                    amount,
+                    match obligation.kind {
+                        ObligationKind::Royalty => "Royalty expense",
+                        ObligationKind::CostSharing => "Cost-sharing expense",
+                    },
+                    {
+                        let mut map = std::collections::HashMap::new();
+                        map.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
+                        map.insert("obligation_id".to_string(), serde_json::Value::String(obligation.obligation_id.to_string()));
+                        map.insert("counterparty".to_string(), serde_json::Value::String(obligation.counterparty.clone()));
+                        map
                     }
+                ).with_id(self.ledger.next_id());
+
+                self.ledger.record_journal_entry(journal_entry).with_context(|| step("post journal entry"), Some(asset_id))?;
+                self.ledger.mark_obligation_accrued(obligation.obligation_id, period_end, amount);
+                events.push(event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Recognize a cost in `period` before it is invoiced
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %spec.asset_id, amount = spec.amount, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn accrue(&mut self, period: FiscalPeriod, spec: AccrualSpec) -> IclResult<Accrual> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let asset_id = spec.asset_id;
+        self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::AccrueObligations, asset_id)?;
+        period.validate()?;
+        if !(spec.amount.is_finite() && spec.amount > 0.0) {
+            return Err(IclError::InvalidEntry(format!("Accrual amount must be positive, not {}", spec.amount)));
+        }
+        let accrual_template = JournalTemplate::new(ACCRUAL_EVENT)
+            .leg(spec.debit_account, spec.credit_account, LegAmount::Value("amount".into()), "{description}");
+        let reversal_template = JournalTemplate::new(ACCRUAL_REVERSAL_EVENT)
+            .leg(spec.credit_account, spec.debit_account, LegAmount::Value("amount".into()), "Reversal of {description}");
+        accrual_template.validate()?;
+
+        let mut details = std::collections::HashMap::new();
+        details.insert("amount".to_string(), serde_json::json!(spec.amount));
+        details.insert("period_start".to_string(), serde_json::json!(period.start.to_rfc3339()));
+        details.insert("period_end".to_string(), serde_json::json!(period.end.to_rfc3339()));
+        details.insert("description".to_string(), serde_json::json!(spec.description));
+        let event = CapitalEvent::new(asset_id, ACCRUAL_EVENT, self.ledger.now(), details.clone())
+            .with_id(self.ledger.next_id());
+        let accrual_event = self.attributed(event).with_effective_date(period.end - Duration::nanoseconds(1));
+        let accrual_event = self.ledger.record_event(accrual_event).with_context(|| "accrue: record accrual", Some(asset_id))?;
+        let accrual_entry = self.ledger.post_journal_template(&accrual_template, accrual_event.event_id, &details)
+            .with_context(|| "accrue: post accrual", Some(asset_id))?
+            .remove(0);
+
+        details.insert(REVERSED_ACCRUAL_DETAIL.to_string(), serde_json::json!(accrual_event.event_id.to_string()));
+        let event = CapitalEvent::new(asset_id, ACCRUAL_REVERSAL_EVENT, self.ledger.now(), details.clone())
+            .with_id(self.ledger.next_id());
+        let reversal_event = self.attributed(event).with_effective_date(period.end);
+        let reversal_event = self.ledger.record_event(reversal_event).with_context(|| "accrue: record reversal", Some(asset_id))?;
+        details.insert(REVERSED_ENTRY_METADATA.to_string(), serde_json::json!(accrual_entry.entry_id.to_string()));
+        let reversal_entry = self.ledger.post_journal_template(&reversal_template, reversal_event.event_id, &details)
+            .with_context(|| "accrue: post reversal", Some(asset_id))?
+            .remove(0);
+
+        Ok(Accrual { period, accrual_event, accrual_entry, reversal_event, reversal_entry })
+    }
+
+    /// Sell an asset to another legal entity of the group at an agreed price
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, buyer_org = %buyer_org, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn transfer_to_org(
+        &mut self,
+        asset_id: AssetId,
+        buyer_org: OrgId,
+        buyer_asset_id: AssetId,
+        buyer_owner: impl Into<OwnerId>,
+        transfer_price: f64
+    ) -> IclResult<IntercompanyTransfer> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let buyer_owner = buyer_owner.into();
+        let asset = self.scoped_asset(asset_id)?.clone();
+        self.authorize(LifecycleOperation::Transfer, asset_id)?;
+        self.authorize(LifecycleOperation::Capitalize, buyer_asset_id)?;
+
+        if asset.status == AssetStatus::Retired {
+            return Err(IclError::AssetRetired(asset_id));
+        }
+        if buyer_org.as_str().is_empty() || buyer_org == asset.org_id {
+            return Err(IclError::InvalidEvent(format!(
+                "Intercompany transfer of asset {} needs a buyer outside namespace {}", asset_id, asset.org_id
+            )));
+        }
+        if !(transfer_price > 0.0 && transfer_price.is_finite()) {
+            return Err(IclError::InvalidEvent("Transfer price must be positive".into()));
+        }
+        if asset.parent_asset_id.is_some() || !self.ledger.components_of(asset_id).is_empty() {
+            return Err(IclError::InvalidEvent(format!(
+                "Asset {} has component improvements or is one; it cannot be transferred on its own", asset_id
+            )));
+        }
+        // Checked up front so a failed capitalization cannot leave the seller's side posted alone
+        if self.ledger.get_asset(buyer_asset_id).is_some() {
+            return Err(IclError::AssetAlreadyExists(buyer_asset_id));
+        }
+        self.ledger.validate_owner(&buyer_owner)?;
+
+        let carrying_value = asset.current_value.unwrap_or(asset.initial_value);
+        let elimination = Elimination {
+            transfer_id: self.ledger.next_id(),
+            seller_org: asset.org_id.clone(),
+            buyer_org: buyer_org.clone(),
+            seller_asset_id: asset_id,
+            buyer_asset_id,
+            transfer_price,
+            carrying_value,
+        };
+        let elimination_detail = serde_json::to_value(&elimination)?;
+
+        let event = CapitalEvent::new(
+            asset_id,
+            INTERCOMPANY_DISPOSAL_EVENT,
+            self.ledger.now(),
+            {
+                let mut map = std::collections::HashMap::new();
+                map.insert("carrying_value".to_string(), serde_json::json!(carrying_value));
+                map.insert("transfer_price".to_string(), serde_json::json!(transfer_price));
+                map.insert("gain".to_string(), serde_json::json!(elimination.unrealized_gain()));
+                map.insert("buyer_org".to_string(), serde_json::Value::String(buyer_org.to_string()));
+                map.insert("buyer_asset_id".to_string(), serde_json::Value::String(buyer_asset_id.to_string()));
+                map.insert(ELIMINATION_DETAIL.to_string(), elimination_detail.clone());
+                map
+            }
+        ).with_id(self.ledger.next_id());
+
+        let disposal_event = self.ledger.record_event(self.attributed(event))
+            .with_context(|| "transfer: record disposal", Some(asset_id))?;
+
+        self.ledger.update_asset(asset_id, |asset| {
+            asset.status = AssetStatus::Retired;
+            asset.current_value = Some(0.0);
+        }).with_context(|| "transfer: retire seller asset", Some(asset_id))?;
+
+        let postings = [
+            (AccountType::DisposalGainLoss, AccountType::Asset, carrying_value, "Intercompany disposal of carrying value"),
+            (AccountType::AccountsReceivable, AccountType::DisposalGainLoss, transfer_price, "Intercompany transfer proceeds"),
+        ];
+        for (debit_account, credit_account, amount, description) in postings {
+            if amount <= 0.0 {
+                continue;
+            }
+            let journal_entry = JournalEntry::new(
+                disposal_event.event_id,
+                self.ledger.now(),
+                debit_account,
+                credit_account,
+                amount,
+                description,
+                {
+                    let mut map = std::collections::HashMap::new();
+                    map.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
+                    map.insert("transfer_id".to_string(), serde_json::Value::String(elimination.transfer_id.to_string()));
+                    map.insert("intercompany".to_string(), serde_json::Value::String(buyer_org.to_string()));
                     map
                 }
+            ).with_id(self.ledger.next_id());
+            self.ledger.record_journal_entry(journal_entry)
+                .with_context(|| format!("transfer: post {}", description), Some(asset_id))?;
+        }
+
+        let useful_life_months = match asset.depreciation_method {
+            DepreciationMethod::ImpairmentOnly => 0,
+            _ => (asset.useful_life_months - months_between(asset.created_at, self.ledger.now())).max(1),
+        };
+        let mut details = std::collections::HashMap::new();
+        details.insert("seller_org".to_string(), serde_json::Value::String(asset.org_id.to_string()));
+        details.insert("seller_asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
+        details.insert("seller_event_id".to_string(), serde_json::Value::String(disposal_event.event_id.to_string()));
+        details.insert(ELIMINATION_DETAIL.to_string(), elimination_detail);
+        let mut buyer = IntelligenceCapitalLifecycle {
+            ledger: &mut *self.ledger,
+            org_id: Some(buyer_org),
+            principal: self.principal.clone(),
+            effective_date: self.effective_date,
+            backdating_approval: None,
+            kind: asset.kind,
+            category: None,
+        };
+        let buyer_asset = buyer.capitalize_with_details(
+            buyer_asset_id, buyer_owner, transfer_price, asset.depreciation_method, useful_life_months, details
+        ).with_context(|| format!("transfer: capitalize buyer asset {}", buyer_asset_id), Some(asset_id))?;
+        let capitalization_event = self.ledger.get_events_for_asset(buyer_asset_id).last().map(|event| (*event).clone())
+            .ok_or(IclError::AssetNotFound(buyer_asset_id))?;
+
+        Ok(IntercompanyTransfer {
+            elimination,
+            transferred_at: disposal_event.timestamp,
+            disposal_event,
+            buyer_asset,
+            capitalization_event,
+        })
+    }
+
+    /// Move an asset to another category, and with it to that category's sub-accounts
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn reclassify(&mut self, asset_id: AssetId, new_category: impl Into<String>) -> IclResult<CapitalEvent> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let new_category = new_category.into();
+        let asset = self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::Reclassify, asset_id)?;
+
+        if asset.status == AssetStatus::Retired {
+            return Err(IclError::AssetRetired(asset_id));
+        }
+        if new_category.trim().is_empty() {
+            return Err(IclError::InvalidEvent("Reclassification category cannot be empty".into()));
+        }
+        let old_category = asset.category.clone();
+        if old_category.as_deref() == Some(new_category.as_str()) {
+            return Err(IclError::InvalidEvent(format!("Asset {} is already in category {}", asset_id, new_category)));
+        }
+        if self.ledger.archived_summaries(asset_id, DateTime::<Utc>::MAX_UTC).next().is_some() {
+            return Err(IclError::IntegrityViolation(format!(
+                "Asset {} has records in an archived period; load the archive before reclassifying it", asset_id
+            )));
+        }
+
+        // Net debit balances of the asset's postings
+        let (mut cost, mut accumulated_depreciation) = (0.0, 0.0);
+        for entry in self.ledger.get_journal_entries_for_asset(asset_id) {
+            if self.ledger.is_quarantined(entry.entry_id) {
+                continue;
+            }
+            for (account, amount) in [(entry.debit_account, entry.amount), (entry.credit_account, -entry.amount)] {
+                match account {
+                    AccountType::Asset => cost += amount,
+                    AccountType::AccumulatedDepreciation => accumulated_depreciation += amount,
+                    _ => {},
+                }
+            }
+        }
+
+        let event = CapitalEvent::new(
+            asset_id,
+            RECLASSIFICATION_EVENT,
+            self.ledger.now(),
+            {
+                let mut map = std::collections::HashMap::new();
+                map.insert("from_category".to_string(), serde_json::json!(old_category));
+                map.insert("to_category".to_string(), serde_json::json!(new_category));
+                map.insert("cost".to_string(), serde_json::json!(cost));
+                map.insert("accumulated_depreciation".to_string(), serde_json::json!(accumulated_depreciation));
+                map
+            }
+        ).with_id(self.ledger.next_id());
+
+        let event = self.ledger.record_event(self.attributed(event))
+            .with_context(|| "reclassify: record event", Some(asset_id))?;
+
+        self.ledger.update_asset(asset_id, |asset| asset.category = Some(new_category.clone()))
+            .with_context(|| "reclassify: update category", Some(asset_id))?;
+
+        for (account, amount) in [(AccountType::Asset, cost), (AccountType::AccumulatedDepreciation, accumulated_depreciation)] {
+            if amount.abs() <= VALUE_TOLERANCE {
+                continue;
+            }
+            // Debit balances move by debiting the new category; credit balances the other way round
+            let (debit_category, credit_category) = if amount > 0.0 {
+                (Some(new_category.as_str()), old_category.as_deref())
+            } else {
+                (old_category.as_deref(), Some(new_category.as_str()))
             };
-            
-            self.ledger.record_journal_entry(journal_entry)?;
+            let journal_entry = JournalEntry::new(
+                event.event_id,
+                self.ledger.now(),
+                account,
+                account,
+                amount.abs(),
+                format!("Reclassification of {} to {}", account, new_category),
+                {
+                    let mut map = std::collections::HashMap::new();
+                    map.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
+                    map.insert(DEBIT_CATEGORY_METADATA.to_string(), serde_json::json!(debit_category));
+                    map.insert(CREDIT_CATEGORY_METADATA.to_string(), serde_json::json!(credit_category));
+                    map
+                }
+            ).with_id(self.ledger.next_id());
+
+            self.ledger.record_journal_entry(journal_entry)
+                .with_context(|| format!("reclassify: move {}", account), Some(asset_id))?;
         }
-        
+
         Ok(event)
     }
 
-    pub fn retire(&mut self, asset_id: Uuid) -> IclResult<CapitalEvent> {
-        let asset = self.ledger.get_asset(asset_id)
-            .ok_or(IclError::AssetNotFound(asset_id))?;
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn retire(&mut self, asset_id: AssetId) -> IclResult<CapitalEvent> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let asset = self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::Retire, asset_id)?;
         
         if asset.status == AssetStatus::Retired {
             return Err(IclError::AssetRetired(asset_id));
         }
+        if let Some(pool) = self.ledger.pool_of(asset_id) {
+            return Err(IclError::InvalidAsset(format!(
+                "Asset {} is in depreciation pool {}; use dispose_from_pool", asset_id, pool.name
+            )));
+        }
         
         let remaining_value = asset.current_value;
-        let mut updated_asset = self.ledger.assets.get(&asset_id).unwrap().clone();
-        updated_asset.status = AssetStatus::Retired;
-        updated_asset.current_value = Some(0.0);
-        self.ledger.assets.insert(asset_id, updated_asset);
-
-        let event = CapitalEvent {
-            event_id: Uuid::new_v4(),
+        let event = CapitalEvent::new(
             asset_id,
-            event_type: "retirement".to_string(),
-            timestamp: Utc::now(),
-            details: {
+            "retirement",
+            self.ledger.now(),
+            {
                 let mut map = std::collections::HashMap::new();
                 map.insert("retired_value".to_string(), serde_json::json!(remaining_value.unwrap_or(0.0)));
                 map
-            },
-        };
+            }
+        ).with_id(self.ledger.next_id());
         
-        self.ledger.record_event(event.clone())?;
+        let event = self.ledger.record_event(self.attributed(event))
+            .with_context(|| "retire: record event", Some(asset_id))?;
+
+        self.ledger.update_asset(asset_id, |asset| {
+            asset.status = AssetStatus::Retired;
+            asset.current_value = Some(0.0);
+        }).with_context(|| "retire: update status", Some(asset_id))?;
+
+        let active_components: Vec<AssetId> = self.ledger.components_of(asset_id).into_iter()
+            .filter(|component| component.status != AssetStatus::Retired)
+            .map(|component| component.asset_id)
+            .collect();
         
         if let Some(current_value) = remaining_value {
             if current_value > 0.0 {
-                let journal_entry = JournalEntry {
-                    entry_id: Uuid::new_v4(),
-                    event_id: event.event_id,
-                    timestamp: Utc::now(),
-                    debit_account: AccountType::AccumulatedDepreciation,
-                    credit_account: AccountType::Asset,
-                    amount: current_value,
-                    description: "Asset retirement write-off".to_string(),
-                    metadata: {
+                let journal_entry = JournalEntry::new(
+                    event.event_id,
+                    self.ledger.now(),
+                    AccountType::AccumulatedDepreciation,
+                    AccountType::Asset,
+                    current_value,
+                    "Asset retirement write-off",
+                    {
                         let mut map = std::collections::HashMap::new();
                         map.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
                         map.insert("retired_value".to_string(), serde_json::json!(current_value));
                         map
                     }
-                };
+                ).with_id(self.ledger.next_id());
                 
-                self.ledger.record_journal_entry(journal_entry)?;
+                self.ledger.record_journal_entry(journal_entry)
+                    .with_context(|| "retire: post write-off", Some(asset_id))?;
             }
         }
+
+        for component_id in active_components {
+            self.retire(component_id).with_context(|| format!("retire: retire component {}", component_id), Some(asset_id))?;
+        }
         
         Ok(event)
     }
 
-    pub fn get_asset_summary(&self, asset_id: Uuid) -> IclResult<serde_json::Value> {
-        let asset = self.ledger.get_asset(asset_id)
-            .ok_or(IclError::AssetNotFound(asset_id))?;
-        
-        let events = self.ledger.get_events_for_asset(asset_id);
-        let journal_entries = self.ledger.get_journal_entries_for_asset(asset_id);
+    /// Dispose of an asset out of its depreciation pool, subject to the `Retire` permission
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all, fields(asset_id = %asset_id, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn dispose_from_pool(&mut self, asset_id: AssetId) -> IclResult<CapitalEvent> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let asset = self.scoped_asset(asset_id)?;
+        self.authorize(LifecycleOperation::Retire, asset_id)?;
+
+        if asset.status == AssetStatus::Retired {
+            return Err(IclError::AssetRetired(asset_id));
+        }
+        let pool = self.ledger.pool_of(asset_id)
+            .ok_or_else(|| IclError::InvalidAsset(format!("Asset {} is not in a depreciation pool", asset_id)))?;
+        let (pool_id, pool_name, cost) = (pool.pool_id, pool.name.clone(), pool.members[&asset_id]);
+
+        let mut details = std::collections::HashMap::new();
+        details.insert(POOL_DETAIL.to_string(), serde_json::json!(pool_id.to_string()));
+        details.insert("pool_name".to_string(), serde_json::json!(pool_name));
+        details.insert("amount".to_string(), serde_json::json!(cost));
+        let event = CapitalEvent::new(asset_id, POOL_DISPOSAL_EVENT, self.ledger.now(), details).with_id(self.ledger.next_id());
+        let event = self.ledger.record_event(self.attributed(event))
+            .with_context(|| format!("dispose from pool {}: record event", pool_name), Some(asset_id))?;
+
+        let journal_entry = JournalEntry::new(
+            event.event_id,
+            self.ledger.now(),
+            AccountType::AccumulatedDepreciation,
+            AccountType::Asset,
+            cost,
+            format!("Disposal from depreciation pool {}", pool_name),
+            {
+                let mut map = std::collections::HashMap::new();
+                map.insert("asset_id".to_string(), serde_json::Value::String(asset_id.to_string()));
+                map.insert(POOL_DETAIL.to_string(), serde_json::Value::String(pool_id.to_string()));
+                map
+            }
+        ).with_id(self.ledger.next_id());
+        self.ledger.record_journal_entry(journal_entry)
+            .with_context(|| format!("dispose from pool {}: post journal entry", pool_name), Some(asset_id))?;
+
+        self.ledger.update_asset(asset_id, |asset| {
+            asset.status = AssetStatus::Retired;
+            asset.current_value = Some(0.0);
+        }).with_context(|| format!("dispose from pool {}: update status", pool_name), Some(asset_id))?;
+        self.ledger.update_depreciation_pool(pool_id, |pool| {
+            pool.members.remove(&asset_id);
+            pool.accumulated_depreciation -= cost;
+            if pool.lead_asset_id == Some(asset_id) {
+                pool.lead_asset_id = pool.members.keys().next().copied();
+            }
+        });
+
+        Ok(event)
+    }
+
+    pub fn get_asset_summary(&self, asset_id: AssetId) -> IclResult<AssetSummary> {
+        let asset = self.scoped_asset(asset_id)?;
         
-        Ok(serde_json::json!({
-            "asset": asset,
-            "event_count": events.len(),
-            "journal_entry_count": journal_entries.len(),
-            "total_depreciation": events.iter()
-                .filter(|e| e.event_type == "depreciation")
-                .filter_map(|e| e.details.get("amount").and_then(|v| v.as_f64()))
-                .sum::<f64>(),
-        }))
-    }
-}
\ No newline at end of file
+        let totals = self.ledger.asset_totals(asset_id).cloned().unwrap_or_default();
+        let components = self.ledger.components_of(asset_id).into_iter()
+            .map(|component| self.get_asset_summary(component.asset_id))
+            .collect::<IclResult<Vec<_>>>()?;
+        let book_value = asset.current_value.unwrap_or(asset.initial_value);
+        let total_depreciation = totals.total_depreciation();
+        let total_revenue = totals.total_revenue();
+        Ok(AssetSummary {
+            asset: asset.clone(),
+            event_count: totals.event_count,
+            journal_entry_count: totals.journal_entry_count,
+            total_depreciation,
+            accumulated_by_type: totals.amount_by_type,
+            book_value,
+            last_event_at: totals.last_event_at,
+            total_revenue,
+            usage: totals.usage,
+            obligations: self.ledger.obligations_for_asset(asset_id).into_iter().map(ObligationBalance::from).collect(),
+            rolled_up_book_value: book_value + components.iter().map(|c| c.rolled_up_book_value).sum::<f64>(),
+            rolled_up_depreciation: total_depreciation + components.iter().map(|c| c.rolled_up_depreciation).sum::<f64>(),
+            components,
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use crate::core::capitalization::CapitalizationPolicy;
+    use crate::core::integrity::Rule;
+    use crate::core::ledger::ValidationMode;
+
+    struct RejectJournalEntries(Arc<AtomicBool>);
+
+    impl Rule for RejectJournalEntries {
+        fn name(&self) -> &str {
+            "reject_journal_entries"
+        }
+
+        fn check_journal_entry(&self, _ledger: &IntelligenceCapitalLedger, _entry: &JournalEntry) -> IclResult<()> {
+            if self.0.load(Ordering::SeqCst) {
+                return Err(IclError::InvalidEntry("The journal is frozen".into()));
+            }
+            Ok(())
+        }
+    }
+
+    fn frozen_journal_ledger() -> (IntelligenceCapitalLedger, AssetId, Arc<AtomicBool>) {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        let frozen = Arc::new(AtomicBool::new(false));
+        ledger.register_rule(RejectJournalEntries(frozen.clone()));
+        ledger.set_validation_mode(ValidationMode::Strict);
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        frozen.store(true, Ordering::SeqCst);
+        (ledger, asset_id, frozen)
+    }
+
+    fn assert_unchanged(ledger: &IntelligenceCapitalLedger, before: &serde_json::Value) {
+        assert_eq!(&serde_json::to_value(ledger).unwrap(), before);
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_rejected_expense_posting_leaves_no_asset() {
+        let (mut ledger, _, _) = frozen_journal_ledger();
+        ledger.set_capitalization_policy(CapitalizationPolicy::threshold(500.0)).unwrap();
+        let before = serde_json::to_value(&ledger).unwrap();
+        let asset_id = AssetId::random();
+
+        let expensed = IntelligenceCapitalLifecycle::new(&mut ledger)
+            .capitalize_or_expense(asset_id, "Team A", 100.0, DepreciationMethod::Linear, 12);
+        assert!(expensed.is_err());
+        assert!(ledger.get_asset(asset_id).is_none());
+        assert_unchanged(&ledger, &before);
+    }
+
+    #[test]
+    fn test_rejected_depreciation_posting_keeps_book_value() {
+        let (mut ledger, asset_id, _) = frozen_journal_ledger();
+        let before = serde_json::to_value(&ledger).unwrap();
+        let now = ledger.now();
+
+        let depreciation = IntelligenceCapitalLifecycle::new(&mut ledger)
+            .depreciate(asset_id, now - Duration::days(30), now, 0.0, 1.0);
+        assert!(depreciation.is_err());
+        assert_eq!(ledger.get_asset(asset_id).unwrap().current_value, Some(1200.0));
+        assert_unchanged(&ledger, &before);
+    }
+
+    #[test]
+    fn test_rejected_impairment_posting_keeps_book_value() {
+        let (mut ledger, asset_id, _) = frozen_journal_ledger();
+        let before = serde_json::to_value(&ledger).unwrap();
+
+        assert!(IntelligenceCapitalLifecycle::new(&mut ledger).impair(asset_id, 300.0, "Benchmark regression").is_err());
+        assert_eq!(ledger.get_asset(asset_id).unwrap().current_value, Some(1200.0));
+        assert_unchanged(&ledger, &before);
+    }
+
+    #[test]
+    fn test_rejected_revenue_posting_records_no_event() {
+        let (mut ledger, asset_id, frozen) = frozen_journal_ledger();
+        let before = serde_json::to_value(&ledger).unwrap();
+        let now = ledger.now();
+        let period = RevenuePeriod::new(now - Duration::days(30), now);
+
+        assert!(IntelligenceCapitalLifecycle::new(&mut ledger).record_revenue(asset_id, 250.0, period).is_err());
+        assert_unchanged(&ledger, &before);
+
+        frozen.store(false, Ordering::SeqCst);
+        let event = IntelligenceCapitalLifecycle::new(&mut ledger).record_revenue(asset_id, 250.0, period).unwrap();
+        assert_eq!(ledger.journal_entries_for_event(event.event_id).len(), 1);
+    }
+}
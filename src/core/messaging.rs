@@ -0,0 +1,233 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::outbox::*;
+use crate::core::hashing::canonical_json;
+use crate::core::error::*;
+
+/// Avro schema of the envelope written by `MessageFormat::Avro`; `data` holds the record as canonical JSON
+pub const AVRO_ENVELOPE_SCHEMA: &str = r#"{"type":"record","name":"IclRecord","namespace":"icl","fields":[{"name":"kind","type":"string"},{"name":"record_id","type":"string"},{"name":"timestamp","type":{"type":"long","logicalType":"timestamp-micros"}},{"name":"data","type":"string"}]}"#;
+
+const EVENTS_STREAM: &str = "events";
+const JOURNAL_ENTRIES_STREAM: &str = "journal_entries";
+const PROOFS_STREAM: &str = "proofs";
+
+/// Broker client (Kafka, NATS, SQS, ...) that publishes keyed messages to topics
+pub trait MessageProducer: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn send(&self, topic: &str, key: &str, payload: &[u8]) -> IclResult<()>;
+}
+
+/// Wire encoding for published records
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum MessageFormat {
+    #[default]
+    Json,
+    Avro,
+}
+
+impl std::fmt::Display for MessageFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageFormat::Json => write!(f, "Json"),
+            MessageFormat::Avro => write!(f, "Avro"),
+        }
+    }
+}
+
+/// Destination topic per record kind
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TopicConfig {
+    pub events: String,
+    pub journal_entries: String,
+    pub proofs: String,
+}
+
+impl Default for TopicConfig {
+    fn default() -> Self {
+        Self {
+            events: "icl.events".into(),
+            journal_entries: "icl.journal_entries".into(),
+            proofs: "icl.proofs".into(),
+        }
+    }
+}
+
+/// Streams ledger records to a message broker
+pub struct QueueConnector {
+    producer: Box<dyn MessageProducer>,
+    format: MessageFormat,
+    topics: TopicConfig,
+    outbox: Outbox,
+}
+
+impl QueueConnector {
+    pub fn new(producer: impl MessageProducer + 'static, format: MessageFormat) -> Self {
+        Self {
+            producer: Box::new(producer),
+            format,
+            topics: TopicConfig::default(),
+            outbox: Outbox::new(),
+        }
+    }
+
+    pub fn with_topics(mut self, topics: TopicConfig) -> Self {
+        self.topics = topics;
+        self
+    }
+
+    pub fn with_outbox(mut self, outbox: Outbox) -> Self {
+        self.outbox = outbox;
+        self
+    }
+
+    pub fn format(&self) -> MessageFormat {
+        self.format
+    }
+
+    pub fn outbox(&self) -> &Outbox {
+        &self.outbox
+    }
+
+    pub fn stage_event(&mut self, event: &CapitalEvent) -> IclResult<()> {
+        let message = self.event_message(event, self.outbox.now())?;
+        self.outbox.enqueue(message)
+    }
+
+    pub fn stage_journal_entry(&mut self, journal_entry: &JournalEntry) -> IclResult<()> {
+        let message = self.journal_entry_message(journal_entry, self.outbox.now())?;
+        self.outbox.enqueue(message)
+    }
+
+    pub fn stage_proof(&mut self, proof: &CapitalProof) -> IclResult<()> {
+        let message = self.proof_message(proof, self.outbox.now())?;
+        self.outbox.enqueue(message)
+    }
+
+    /// Stage every event, journal entry and proof recorded since the last call
+    pub fn stage_new_records(&mut self, ledger: &IntelligenceCapitalLedger) -> IclResult<usize> {
+        let events = ledger.events().iter().skip(self.outbox.position(EVENTS_STREAM))
+            .map(|e| Ok(self.event_message(e, ledger.now())?.with_id(ledger.next_id())))
+            .collect::<IclResult<Vec<_>>>()?;
+        let journal_entries = ledger.journal_entries().iter().skip(self.outbox.position(JOURNAL_ENTRIES_STREAM))
+            .map(|j| Ok(self.journal_entry_message(j, ledger.now())?.with_id(ledger.next_id())))
+            .collect::<IclResult<Vec<_>>>()?;
+        let proofs = ledger.proofs().iter().skip(self.outbox.position(PROOFS_STREAM))
+            .map(|p| Ok(self.proof_message(p, ledger.now())?.with_id(ledger.next_id())))
+            .collect::<IclResult<Vec<_>>>()?;
+
+        let staged = events.len() + journal_entries.len() + proofs.len();
+        self.outbox.enqueue_batch(events, EVENTS_STREAM, ledger.events().len())?;
+        self.outbox.enqueue_batch(journal_entries, JOURNAL_ENTRIES_STREAM, ledger.journal_entries().len())?;
+        self.outbox.enqueue_batch(proofs, PROOFS_STREAM, ledger.proofs().len())?;
+        Ok(staged)
+    }
+
+    /// Publish staged messages in order, stopping at the first failure so ordering is preserved
+    pub fn flush(&mut self) -> IclResult<usize> {
+        let mut published = 0;
+        while let Some(message) = self.outbox.peek() {
+            let message_id = message.message_id;
+            match self.producer.send(&message.topic, &message.key, &message.payload) {
+                Ok(()) => {
+                    self.outbox.acknowledge(message_id)?;
+                    published += 1;
+                },
+                Err(e) => {
+                    self.outbox.record_failure(message_id, &e.to_string())?;
+                    return Err(IclError::IntegrationError(format!(
+                        "{} rejected message {} after publishing {}: {}",
+                        self.producer.name(), message_id, published, e
+                    )));
+                },
+            }
+        }
+        Ok(published)
+    }
+
+    /// Stage new ledger records and publish everything pending
+    pub fn publish_new_records(&mut self, ledger: &IntelligenceCapitalLedger) -> IclResult<usize> {
+        self.stage_new_records(ledger)?;
+        self.flush()
+    }
+
+    fn event_message(&self, event: &CapitalEvent, created_at: DateTime<Utc>) -> IclResult<OutboxMessage> {
+        let payload = self.encode("capital_event", &event.event_id.to_string(), event.timestamp, event)?;
+        Ok(OutboxMessage::new(&self.topics.events, event.asset_id.to_string(), payload, created_at))
+    }
+
+    fn journal_entry_message(&self, journal_entry: &JournalEntry, created_at: DateTime<Utc>) -> IclResult<OutboxMessage> {
+        let payload = self.encode(
+            "journal_entry", &journal_entry.entry_id.to_string(), journal_entry.timestamp, journal_entry,
+        )?;
+        Ok(OutboxMessage::new(&self.topics.journal_entries, journal_entry.event_id.to_string(), payload, created_at))
+    }
+
+    fn proof_message(&self, proof: &CapitalProof, created_at: DateTime<Utc>) -> IclResult<OutboxMessage> {
+        let payload = self.encode("capital_proof", &proof.proof_id.to_string(), proof.timestamp, proof)?;
+        Ok(OutboxMessage::new(&self.topics.proofs, proof.asset_id.to_string(), payload, created_at))
+    }
+
+    fn encode<T: Serialize>(&self, kind: &str, record_id: &str, timestamp: DateTime<Utc>, record: &T) -> IclResult<Vec<u8>> {
+        let data = serde_json::to_value(record)?;
+        match self.format {
+            MessageFormat::Json => Ok(serde_json::to_vec(&serde_json::json!({
+                "kind": kind,
+                "record_id": record_id,
+                "timestamp": timestamp,
+                "data": data,
+            }))?),
+            MessageFormat::Avro => {
+                let mut out = Vec::new();
+                avro_write_string(&mut out, kind);
+                avro_write_string(&mut out, record_id);
+                avro_write_long(&mut out, timestamp.timestamp_micros());
+                avro_write_string(&mut out, &canonical_json(&data));
+                Ok(out)
+            },
+        }
+    }
+}
+
+impl std::fmt::Debug for QueueConnector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueueConnector")
+            .field("producer", &self.producer.name())
+            .field("format", &self.format)
+            .field("topics", &self.topics)
+            .field("outbox", &self.outbox)
+            .finish()
+    }
+}
+
+fn avro_write_long(out: &mut Vec<u8>, value: i64) {
+    let mut n = ((value << 1) ^ (value >> 63)) as u64;
+    while n >= 0x80 {
+        out.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn avro_write_string(out: &mut Vec<u8>, value: &str) {
+    avro_write_long(out, value.len() as i64);
+    out.extend_from_slice(value.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_avro_long_zigzag_encoding() {
+        let cases: [(i64, &[u8]); 5] = [(0, &[0x00]), (-1, &[0x01]), (1, &[0x02]), (-64, &[0x7f]), (64, &[0x80, 0x01])];
+        for (value, expected) in cases {
+            let mut out = Vec::new();
+            avro_write_long(&mut out, value);
+            assert_eq!(out, expected);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// Latencies of successful event and journal writes, in microseconds
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub total_us: u64,
+    pub max_us: u64,
+}
+
+impl LatencySummary {
+    pub fn mean_us(&self) -> Option<u64> {
+        self.total_us.checked_div(self.count)
+    }
+}
+
+/// Point-in-time copy of a ledger's operational counters, as returned by
+/// `IntelligenceCapitalLedger::metrics`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LedgerMetrics {
+    pub events_recorded: u64,
+    pub events_by_type: BTreeMap<String, u64>,
+    pub last_recorded_by_type: BTreeMap<String, DateTime<Utc>>,
+    pub journal_entries_posted: u64,
+    pub writes_rejected: u64,
+    pub proofs_generated: u64,
+    pub integrity_checks: u64,
+    pub integrity_failures: u64,
+    pub write_latency: LatencySummary,
+}
+
+impl LedgerMetrics {
+    /// Time since an event of `event_type` was last recorded, e.g. to alert when `depreciation` has
+    /// not run for over a month
+    pub fn time_since(&self, event_type: &str, now: DateTime<Utc>) -> Option<Duration> {
+        self.last_recorded_by_type.get(event_type).map(|at| now - *at)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MetricsRecorder {
+    events_recorded: AtomicU64,
+    journal_entries_posted: AtomicU64,
+    writes_rejected: AtomicU64,
+    proofs_generated: AtomicU64,
+    integrity_checks: AtomicU64,
+    integrity_failures: AtomicU64,
+    write_count: AtomicU64,
+    write_total_us: AtomicU64,
+    write_max_us: AtomicU64,
+    by_type: Mutex<BTreeMap<String, (u64, DateTime<Utc>)>>,
+}
+
+impl Clone for MetricsRecorder {
+    fn clone(&self) -> Self {
+        let copy = |counter: &AtomicU64| AtomicU64::new(counter.load(Ordering::Relaxed));
+        Self {
+            events_recorded: copy(&self.events_recorded),
+            journal_entries_posted: copy(&self.journal_entries_posted),
+            writes_rejected: copy(&self.writes_rejected),
+            proofs_generated: copy(&self.proofs_generated),
+            integrity_checks: copy(&self.integrity_checks),
+            integrity_failures: copy(&self.integrity_failures),
+            write_count: copy(&self.write_count),
+            write_total_us: copy(&self.write_total_us),
+            write_max_us: copy(&self.write_max_us),
+            by_type: Mutex::new(self.by_type.lock().clone()),
+        }
+    }
+}
+
+impl MetricsRecorder {
+    pub(crate) fn snapshot(&self) -> LedgerMetrics {
+        let by_type = self.by_type.lock();
+        LedgerMetrics {
+            events_recorded: self.events_recorded.load(Ordering::Relaxed),
+            events_by_type: by_type.iter().map(|(t, (count, _))| (t.clone(), *count)).collect(),
+            last_recorded_by_type: by_type.iter().map(|(t, (_, at))| (t.clone(), *at)).collect(),
+            journal_entries_posted: self.journal_entries_posted.load(Ordering::Relaxed),
+            writes_rejected: self.writes_rejected.load(Ordering::Relaxed),
+            proofs_generated: self.proofs_generated.load(Ordering::Relaxed),
+            integrity_checks: self.integrity_checks.load(Ordering::Relaxed),
+            integrity_failures: self.integrity_failures.load(Ordering::Relaxed),
+            write_latency: LatencySummary {
+                count: self.write_count.load(Ordering::Relaxed),
+                total_us: self.write_total_us.load(Ordering::Relaxed),
+                max_us: self.write_max_us.load(Ordering::Relaxed),
+            },
+        }
+    }
+
+    pub(crate) fn event_recorded(&self, event_type: &str, recorded_at: DateTime<Utc>, started: Instant) {
+        self.events_recorded.fetch_add(1, Ordering::Relaxed);
+        let mut by_type = self.by_type.lock();
+        let (count, last) = by_type.entry(event_type.to_string()).or_insert((0, recorded_at));
+        *count += 1;
+        *last = recorded_at;
+        drop(by_type);
+        self.write_completed("event", started);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("icl_events_recorded_total", "event_type" => event_type.to_string()).increment(1);
+    }
+
+    pub(crate) fn journal_entry_posted(&self, started: Instant) {
+        self.journal_entries_posted.fetch_add(1, Ordering::Relaxed);
+        self.write_completed("journal_entry", started);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("icl_journal_entries_posted_total").increment(1);
+    }
+
+    pub(crate) fn write_rejected(&self, record: &'static str) {
+        self.writes_rejected.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("icl_writes_rejected_total", "record" => record).increment(1);
+        #[cfg(not(feature = "metrics"))]
+        let _ = record;
+    }
+
+    pub(crate) fn proof_generated(&self, kind: &'static str) {
+        self.proofs_generated.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::counter!("icl_proofs_generated_total", "kind" => kind).increment(1);
+        #[cfg(not(feature = "metrics"))]
+        let _ = kind;
+    }
+
+    pub(crate) fn integrity_checked(&self, errors: usize) {
+        self.integrity_checks.fetch_add(1, Ordering::Relaxed);
+        self.integrity_failures.fetch_add(errors as u64, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        {
+            ::metrics::counter!("icl_integrity_checks_total").increment(1);
+            ::metrics::counter!("icl_integrity_failures_total").increment(errors as u64);
+            ::metrics::gauge!("icl_integrity_errors").set(errors as f64);
+        }
+    }
+
+    fn write_completed(&self, record: &'static str, started: Instant) {
+        let elapsed = started.elapsed();
+        let micros = elapsed.as_micros() as u64;
+        self.write_count.fetch_add(1, Ordering::Relaxed);
+        self.write_total_us.fetch_add(micros, Ordering::Relaxed);
+        self.write_max_us.fetch_max(micros, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        ::metrics::histogram!("icl_write_latency_seconds", "record" => record).record(elapsed.as_secs_f64());
+        #[cfg(not(feature = "metrics"))]
+        let _ = record;
+    }
+}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::error::{IclError, IclResult};
+
+/// Version of the audit trail format `export_audit_trail` writes
+pub const AUDIT_TRAIL_VERSION: &str = "1.0.0";
+
+/// Version assumed for exports that carry none
+pub const UNVERSIONED_AUDIT_TRAIL: &str = "1.0.0";
+
+/// Rewrites a JSON audit trail export from one format version to the next
+pub type MigrateFn = fn(serde_json::Value) -> IclResult<serde_json::Value>;
+
+/// One step of the audit trail migration path
+#[derive(Debug, Clone, Copy)]
+pub struct AuditTrailMigration {
+    pub from_version: &'static str,
+    pub to_version: &'static str,
+    pub migrate: MigrateFn,
+}
+
+const BUILT_IN_MIGRATIONS: &[AuditTrailMigration] = &[];
+
+/// Registry of audit trail migrations, chained to bring an export of any known version up to
+/// `AUDIT_TRAIL_VERSION`
+#[derive(Debug, Clone)]
+pub struct AuditTrailMigrations {
+    migrations: Vec<AuditTrailMigration>,
+}
+
+impl Default for AuditTrailMigrations {
+    fn default() -> Self {
+        Self { migrations: BUILT_IN_MIGRATIONS.to_vec() }
+    }
+}
+
+impl AuditTrailMigrations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_migration(mut self, migration: AuditTrailMigration) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    pub fn migrations(&self) -> &[AuditTrailMigration] {
+        &self.migrations
+    }
+
+    /// Bring `trail`, written in `from_version`, up to `AUDIT_TRAIL_VERSION`, stamping each step's
+    /// version into it
+    pub fn migrate(&self, mut trail: serde_json::Value, from_version: &str) -> IclResult<serde_json::Value> {
+        let target = parse_version(AUDIT_TRAIL_VERSION)?;
+        let mut version = from_version.to_string();
+        // Each step must move forward, so a cyclic registry cannot loop
+        loop {
+            let current = parse_version(&version)?;
+            if current == target {
+                return Ok(trail);
+            }
+            if current > target {
+                return Err(IclError::UnsupportedFormat(format!(
+                    "audit trail version {} is newer than supported version {}", version, AUDIT_TRAIL_VERSION
+                )));
+            }
+            let step = self.migrations.iter()
+                .find(|m| m.from_version == version && parse_version(m.to_version).is_ok_and(|to| to > current))
+                .ok_or_else(|| IclError::UnsupportedFormat(format!(
+                    "audit trail version {} has no migration to {}", version, AUDIT_TRAIL_VERSION
+                )))?;
+            trail = (step.migrate)(trail)?;
+            if let Some(object) = trail.as_object_mut() {
+                object.insert("version".into(), serde_json::json!(step.to_version));
+            }
+            version = step.to_version.to_string();
+        }
+    }
+}
+
+/// Bring a JSON audit trail export up to `AUDIT_TRAIL_VERSION` with the built-in migrations,
+/// reading its version from its `version` field
+pub fn migrate_audit_trail(trail: serde_json::Value) -> IclResult<serde_json::Value> {
+    let from_version = audit_trail_version(&trail).to_string();
+    AuditTrailMigrations::default().migrate(trail, &from_version)
+}
+
+/// Format version of a JSON audit trail export
+pub fn audit_trail_version(trail: &serde_json::Value) -> &str {
+    trail.get("version").and_then(|v| v.as_str()).unwrap_or(UNVERSIONED_AUDIT_TRAIL)
+}
+
+fn parse_version(version: &str) -> IclResult<(u64, u64, u64)> {
+    let invalid = || IclError::UnsupportedFormat(format!("audit trail version {}", version));
+    let mut parts = version.split('.').map(|part| part.parse::<u64>().map_err(|_| invalid()));
+    let major = parts.next().ok_or_else(invalid)??;
+    let minor = parts.next().transpose()?.unwrap_or(0);
+    let patch = parts.next().transpose()?.unwrap_or(0);
+    if parts.next().is_some() {
+        return Err(invalid());
+    }
+    Ok((major, minor, patch))
+}
+
+/// Version of the ledger data model written into serialized ledgers and snapshots
+pub const LEDGER_DATA_MODEL_VERSION: u32 = 2;
+
+/// Version assumed for serialized ledgers that carry none, written before versioning
+pub const UNVERSIONED_DATA_MODEL: u32 = 1;
+
+/// One in-place step of the ledger upgrade path, returning how many records it changed
+#[derive(Debug, Clone, Copy)]
+pub struct LedgerMigration {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub apply: fn(&mut IntelligenceCapitalLedger) -> IclResult<usize>,
+}
+
+/// The ledger migrations, oldest first
+pub const LEDGER_MIGRATIONS: &[LedgerMigration] = &[
+    LedgerMigration {
+        from_version: 1,
+        to_version: 2,
+        name: "proof_hashes",
+        description: "Re-seal proofs hashed under the legacy scheme with the canonical versioned hash",
+        apply: IntelligenceCapitalLedger::migrate_proof_hashes,
+    },
+];
+
+/// A migration `IntelligenceCapitalLedger::upgrade` applied, or would apply on a dry run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpgradeStep {
+    pub name: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub description: String,
+    pub records_changed: usize,
+}
+
+/// What an upgrade of a ledger's data model did, or would do on a dry run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpgradeReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub dry_run: bool,
+    pub steps: Vec<UpgradeStep>,
+}
+
+impl UpgradeReport {
+    pub fn is_noop(&self) -> bool {
+        self.from_version == self.to_version
+    }
+}
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Months, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::types::AssetId;
+
+/// Event type of periodic royalty and cost-sharing expense
+pub const OBLIGATION_EVENT: &str = "obligation_expense";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ObligationKind {
+    Royalty,
+    CostSharing,
+}
+
+impl std::fmt::Display for ObligationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObligationKind::Royalty => write!(f, "Royalty"),
+            ObligationKind::CostSharing => write!(f, "CostSharing"),
+        }
+    }
+}
+
+/// How much an obligation costs each period
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ObligationBasis {
+    Fixed(f64),
+    RevenueShare(f64),
+}
+
+impl std::fmt::Display for ObligationBasis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObligationBasis::Fixed(amount) => write!(f, "Fixed({})", amount),
+            ObligationBasis::RevenueShare(rate) => write!(f, "RevenueShare({})", rate),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ObligationFrequency {
+    Monthly,
+    Quarterly,
+    Annually,
+}
+
+impl ObligationFrequency {
+    pub fn months(&self) -> u32 {
+        match self {
+            ObligationFrequency::Monthly => 1,
+            ObligationFrequency::Quarterly => 3,
+            ObligationFrequency::Annually => 12,
+        }
+    }
+}
+
+impl std::fmt::Display for ObligationFrequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObligationFrequency::Monthly => write!(f, "Monthly"),
+            ObligationFrequency::Quarterly => write!(f, "Quarterly"),
+            ObligationFrequency::Annually => write!(f, "Annually"),
+        }
+    }
+}
+
+/// A recurring royalty or cost-sharing obligation attached to an asset
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecurringObligation {
+    pub obligation_id: Uuid,
+    pub asset_id: AssetId,
+    pub kind: ObligationKind,
+    pub counterparty: String,
+    pub basis: ObligationBasis,
+    pub frequency: ObligationFrequency,
+    pub starts_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub accrued: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accrued_through: Option<DateTime<Utc>>,
+}
+
+impl RecurringObligation {
+    pub fn royalty(
+        asset_id: AssetId,
+        counterparty: impl Into<String>,
+        basis: ObligationBasis,
+        frequency: ObligationFrequency,
+        starts_at: DateTime<Utc>
+    ) -> Self {
+        Self::new(asset_id, ObligationKind::Royalty, counterparty.into(), basis, frequency, starts_at)
+    }
+
+    pub fn cost_sharing(
+        asset_id: AssetId,
+        counterparty: impl Into<String>,
+        amount: f64,
+        frequency: ObligationFrequency,
+        starts_at: DateTime<Utc>
+    ) -> Self {
+        Self::new(asset_id, ObligationKind::CostSharing, counterparty.into(), ObligationBasis::Fixed(amount), frequency, starts_at)
+    }
+
+    fn new(
+        asset_id: AssetId,
+        kind: ObligationKind,
+        counterparty: String,
+        basis: ObligationBasis,
+        frequency: ObligationFrequency,
+        starts_at: DateTime<Utc>
+    ) -> Self {
+        Self {
+            obligation_id: Uuid::nil(),
+            asset_id,
+            kind,
+            counterparty,
+            basis,
+            frequency,
+            starts_at,
+            ends_at: None,
+            accrued: 0.0,
+            accrued_through: None,
+        }
+    }
+
+    pub fn ending_at(mut self, ends_at: DateTime<Utc>) -> Self {
+        self.ends_at = Some(ends_at);
+        self
+    }
+
+    /// Periods `[start, end)` that have ended by `as_of` and are not yet accrued, oldest first
+    pub fn due_periods(&self, as_of: DateTime<Utc>) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let mut periods = Vec::new();
+        let mut start = self.accrued_through.unwrap_or(self.starts_at);
+        while self.ends_at.is_none_or(|end| start < end) {
+            let Some(end) = start.checked_add_months(Months::new(self.frequency.months())) else {
+                break;
+            };
+            if end > as_of {
+                break;
+            }
+            periods.push((start, end));
+            start = end;
+        }
+        periods
+    }
+
+    /// Fixed-basis expense still to be accrued before the obligation ends; `None` when it is
+    /// open-ended or depends on revenue
+    pub fn remaining_commitment(&self) -> Option<f64> {
+        let (ObligationBasis::Fixed(amount), Some(ends_at)) = (self.basis, self.ends_at) else {
+            return None;
+        };
+        let mut remaining = 0.0;
+        let mut start = self.accrued_through.unwrap_or(self.starts_at);
+        while start < ends_at {
+            remaining += amount;
+            start = start.checked_add_months(Months::new(self.frequency.months()))?;
+        }
+        Some(remaining)
+    }
+}
+
+/// Where an obligation stands, as listed in an asset summary
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ObligationBalance {
+    pub obligation_id: Uuid,
+    pub kind: ObligationKind,
+    pub counterparty: String,
+    pub accrued: f64,
+    pub accrued_through: Option<DateTime<Utc>>,
+    pub remaining_commitment: Option<f64>,
+}
+
+impl From<&RecurringObligation> for ObligationBalance {
+    fn from(obligation: &RecurringObligation) -> Self {
+        Self {
+            obligation_id: obligation.obligation_id,
+            kind: obligation.kind,
+            counterparty: obligation.counterparty.clone(),
+            accrued: obligation.accrued,
+            accrued_through: obligation.accrued_through,
+            remaining_commitment: obligation.remaining_commitment(),
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::error::*;
+
+/// Delivery state of an outbox message
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum DeliveryStatus {
+    #[default]
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl std::fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeliveryStatus::Pending => write!(f, "Pending"),
+            DeliveryStatus::Delivered => write!(f, "Delivered"),
+            DeliveryStatus::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+/// Exponential backoff between delivery attempts
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub initial_backoff_ms: i64,
+    pub max_backoff_ms: i64,
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 15 * 60 * 1_000,
+            max_attempts: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 1i64.checked_shl(attempts.saturating_sub(1)).unwrap_or(i64::MAX);
+        Duration::milliseconds(self.initial_backoff_ms.saturating_mul(factor).min(self.max_backoff_ms))
+    }
+}
+
+/// A serialized record waiting to be published to a downstream topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxMessage {
+    pub message_id: Uuid,
+    pub topic: String,
+    pub key: String,
+    pub payload: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub status: DeliveryStatus,
+    #[serde(default)]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxMessage {
+    /// Message created at `created_at`, typically the ledger's `now()`; enqueueing restamps it
+    /// with the outbox's clock
+    pub fn new(topic: impl Into<String>, key: impl Into<String>, payload: Vec<u8>, created_at: DateTime<Utc>) -> Self {
+        Self {
+            message_id: Uuid::new_v4(),
+            topic: topic.into(),
+            key: key.into(),
+            payload,
+            created_at,
+            attempts: 0,
+            last_error: None,
+            status: DeliveryStatus::Pending,
+            next_attempt_at: None,
+            delivered_at: None,
+        }
+    }
+
+    pub fn with_id(mut self, message_id: Uuid) -> Self {
+        self.message_id = message_id;
+        self
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == DeliveryStatus::Pending && self.next_attempt_at.is_none_or(|at| at <= now)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct OutboxState {
+    #[serde(default)]
+    messages: VecDeque<OutboxMessage>,
+    #[serde(default)]
+    positions: BTreeMap<String, usize>,
+}
+
+impl OutboxState {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.messages.is_empty() && self.positions.is_empty()
+    }
+}
+
+/// Durable FIFO of messages awaiting publication
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    path: Option<PathBuf>,
+    state: OutboxState,
+    retry_policy: RetryPolicy,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for Outbox {
+    fn default() -> Self {
+        Self {
+            path: None,
+            state: OutboxState::default(),
+            retry_policy: RetryPolicy::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+}
+
+impl Outbox {
+    /// In-memory outbox with no backing file
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the outbox persisted at `path`, creating an empty one if the file does not exist
+    pub fn open(path: impl AsRef<Path>) -> IclResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let state = if path.exists() {
+            serde_json::from_slice(&std::fs::read(&path)?)?
+        } else {
+            OutboxState::default()
+        };
+        Ok(Self { path: Some(path), state, ..Self::default() })
+    }
+
+    pub(crate) fn from_state(state: OutboxState) -> Self {
+        Self { state, ..Self::default() }
+    }
+
+    pub(crate) fn undelivered_state(&self) -> OutboxState {
+        OutboxState {
+            messages: self.pending().cloned().collect(),
+            positions: self.state.positions.clone(),
+        }
+    }
+
+    pub(crate) fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub(crate) fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn enqueue(&mut self, mut message: OutboxMessage) -> IclResult<()> {
+        message.created_at = self.clock.now();
+        self.state.messages.push_back(message);
+        self.persist()
+    }
+
+    /// Append a batch of messages and advance a stream position in a single write
+    pub fn enqueue_batch(&mut self, messages: Vec<OutboxMessage>, stream: &str, position: usize) -> IclResult<()> {
+        let now = self.clock.now();
+        self.state.messages.extend(messages.into_iter().map(|mut m| {
+            m.created_at = now;
+            m
+        }));
+        self.state.positions.insert(stream.to_string(), position);
+        self.persist()
+    }
+
+    pub fn peek(&self) -> Option<&OutboxMessage> {
+        self.pending().next()
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &OutboxMessage> {
+        self.state.messages.iter().filter(|m| m.status != DeliveryStatus::Delivered)
+    }
+
+    pub fn due(&self, now: DateTime<Utc>) -> impl Iterator<Item = &OutboxMessage> {
+        self.state.messages.iter().filter(move |m| m.is_due(now))
+    }
+
+    pub fn get(&self, message_id: Uuid) -> Option<&OutboxMessage> {
+        self.state.messages.iter().find(|m| m.message_id == message_id)
+    }
+
+    pub fn acknowledge(&mut self, message_id: Uuid) -> IclResult<bool> {
+        let before = self.state.messages.len();
+        self.state.messages.retain(|m| m.message_id != message_id);
+        if self.state.messages.len() == before {
+            return Ok(false);
+        }
+        self.persist()?;
+        Ok(true)
+    }
+
+    pub fn mark_delivered(&mut self, message_id: Uuid) -> IclResult<bool> {
+        let Some(message) = self.state.messages.iter_mut().find(|m| m.message_id == message_id) else {
+            return Ok(false);
+        };
+        message.attempts += 1;
+        message.status = DeliveryStatus::Delivered;
+        message.delivered_at = Some(self.clock.now());
+        message.next_attempt_at = None;
+        self.persist()?;
+        Ok(true)
+    }
+
+    pub fn prune_delivered(&mut self) -> IclResult<usize> {
+        let before = self.state.messages.len();
+        self.state.messages.retain(|m| m.status != DeliveryStatus::Delivered);
+        let pruned = before - self.state.messages.len();
+        if pruned > 0 {
+            self.persist()?;
+        }
+        Ok(pruned)
+    }
+
+    /// Note a failed attempt and schedule the next one according to the retry policy
+    pub fn record_failure(&mut self, message_id: Uuid, error: &str) -> IclResult<()> {
+        let retry_policy = self.retry_policy;
+        let now = self.clock.now();
+        if let Some(message) = self.state.messages.iter_mut().find(|m| m.message_id == message_id) {
+            message.attempts += 1;
+            message.last_error = Some(error.to_string());
+            if retry_policy.max_attempts.is_some_and(|max| message.attempts >= max) {
+                message.status = DeliveryStatus::Failed;
+                message.next_attempt_at = None;
+            } else {
+                message.next_attempt_at = Some(now + retry_policy.backoff(message.attempts));
+            }
+        }
+        self.persist()
+    }
+
+    pub fn retry_failed(&mut self) -> IclResult<usize> {
+        let mut requeued = 0;
+        for message in self.state.messages.iter_mut().filter(|m| m.status == DeliveryStatus::Failed) {
+            message.status = DeliveryStatus::Pending;
+            message.next_attempt_at = None;
+            requeued += 1;
+        }
+        self.persist()?;
+        Ok(requeued)
+    }
+
+    pub fn position(&self, stream: &str) -> usize {
+        self.state.positions.get(stream).copied().unwrap_or(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.peek().is_none()
+    }
+
+    fn persist(&self) -> IclResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&self.state)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::ManualClock;
+    use crate::core::ledger::IntelligenceCapitalLedger;
+
+    fn message(key: &str) -> OutboxMessage {
+        OutboxMessage::new("events", key, key.as_bytes().to_vec(), Utc::now())
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy { initial_backoff_ms: 1_000, max_backoff_ms: 5_000, max_attempts: None };
+        let schedule: Vec<i64> = (1..=5).map(|attempts| policy.backoff(attempts).num_milliseconds()).collect();
+        assert_eq!(schedule, vec![1_000, 2_000, 4_000, 5_000, 5_000]);
+        assert_eq!(policy.backoff(200).num_milliseconds(), 5_000);
+    }
+
+    #[test]
+    fn test_failed_delivery_waits_for_backoff() {
+        let clock = Arc::new(ManualClock::new(Utc::now()));
+        let policy = RetryPolicy { initial_backoff_ms: 1_000, max_backoff_ms: 60_000, max_attempts: Some(3) };
+        let mut outbox = Outbox::new().with_clock(clock.clone()).with_retry_policy(policy);
+        let first = message("a");
+        let message_id = first.message_id;
+        outbox.enqueue(first).unwrap();
+        assert_eq!(outbox.due(clock.now()).count(), 1);
+
+        for (attempts, backoff_ms) in [(1, 1_000), (2, 2_000)] {
+            outbox.record_failure(message_id, "timeout").unwrap();
+            let pending = outbox.get(message_id).unwrap();
+            assert_eq!(pending.attempts, attempts);
+            assert_eq!(pending.next_attempt_at, Some(clock.now() + Duration::milliseconds(backoff_ms)));
+            assert_eq!(outbox.due(clock.now()).count(), 0);
+            clock.advance(Duration::milliseconds(backoff_ms));
+            assert_eq!(outbox.due(clock.now()).count(), 1);
+        }
+
+        outbox.record_failure(message_id, "timeout").unwrap();
+        assert_eq!(outbox.get(message_id).unwrap().status, DeliveryStatus::Failed);
+        assert_eq!(outbox.due(clock.now() + Duration::days(1)).count(), 0);
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox.retry_failed().unwrap(), 1);
+        assert_eq!(outbox.due(clock.now()).count(), 1);
+
+        assert!(outbox.mark_delivered(message_id).unwrap());
+        let delivered = outbox.get(message_id).unwrap();
+        assert_eq!((delivered.status, delivered.attempts, delivered.delivered_at), (DeliveryStatus::Delivered, 4, Some(clock.now())));
+        assert!(outbox.is_empty());
+        assert!(!outbox.mark_delivered(Uuid::new_v4()).unwrap());
+    }
+
+    #[test]
+    fn test_pending_emissions_survive_ledger_reload() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let (delivered, failed) = (message("a"), message("b"));
+        let (delivered_id, failed_id) = (delivered.message_id, failed.message_id);
+        ledger.emission_outbox_mut().enqueue_batch(vec![delivered, failed], "events", 2).unwrap();
+        ledger.emission_outbox_mut().record_failure(failed_id, "timeout").unwrap();
+        assert!(ledger.mark_delivered(delivered_id).unwrap());
+        let scheduled = ledger.emission_outbox().get(failed_id).unwrap().next_attempt_at;
+
+        let reloaded: IntelligenceCapitalLedger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+        let pending = reloaded.pending_emissions();
+        assert_eq!(pending.len(), 1);
+        assert_eq!((pending[0].message_id, pending[0].attempts), (failed_id, 1));
+        assert_eq!(pending[0].next_attempt_at, scheduled);
+        assert_eq!(reloaded.emission_outbox().position("events"), 2);
+        assert!(reloaded.emission_outbox().get(delivered_id).is_none());
+    }
+
+    #[test]
+    fn test_outbox_file_persists_across_open() {
+        let path = std::env::temp_dir().join(format!("icl-outbox-{}.json", Uuid::new_v4()));
+        let (delivered, pending) = (message("a"), message("b"));
+        let (delivered_id, pending_id) = (delivered.message_id, pending.message_id);
+        let mut outbox = Outbox::open(&path).unwrap();
+        outbox.enqueue_batch(vec![delivered, pending], "journal", 7).unwrap();
+        outbox.mark_delivered(delivered_id).unwrap();
+        outbox.record_failure(pending_id, "refused").unwrap();
+
+        let reopened = Outbox::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reopened.get(delivered_id).map(|m| m.status), Some(DeliveryStatus::Delivered));
+        let pending = reopened.get(pending_id).unwrap();
+        assert_eq!((pending.attempts, pending.last_error.as_deref()), (1, Some("refused")));
+        assert_eq!(reopened.position("journal"), 7);
+        assert_eq!(reopened.len(), 1);
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::hashing::sha256_hex;
+use crate::core::error::*;
+
+/// Event type recorded by `anonymize_owner`
+pub const OWNER_ANONYMIZED_EVENT: &str = "owner_anonymized";
+
+/// What kind of party an owner is
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum OwnerKind {
+    Person,
+    Team,
+    LegalEntity,
+}
+
+impl std::fmt::Display for OwnerKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OwnerKind::Person => write!(f, "Person"),
+            OwnerKind::Team => write!(f, "Team"),
+            OwnerKind::LegalEntity => write!(f, "LegalEntity"),
+        }
+    }
+}
+
+/// A registered owner assets can be capitalized to and allocated between
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Owner {
+    pub owner_id: OwnerId,
+    pub display_name: String,
+    pub kind: OwnerKind,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl Owner {
+    pub fn new(owner_id: impl Into<OwnerId>, display_name: impl Into<String>, kind: OwnerKind) -> Self {
+        Self {
+            owner_id: owner_id.into(),
+            display_name: display_name.into(),
+            kind,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Owners known to the ledger
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OwnerRegistry {
+    owners: BTreeMap<OwnerId, Owner>,
+    #[serde(default)]
+    aliases: BTreeMap<String, OwnerId>,
+}
+
+impl OwnerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, owner: Owner) -> IclResult<()> {
+        if owner.owner_id.is_empty() {
+            return Err(IclError::InvalidAsset("Owner ID cannot be empty".into()));
+        }
+        if owner.display_name.is_empty() {
+            return Err(IclError::InvalidAsset("Owner display name cannot be empty".into()));
+        }
+        if self.owners.contains_key(&owner.owner_id) {
+            return Err(IclError::OwnerAlreadyExists(owner.owner_id));
+        }
+        self.owners.insert(owner.owner_id.clone(), owner);
+        Ok(())
+    }
+
+    pub fn get(&self, owner_id: &OwnerId) -> Option<&Owner> {
+        self.owners.get(owner_id)
+    }
+
+    pub fn contains(&self, owner_id: &OwnerId) -> bool {
+        self.owners.contains_key(owner_id)
+    }
+
+    pub(crate) fn remove(&mut self, owner_id: &OwnerId) -> Option<Owner> {
+        self.aliases.retain(|_, target| target != owner_id);
+        self.owners.remove(owner_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Owner> {
+        self.owners.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+
+    /// Map a free-text owner string to a registered owner
+    pub fn add_alias(&mut self, alias: impl Into<String>, owner_id: OwnerId) -> IclResult<()> {
+        if !self.contains(&owner_id) {
+            return Err(IclError::OwnerNotFound(owner_id));
+        }
+        self.aliases.insert(alias.into(), owner_id);
+        Ok(())
+    }
+
+    pub fn aliases(&self) -> &BTreeMap<String, OwnerId> {
+        &self.aliases
+    }
+
+    /// Owner ID for a string found on a record: its alias target if it has one, otherwise the
+    /// string itself
+    pub fn resolve(&self, owner: &str) -> OwnerId {
+        self.aliases.get(owner).cloned().unwrap_or_else(|| OwnerId::new(owner))
+    }
+
+    pub fn display_name<'a>(&'a self, owner_id: &'a OwnerId) -> &'a str {
+        self.get(owner_id).map_or(owner_id.as_str(), |o| o.display_name.as_str())
+    }
+}
+
+/// Outcome of `IntelligenceCapitalLedger::migrate_owners`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OwnerMigration {
+    pub registered: Vec<OwnerId>,
+    pub mapping: BTreeMap<String, OwnerId>,
+    pub assets_updated: usize,
+}
+
+/// What an anonymized owner leaves behind: the pseudonym that replaced it and a salted
+/// commitment to the original ID
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OwnerPseudonym {
+    pub pseudonym: OwnerId,
+    pub commitment: String,
+    pub anonymized_at: DateTime<Utc>,
+}
+
+impl OwnerPseudonym {
+    pub fn commit(original: &str, salt: &str) -> String {
+        sha256_hex(format!("{}:{}", salt, original).as_bytes())
+    }
+
+    pub fn verify(&self, original: &str, salt: &str) -> bool {
+        Self::commit(original, salt) == self.commitment
+    }
+}
+
+/// What an anonymization rewrote, kept in the details of its `owner_anonymized` event: the
+/// chain digest each rewritten event, ledger entry, and journal entry has now, which the record
+/// chains accept in place of the hash the record was sealed with
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OwnerRewrite {
+    pub pseudonym: OwnerPseudonym,
+    pub rewritten_records: BTreeMap<Uuid, String>,
+}
+
+impl OwnerRewrite {
+    /// The rewrite recorded by an owner anonymized event
+    pub fn from_event(event: &CapitalEvent) -> Option<Self> {
+        if event.event_type != OWNER_ANONYMIZED_EVENT {
+            return None;
+        }
+        let details = event.details.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        serde_json::from_value(serde_json::Value::Object(details)).ok()
+    }
+
+    pub(crate) fn to_details(&self) -> IclResult<HashMap<String, serde_json::Value>> {
+        match serde_json::to_value(self)? {
+            serde_json::Value::Object(details) => Ok(details.into_iter().collect()),
+            _ => Err(IclError::SerializationError("Owner rewrite did not serialize to an object".into())),
+        }
+    }
+}
+
+/// Outcome of `IntelligenceCapitalLedger::anonymize_owner`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnerAnonymization {
+    pub pseudonym: OwnerPseudonym,
+    pub salt: String,
+    pub assets_updated: usize,
+    pub events_updated: usize,
+    pub proofs_redacted: usize,
+    pub proofs_reattested: usize,
+}
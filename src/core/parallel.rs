@@ -0,0 +1,127 @@
+use rayon::prelude::*;
+
+use crate::core::types::*;
+use crate::core::hashing::record_chain_hash;
+use crate::core::archive::chain_bridge;
+use crate::core::integrity::{rewritten_digests, verify_asset_proof_chain, verify_chain, verify_chain_head, FindingKind, IntegrityChecker, IntegrityFinding, IntegrityReport};
+
+impl IntegrityChecker<'_> {
+    /// `check_all_integrity` spread over the rayon thread pool
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(duration_us = tracing::field::Empty)
+    ))]
+    pub fn check_all_integrity_parallel(&self) -> IntegrityReport {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        let mut report = IntegrityReport::new(self.ledger.now());
+        let assets: Vec<&IntelligenceAsset> = self.ledger.assets_iter().collect();
+
+        report.extend(assets.par_iter()
+            .filter_map(|asset| self.validate_asset(asset).err().map(|e| {
+                IntegrityFinding::error(FindingKind::InvalidAsset, Some(asset.asset_id.into()), e.to_string())
+            }))
+            .collect::<Vec<_>>());
+        report.extend(self.ledger.events().par_iter()
+            .filter_map(|event| self.validate_event(event).err().map(|e| {
+                IntegrityFinding::error(FindingKind::InvalidEvent, Some(event.event_id.into()), e.to_string())
+            }))
+            .collect::<Vec<_>>());
+        report.extend(self.ledger.entries().par_iter()
+            .enumerate()
+            .flat_map_iter(|(i, entry)| self.check_entry(i, entry))
+            .collect::<Vec<_>>());
+
+        let (reconciliation, duplicates) = rayon::join(
+            || self.check_event_journal_reconciliation(),
+            || self.find_duplicates(),
+        );
+        report.extend(reconciliation);
+        report.extend(self.check_void_reversals());
+        report.extend(assets.par_iter()
+            .flat_map_iter(|asset| self.check_asset_depreciation_totals(asset))
+            .collect::<Vec<_>>());
+        report.extend(duplicates);
+        report.extend(self.verify_record_chain_parallel());
+        report.extend(self.verify_proof_chain_parallel());
+        report.extend(self.run_rules_parallel());
+
+        self.ledger.metrics_recorder().integrity_checked(report.errors().count());
+        report
+    }
+
+    pub fn verify_proof_chain_parallel(&self) -> Vec<IntegrityFinding> {
+        let chains: Vec<_> = self.ledger.proof_chains().collect();
+        chains.into_par_iter()
+            .flat_map_iter(|(asset_id, proofs)| verify_asset_proof_chain(asset_id, proofs))
+            .collect()
+    }
+
+    fn verify_record_chain_parallel(&self) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+        let algorithm = self.ledger.record_hash_algorithm();
+        let events: Vec<_> = self.ledger.events().par_iter().map(|e| record_chain_hash(algorithm, e)).collect();
+        let entries: Vec<_> = self.ledger.entries().par_iter().map(|e| record_chain_hash(algorithm, e)).collect();
+        let journal_entries: Vec<_> = self.ledger.journal_entries().par_iter().map(|j| record_chain_hash(algorithm, j)).collect();
+        let archives = self.committed_archives(&mut findings);
+        let purges: Vec<_> = self.ledger.retention_purges().collect();
+        let rewrites: Vec<_> = self.ledger.owner_rewrites().collect();
+        let rewritten = rewritten_digests(&rewrites);
+        let heads = self.ledger.record_chain_heads();
+        let event_links: Vec<_> = archives.iter().flat_map(|a| &a.event_links).chain(purges.iter().flat_map(|p| &p.event_links)).collect();
+        verify_chain(
+            "Event",
+            self.ledger.events().iter().zip(events).map(|(e, hash)| (e.event_id, &e.previous_hash, &e.record_hash, hash)),
+            &chain_bridge(event_links.iter().copied()),
+            &rewritten,
+            &mut findings,
+        );
+        verify_chain_head("Event", self.ledger.events().iter().map(|e| &e.record_hash), &event_links, &heads.events, &mut findings);
+        let entry_links: Vec<_> = archives.iter().flat_map(|a| &a.entry_links).chain(purges.iter().flat_map(|p| &p.entry_links)).collect();
+        verify_chain(
+            "Ledger entry",
+            self.ledger.entries().iter().zip(entries).map(|(e, hash)| (e.entry_id, &e.previous_hash, &e.record_hash, hash)),
+            &chain_bridge(entry_links.iter().copied()),
+            &rewritten,
+            &mut findings,
+        );
+        verify_chain_head("Ledger entry", self.ledger.entries().iter().map(|e| &e.record_hash), &entry_links, &heads.entries, &mut findings);
+        let journal_links: Vec<_> = archives.iter().flat_map(|a| &a.journal_links).collect();
+        verify_chain(
+            "Journal entry",
+            self.ledger.journal_entries().iter().zip(journal_entries).map(|(j, hash)| (j.entry_id, &j.previous_hash, &j.record_hash, hash)),
+            &chain_bridge(journal_links.iter().copied()),
+            &rewritten,
+            &mut findings,
+        );
+        verify_chain_head(
+            "Journal entry",
+            self.ledger.journal_entries().iter().map(|j| &j.record_hash),
+            &journal_links,
+            &heads.journal_entries,
+            &mut findings,
+        );
+        findings
+    }
+
+    fn run_rules_parallel(&self) -> Vec<IntegrityFinding> {
+        let mut findings = Vec::new();
+        for rule in self.ledger.rules().iter() {
+            findings.extend(rule.check(self.ledger));
+            findings.extend(self.ledger.events().par_iter()
+                .filter_map(|event| rule.check_event(self.ledger, event).err().map(|e| IntegrityFinding::error(
+                    FindingKind::RuleViolation,
+                    Some(event.event_id.into()),
+                    format!("{}: {}", rule.name(), e),
+                )))
+                .collect::<Vec<_>>());
+            findings.extend(self.ledger.journal_entries().par_iter()
+                .filter_map(|journal_entry| rule.check_journal_entry(self.ledger, journal_entry).err().map(|e| IntegrityFinding::error(
+                    FindingKind::RuleViolation,
+                    Some(journal_entry.entry_id.into()),
+                    format!("{}: {}", rule.name(), e),
+                )))
+                .collect::<Vec<_>>());
+        }
+        findings
+    }
+}
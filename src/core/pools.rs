@@ -0,0 +1,252 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::depreciation::months_between;
+use crate::core::journal_templates::{JournalTemplate, LegAmount};
+use crate::core::integrity::VALUE_TOLERANCE;
+use crate::core::error::{IclError, IclResult, IclResultExt};
+
+/// Event type recorded on an asset when it joins a depreciation pool
+pub const POOL_ADDITION_EVENT: &str = "pool_addition";
+
+/// Event type recorded by `depreciate_pool`, once per pool and period, on the pool's lead asset
+pub const POOL_DEPRECIATION_EVENT: &str = "pool_depreciation";
+
+/// Event type recorded on an asset disposed of out of a depreciation pool
+pub const POOL_DISPOSAL_EVENT: &str = "pool_disposal";
+
+/// Event and journal entry detail naming a depreciation pool
+pub const POOL_DETAIL: &str = "pool_id";
+
+/// Pool depreciation event detail holding each member's share of the charge, keyed by asset ID
+pub const POOL_ALLOCATIONS_DETAIL: &str = "allocations";
+
+/// Similar assets, such as many small fine-tunes, depreciated as a group at one composite rate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DepreciationPool {
+    pub pool_id: Uuid,
+    pub name: String,
+    pub annual_rate: f64,
+    pub opened_at: DateTime<Utc>,
+    pub members: BTreeMap<AssetId, f64>,
+    pub lead_asset_id: Option<AssetId>,
+    pub accumulated_depreciation: f64,
+    pub depreciated_until: Option<DateTime<Utc>>,
+}
+
+impl DepreciationPool {
+    pub fn cost(&self) -> f64 {
+        self.members.values().sum()
+    }
+
+    pub fn net_book_value(&self) -> f64 {
+        (self.cost() - self.accumulated_depreciation).max(0.0)
+    }
+}
+
+impl IntelligenceCapitalLedger {
+    /// Open an empty depreciation pool with a composite annual rate, e.g. `0.25` to write the
+    /// pool off over four years
+    pub fn open_depreciation_pool(&mut self, name: impl Into<String>, annual_rate: f64) -> IclResult<DepreciationPool> {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err(IclError::InvalidEntry("Depreciation pool name cannot be empty".into()));
+        }
+        if !(annual_rate.is_finite() && annual_rate > 0.0 && annual_rate <= 1.0) {
+            return Err(IclError::InvalidEntry(format!(
+                "Composite rate must be greater than 0 and at most 1, not {}", annual_rate
+            )));
+        }
+        let pool = DepreciationPool {
+            pool_id: self.next_id(),
+            name,
+            annual_rate,
+            opened_at: self.now(),
+            members: BTreeMap::new(),
+            lead_asset_id: None,
+            accumulated_depreciation: 0.0,
+            depreciated_until: None,
+        };
+        self.insert_depreciation_pool(pool.clone());
+        Ok(pool)
+    }
+
+    /// Add an asset to a pool at its current book value, recording a `pool_addition` event
+    pub fn add_to_pool(&mut self, pool_id: Uuid, asset_id: AssetId) -> IclResult<CapitalEvent> {
+        let pool = self.depreciation_pool(pool_id).ok_or_else(|| unknown_pool(pool_id))?;
+        let asset = self.get_asset(asset_id).ok_or(IclError::AssetNotFound(asset_id))?;
+        if asset.status == AssetStatus::Retired {
+            return Err(IclError::AssetRetired(asset_id));
+        }
+        if let Some(current) = self.pool_of(asset_id) {
+            return Err(IclError::InvalidAsset(format!(
+                "Asset {} is already in depreciation pool {}", asset_id, current.name
+            )));
+        }
+        let cost = asset.current_value.unwrap_or(asset.initial_value);
+        if cost <= VALUE_TOLERANCE {
+            return Err(IclError::InvalidAsset(format!("Asset {} has no book value to pool", asset_id)));
+        }
+        let pool_name = pool.name.clone();
+
+        let mut details = HashMap::new();
+        details.insert(POOL_DETAIL.to_string(), serde_json::json!(pool_id.to_string()));
+        details.insert("pool_name".to_string(), serde_json::json!(pool_name));
+        details.insert("amount".to_string(), serde_json::json!(cost));
+        let event = CapitalEvent::new(asset_id, POOL_ADDITION_EVENT, self.now(), details).with_id(self.next_id());
+        let event = self.record_event(event).with_context(|| format!("add to pool {}", pool_name), Some(asset_id))?;
+        self.update_depreciation_pool(pool_id, |pool| {
+            pool.members.insert(asset_id, cost);
+            pool.lead_asset_id.get_or_insert(asset_id);
+        });
+        Ok(event)
+    }
+
+    /// Depreciate a pool for `period` at its composite rate with one event on the lead asset and
+    /// one journal entry, capped at the pool's net book value
+    pub fn depreciate_pool(&mut self, pool_id: Uuid, period: FiscalPeriod) -> IclResult<CapitalEvent> {
+        period.validate()?;
+        let pool = self.depreciation_pool(pool_id).ok_or_else(|| unknown_pool(pool_id))?;
+        let lead_asset_id = pool.lead_asset_id
+            .filter(|_| !pool.members.is_empty())
+            .ok_or_else(|| IclError::DepreciationError(format!("Depreciation pool {} has no assets", pool.name)))?;
+        if let Some(until) = pool.depreciated_until.filter(|until| period.start < *until) {
+            return Err(IclError::DepreciationError(format!(
+                "Depreciation pool {} is already depreciated until {}", pool.name, until.to_rfc3339()
+            )));
+        }
+        let months = months_between(period.start, period.end);
+        let charge = (pool.cost() * pool.annual_rate * months as f64 / 12.0).min(pool.net_book_value());
+        // No member is written down below zero
+        let allocations: BTreeMap<AssetId, f64> = pool.members.iter()
+            .map(|(asset_id, cost)| {
+                let book_value = self.get_asset(*asset_id).and_then(|a| a.current_value).unwrap_or(*cost);
+                (*asset_id, (charge * cost / pool.cost()).min(book_value.max(0.0)))
+            })
+            .collect();
+        let amount: f64 = allocations.values().sum();
+        let pool_name = pool.name.clone();
+
+        let mut details = HashMap::new();
+        details.insert(POOL_DETAIL.to_string(), serde_json::json!(pool_id.to_string()));
+        details.insert("pool_name".to_string(), serde_json::json!(pool_name));
+        details.insert("amount".to_string(), serde_json::json!(amount));
+        details.insert("pool_cost".to_string(), serde_json::json!(pool.cost()));
+        details.insert("annual_rate".to_string(), serde_json::json!(pool.annual_rate));
+        details.insert("member_count".to_string(), serde_json::json!(pool.members.len()));
+        details.insert("start_date".to_string(), serde_json::json!(period.start.to_rfc3339()));
+        details.insert("end_date".to_string(), serde_json::json!(period.end.to_rfc3339()));
+        details.insert(POOL_ALLOCATIONS_DETAIL.to_string(), serde_json::json!(allocations));
+        let event = CapitalEvent::new(lead_asset_id, POOL_DEPRECIATION_EVENT, self.now(), details.clone())
+            .with_id(self.next_id());
+        let event = self.record_event(event)
+            .with_context(|| format!("depreciate pool {}: record event", pool_name), Some(lead_asset_id))?;
+        let template = JournalTemplate::new(POOL_DEPRECIATION_EVENT).leg(
+            AccountType::DepreciationExpense,
+            AccountType::AccumulatedDepreciation,
+            LegAmount::Value("amount".into()),
+            "Depreciation of pool {pool_name}",
+        );
+        details.insert("asset_id".to_string(), serde_json::json!(lead_asset_id.to_string()));
+        self.post_journal_template(&template, event.event_id, &details)
+            .with_context(|| format!("depreciate pool {}: post journal entry", pool_name), Some(lead_asset_id))?;
+        for (asset_id, share) in &allocations {
+            self.update_asset(*asset_id, |asset| {
+                let book_value = asset.current_value.unwrap_or(asset.initial_value) - share;
+                asset.current_value = Some(book_value);
+                if book_value <= VALUE_TOLERANCE {
+                    asset.status = AssetStatus::Depreciated;
+                }
+            })?;
+        }
+        self.update_depreciation_pool(pool_id, |pool| {
+            pool.accumulated_depreciation += amount;
+            pool.depreciated_until = Some(period.end);
+        });
+        Ok(event)
+    }
+
+    pub fn pool_of(&self, asset_id: AssetId) -> Option<&DepreciationPool> {
+        self.depreciation_pools().find(|pool| pool.members.contains_key(&asset_id))
+    }
+
+    pub(crate) fn pool_depreciation_charging(&self, asset_id: AssetId) -> impl Iterator<Item = &CapitalEvent> {
+        self.events_of_type(POOL_DEPRECIATION_EVENT).filter(move |event| pool_share(event, asset_id).is_some())
+    }
+}
+
+pub(crate) fn pool_allocations(event: &CapitalEvent) -> impl Iterator<Item = (AssetId, f64)> + '_ {
+    event.details.get(POOL_ALLOCATIONS_DETAIL)
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(asset_id, share)| Some((asset_id.parse().ok()?, share.as_f64()?)))
+}
+
+pub(crate) fn pool_share(event: &CapitalEvent, asset_id: AssetId) -> Option<f64> {
+    pool_allocations(event).find(|(member, _)| *member == asset_id).map(|(_, share)| share)
+}
+
+fn unknown_pool(pool_id: Uuid) -> IclError {
+    IclError::InvalidEntry(format!("Unknown depreciation pool {}", pool_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+    use crate::core::reports::{ReportGenerator, ReportGrouping};
+
+    #[test]
+    fn test_pool_depreciates_as_one() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let assets: Vec<AssetId> = (0..3).map(|_| AssetId::random()).collect();
+        for asset_id in &assets {
+            IntelligenceCapitalLifecycle::new(&mut ledger)
+                .capitalize(*asset_id, "Team A", 400.0, DepreciationMethod::Linear, 24)
+                .unwrap();
+        }
+        let pool = ledger.open_depreciation_pool("fine-tunes", 0.5).unwrap();
+        for asset_id in &assets {
+            ledger.add_to_pool(pool.pool_id, *asset_id).unwrap();
+        }
+        assert!(ledger.add_to_pool(pool.pool_id, assets[0]).is_err());
+
+        let start = ledger.now();
+        let entries_before = ledger.journal_entries().len();
+        let event = ledger.depreciate_pool(pool.pool_id, FiscalPeriod::new(start, start + Duration::days(365))).unwrap();
+        assert_eq!(event.asset_id, assets[0]);
+        assert_eq!(ledger.journal_entries().len(), entries_before + 1);
+        assert!((event.details["amount"].as_f64().unwrap() - 600.0).abs() < 1e-9);
+        for asset_id in &assets {
+            let position = ledger.asset_position(*asset_id, ledger.now()).unwrap();
+            assert!((position.book_value - 200.0).abs() < 1e-9);
+            assert!((position.accumulated_depreciation - 200.0).abs() < 1e-9);
+        }
+        assert!((ledger.portfolio_summary(ledger.now()).total_book_value - 600.0).abs() < 1e-9);
+        let by_period = ReportGenerator::new(&ledger)
+            .depreciation_by_period(start, start + Duration::days(365), ReportGrouping::Asset)
+            .unwrap();
+        assert_eq!(by_period.rows.len(), 3);
+        assert!((by_period.total - 600.0).abs() < 1e-3);
+        assert!(IntelligenceCapitalLifecycle::new(&mut ledger)
+            .depreciate(assets[1], start, start + Duration::days(31), 0.0, 1.0)
+            .is_err());
+
+        IntelligenceCapitalLifecycle::new(&mut ledger).dispose_from_pool(assets[0]).unwrap();
+        let pool = ledger.depreciation_pool(pool.pool_id).unwrap().clone();
+        assert_eq!(pool.cost(), 800.0);
+        assert!((pool.accumulated_depreciation - 200.0).abs() < 1e-9);
+        assert!(pool.lead_asset_id.is_some_and(|lead| lead != assets[0] && pool.members.contains_key(&lead)));
+        assert_eq!(ledger.get_asset(assets[0]).unwrap().status, AssetStatus::Retired);
+        assert!(ledger.assert_invariants().is_ok());
+
+        let restored: IntelligenceCapitalLedger = serde_json::from_str(&serde_json::to_string(&ledger).unwrap()).unwrap();
+        assert_eq!(restored.depreciation_pool(pool.pool_id), Some(&pool));
+    }
+}
@@ -1,10 +1,269 @@
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 use crate::core::types::*;
 use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::hashing::*;
 use crate::core::error::*;
 
+/// Current layout version of exported proof bundles
+pub const PROOF_BUNDLE_VERSION: u32 = 1;
+
+/// Proof content field holding the digest of the event a proof commits to, so the proof hash
+/// covers the event
+pub const EVENT_DIGEST_CONTENT: &str = "event_digest";
+
+/// Self-verifying package of an asset's proof chain and the events those proofs commit to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub bundle_version: u32,
+    pub asset_id: AssetId,
+    pub exported_at: DateTime<Utc>,
+    pub proofs: Vec<CapitalProof>,
+    pub events: Vec<CapitalEvent>,
+    pub event_digests: BTreeMap<String, String>,
+    pub chain_head: Option<String>,
+    pub proof_count: usize,
+    pub bundle_hash: String,
+}
+
+impl ProofBundle {
+    pub fn compute_bundle_hash(&self) -> IclResult<String> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("bundle_hash");
+        }
+        Ok(sha256_hex(canonical_json(&value).as_bytes()))
+    }
+
+    /// Check the bundle without access to the ledger that produced it
+    pub fn verify(&self) -> IclResult<()> {
+        if self.bundle_version != PROOF_BUNDLE_VERSION {
+            return Err(IclError::UnsupportedFormat(format!("proof bundle version {}", self.bundle_version)));
+        }
+
+        if self.compute_bundle_hash()? != self.bundle_hash {
+            return Err(IclError::IntegrityViolation("Proof bundle hash mismatch".into()));
+        }
+
+        if self.proofs.len() != self.proof_count {
+            return Err(IclError::IntegrityViolation(format!(
+                "Proof bundle declares {} proofs but contains {}", self.proof_count, self.proofs.len()
+            )));
+        }
+
+        // An event rewritten since it was proven, as by an owner anonymization, is checked against the
+        // latest proof committing to it
+        let latest: HashMap<EventId, Uuid> = self.proofs.iter()
+            .filter_map(|p| Some((p.event_id?, p.proof_id)))
+            .collect();
+        let mut previous_hash: Option<&String> = None;
+        for proof in &self.proofs {
+            if proof.asset_id != self.asset_id {
+                return Err(IclError::IntegrityViolation(format!(
+                    "Proof {} belongs to asset {}, not {}", proof.proof_id, proof.asset_id, self.asset_id
+                )));
+            }
+            if proof.proof_hash.as_ref() != Some(&proof.compute_hash()?) {
+                return Err(IclError::IntegrityViolation(format!("Proof {} hash does not verify", proof.proof_id)));
+            }
+            if proof.previous_proof_hash.as_ref() != previous_hash {
+                return Err(IclError::IntegrityViolation(format!(
+                    "Proof {} does not link to its predecessor", proof.proof_id
+                )));
+            }
+            // A redacted digest is carried outside the proof hash and proves nothing about the event
+            if proof.redacted.contains_key(EVENT_DIGEST_CONTENT) {
+                return Err(IclError::IntegrityViolation(format!(
+                    "Proof {} redacts its event digest, so its event cannot be verified", proof.proof_id
+                )));
+            }
+            if let Some(event_id) = proof.event_id.filter(|id| latest.get(id) == Some(&proof.proof_id)) {
+                let event = self.events.iter().find(|e| e.event_id == event_id).ok_or_else(|| {
+                    IclError::IntegrityViolation(format!("Proof {} commits to missing event {}", proof.proof_id, event_id))
+                })?;
+                let committed = proof.content.get(EVENT_DIGEST_CONTENT).and_then(|v| v.as_str()).ok_or_else(|| {
+                    IclError::IntegrityViolation(format!("Proof {} does not commit to a digest of event {}", proof.proof_id, event_id))
+                })?;
+                if committed != proof.hash_algorithm.digest_record(event)?
+                    || self.event_digests.get(&event_id.to_string()).map(String::as_str) != Some(committed) {
+                    return Err(IclError::IntegrityViolation(format!("Event {} digest mismatch", event_id)));
+                }
+            }
+            previous_hash = proof.proof_hash.as_ref();
+        }
+
+        if previous_hash != self.chain_head.as_ref() {
+            return Err(IclError::IntegrityViolation("Proof bundle chain head mismatch".into()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse and verify a serialized proof bundle, returning it only if every check passes
+pub fn verify_proof_bundle(bytes: &[u8]) -> IclResult<ProofBundle> {
+    let bundle: ProofBundle = serde_json::from_slice(bytes)?;
+    bundle.verify()?;
+    Ok(bundle)
+}
+
+/// Auditor package for a reporting period, sealed with a hash chained to the previous report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub report_id: Uuid,
+    pub generated_at: DateTime<Utc>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub asset_snapshots: Vec<IntelligenceAsset>,
+    pub depreciation_by_asset: BTreeMap<String, f64>,
+    pub total_depreciation: f64,
+    pub journal_entries: Vec<JournalEntry>,
+    pub previous_report_hash: Option<String>,
+    pub hash_algorithm: HashAlgorithm,
+    pub report_hash: String,
+}
+
+impl AuditReport {
+    pub fn builder(ledger: &IntelligenceCapitalLedger) -> AuditReportBuilder<'_> {
+        AuditReportBuilder::new(ledger)
+    }
+
+    pub fn compute_hash(&self) -> IclResult<String> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("report_hash");
+        }
+        Ok(self.hash_algorithm.digest_hex(canonical_json(&value).as_bytes()))
+    }
+
+    pub fn verify(&self) -> IclResult<bool> {
+        Ok(self.compute_hash()? == self.report_hash)
+    }
+
+    pub fn to_json(&self) -> IclResult<String> {
+        serde_json::to_string_pretty(self).map_err(IclError::from)
+    }
+
+    pub fn summary(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("Audit report {}\n", self.report_id));
+        out.push_str(&format!(
+            "Period: {} to {}\n",
+            self.period_start.to_rfc3339(),
+            self.period_end.to_rfc3339()
+        ));
+        out.push_str(&format!("Assets: {}\n", self.asset_snapshots.len()));
+        out.push_str(&format!("Journal entries: {}\n", self.journal_entries.len()));
+        out.push_str(&format!("Total depreciation: {:.2}\n", self.total_depreciation));
+        for asset in &self.asset_snapshots {
+            out.push_str(&format!(
+                "  {} owner={} status={} value={:.2} depreciation={:.2}\n",
+                asset.asset_id,
+                asset.owner,
+                asset.status,
+                asset.current_value.unwrap_or_default(),
+                self.depreciation_by_asset.get(&asset.asset_id.to_string()).copied().unwrap_or_default()
+            ));
+        }
+        out.push_str(&format!("Report hash ({}): {}\n", self.hash_algorithm, self.report_hash));
+        if let Some(previous) = &self.previous_report_hash {
+            out.push_str(&format!("Previous report hash: {}\n", previous));
+        }
+        out
+    }
+}
+
+/// Assembles an `AuditReport` from ledger state for a date range
+#[derive(Debug)]
+pub struct AuditReportBuilder<'a> {
+    ledger: &'a IntelligenceCapitalLedger,
+    period: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    asset_ids: Option<Vec<AssetId>>,
+    previous_report_hash: Option<String>,
+}
+
+impl<'a> AuditReportBuilder<'a> {
+    pub fn new(ledger: &'a IntelligenceCapitalLedger) -> Self {
+        Self {
+            ledger,
+            period: None,
+            asset_ids: None,
+            previous_report_hash: None,
+        }
+    }
+
+    pub fn period(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.period = Some((start, end));
+        self
+    }
+
+    pub fn assets(mut self, asset_ids: &[AssetId]) -> Self {
+        self.asset_ids = Some(asset_ids.to_vec());
+        self
+    }
+
+    pub fn previous_report(mut self, previous: &AuditReport) -> Self {
+        self.previous_report_hash = Some(previous.report_hash.clone());
+        self
+    }
+
+    pub fn build(self) -> IclResult<AuditReport> {
+        let (period_start, period_end) = self.period
+            .ok_or_else(|| IclError::InvalidEvent("Audit report requires a period".into()))?;
+        if period_start >= period_end {
+            return Err(IclError::InvalidDateRange {
+                start: period_start.to_rfc3339(),
+                end: period_end.to_rfc3339(),
+            });
+        }
+
+        let mut asset_snapshots: Vec<IntelligenceAsset> = match &self.asset_ids {
+            Some(ids) => ids.iter()
+                .map(|id| self.ledger.get_asset(*id).cloned().ok_or(IclError::AssetNotFound(*id)))
+                .collect::<IclResult<_>>()?,
+            None => self.ledger.assets_iter().cloned().collect(),
+        };
+        asset_snapshots.sort_by_key(|a| a.asset_id);
+
+        let in_period = |ts: DateTime<Utc>| ts >= period_start && ts < period_end;
+
+        let mut depreciation_by_asset = BTreeMap::new();
+        for asset in &asset_snapshots {
+            let amount: f64 = self.ledger.get_events_for_asset(asset.asset_id).iter()
+                .filter(|e| e.event_type == "depreciation" && in_period(e.effective_date()) && !self.ledger.is_voided(e.event_id))
+                .filter_map(|e| e.details.get("amount").and_then(|v| v.as_f64()))
+                .sum();
+            depreciation_by_asset.insert(asset.asset_id.to_string(), amount);
+        }
+        let total_depreciation = depreciation_by_asset.values().sum();
+
+        let journal_entries: Vec<JournalEntry> = asset_snapshots.iter()
+            .flat_map(|a| self.ledger.get_journal_entries_for_asset(a.asset_id))
+            .filter(|j| in_period(j.effective_date()))
+            .cloned()
+            .collect();
+
+        let mut report = AuditReport {
+            report_id: self.ledger.next_id(),
+            generated_at: self.ledger.now(),
+            period_start,
+            period_end,
+            asset_snapshots,
+            depreciation_by_asset,
+            total_depreciation,
+            journal_entries,
+            previous_report_hash: self.previous_report_hash,
+            hash_algorithm: self.ledger.hash_algorithm(),
+            report_hash: String::new(),
+        };
+        report.report_hash = report.compute_hash()?;
+        Ok(report)
+    }
+}
+
 #[derive(Debug)]
 pub struct CapitalProofGenerator<'a> {
     pub ledger: &'a IntelligenceCapitalLedger,
@@ -15,55 +274,53 @@ impl<'a> CapitalProofGenerator<'a> {
         Self { ledger }
     }
 
-    pub fn generate_asset_proof(&self, asset_id: Uuid) -> IclResult<CapitalProof> {
+    pub fn generate_asset_proof(&self, asset_id: AssetId) -> IclResult<CapitalProof> {
         let asset = self.ledger.get_asset(asset_id)
             .ok_or(IclError::AssetNotFound(asset_id))?;
         
-        let previous_hash = self.ledger.proofs.iter()
-            .filter(|p| p.asset_id == asset_id)
-            .last()
+        let previous_hash = self.ledger.latest_proof_for_asset(asset_id)
             .and_then(|p| p.proof_hash.clone());
         
-        let mut content: std::collections::HashMap<String, serde_json::Value> = std::collections::HashMap::new();
-        content.insert("asset_id".to_string(), serde_json::Value::String(asset.asset_id.to_string()));
-        content.insert("owner".to_string(), serde_json::Value::String(asset.owner.clone()));
-        content.insert("initial_value".to_string(), serde_json::json!(asset.initial_value));
-        content.insert("depreciation_method".to_string(), serde_json::Value::String(asset.depreciation_method.to_string()));
-        content.insert("useful_life_months".to_string(), serde_json::Value::Number(serde_json::Number::from(asset.useful_life_months)));
-        content.insert("status".to_string(), serde_json::Value::String(asset.status.to_string()));
-        content.insert("current_value".to_string(), serde_json::json!(asset.current_value.unwrap_or_default()));
+        let content = asset.proof_content();
 
         let mut proof = CapitalProof {
-            proof_id: Uuid::new_v4(),
+            proof_id: self.ledger.next_id(),
             asset_id,
             event_id: None,
-            timestamp: Utc::now(),
+            timestamp: self.ledger.now(),
             origin: "ICL".to_string(),
             previous_proof_hash: previous_hash,
             content,
             proof_hash: None,
+            hash_version: PROOF_HASH_VERSION,
+            hash_algorithm: self.ledger.hash_algorithm(),
+            redacted: BTreeMap::new(),
+            legacy_content_order: Vec::new(),
         };
         
-        proof.proof_hash = Some(proof.compute_hash());
+        proof.proof_hash = Some(proof.compute_hash()?);
         
         Ok(proof)
     }
 
     pub fn generate_execution_proof(
         &self,
-        asset_id: Uuid,
-        event_id: Uuid
+        asset_id: AssetId,
+        event_id: EventId
     ) -> IclResult<CapitalProof> {
         let mut proof = self.generate_asset_proof(asset_id)?;
         proof.event_id = Some(event_id);
         proof.content.insert("proof_type".to_string(), serde_json::json!("execution"));
-        proof.proof_hash = Some(proof.compute_hash());
+        if let Some(event) = self.ledger.get_event(event_id) {
+            proof.content.insert(EVENT_DIGEST_CONTENT.to_string(), serde_json::json!(proof.hash_algorithm.digest_record(event)?));
+        }
+        proof.proof_hash = Some(proof.compute_hash()?);
         Ok(proof)
     }
 
     pub fn generate_financial_outcome_proof(
         &self,
-        asset_id: Uuid,
+        asset_id: AssetId,
         start_date: &str,
         end_date: &str
     ) -> IclResult<CapitalProof> {
@@ -74,20 +331,75 @@ impl<'a> CapitalProofGenerator<'a> {
         
         let events = self.ledger.get_events_for_asset(asset_id);
         let total_depreciation: f64 = events.iter()
-            .filter(|e| e.event_type == "depreciation")
+            .filter(|e| e.event_type == "depreciation" && !self.ledger.is_voided(e.event_id))
             .filter_map(|e| e.details.get("amount").and_then(|v| v.as_f64()))
             .sum();
         proof.content.insert("total_depreciation".to_string(), serde_json::json!(total_depreciation));
         
-        proof.proof_hash = Some(proof.compute_hash());
+        proof.proof_hash = Some(proof.compute_hash()?);
         Ok(proof)
     }
 
+    pub fn export_proof_bundle(&self, asset_id: AssetId) -> IclResult<Vec<u8>> {
+        if self.ledger.get_asset(asset_id).is_none() {
+            return Err(IclError::AssetNotFound(asset_id));
+        }
+
+        let proofs: Vec<CapitalProof> = self.ledger.get_proofs_for_asset(asset_id)
+            .into_iter()
+            .cloned()
+            .collect();
+
+        // A bundle that could not verify is refused here rather than handed to an auditor; each event
+        // is checked against the latest proof committing to it
+        let mut events = Vec::new();
+        let mut event_digests = BTreeMap::new();
+        for proof in proofs.iter().rev() {
+            let Some(event_id) = proof.event_id.filter(|id| !event_digests.contains_key(&id.to_string())) else {
+                continue;
+            };
+            if !proof.content.contains_key(EVENT_DIGEST_CONTENT) {
+                return Err(IclError::UnsupportedFormat(format!(
+                    "Proof {} predates event digests, so event {} cannot be verified from a bundle", proof.proof_id, event_id
+                )));
+            }
+            let Some(event) = self.ledger.get_event(event_id) else {
+                if self.ledger.retention_purges().any(|p| p.purged_event_ids.contains(&event_id)) {
+                    return Err(IclError::IntegrityViolation(format!(
+                        "Event {} proven by proof {} was purged by retention and cannot be bundled", event_id, proof.proof_id
+                    )));
+                }
+                return Err(IclError::IntegrityViolation(format!(
+                    "Event {} proven by proof {} is not in the ledger; load the archived period holding it first",
+                    event_id, proof.proof_id
+                )));
+            };
+            event_digests.insert(event_id.to_string(), proof.hash_algorithm.digest_record(event)?);
+            events.push(event.clone());
+        }
+        events.reverse();
+
+        let mut bundle = ProofBundle {
+            bundle_version: PROOF_BUNDLE_VERSION,
+            asset_id,
+            exported_at: self.ledger.now(),
+            chain_head: proofs.last().and_then(|p| p.proof_hash.clone()),
+            proof_count: proofs.len(),
+            proofs,
+            events,
+            event_digests,
+            bundle_hash: String::new(),
+        };
+        bundle.bundle_hash = bundle.compute_bundle_hash()?;
+
+        serde_json::to_vec_pretty(&bundle).map_err(IclError::from)
+    }
+
     pub fn reconstruct_proof(&self, proof_id: Uuid) -> Option<&CapitalProof> {
-        self.ledger.proofs.iter().find(|p| p.proof_id == proof_id)
+        self.ledger.proofs().iter().find(|p| p.proof_id == proof_id)
     }
 
-    pub fn get_asset_history(&self, asset_id: Uuid) -> Vec<serde_json::Value> {
+    pub fn get_asset_history(&self, asset_id: AssetId) -> Vec<serde_json::Value> {
         let events = self.ledger.get_events_for_asset(asset_id);
         events.iter().map(|e| {
             serde_json::json!({
@@ -101,9 +413,95 @@ impl<'a> CapitalProofGenerator<'a> {
 
     pub fn verify_proof(&self, proof: &CapitalProof) -> bool {
         if let Some(stored_hash) = &proof.proof_hash {
-            let computed = proof.compute_hash();
-            return stored_hash == &computed;
+            return proof.compute_hash().is_ok_and(|computed| *stored_hash == computed);
         }
         false
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+
+    fn ledger_with_execution_proof() -> (IntelligenceCapitalLedger, AssetId, EventId) {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let event = lifecycle.utilize(asset_id, 50.0).unwrap();
+        ledger.generate_proof(asset_id, Some(event.event_id)).unwrap();
+        (ledger, asset_id, event.event_id)
+    }
+
+    fn reseal(bundle: &mut ProofBundle) {
+        bundle.bundle_hash = bundle.compute_bundle_hash().unwrap();
+    }
+
+    #[test]
+    fn test_exported_bundle_verifies() {
+        let (ledger, asset_id, event_id) = ledger_with_execution_proof();
+        let bytes = CapitalProofGenerator::new(&ledger).export_proof_bundle(asset_id).unwrap();
+        let bundle = verify_proof_bundle(&bytes).unwrap();
+        assert_eq!(bundle.events.len(), 1);
+        assert!(bundle.event_digests.contains_key(&event_id.to_string()));
+    }
+
+    #[test]
+    fn test_export_refuses_archived_events_until_loaded() {
+        let start = Utc::now();
+        let clock = std::sync::Arc::new(crate::core::clock::ManualClock::new(start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        let asset_id = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let event = lifecycle.utilize(asset_id, 50.0).unwrap();
+        ledger.generate_proof(asset_id, Some(event.event_id)).unwrap();
+        clock.advance(chrono::Duration::days(70));
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .depreciate(asset_id, start, start + chrono::Duration::days(62), 0.0, 1.0)
+            .unwrap();
+        ledger.close_period(start + chrono::Duration::days(30)).unwrap();
+        let file = ledger.archive_period(FiscalPeriod::new(start - chrono::Duration::days(1), start + chrono::Duration::days(30))).unwrap();
+
+        let err = CapitalProofGenerator::new(&ledger).export_proof_bundle(asset_id).unwrap_err();
+        assert!(err.to_string().contains("load the archived period"));
+
+        ledger.load_archive(&file).unwrap();
+        let bytes = CapitalProofGenerator::new(&ledger).export_proof_bundle(asset_id).unwrap();
+        assert!(verify_proof_bundle(&bytes).is_ok());
+    }
+
+    #[test]
+    fn test_forged_event_behind_redacted_digest_fails() {
+        let (ledger, asset_id, _) = ledger_with_execution_proof();
+        let bytes = CapitalProofGenerator::new(&ledger).export_proof_bundle(asset_id).unwrap();
+        let mut bundle: ProofBundle = serde_json::from_slice(&bytes).unwrap();
+
+        bundle.events[0].details.insert("amount".to_string(), serde_json::json!(5000.0));
+        let forged = bundle.proofs[0].hash_algorithm.digest_record(&bundle.events[0]).unwrap();
+        // Hand-built, since `redact_field` refuses the event digest
+        let proof = &mut bundle.proofs[0];
+        let committed = proof.content_digests().remove(EVENT_DIGEST_CONTENT).unwrap();
+        proof.redacted.insert(EVENT_DIGEST_CONTENT.to_string(), committed);
+        proof.content.insert(EVENT_DIGEST_CONTENT.to_string(), serde_json::json!(forged));
+        bundle.event_digests.insert(bundle.events[0].event_id.to_string(), forged);
+        assert!(bundle.proofs[0].verify_hash().unwrap());
+        reseal(&mut bundle);
+
+        let err = bundle.verify().unwrap_err();
+        assert!(err.to_string().contains("redacts its event digest"));
+    }
+
+    #[test]
+    fn test_tampered_event_fails() {
+        let (ledger, asset_id, _) = ledger_with_execution_proof();
+        let bytes = CapitalProofGenerator::new(&ledger).export_proof_bundle(asset_id).unwrap();
+        let mut bundle: ProofBundle = serde_json::from_slice(&bytes).unwrap();
+
+        bundle.events[0].details.insert("amount".to_string(), serde_json::json!(5000.0));
+        reseal(&mut bundle);
+        assert!(matches!(bundle.verify(), Err(IclError::IntegrityViolation(_))));
+    }
+}
@@ -0,0 +1,452 @@
+use std::marker::PhantomData;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+
+/// Event types recorded by the lifecycle and the integration adapter
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    Capitalization,
+    Allocation,
+    Utilization,
+    Depreciation,
+    Retirement,
+    InferenceExpense,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Capitalization => "capitalization",
+            EventKind::Allocation => "allocation",
+            EventKind::Utilization => "utilization",
+            EventKind::Depreciation => "depreciation",
+            EventKind::Retirement => "retirement",
+            EventKind::InferenceExpense => "inference_expense",
+        }
+    }
+}
+
+impl std::fmt::Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventKind::Capitalization => write!(f, "Capitalization"),
+            EventKind::Allocation => write!(f, "Allocation"),
+            EventKind::Utilization => write!(f, "Utilization"),
+            EventKind::Depreciation => write!(f, "Depreciation"),
+            EventKind::Retirement => write!(f, "Retirement"),
+            EventKind::InferenceExpense => write!(f, "InferenceExpense"),
+        }
+    }
+}
+
+/// How a query gathers candidate records before applying its remaining filters
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum QueryPlan {
+    EventIndex(EventId),
+    AssetIndex(AssetId),
+    FullScan,
+}
+
+impl std::fmt::Display for QueryPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryPlan::EventIndex(id) => write!(f, "EventIndex({})", id),
+            QueryPlan::AssetIndex(id) => write!(f, "AssetIndex({})", id),
+            QueryPlan::FullScan => write!(f, "FullScan"),
+        }
+    }
+}
+
+/// A ledger record type that `Query` can select
+pub trait QueryRecord: Sized {
+    /// Records the plan selects, in recording order
+    fn candidates(ledger: &IntelligenceCapitalLedger, plan: QueryPlan) -> Vec<&Self>;
+    fn record_id(&self) -> Uuid;
+    fn event_id(&self) -> EventId;
+    fn timestamp(&self) -> DateTime<Utc>;
+    fn amount(&self) -> f64;
+
+    fn asset_id(&self, ledger: &IntelligenceCapitalLedger) -> Option<AssetId> {
+        ledger.get_event(self.event_id()).map(|e| e.asset_id)
+    }
+
+    fn org_id<'l>(&'l self, ledger: &'l IntelligenceCapitalLedger) -> Option<&'l OrgId> {
+        ledger.get_event(self.event_id()).map(|e| &e.org_id)
+    }
+}
+
+impl QueryRecord for CapitalEvent {
+    fn candidates(ledger: &IntelligenceCapitalLedger, plan: QueryPlan) -> Vec<&Self> {
+        match plan {
+            QueryPlan::EventIndex(event_id) => ledger.get_event(event_id).into_iter().collect(),
+            QueryPlan::AssetIndex(asset_id) => ledger.get_events_for_asset(asset_id),
+            QueryPlan::FullScan => ledger.events().iter().collect(),
+        }
+    }
+
+    fn record_id(&self) -> Uuid {
+        self.event_id.into()
+    }
+
+    fn event_id(&self) -> EventId {
+        self.event_id
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.effective_date()
+    }
+
+    fn amount(&self) -> f64 {
+        self.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0)
+    }
+
+    fn asset_id(&self, _ledger: &IntelligenceCapitalLedger) -> Option<AssetId> {
+        Some(self.asset_id)
+    }
+
+    fn org_id<'l>(&'l self, _ledger: &'l IntelligenceCapitalLedger) -> Option<&'l OrgId> {
+        Some(&self.org_id)
+    }
+}
+
+impl QueryRecord for LedgerEntry {
+    fn candidates(ledger: &IntelligenceCapitalLedger, plan: QueryPlan) -> Vec<&Self> {
+        match plan {
+            QueryPlan::EventIndex(event_id) => match ledger.get_event(event_id) {
+                Some(event) => ledger.get_entries_for_asset(event.asset_id).into_iter()
+                    .filter(|e| e.event_id == event_id)
+                    .collect(),
+                None => Vec::new(),
+            },
+            QueryPlan::AssetIndex(asset_id) => ledger.get_entries_for_asset(asset_id),
+            QueryPlan::FullScan => ledger.entries().iter().collect(),
+        }
+    }
+
+    fn record_id(&self) -> Uuid {
+        self.entry_id.into()
+    }
+
+    fn event_id(&self) -> EventId {
+        self.event_id
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.effective_date()
+    }
+
+    fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    fn asset_id(&self, _ledger: &IntelligenceCapitalLedger) -> Option<AssetId> {
+        Some(self.asset_id)
+    }
+}
+
+impl QueryRecord for JournalEntry {
+    fn candidates(ledger: &IntelligenceCapitalLedger, plan: QueryPlan) -> Vec<&Self> {
+        match plan {
+            QueryPlan::EventIndex(event_id) => ledger.journal_entries_for_event(event_id),
+            QueryPlan::AssetIndex(asset_id) => ledger.get_journal_entries_for_asset(asset_id),
+            QueryPlan::FullScan => ledger.journal_entries().iter().collect(),
+        }
+    }
+
+    fn record_id(&self) -> Uuid {
+        self.entry_id.into()
+    }
+
+    fn event_id(&self) -> EventId {
+        self.event_id
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.effective_date()
+    }
+
+    fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    fn org_id<'l>(&'l self, _ledger: &'l IntelligenceCapitalLedger) -> Option<&'l OrgId> {
+        Some(&self.org_id)
+    }
+}
+
+/// Composable filter over events, ledger entries, or journal entries, e.g.
+/// `Query::events().asset(id).kind(EventKind::Depreciation).between(start, end).min_amount(100.0)`
+#[derive(Debug, Clone)]
+pub struct Query<R> {
+    org_id: Option<OrgId>,
+    asset_id: Option<AssetId>,
+    event_id: Option<EventId>,
+    event_type: Option<String>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    min_amount: Option<f64>,
+    max_amount: Option<f64>,
+    include_quarantined: bool,
+    limit: Option<usize>,
+    _record: PhantomData<fn() -> R>,
+}
+
+impl<R> Default for Query<R> {
+    fn default() -> Self {
+        Self {
+            org_id: None,
+            asset_id: None,
+            event_id: None,
+            event_type: None,
+            start: None,
+            end: None,
+            min_amount: None,
+            max_amount: None,
+            include_quarantined: false,
+            limit: None,
+            _record: PhantomData,
+        }
+    }
+}
+
+impl Query<CapitalEvent> {
+    pub fn events() -> Self {
+        Self::default()
+    }
+}
+
+impl Query<LedgerEntry> {
+    pub fn entries() -> Self {
+        Self::default()
+    }
+}
+
+impl Query<JournalEntry> {
+    pub fn journal_entries() -> Self {
+        Self::default()
+    }
+}
+
+impl<R: QueryRecord> Query<R> {
+    pub fn org(mut self, org_id: OrgId) -> Self {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn asset(mut self, asset_id: AssetId) -> Self {
+        self.asset_id = Some(asset_id);
+        self
+    }
+
+    pub fn event(mut self, event_id: EventId) -> Self {
+        self.event_id = Some(event_id);
+        self
+    }
+
+    pub fn kind(self, kind: EventKind) -> Self {
+        self.event_type(kind.as_str())
+    }
+
+    pub fn event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_type = Some(event_type.into());
+        self
+    }
+
+    /// Records effective in the half-open range `[start, end)`
+    pub fn between(self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.since(start).until(end)
+    }
+
+    pub fn since(mut self, start: DateTime<Utc>) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    pub fn until(mut self, end: DateTime<Utc>) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    pub fn min_amount(mut self, amount: f64) -> Self {
+        self.min_amount = Some(amount);
+        self
+    }
+
+    pub fn max_amount(mut self, amount: f64) -> Self {
+        self.max_amount = Some(amount);
+        self
+    }
+
+    pub fn include_quarantined(mut self) -> Self {
+        self.include_quarantined = true;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn plan(&self) -> QueryPlan {
+        match (self.event_id, self.asset_id) {
+            (Some(event_id), _) => QueryPlan::EventIndex(event_id),
+            (None, Some(asset_id)) => QueryPlan::AssetIndex(asset_id),
+            (None, None) => QueryPlan::FullScan,
+        }
+    }
+
+    /// Matching records in recording order
+    pub fn run<'l>(&self, ledger: &'l IntelligenceCapitalLedger) -> Vec<&'l R> {
+        let plan = self.plan();
+        R::candidates(ledger, plan).into_iter()
+            .filter(|r| self.matches(ledger, plan, *r))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    pub fn count(&self, ledger: &IntelligenceCapitalLedger) -> usize {
+        self.run(ledger).len()
+    }
+
+    fn matches(&self, ledger: &IntelligenceCapitalLedger, plan: QueryPlan, record: &R) -> bool {
+        if !self.include_quarantined && ledger.is_quarantined(record.record_id()) {
+            return false;
+        }
+        if self.start.is_some_and(|start| record.timestamp() < start) {
+            return false;
+        }
+        if self.end.is_some_and(|end| record.timestamp() >= end) {
+            return false;
+        }
+        if self.min_amount.is_some_and(|min| record.amount() < min) {
+            return false;
+        }
+        if self.max_amount.is_some_and(|max| record.amount() > max) {
+            return false;
+        }
+        if let Some(org_id) = &self.org_id {
+            if record.org_id(ledger) != Some(org_id) {
+                return false;
+            }
+        }
+        // The plan already guarantees whichever of these filters it was built from
+        if let Some(asset_id) = self.asset_id {
+            if !matches!(plan, QueryPlan::AssetIndex(_)) && record.asset_id(ledger) != Some(asset_id) {
+                return false;
+            }
+        }
+        if let Some(event_type) = &self.event_type {
+            let recorded = ledger.get_event(record.event_id()).map(|e| e.event_type.as_str());
+            if recorded != Some(event_type.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use chrono::Duration;
+    use crate::core::clock::{Clock, ManualClock};
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+
+    /// Two assets in different namespaces, each utilized daily for amounts 10, 20, 30
+    fn queried_ledger() -> (IntelligenceCapitalLedger, Arc<ManualClock>, [AssetId; 2]) {
+        let start = "2026-01-01T00:00:00Z".parse().unwrap();
+        let clock = Arc::new(ManualClock::new(start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        let asset_ids = [AssetId::random(), AssetId::random()];
+        for (asset_id, org) in asset_ids.iter().zip(["research", "product"]) {
+            IntelligenceCapitalLifecycle::new(&mut ledger)
+                .in_org(OrgId::new(org))
+                .capitalize(*asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12)
+                .unwrap();
+        }
+        for amount in [10.0, 20.0, 30.0] {
+            clock.advance(Duration::days(1));
+            for (asset_id, org) in asset_ids.iter().zip(["research", "product"]) {
+                IntelligenceCapitalLifecycle::new(&mut ledger).in_org(OrgId::new(org)).utilize(*asset_id, amount).unwrap();
+            }
+        }
+        (ledger, clock, asset_ids)
+    }
+
+    /// The same query answered by a full scan, to check the indexed plans against
+    fn scanned<R: QueryRecord>(query: &Query<R>, ledger: &IntelligenceCapitalLedger) -> Vec<Uuid> {
+        let plan = QueryPlan::FullScan;
+        R::candidates(ledger, plan).into_iter()
+            .filter(|r| query.matches(ledger, plan, *r))
+            .filter(|r| query.event_id.is_none_or(|event_id| r.event_id() == event_id))
+            .map(|r| r.record_id())
+            .collect()
+    }
+
+    fn ids<R: QueryRecord>(records: Vec<&R>) -> Vec<Uuid> {
+        records.into_iter().map(|r| r.record_id()).collect()
+    }
+
+    #[test]
+    fn test_plan_uses_most_selective_index() {
+        let (asset_id, event_id) = (AssetId::random(), EventId::random());
+        assert_eq!(Query::events().plan(), QueryPlan::FullScan);
+        assert_eq!(Query::events().org(OrgId::new("research")).min_amount(1.0).plan(), QueryPlan::FullScan);
+        assert_eq!(Query::entries().asset(asset_id).plan(), QueryPlan::AssetIndex(asset_id));
+        assert_eq!(Query::journal_entries().asset(asset_id).event(event_id).plan(), QueryPlan::EventIndex(event_id));
+    }
+
+    #[test]
+    fn test_combined_filters_match_full_scan() {
+        let (ledger, clock, [research, product]) = queried_ledger();
+        let day = |n: i64| clock.now() - Duration::days(3 - n);
+        let queries = [
+            Query::events().asset(research).kind(EventKind::Utilization),
+            Query::events().asset(research).kind(EventKind::Utilization).min_amount(15.0).max_amount(25.0),
+            Query::events().org(OrgId::new("product")).between(day(1), day(3)),
+            Query::events().kind(EventKind::Utilization).since(day(2)).limit(1),
+            Query::events().asset(product).org(OrgId::new("research")),
+        ];
+        let expected = [3, 1, 2, 1, 0];
+        for (query, expected) in queries.iter().zip(expected) {
+            let found = ids(query.run(&ledger));
+            assert_eq!(found.len(), expected, "{:?}", query);
+            assert_eq!(found, scanned(query, &ledger).into_iter().take(found.len()).collect::<Vec<_>>());
+        }
+
+        let amounts: Vec<f64> = Query::events().asset(product).kind(EventKind::Utilization).since(day(2))
+            .run(&ledger).into_iter().map(|e| e.amount()).collect();
+        assert_eq!(amounts, vec![20.0, 30.0]);
+    }
+
+    #[test]
+    fn test_event_index_still_applies_other_filters() {
+        let (ledger, _, [research, product]) = queried_ledger();
+        let event = Query::events().asset(research).kind(EventKind::Capitalization).run(&ledger)[0];
+        let by_event = Query::journal_entries().event(event.event_id);
+        assert_eq!(by_event.plan(), QueryPlan::EventIndex(event.event_id));
+        assert_eq!(ids(by_event.run(&ledger)), scanned(&by_event, &ledger));
+        assert!(!by_event.run(&ledger).is_empty());
+        assert!(Query::events().event(event.event_id).asset(product).run(&ledger).is_empty());
+        assert!(Query::events().event(event.event_id).kind(EventKind::Retirement).run(&ledger).is_empty());
+        let by_entry = Query::entries().event(event.event_id).asset(research);
+        assert_eq!(ids(by_entry.run(&ledger)), scanned(&by_entry, &ledger));
+    }
+
+    #[test]
+    fn test_quarantined_records_are_excluded_by_default() {
+        let (mut ledger, _, [research, _]) = queried_ledger();
+        let query = Query::events().asset(research).kind(EventKind::Utilization);
+        let quarantined = query.run(&ledger)[1].event_id;
+        ledger.quarantine_record(quarantined);
+        assert_eq!(query.count(&ledger), 2);
+        assert!(query.run(&ledger).iter().all(|e| e.event_id != quarantined));
+        assert_eq!(query.clone().include_quarantined().count(&ledger), 3);
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::{BTreeMap, HashSet};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::exporters::ChartOfAccounts;
+
+const MIN_REFERENCE_PREFIX: usize = 8;
+
+/// A posting as reported by the external general ledger
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExternalGlEntry {
+    #[serde(default)]
+    pub reference: Option<String>,
+    pub date: NaiveDate,
+    pub amount: f64,
+    #[serde(default)]
+    pub account: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// One account line of an external trial balance, debit positive
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExternalAccountBalance {
+    pub account_code: String,
+    pub balance: f64,
+}
+
+/// An ICL journal entry paired with its external counterpart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationMatch {
+    pub entry_id: EntryId,
+    pub external: ExternalGlEntry,
+    pub matched_by_reference: bool,
+}
+
+/// A reference match whose amounts disagree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmountMismatch {
+    pub entry_id: EntryId,
+    pub external: ExternalGlEntry,
+    pub icl_amount: f64,
+    pub external_amount: f64,
+    pub difference: f64,
+}
+
+/// Outcome of reconciling ICL journal entries against an external entry list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub reconciled_at: DateTime<Utc>,
+    pub matched: Vec<ReconciliationMatch>,
+    pub missing_in_icl: Vec<ExternalGlEntry>,
+    pub missing_in_gl: Vec<JournalEntry>,
+    pub amount_mismatches: Vec<AmountMismatch>,
+}
+
+impl ReconciliationReport {
+    pub fn is_reconciled(&self) -> bool {
+        self.missing_in_icl.is_empty() && self.missing_in_gl.is_empty() && self.amount_mismatches.is_empty()
+    }
+}
+
+/// ICL and external balance for one account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountBalanceComparison {
+    pub account_code: String,
+    pub icl_balance: f64,
+    pub external_balance: f64,
+    pub difference: f64,
+}
+
+/// Outcome of reconciling ICL account balances against an external trial balance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrialBalanceReport {
+    pub reconciled_at: DateTime<Utc>,
+    pub matched: Vec<AccountBalanceComparison>,
+    pub missing_in_icl: Vec<ExternalAccountBalance>,
+    pub missing_in_gl: Vec<AccountBalanceComparison>,
+    pub amount_mismatches: Vec<AccountBalanceComparison>,
+}
+
+impl TrialBalanceReport {
+    pub fn is_reconciled(&self) -> bool {
+        self.missing_in_icl.is_empty() && self.missing_in_gl.is_empty() && self.amount_mismatches.is_empty()
+    }
+}
+
+/// Matches ledger journal entries against postings or balances from an external general ledger
+#[derive(Debug)]
+pub struct Reconciler<'a> {
+    ledger: &'a IntelligenceCapitalLedger,
+    amount_tolerance: f64,
+    date_tolerance_days: i64,
+    account_level: usize,
+}
+
+impl<'a> Reconciler<'a> {
+    pub fn new(ledger: &'a IntelligenceCapitalLedger) -> Self {
+        Self { ledger, amount_tolerance: 0.005, date_tolerance_days: 0, account_level: 0 }
+    }
+
+    pub fn with_amount_tolerance(mut self, tolerance: f64) -> Self {
+        self.amount_tolerance = tolerance;
+        self
+    }
+
+    pub fn with_date_tolerance(mut self, days: i64) -> Self {
+        self.date_tolerance_days = days;
+        self
+    }
+
+    /// Level of the chart-of-accounts hierarchy trial balances are compared at; by default the
+    /// mapped accounts, with sub-account balances rolled up into them
+    pub fn with_account_level(mut self, level: usize) -> Self {
+        self.account_level = level;
+        self
+    }
+
+    /// Match external postings to journal entries, first by reference and then by amount and date
+    pub fn reconcile_entries(&self, external: &[ExternalGlEntry]) -> ReconciliationReport {
+        let entries: Vec<&JournalEntry> = self.ledger.journal_entries().iter()
+            .filter(|j| !self.ledger.is_quarantined(j.entry_id))
+            .collect();
+        let mut claimed: HashSet<EntryId> = HashSet::new();
+        let mut report = ReconciliationReport {
+            reconciled_at: self.ledger.now(),
+            matched: Vec::new(),
+            missing_in_icl: Vec::new(),
+            missing_in_gl: Vec::new(),
+            amount_mismatches: Vec::new(),
+        };
+
+        let mut unreferenced = Vec::new();
+        for posting in external {
+            let referenced = posting.reference.as_deref()
+                .and_then(|reference| self.find_by_reference(&entries, &claimed, reference));
+            match referenced {
+                Some(entry) => {
+                    claimed.insert(entry.entry_id);
+                    let external_amount = posting.amount.abs();
+                    if self.amounts_equal(entry.amount, external_amount) {
+                        report.matched.push(ReconciliationMatch {
+                            entry_id: entry.entry_id,
+                            external: posting.clone(),
+                            matched_by_reference: true,
+                        });
+                    } else {
+                        report.amount_mismatches.push(AmountMismatch {
+                            entry_id: entry.entry_id,
+                            external: posting.clone(),
+                            icl_amount: entry.amount,
+                            external_amount,
+                            difference: external_amount - entry.amount,
+                        });
+                    }
+                },
+                None => unreferenced.push(posting),
+            }
+        }
+
+        for posting in unreferenced {
+            let candidate = entries.iter()
+                .filter(|j| !claimed.contains(&j.entry_id))
+                .filter(|j| self.amounts_equal(j.amount, posting.amount.abs()))
+                .map(|j| (j, (j.effective_date().date_naive() - posting.date).num_days().abs()))
+                .filter(|(_, days)| *days <= self.date_tolerance_days)
+                .min_by_key(|(_, days)| *days)
+                .map(|(j, _)| *j);
+            match candidate {
+                Some(entry) => {
+                    claimed.insert(entry.entry_id);
+                    report.matched.push(ReconciliationMatch {
+                        entry_id: entry.entry_id,
+                        external: posting.clone(),
+                        matched_by_reference: false,
+                    });
+                },
+                None => report.missing_in_icl.push(posting.clone()),
+            }
+        }
+
+        report.missing_in_gl = entries.into_iter()
+            .filter(|j| !claimed.contains(&j.entry_id))
+            .cloned()
+            .collect();
+        report
+    }
+
+    /// Compare per-account net balances (debit positive), archived periods included, against an
+    /// external trial balance at the configured account level, translating ledger account types
+    /// to codes through the chart of accounts
+    pub fn reconcile_trial_balance(&self, chart: &ChartOfAccounts, external: &[ExternalAccountBalance]) -> TrialBalanceReport {
+        let posted = chart.posted_balances(
+            &self.ledger.posting_assets(),
+            self.ledger.journal_entries().iter().filter(|j| !self.ledger.is_quarantined(j.entry_id)),
+            self.ledger.archives().iter().flat_map(|a| a.account_balances.iter().map(|(account, balance)| (*account, *balance))),
+        );
+        let mut icl_balances: BTreeMap<String, f64> = chart.roll_up(&posted, self.account_level).into_iter()
+            .map(|line| (line.account_code, line.balance))
+            .collect();
+
+        let mut report = TrialBalanceReport {
+            reconciled_at: self.ledger.now(),
+            matched: Vec::new(),
+            missing_in_icl: Vec::new(),
+            missing_in_gl: Vec::new(),
+            amount_mismatches: Vec::new(),
+        };
+        for line in external {
+            let Some(icl_balance) = icl_balances.remove(&line.account_code) else {
+                if !self.amounts_equal(line.balance, 0.0) {
+                    report.missing_in_icl.push(line.clone());
+                }
+                continue;
+            };
+            let comparison = AccountBalanceComparison {
+                account_code: line.account_code.clone(),
+                icl_balance,
+                external_balance: line.balance,
+                difference: line.balance - icl_balance,
+            };
+            if self.amounts_equal(icl_balance, line.balance) {
+                report.matched.push(comparison);
+            } else {
+                report.amount_mismatches.push(comparison);
+            }
+        }
+        report.missing_in_gl = icl_balances.into_iter()
+            .filter(|(_, balance)| !self.amounts_equal(*balance, 0.0))
+            .map(|(account_code, icl_balance)| AccountBalanceComparison {
+                account_code,
+                icl_balance,
+                external_balance: 0.0,
+                difference: -icl_balance,
+            })
+            .collect();
+        report
+    }
+
+    fn amounts_equal(&self, a: f64, b: f64) -> bool {
+        (a - b).abs() <= self.amount_tolerance
+    }
+
+    fn find_by_reference<'e>(&self, entries: &[&'e JournalEntry], claimed: &HashSet<EntryId>, reference: &str) -> Option<&'e JournalEntry> {
+        let reference = reference.trim().replace('-', "").to_lowercase();
+        if reference.len() < MIN_REFERENCE_PREFIX {
+            return None;
+        }
+        let mut candidates = entries.iter()
+            .filter(|j| !claimed.contains(&j.entry_id))
+            .filter(|j| {
+                j.entry_id.as_uuid().simple().to_string().starts_with(&reference)
+                    || j.event_id.as_uuid().simple().to_string().starts_with(&reference)
+            });
+        let first = candidates.next()?;
+        if candidates.next().is_some() {
+            return None;
+        }
+        Some(*first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+
+    #[test]
+    fn test_reconcile_entries_buckets() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let first = AssetId::random();
+        let second = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(first, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.capitalize(second, "Team B", 800.0, DepreciationMethod::Linear, 12).unwrap();
+        lifecycle.capitalize(AssetId::random(), "Team C", 500.0, DepreciationMethod::Linear, 12).unwrap();
+
+        let today = Utc::now().date_naive();
+        let first_entry = &ledger.journal_entries()[0];
+        let external = vec![
+            ExternalGlEntry {
+                reference: Some(first_entry.entry_id.as_uuid().simple().to_string()[..8].to_string()),
+                date: today,
+                amount: 1200.0,
+                account: None,
+                description: None,
+            },
+            ExternalGlEntry { reference: None, date: today, amount: -800.0, account: None, description: None },
+            ExternalGlEntry { reference: None, date: today, amount: 42.0, account: None, description: None },
+        ];
+
+        let report = Reconciler::new(&ledger).reconcile_entries(&external);
+        assert_eq!(report.matched.len(), 2);
+        assert!(report.matched[0].matched_by_reference);
+        assert!(!report.matched[1].matched_by_reference);
+        assert_eq!(report.missing_in_icl.len(), 1);
+        assert_eq!(report.missing_in_gl.len(), 1);
+        assert!((report.missing_in_gl[0].amount - 500.0).abs() < 1e-9);
+        assert!(!report.is_reconciled());
+    }
+}
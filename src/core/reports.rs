@@ -0,0 +1,1064 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::ledger::{AssetPosition, IntelligenceCapitalLedger, UNCATEGORIZED};
+use crate::core::depreciation::months_between;
+use crate::core::exporters::{csv_field, ChartOfAccounts, TrialBalanceLine};
+use crate::core::formatting::NumberFormat;
+use crate::core::integrity::VALUE_TOLERANCE;
+use crate::core::licensing::REVENUE_EVENT;
+use crate::core::obligations::OBLIGATION_EVENT;
+use crate::core::intercompany::INTERCOMPANY_DISPOSAL_EVENT;
+use crate::core::pools::{pool_allocations, pool_share, POOL_DEPRECIATION_EVENT, POOL_DISPOSAL_EVENT};
+use crate::core::error::*;
+
+/// Event type of capitalized improvements to an existing asset
+pub const IMPROVEMENT_EVENT: &str = "improvement";
+
+/// Event type of write-downs below depreciated book value
+pub const IMPAIRMENT_EVENT: &str = "impairment";
+
+/// Report rendered as `json` or `csv`, with `csv` amounts written in a `NumberFormat`
+pub trait ReportExport: Serialize {
+    fn write_csv(&self, number_format: &NumberFormat) -> String;
+
+    fn export_with(&self, format: &str, number_format: &NumberFormat) -> IclResult<String> {
+        match format {
+            "json" => serde_json::to_string_pretty(self).map_err(IclError::from),
+            "csv" => Ok(self.write_csv(number_format)),
+            _ => Err(IclError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    fn export(&self, format: &str) -> IclResult<String> {
+        self.export_with(format, &NumberFormat::plain())
+    }
+}
+
+/// One line of the fixed-asset register
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetRegisterRow {
+    pub asset_id: AssetId,
+    pub owner: OwnerId,
+    pub owner_name: String,
+    pub category: Option<String>,
+    pub status: AssetStatus,
+    pub depreciation_method: DepreciationMethod,
+    pub created_at: DateTime<Utc>,
+    pub cost: f64,
+    pub additions: f64,
+    pub accumulated_depreciation: f64,
+    pub net_book_value: f64,
+    pub useful_life_months: i32,
+    pub remaining_life_months: i32,
+}
+
+/// Fixed-asset register: every asset that existed at `as_of` with its cost and valuation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetRegister {
+    pub as_of: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub rows: Vec<AssetRegisterRow>,
+    pub total_cost: f64,
+    pub total_additions: f64,
+    pub total_accumulated_depreciation: f64,
+    pub total_net_book_value: f64,
+}
+
+impl ReportExport for AssetRegister {
+    fn write_csv(&self, number_format: &NumberFormat) -> String {
+        let money = |value: f64| csv_field(&number_format.format(value));
+        let mut csv = String::from(
+            "asset_id,owner,owner_name,category,status,depreciation_method,created_at,cost,additions,\
+             accumulated_depreciation,net_book_value,useful_life_months,remaining_life_months\n",
+        );
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                row.asset_id,
+                csv_field(row.owner.as_str()),
+                csv_field(&row.owner_name),
+                csv_field(row.category.as_deref().unwrap_or_default()),
+                row.status,
+                row.depreciation_method,
+                row.created_at.to_rfc3339(),
+                money(row.cost),
+                money(row.additions),
+                money(row.accumulated_depreciation),
+                money(row.net_book_value),
+                row.useful_life_months,
+                row.remaining_life_months,
+            ));
+        }
+        csv
+    }
+}
+
+/// Length of the buckets in period reports
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum PeriodGranularity {
+    #[default]
+    Month,
+    Quarter,
+}
+
+impl std::fmt::Display for PeriodGranularity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PeriodGranularity::Month => write!(f, "Month"),
+            PeriodGranularity::Quarter => write!(f, "Quarter"),
+        }
+    }
+}
+
+/// Dimension report rows are grouped by
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ReportGrouping {
+    Owner,
+    Category,
+    Asset,
+    Dimension(String),
+}
+
+impl std::fmt::Display for ReportGrouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportGrouping::Owner => write!(f, "Owner"),
+            ReportGrouping::Category => write!(f, "Category"),
+            ReportGrouping::Asset => write!(f, "Asset"),
+            ReportGrouping::Dimension(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// One reporting bucket, clipped to the report range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportPeriod {
+    pub label: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Depreciation expense of one group, aligned with the report's periods
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepreciationPeriodRow {
+    pub group: String,
+    pub amounts: Vec<f64>,
+    pub total: f64,
+}
+
+/// Depreciation expense bucketed by period and grouped by a reporting dimension
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepreciationByPeriod {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub granularity: PeriodGranularity,
+    pub group_by: ReportGrouping,
+    pub periods: Vec<ReportPeriod>,
+    pub rows: Vec<DepreciationPeriodRow>,
+    pub period_totals: Vec<f64>,
+    pub total: f64,
+}
+
+impl ReportExport for DepreciationByPeriod {
+    fn write_csv(&self, number_format: &NumberFormat) -> String {
+        let money = |value: f64| csv_field(&number_format.format(value));
+        let mut csv = csv_field(&self.group_by.to_string().to_lowercase());
+        for period in &self.periods {
+            csv.push(',');
+            csv.push_str(&period.label);
+        }
+        csv.push_str(",total\n");
+        for row in &self.rows {
+            csv.push_str(&csv_field(&row.group));
+            for amount in &row.amounts {
+                csv.push(',');
+                csv.push_str(&money(*amount));
+            }
+            csv.push_str(&format!(",{}\n", money(row.total)));
+        }
+        csv
+    }
+}
+
+/// Book value movements over a period; `closing` is always `opening` plus the movements
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct RollforwardMovements {
+    pub opening: f64,
+    pub additions: f64,
+    pub improvements: f64,
+    pub depreciation: f64,
+    pub impairments: f64,
+    pub disposals: f64,
+    pub closing: f64,
+}
+
+impl RollforwardMovements {
+    fn add(&mut self, other: &RollforwardMovements) {
+        self.opening += other.opening;
+        self.additions += other.additions;
+        self.improvements += other.improvements;
+        self.depreciation += other.depreciation;
+        self.impairments += other.impairments;
+        self.disposals += other.disposals;
+        self.closing += other.closing;
+    }
+}
+
+/// Rollforward of one asset, with the events behind its movement lines
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollforwardLine {
+    pub asset_id: AssetId,
+    pub movements: RollforwardMovements,
+    pub recorded_closing: f64,
+    pub difference: f64,
+    pub event_ids: Vec<EventId>,
+}
+
+/// Opening-to-closing book value movement for `[from, to)`, per asset and in aggregate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapitalRollforward {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub lines: Vec<RollforwardLine>,
+    pub total: RollforwardMovements,
+}
+
+impl CapitalRollforward {
+    pub fn is_reconciled(&self) -> bool {
+        self.lines.iter().all(|l| l.difference.abs() <= VALUE_TOLERANCE)
+    }
+}
+
+impl ReportExport for CapitalRollforward {
+    fn write_csv(&self, number_format: &NumberFormat) -> String {
+        let money = |value: f64| csv_field(&number_format.format(value));
+        let mut csv = String::from(
+            "asset_id,opening,additions,improvements,depreciation,impairments,disposals,closing,\
+             recorded_closing,difference\n",
+        );
+        let row = |id: &str, m: &RollforwardMovements, recorded: f64, difference: f64| format!(
+            "{},{},{},{},{},{},{},{},{},{}\n",
+            id, money(m.opening), money(m.additions), money(m.improvements), money(m.depreciation),
+            money(m.impairments), money(m.disposals), money(m.closing), money(recorded), money(difference)
+        );
+        for line in &self.lines {
+            csv.push_str(&row(&line.asset_id.to_string(), &line.movements, line.recorded_closing, line.difference));
+        }
+        let recorded: f64 = self.lines.iter().map(|l| l.recorded_closing).sum();
+        csv.push_str(&row("total", &self.total, recorded, recorded - self.total.closing));
+        csv
+    }
+}
+
+/// Key for assets with no value for the requested dimension
+pub const UNASSIGNED: &str = "unassigned";
+
+/// Totals for one value of a reporting dimension
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionAggregate {
+    pub key: String,
+    pub asset_count: usize,
+    pub book_value: f64,
+    pub period_depreciation: f64,
+    pub utilization: f64,
+}
+
+/// Book value, depreciation, and utilization for `[from, to)` grouped by one dimension, ordered by key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionReport {
+    pub dimension: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub rows: Vec<DimensionAggregate>,
+}
+
+impl ReportExport for DimensionReport {
+    fn write_csv(&self, number_format: &NumberFormat) -> String {
+        let money = |value: f64| csv_field(&number_format.format(value));
+        let mut csv = format!("{},asset_count,book_value,period_depreciation,utilization\n", csv_field(&self.dimension));
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                csv_field(&row.key), row.asset_count, money(row.book_value), money(row.period_depreciation),
+                csv_field(&number_format.format_number(row.utilization, 2))
+            ));
+        }
+        csv
+    }
+}
+
+/// Service delivered by one asset over a period and what it cost per unit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UtilizationRow {
+    pub asset_id: AssetId,
+    pub owner: OwnerId,
+    pub usage: Usage,
+    pub inference_cost: f64,
+    pub depreciation: f64,
+    pub cost_per_1k_tokens: Option<f64>,
+    pub cost_per_request: Option<f64>,
+    pub cost_per_gpu_hour: Option<f64>,
+}
+
+/// Utilization and unit economics of every asset used in `[from, to)`, ordered by asset ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UtilizationReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub rows: Vec<UtilizationRow>,
+    pub total_usage: Usage,
+    pub total_cost: f64,
+}
+
+impl ReportExport for UtilizationReport {
+    fn write_csv(&self, number_format: &NumberFormat) -> String {
+        let money = |value: f64| csv_field(&number_format.format(value));
+        let per_unit = |value: Option<f64>| {
+            value.map(|v| csv_field(&number_format.clone().with_decimals(6).format(v))).unwrap_or_default()
+        };
+        let mut csv = String::from(
+            "asset_id,owner,tokens,requests,gpu_hours,inference_cost,depreciation,\
+             cost_per_1k_tokens,cost_per_request,cost_per_gpu_hour\n",
+        );
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                row.asset_id, csv_field(row.owner.as_str()), row.usage.tokens, row.usage.requests,
+                csv_field(&number_format.format_number(row.usage.gpu_hours, 2)), money(row.inference_cost),
+                money(row.depreciation),
+                per_unit(row.cost_per_1k_tokens), per_unit(row.cost_per_request), per_unit(row.cost_per_gpu_hour)
+            ));
+        }
+        csv
+    }
+}
+
+/// Revenue one asset earned over a period against the costs it carried
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetReturnRow {
+    pub asset_id: AssetId,
+    pub owner: OwnerId,
+    pub revenue: f64,
+    pub inference_cost: f64,
+    pub obligation_expense: f64,
+    pub depreciation: f64,
+    pub impairment: f64,
+    pub net_return: f64,
+    pub cost_basis: f64,
+    pub roi: Option<f64>,
+}
+
+/// Revenue, costs, and return on investment of every asset active in `[from, to)`, ordered by
+/// asset ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetReturnReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub rows: Vec<AssetReturnRow>,
+    pub total_revenue: f64,
+    pub total_net_return: f64,
+}
+
+impl ReportExport for AssetReturnReport {
+    fn write_csv(&self, number_format: &NumberFormat) -> String {
+        let money = |value: f64| csv_field(&number_format.format(value));
+        let mut csv = String::from(
+            "asset_id,owner,revenue,inference_cost,obligation_expense,depreciation,impairment,net_return,cost_basis,roi\n",
+        );
+        for row in &self.rows {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                row.asset_id, csv_field(row.owner.as_str()), money(row.revenue), money(row.inference_cost),
+                money(row.obligation_expense), money(row.depreciation), money(row.impairment),
+                money(row.net_return), money(row.cost_basis),
+                row.roi.map(|roi| csv_field(&number_format.format_number(roi, 6))).unwrap_or_default()
+            ));
+        }
+        csv
+    }
+}
+
+/// Default month boundaries of aging buckets: 0–6, 6–12, 12–24, 24–36, and 36+ months
+pub const AGING_BUCKET_BOUNDS: [i32; 4] = [6, 12, 24, 36];
+
+/// Assets whose age or remaining life falls in `[from_months, to_months)`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgingBucket {
+    pub label: String,
+    pub from_months: i32,
+    pub to_months: Option<i32>,
+    pub asset_count: usize,
+    pub book_value: f64,
+    pub asset_ids: Vec<AssetId>,
+}
+
+/// Non-retired assets at `as_of` bucketed by months since capitalization and by remaining useful life
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetAgingReport {
+    pub as_of: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub by_age: Vec<AgingBucket>,
+    pub by_remaining_life: Vec<AgingBucket>,
+    pub total_book_value: f64,
+}
+
+impl ReportExport for AssetAgingReport {
+    fn write_csv(&self, number_format: &NumberFormat) -> String {
+        let mut csv = String::from("basis,bucket,from_months,to_months,asset_count,book_value\n");
+        for (basis, buckets) in [("age", &self.by_age), ("remaining_life", &self.by_remaining_life)] {
+            for bucket in buckets {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{}\n",
+                    basis,
+                    bucket.label,
+                    bucket.from_months,
+                    bucket.to_months.map(|m| m.to_string()).unwrap_or_default(),
+                    bucket.asset_count,
+                    csv_field(&number_format.format(bucket.book_value)),
+                ));
+            }
+        }
+        csv
+    }
+}
+
+/// Account balances at `as_of` at one level of the chart-of-accounts hierarchy, ordered by code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrialBalance {
+    pub as_of: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub level: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<String>,
+    pub lines: Vec<TrialBalanceLine>,
+    pub total_debits: f64,
+    pub total_credits: f64,
+}
+
+impl ReportExport for TrialBalance {
+    fn write_csv(&self, number_format: &NumberFormat) -> String {
+        let mut csv = String::new();
+        if let Some(dimension) = &self.dimension {
+            csv.push_str(&csv_field(dimension));
+            csv.push(',');
+        }
+        csv.push_str("account_code,account_name,parent_code,level,balance\n");
+        for line in &self.lines {
+            if self.dimension.is_some() {
+                csv.push_str(&csv_field(line.group.as_deref().unwrap_or_default()));
+                csv.push(',');
+            }
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                csv_field(&line.account_code),
+                csv_field(&line.account_name),
+                csv_field(line.parent_code.as_deref().unwrap_or_default()),
+                line.level,
+                csv_field(&number_format.format(line.balance)),
+            ));
+        }
+        csv
+    }
+}
+
+pub(crate) fn book_value_change(event: &CapitalEvent, asset_id: AssetId) -> Option<f64> {
+    let amount = |key: &str| event.details.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+    let change = match event.event_type.as_str() {
+        "capitalization" => amount("amount"),
+        IMPROVEMENT_EVENT => amount("amount"),
+        "depreciation" => -amount("amount"),
+        IMPAIRMENT_EVENT => -amount("amount"),
+        "retirement" => -amount("retired_value"),
+        INTERCOMPANY_DISPOSAL_EVENT => -amount("carrying_value"),
+        POOL_DISPOSAL_EVENT => -amount("amount"),
+        POOL_DEPRECIATION_EVENT => -pool_share(event, asset_id)?,
+        _ => return None,
+    };
+    Some(change)
+}
+
+/// Builds finance reports from ledger records
+#[derive(Debug)]
+pub struct ReportGenerator<'a> {
+    ledger: &'a IntelligenceCapitalLedger,
+    granularity: PeriodGranularity,
+    aging_bounds: Vec<i32>,
+    org_id: Option<OrgId>,
+}
+
+impl<'a> ReportGenerator<'a> {
+    pub fn new(ledger: &'a IntelligenceCapitalLedger) -> Self {
+        Self {
+            ledger,
+            granularity: PeriodGranularity::default(),
+            aging_bounds: AGING_BUCKET_BOUNDS.to_vec(),
+            org_id: None,
+        }
+    }
+
+    pub fn for_org(mut self, org_id: OrgId) -> Self {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    fn in_scope(&self, org_id: &OrgId) -> bool {
+        self.org_id.as_ref().is_none_or(|scope| scope == org_id)
+    }
+
+    fn assets(&self) -> impl Iterator<Item = &'a IntelligenceAsset> + '_ {
+        self.ledger.assets_iter().filter(|a| self.in_scope(&a.org_id))
+    }
+
+    pub fn with_granularity(mut self, granularity: PeriodGranularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    pub fn with_aging_buckets(mut self, bounds: &[i32]) -> Self {
+        let mut bounds: Vec<i32> = bounds.iter().copied().filter(|b| *b > 0).collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+        self.aging_bounds = bounds;
+        self
+    }
+
+    /// Register of every asset created at or before `as_of`, valued from the events effective
+    /// up to that instant and ordered by creation time
+    pub fn asset_register(&self, as_of: DateTime<Utc>) -> AssetRegister {
+        let mut rows: Vec<AssetRegisterRow> = self.assets()
+            .filter_map(|asset| {
+                let position = self.ledger.asset_position(asset.asset_id, as_of)?;
+                let additions: f64 = self.ledger.get_events_for_asset(asset.asset_id).iter()
+                    .filter(|e| e.event_type == IMPROVEMENT_EVENT && e.effective_date() <= as_of)
+                    .filter_map(|e| e.details.get("amount").and_then(|v| v.as_f64()))
+                    // An empty f64 sum is -0.0, which would export as "-0.00"
+                    .fold(0.0, |total, amount| total + amount)
+                    + self.ledger.archived_summaries(asset.asset_id, as_of)
+                        .fold(0.0, |total, summary| total + summary.improvements);
+                let remaining_life_months = match position.status {
+                    AssetStatus::Active => (asset.useful_life_months - months_between(asset.created_at, as_of)).max(0),
+                    AssetStatus::Depreciated | AssetStatus::Retired => 0,
+                };
+                Some(AssetRegisterRow {
+                    asset_id: asset.asset_id,
+                    owner_name: self.ledger.owners().display_name(&position.owner).to_string(),
+                    owner: position.owner,
+                    category: asset.category.clone(),
+                    status: position.status,
+                    depreciation_method: asset.depreciation_method,
+                    created_at: asset.created_at,
+                    cost: asset.initial_value,
+                    additions,
+                    accumulated_depreciation: position.accumulated_depreciation,
+                    net_book_value: position.book_value,
+                    useful_life_months: asset.useful_life_months,
+                    remaining_life_months,
+                })
+            })
+            .collect();
+        rows.sort_by_key(|row| (row.created_at, row.asset_id));
+
+        AssetRegister {
+            as_of,
+            generated_at: self.ledger.now(),
+            total_cost: rows.iter().map(|r| r.cost).sum(),
+            total_additions: rows.iter().map(|r| r.additions).sum(),
+            total_accumulated_depreciation: rows.iter().map(|r| r.accumulated_depreciation).sum(),
+            total_net_book_value: rows.iter().map(|r| r.net_book_value).sum(),
+            rows,
+        }
+    }
+    /// Depreciation expense for `[from, to)` by period and group
+    pub fn depreciation_by_period(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        group_by: ReportGrouping
+    ) -> IclResult<DepreciationByPeriod> {
+        if from >= to {
+            return Err(IclError::InvalidDateRange {
+                start: from.to_rfc3339(),
+                end: to.to_rfc3339(),
+            });
+        }
+        self.ledger.ensure_unarchived(from, to)?;
+        let periods = report_periods(from, to, self.granularity)?;
+
+        let mut groups: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+        let depreciation = self.ledger.events_of_type("depreciation")
+            .chain(self.ledger.events_of_type(POOL_DEPRECIATION_EVENT))
+            .filter(|e| !self.ledger.is_quarantined(e.event_id) && !self.ledger.is_voided(e.event_id))
+            .filter(|e| self.in_scope(&e.org_id));
+        for event in depreciation {
+            let date_detail = |key: &str| event.details.get(key)
+                .and_then(|v| v.as_str())
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|d| d.with_timezone(&Utc));
+            // Share of the event's expense falling in each period
+            let weights: Vec<f64> = match (date_detail("start_date"), date_detail("end_date")) {
+                (Some(start), Some(end)) if end > start => {
+                    let covered = (end - start).num_seconds() as f64;
+                    periods.iter()
+                        .map(|p| (end.min(p.end) - start.max(p.start)).num_seconds().max(0) as f64 / covered)
+                        .collect()
+                },
+                _ => periods.iter()
+                    .map(|p| if event.effective_date() >= p.start && event.effective_date() < p.end { 1.0 } else { 0.0 })
+                    .collect(),
+            };
+            if weights.iter().all(|weight| *weight == 0.0) {
+                continue;
+            }
+
+            // Pool depreciation is reported against each member by its share
+            let charges: Vec<(AssetId, f64)> = if event.event_type == POOL_DEPRECIATION_EVENT {
+                pool_allocations(event).collect()
+            } else {
+                vec![(event.asset_id, event.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0))]
+            };
+            for (asset_id, amount) in charges {
+                let group = self.group_key(asset_id, event, &group_by);
+                let amounts = groups.entry(group).or_insert_with(|| vec![0.0; periods.len()]);
+                for (total, weight) in amounts.iter_mut().zip(&weights) {
+                    *total += weight * amount;
+                }
+            }
+        }
+
+        let rows: Vec<DepreciationPeriodRow> = groups.into_iter()
+            .map(|(group, amounts)| DepreciationPeriodRow { group, total: amounts.iter().sum(), amounts })
+            .collect();
+        let period_totals: Vec<f64> = (0..periods.len())
+            .map(|i| rows.iter().map(|r| r.amounts[i]).sum())
+            .collect();
+        Ok(DepreciationByPeriod {
+            from,
+            to,
+            granularity: self.granularity,
+            group_by,
+            total: period_totals.iter().sum(),
+            periods,
+            rows,
+            period_totals,
+        })
+    }
+
+    /// Roll each asset's book value forward from `from` to `to` using the recorded events, and
+    /// compare the result with the book value the ledger holds at the end of the period
+    pub fn capital_rollforward(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> IclResult<CapitalRollforward> {
+        if from >= to {
+            return Err(IclError::InvalidDateRange {
+                start: from.to_rfc3339(),
+                end: to.to_rfc3339(),
+            });
+        }
+
+        self.ledger.ensure_unarchived(from, to)?;
+
+        let mut lines = Vec::new();
+        let mut total = RollforwardMovements::default();
+        let mut assets: Vec<&IntelligenceAsset> = self.assets().filter(|a| a.created_at < to).collect();
+        assets.sort_by_key(|a| (a.created_at, a.asset_id));
+        for asset in assets {
+            let mut movements = RollforwardMovements::default();
+            let mut event_ids = Vec::new();
+            movements.opening = self.ledger.archived_summaries(asset.asset_id, from)
+                .map(|summary| summary.book_value_change)
+                .sum();
+            let events = self.ledger.valuation_events(asset.asset_id);
+            let reported = events.iter()
+                .filter(|e| e.effective_date() < to && !self.ledger.is_quarantined(e.event_id) && !self.ledger.is_voided(e.event_id));
+            for event in reported {
+                let Some(change) = book_value_change(event, asset.asset_id) else {
+                    continue;
+                };
+                if event.effective_date() < from {
+                    movements.opening += change;
+                    continue;
+                }
+                match event.event_type.as_str() {
+                    "capitalization" => movements.additions += change,
+                    IMPROVEMENT_EVENT => movements.improvements += change,
+                    "depreciation" | POOL_DEPRECIATION_EVENT => movements.depreciation -= change,
+                    IMPAIRMENT_EVENT => movements.impairments -= change,
+                    _ => movements.disposals -= change,
+                }
+                event_ids.push(event.event_id);
+            }
+            movements.closing = movements.opening + movements.additions + movements.improvements
+                - movements.depreciation - movements.impairments - movements.disposals;
+
+            let recorded_closing = self.ledger.asset_position(asset.asset_id, to - Duration::nanoseconds(1))
+                .map_or(0.0, |p| p.book_value);
+            total.add(&movements);
+            lines.push(RollforwardLine {
+                asset_id: asset.asset_id,
+                movements,
+                recorded_closing,
+                difference: recorded_closing - movements.closing,
+                event_ids,
+            });
+        }
+
+        Ok(CapitalRollforward { from, to, generated_at: self.ledger.now(), lines, total })
+    }
+
+    /// Aggregate by the owner each asset had at the end of the period
+    pub fn by_owner(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> IclResult<DimensionReport> {
+        self.aggregate("owner", None, from, to, |position, _| position.owner.to_string())
+    }
+
+    /// Aggregate by a dimension such as `cost_center`
+    pub fn by_dimension(&self, dimension: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> IclResult<DimensionReport> {
+        self.aggregate(dimension, Some(dimension), from, to, |_, asset| {
+            asset.dimensions.get(dimension).cloned().unwrap_or_else(|| UNASSIGNED.to_string())
+        })
+    }
+
+    /// Usage per asset over `[from, to)` with the inference cost and depreciation it carried,
+    /// and the resulting cost per token, request, and GPU-hour
+    pub fn utilization(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> IclResult<UtilizationReport> {
+        if from >= to {
+            return Err(IclError::InvalidDateRange {
+                start: from.to_rfc3339(),
+                end: to.to_rfc3339(),
+            });
+        }
+        self.ledger.ensure_unarchived(from, to)?;
+
+        let mut rows = Vec::new();
+        let mut total_usage = Usage::default();
+        let mut total_cost = 0.0;
+        let mut assets: Vec<&IntelligenceAsset> = self.assets().collect();
+        assets.sort_by_key(|a| a.asset_id);
+        for asset in assets {
+            let mut usage = Usage::default();
+            let (mut inference_cost, mut depreciation) = (0.0, 0.0);
+            let in_period = self.ledger.get_events_for_asset(asset.asset_id).into_iter()
+                .filter(|e| e.effective_date() >= from && e.effective_date() < to)
+                .filter(|e| !self.ledger.is_quarantined(e.event_id) && !self.ledger.is_voided(e.event_id));
+            for event in in_period {
+                let amount = event.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                match event.event_type.as_str() {
+                    "utilization" => {
+                        inference_cost += amount;
+                        usage.add(&Usage::from_details(&event.details));
+                    },
+                    "depreciation" => depreciation += amount,
+                    _ => {},
+                }
+            }
+            if inference_cost == 0.0 && usage.is_empty() {
+                continue;
+            }
+
+            let cost = inference_cost + depreciation;
+            let per = |quantity: f64| (quantity > 0.0).then(|| cost / quantity);
+            total_usage.add(&usage);
+            total_cost += cost;
+            rows.push(UtilizationRow {
+                asset_id: asset.asset_id,
+                owner: asset.owner.clone(),
+                cost_per_1k_tokens: per(usage.tokens as f64 / 1000.0),
+                cost_per_request: per(usage.requests as f64),
+                cost_per_gpu_hour: per(usage.gpu_hours),
+                usage,
+                inference_cost,
+                depreciation,
+            });
+        }
+
+        Ok(UtilizationReport { from, to, generated_at: self.ledger.now(), rows, total_usage, total_cost })
+    }
+
+    /// Revenue per asset over `[from, to)` against the inference cost, obligation expense,
+    /// depreciation, and impairment it carried, with the return on the asset's capitalized cost
+    pub fn asset_returns(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> IclResult<AssetReturnReport> {
+        if from >= to {
+            return Err(IclError::InvalidDateRange {
+                start: from.to_rfc3339(),
+                end: to.to_rfc3339(),
+            });
+        }
+        self.ledger.ensure_unarchived(from, to)?;
+
+        let mut rows = Vec::new();
+        let (mut total_revenue, mut total_net_return) = (0.0, 0.0);
+        let mut assets: Vec<&IntelligenceAsset> = self.assets().collect();
+        assets.sort_by_key(|a| a.asset_id);
+        for asset in assets {
+            let (mut revenue, mut inference_cost, mut obligation_expense) = (0.0, 0.0, 0.0);
+            let (mut depreciation, mut impairment) = (0.0, 0.0);
+            let mut active = false;
+            let in_period = self.ledger.get_events_for_asset(asset.asset_id).into_iter()
+                .filter(|e| e.effective_date() >= from && e.effective_date() < to)
+                .filter(|e| !self.ledger.is_quarantined(e.event_id) && !self.ledger.is_voided(e.event_id));
+            for event in in_period {
+                let amount = event.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                match event.event_type.as_str() {
+                    REVENUE_EVENT => revenue += amount,
+                    "utilization" | "inference_expense" => inference_cost += amount,
+                    OBLIGATION_EVENT => obligation_expense += amount,
+                    "depreciation" => depreciation += amount,
+                    IMPAIRMENT_EVENT => impairment += amount,
+                    _ => continue,
+                }
+                active = true;
+            }
+            if !active {
+                continue;
+            }
+
+            let net_return = revenue - inference_cost - obligation_expense - depreciation - impairment;
+            let cost_basis = asset.initial_value;
+            total_revenue += revenue;
+            total_net_return += net_return;
+            rows.push(AssetReturnRow {
+                asset_id: asset.asset_id,
+                owner: asset.owner.clone(),
+                revenue,
+                inference_cost,
+                obligation_expense,
+                depreciation,
+                impairment,
+                net_return,
+                cost_basis,
+                roi: (cost_basis > 0.0).then(|| net_return / cost_basis),
+            });
+        }
+
+        Ok(AssetReturnReport { from, to, generated_at: self.ledger.now(), rows, total_revenue, total_net_return })
+    }
+
+    fn aggregate(
+        &self,
+        dimension: &str,
+        posting_dimension: Option<&str>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        key: impl Fn(&AssetPosition, &IntelligenceAsset) -> String
+    ) -> IclResult<DimensionReport> {
+        if from >= to {
+            return Err(IclError::InvalidDateRange {
+                start: from.to_rfc3339(),
+                end: to.to_rfc3339(),
+            });
+        }
+        self.ledger.ensure_unarchived(from, to)?;
+
+        let end = to - Duration::nanoseconds(1);
+        let mut groups: BTreeMap<String, DimensionAggregate> = BTreeMap::new();
+        for asset in self.assets() {
+            let Some(position) = self.ledger.asset_position(asset.asset_id, end) else {
+                continue;
+            };
+            let key = key(&position, asset);
+            let row = dimension_row(&mut groups, &key);
+            row.asset_count += 1;
+            row.book_value += position.book_value;
+
+            let in_period = self.ledger.get_events_for_asset(asset.asset_id).into_iter()
+                .filter(|e| e.effective_date() >= from && e.effective_date() < to)
+                .filter(|e| !self.ledger.is_quarantined(e.event_id) && !self.ledger.is_voided(e.event_id));
+            for event in in_period {
+                let amount = event.details.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let posted_under = posting_dimension.and_then(|name| event.dimensions.get(name)).unwrap_or(&key);
+                match event.event_type.as_str() {
+                    "depreciation" => dimension_row(&mut groups, posted_under).period_depreciation += amount,
+                    "utilization" => dimension_row(&mut groups, posted_under).utilization += amount,
+                    _ => {},
+                }
+            }
+        }
+
+        Ok(DimensionReport {
+            dimension: dimension.to_string(),
+            from,
+            to,
+            rows: groups.into_values().collect(),
+        })
+    }
+
+    /// Aging of every asset not retired at `as_of`, by age and by remaining useful life
+    pub fn asset_aging(&self, as_of: DateTime<Utc>) -> AssetAgingReport {
+        let mut by_age = self.aging_buckets();
+        let mut by_remaining_life = self.aging_buckets();
+        let mut assets: Vec<&IntelligenceAsset> = self.assets().collect();
+        assets.sort_by_key(|a| (a.created_at, a.asset_id));
+
+        for asset in assets {
+            let Some(position) = self.ledger.asset_position(asset.asset_id, as_of) else {
+                continue;
+            };
+            let age = months_between(asset.created_at, as_of);
+            let remaining = match position.status {
+                AssetStatus::Active => (asset.useful_life_months - age).max(0),
+                AssetStatus::Depreciated => 0,
+                AssetStatus::Retired => continue,
+            };
+            for (buckets, months) in [(&mut by_age, age), (&mut by_remaining_life, remaining)] {
+                let index = self.aging_bounds.iter().take_while(|b| months >= **b).count();
+                let bucket = &mut buckets[index];
+                bucket.asset_count += 1;
+                bucket.book_value += position.book_value;
+                bucket.asset_ids.push(asset.asset_id);
+            }
+        }
+
+        AssetAgingReport {
+            as_of,
+            generated_at: self.ledger.now(),
+            total_book_value: by_age.iter().map(|b| b.book_value).sum(),
+            by_age,
+            by_remaining_life,
+        }
+    }
+
+    /// Trial balance of the journal entries effective at or before `as_of`, with balances rolled up
+    /// to `level` of the chart's account hierarchy (0 for the mapped accounts)
+    pub fn trial_balance(&self, chart: &ChartOfAccounts, as_of: DateTime<Utc>, level: usize) -> IclResult<TrialBalance> {
+        self.grouped_trial_balance(chart, as_of, level, None)
+    }
+
+    /// `trial_balance` with a set of lines for each value of a posting dimension, taken from the
+    /// value each journal entry was recorded with
+    pub fn trial_balance_by_dimension(
+        &self,
+        chart: &ChartOfAccounts,
+        as_of: DateTime<Utc>,
+        level: usize,
+        dimension: &str,
+    ) -> IclResult<TrialBalance> {
+        self.grouped_trial_balance(chart, as_of, level, Some(dimension))
+    }
+
+    fn grouped_trial_balance(
+        &self,
+        chart: &ChartOfAccounts,
+        as_of: DateTime<Utc>,
+        level: usize,
+        dimension: Option<&str>,
+    ) -> IclResult<TrialBalance> {
+        let archives: Vec<_> = self.ledger.archives().iter().filter(|a| a.period.start <= as_of).collect();
+        if let Some(archive) = archives.iter().find(|a| !a.period.has_ended_by(as_of) || self.org_id.is_some()) {
+            return Err(IclError::IntegrityViolation(format!(
+                "Period {} is archived; load archive {} to report on it", archive.period, archive.archive_id
+            )));
+        }
+        let mut groups: BTreeMap<Option<&str>, Vec<&JournalEntry>> = BTreeMap::new();
+        if !archives.is_empty() {
+            groups.insert(dimension.map(|_| UNASSIGNED), Vec::new());
+        }
+        let entries = self.ledger.journal_entries().iter()
+            .filter(|j| j.effective_date() <= as_of && self.in_scope(&j.org_id))
+            .filter(|j| !self.ledger.is_quarantined(j.entry_id));
+        for entry in entries {
+            let group = dimension.map(|name| entry.dimensions.get(name).map_or(UNASSIGNED, String::as_str));
+            groups.entry(group).or_default().push(entry);
+        }
+
+        let posting_assets = self.ledger.posting_assets();
+        let mut lines = Vec::new();
+        for (group, entries) in groups {
+            let archived = archives.iter()
+                .filter(|_| group == dimension.map(|_| UNASSIGNED))
+                .flat_map(|a| a.account_balances.iter().map(|(account, balance)| (*account, *balance)));
+            let posted = chart.posted_balances(&posting_assets, entries, archived);
+            lines.extend(chart.roll_up(&posted, level).into_iter().map(|line| TrialBalanceLine {
+                group: group.map(str::to_string),
+                ..line
+            }));
+        }
+
+        Ok(TrialBalance {
+            as_of,
+            generated_at: self.ledger.now(),
+            level,
+            dimension: dimension.map(str::to_string),
+            total_debits: lines.iter().map(|l| l.balance.max(0.0)).sum(),
+            total_credits: lines.iter().map(|l| (-l.balance).max(0.0)).sum(),
+            lines,
+        })
+    }
+
+    fn aging_buckets(&self) -> Vec<AgingBucket> {
+        let lower = std::iter::once(0).chain(self.aging_bounds.iter().copied());
+        let upper = self.aging_bounds.iter().copied().map(Some).chain(std::iter::once(None));
+        lower.zip(upper)
+            .map(|(from_months, to_months)| AgingBucket {
+                label: match to_months {
+                    Some(to) => format!("{}-{}", from_months, to),
+                    None => format!("{}+", from_months),
+                },
+                from_months,
+                to_months,
+                asset_count: 0,
+                book_value: 0.0,
+                asset_ids: Vec::new(),
+            })
+            .collect()
+    }
+
+    fn group_key(&self, asset_id: AssetId, event: &CapitalEvent, group_by: &ReportGrouping) -> String {
+        match group_by {
+            ReportGrouping::Asset => asset_id.to_string(),
+            ReportGrouping::Owner => self.ledger.asset_position(asset_id, event.effective_date())
+                .map(|p| p.owner.to_string())
+                .unwrap_or_default(),
+            ReportGrouping::Category => self.ledger.get_asset(asset_id)
+                .and_then(|a| a.category.clone())
+                .unwrap_or_else(|| UNCATEGORIZED.to_string()),
+            ReportGrouping::Dimension(name) => event.dimensions.get(name)
+                .cloned()
+                .unwrap_or_else(|| UNASSIGNED.to_string()),
+        }
+    }
+}
+
+fn dimension_row<'g>(groups: &'g mut BTreeMap<String, DimensionAggregate>, key: &str) -> &'g mut DimensionAggregate {
+    groups.entry(key.to_string()).or_insert_with(|| DimensionAggregate {
+        key: key.to_string(),
+        asset_count: 0,
+        book_value: 0.0,
+        period_depreciation: 0.0,
+        utilization: 0.0,
+    })
+}
+
+fn report_periods(from: DateTime<Utc>, to: DateTime<Utc>, granularity: PeriodGranularity) -> IclResult<Vec<ReportPeriod>> {
+    let step = match granularity {
+        PeriodGranularity::Month => 1,
+        PeriodGranularity::Quarter => 3,
+    };
+    // Period boundaries past the last representable month cannot be reported on
+    let month_start = |year: i32, month0: u32| Utc.with_ymd_and_hms(year, month0 + 1, 1, 0, 0, 0).single().ok_or_else(|| {
+        IclError::InvalidDateRange { start: from.to_rfc3339(), end: to.to_rfc3339() }
+    });
+    let mut year = from.year();
+    let mut month0 = from.month0() / step * step;
+    let mut periods = Vec::new();
+    loop {
+        let start = month_start(year, month0)?;
+        if start >= to {
+            break;
+        }
+        month0 += step;
+        if month0 >= 12 {
+            month0 -= 12;
+            year += 1;
+        }
+        let next = month_start(year, month0)?;
+        let label = match granularity {
+            PeriodGranularity::Month => start.format("%Y-%m").to_string(),
+            PeriodGranularity::Quarter => format!("{}-Q{}", start.year(), start.month0() / 3 + 1),
+        };
+        periods.push(ReportPeriod { label, start: start.max(from), end: next.min(to) });
+    }
+    Ok(periods)
+}
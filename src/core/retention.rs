@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Months, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::archive::ChainLink;
+use crate::core::ledger::AssetTotals;
+use crate::core::error::{IclError, IclResult};
+
+/// Event type recorded by `apply_retention` for each asset a retention rule purged events of
+pub const RETENTION_PURGE_EVENT: &str = "retention_purge";
+
+/// Keep events of one type for `retain_months` after their effective date, then purge them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetentionRule {
+    pub event_type: String,
+    pub retain_months: u32,
+}
+
+impl RetentionRule {
+    pub fn new(event_type: impl Into<String>, retain_months: u32) -> Self {
+        Self { event_type: event_type.into(), retain_months }
+    }
+
+    pub fn years(event_type: impl Into<String>, years: u32) -> Self {
+        Self::new(event_type, years.saturating_mul(12))
+    }
+
+    pub fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now.checked_sub_months(Months::new(self.retain_months)).unwrap_or(DateTime::<Utc>::MIN_UTC)
+    }
+}
+
+/// What a retention purge removed, kept in the details of its `retention_purge` event: the
+/// purged events' share of the asset's totals, and the chain links that let the event and
+/// ledger entry hash chains verify across the gap
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetentionPurge {
+    pub purged_event_type: String,
+    pub retain_months: u32,
+    pub cutoff: DateTime<Utc>,
+    pub purged_event_ids: Vec<EventId>,
+    pub purged_totals: AssetTotals,
+    pub event_links: Vec<ChainLink>,
+    pub entry_links: Vec<ChainLink>,
+}
+
+impl RetentionPurge {
+    /// The purge recorded by a retention purge event
+    pub fn from_event(event: &CapitalEvent) -> Option<Self> {
+        if event.event_type != RETENTION_PURGE_EVENT {
+            return None;
+        }
+        let details = event.details.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        serde_json::from_value(serde_json::Value::Object(details)).ok()
+    }
+
+    pub(crate) fn to_details(&self) -> IclResult<HashMap<String, serde_json::Value>> {
+        match serde_json::to_value(self)? {
+            serde_json::Value::Object(details) => Ok(details.into_iter().collect()),
+            _ => Err(IclError::SerializationError("Retention purge did not serialize to an object".into())),
+        }
+    }
+}
+
+/// Outcome of `apply_retention`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub applied_at: DateTime<Utc>,
+    pub purge_events: Vec<CapitalEvent>,
+    pub purged_event_count: usize,
+}
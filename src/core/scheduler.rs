@@ -0,0 +1,274 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::*;
+use crate::core::ledger::{IntelligenceCapitalLedger, SharedLedger};
+use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+use crate::core::integrity::IntegrityChecker;
+use crate::core::connectors::ConnectorRegistry;
+use crate::core::error::*;
+
+/// Period-end work a scheduled job performs
+#[derive(Debug, Clone)]
+pub enum ScheduledTask {
+    MonthlyDepreciation,
+    ObligationAccrual,
+    ProofGeneration,
+    IntegrityCheck,
+    GlExport(Arc<Mutex<ConnectorRegistry>>),
+}
+
+impl std::fmt::Display for ScheduledTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScheduledTask::MonthlyDepreciation => write!(f, "MonthlyDepreciation"),
+            ScheduledTask::ObligationAccrual => write!(f, "ObligationAccrual"),
+            ScheduledTask::ProofGeneration => write!(f, "ProofGeneration"),
+            ScheduledTask::IntegrityCheck => write!(f, "IntegrityCheck"),
+            ScheduledTask::GlExport(_) => write!(f, "GlExport"),
+        }
+    }
+}
+
+/// A task and the cron expression it runs on
+#[derive(Debug, Clone)]
+pub struct ScheduledJob {
+    pub name: String,
+    pub task: ScheduledTask,
+    schedule: cron::Schedule,
+}
+
+impl ScheduledJob {
+    pub fn new(name: impl Into<String>, cron_expression: &str, task: ScheduledTask) -> IclResult<Self> {
+        let schedule = cron::Schedule::from_str(cron_expression).map_err(|e| {
+            IclError::IntegrationError(format!("Invalid schedule '{}': {}", cron_expression, e))
+        })?;
+        Ok(Self { name: name.into(), task, schedule })
+    }
+
+    pub fn cron_expression(&self) -> &str {
+        self.schedule.source()
+    }
+
+    pub fn next_run_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.schedule.after(&after).next()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum RunStatus {
+    Succeeded,
+    Partial,
+    Failed,
+}
+
+impl std::fmt::Display for RunStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunStatus::Succeeded => write!(f, "Succeeded"),
+            RunStatus::Partial => write!(f, "Partial"),
+            RunStatus::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+/// Outcome of one execution of a scheduled job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunReport {
+    pub job: String,
+    pub task: String,
+    pub scheduled_for: DateTime<Utc>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub status: RunStatus,
+    pub processed: usize,
+    pub skipped: usize,
+    pub messages: Vec<String>,
+}
+
+/// Runs period-end jobs against a shared ledger on their schedules, timed by the ledger's clock
+#[derive(Debug)]
+pub struct Scheduler {
+    ledger: SharedLedger,
+    jobs: Vec<ScheduledJob>,
+}
+
+impl Scheduler {
+    pub fn new(ledger: SharedLedger) -> Self {
+        Self { ledger, jobs: Vec::new() }
+    }
+
+    pub fn with_job(mut self, job: ScheduledJob) -> Self {
+        self.jobs.push(job);
+        self
+    }
+
+    pub fn jobs(&self) -> &[ScheduledJob] {
+        &self.jobs
+    }
+
+    pub fn next_run_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.jobs.iter().filter_map(|job| job.next_run_after(after)).min()
+    }
+
+    /// Run, in registration order, every job due in `(after, until]`
+    pub fn run_due(&self, after: DateTime<Utc>, until: DateTime<Utc>) -> Vec<RunReport> {
+        self.jobs.iter()
+            .filter_map(|job| {
+                let due = job.schedule.after(&after).take_while(|at| *at <= until).last()?;
+                Some(self.run_job(job, due))
+            })
+            .collect()
+    }
+
+    /// Run one job immediately as if it were due at `scheduled_for`
+    pub fn run_now(&self, job_name: &str, scheduled_for: DateTime<Utc>) -> IclResult<RunReport> {
+        let job = self.jobs.iter().find(|job| job.name == job_name)
+            .ok_or_else(|| IclError::IntegrationError(format!("No scheduled job named {}", job_name)))?;
+        Ok(self.run_job(job, scheduled_for))
+    }
+
+    /// Sleep until each job is due and run it, sending a report per execution
+    pub async fn run(self, reports: tokio::sync::mpsc::Sender<RunReport>) {
+        let mut last = self.ledger.read().now();
+        while let Some(next) = self.next_run_after(last) {
+            let now = self.ledger.read().now();
+            tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+            for report in self.run_due(last, next) {
+                if reports.send(report).await.is_err() {
+                    return;
+                }
+            }
+            last = next;
+        }
+    }
+
+    /// Start `run` on the current tokio runtime, returning the task and its report stream
+    pub fn spawn(self, buffer: usize) -> (tokio::task::JoinHandle<()>, tokio::sync::mpsc::Receiver<RunReport>) {
+        let (sender, receiver) = tokio::sync::mpsc::channel(buffer);
+        (tokio::spawn(self.run(sender)), receiver)
+    }
+
+    fn run_job(&self, job: &ScheduledJob, scheduled_for: DateTime<Utc>) -> RunReport {
+        let mut ledger = self.ledger.write();
+        let mut report = RunReport {
+            job: job.name.clone(),
+            task: job.task.to_string(),
+            scheduled_for,
+            started_at: ledger.now(),
+            finished_at: ledger.now(),
+            status: RunStatus::Succeeded,
+            processed: 0,
+            skipped: 0,
+            messages: Vec::new(),
+        };
+        let result = match &job.task {
+            ScheduledTask::MonthlyDepreciation => run_monthly_depreciation(&mut ledger, scheduled_for, &mut report),
+            ScheduledTask::ObligationAccrual => run_obligation_accrual(&mut ledger, scheduled_for, &mut report),
+            ScheduledTask::ProofGeneration => run_proof_generation(&mut ledger, &mut report),
+            ScheduledTask::IntegrityCheck => run_integrity_check(&ledger, &mut report),
+            ScheduledTask::GlExport(connectors) => connectors.lock().push_new_journal_entries(&ledger).map(|receipts| {
+                report.processed = receipts.iter().map(|r| r.entry_count).sum();
+            }),
+        };
+        if let Err(e) = result {
+            report.status = RunStatus::Failed;
+            report.messages.push(e.to_string());
+        } else if report.status == RunStatus::Succeeded && !report.messages.is_empty() {
+            report.status = RunStatus::Partial;
+        }
+        report.finished_at = ledger.now();
+        report
+    }
+}
+
+fn month_start(date: NaiveDate) -> DateTime<Utc> {
+    date.with_day(1).unwrap_or(date).and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+fn run_monthly_depreciation(
+    ledger: &mut IntelligenceCapitalLedger,
+    scheduled_for: DateTime<Utc>,
+    report: &mut RunReport
+) -> IclResult<()> {
+    let period_end = month_start(scheduled_for.date_naive());
+    let period_start = month_start((period_end - Duration::days(1)).date_naive());
+
+    // Impairment-only assets are never depreciated
+    let mut assets: Vec<(AssetId, DateTime<Utc>)> = ledger.assets_by_status(AssetStatus::Active)
+        .filter(|a| a.created_at < period_end && a.depreciation_method != DepreciationMethod::ImpairmentOnly)
+        .map(|a| (a.asset_id, a.created_at))
+        .collect();
+    assets.sort_by_key(|(asset_id, created_at)| (*created_at, *asset_id));
+
+    for (asset_id, created_at) in assets {
+        let already_depreciated = ledger.get_events_for_asset(asset_id).iter()
+            .filter(|e| e.event_type == "depreciation" && !ledger.is_voided(e.event_id))
+            .any(|e| e.effective_date() >= period_start && e.effective_date() < period_end);
+        if already_depreciated {
+            report.skipped += 1;
+            continue;
+        }
+        let result = IntelligenceCapitalLifecycle::new(ledger)
+            .effective_on(period_end - Duration::seconds(1))
+            .depreciate(asset_id, period_start.max(created_at), period_end, 0.0, 1.0);
+        match result {
+            Ok(_) => report.processed += 1,
+            Err(e) => {
+                report.skipped += 1;
+                report.messages.push(format!("Asset {}: {}", asset_id, e));
+            },
+        }
+    }
+    Ok(())
+}
+
+fn run_obligation_accrual(
+    ledger: &mut IntelligenceCapitalLedger,
+    scheduled_for: DateTime<Utc>,
+    report: &mut RunReport
+) -> IclResult<()> {
+    let mut assets: Vec<AssetId> = ledger.obligations_iter().map(|o| o.asset_id).collect();
+    assets.sort();
+    assets.dedup();
+
+    for asset_id in assets {
+        match IntelligenceCapitalLifecycle::new(ledger).accrue_obligations(asset_id, scheduled_for) {
+            Ok(events) => report.processed += events.len(),
+            Err(e) => {
+                report.skipped += 1;
+                report.messages.push(format!("Asset {}: {}", asset_id, e));
+            },
+        }
+    }
+    Ok(())
+}
+
+fn run_proof_generation(ledger: &mut IntelligenceCapitalLedger, report: &mut RunReport) -> IclResult<()> {
+    let mut assets: Vec<(AssetId, DateTime<Utc>)> = ledger.assets_iter()
+        .filter(|a| a.status != AssetStatus::Retired)
+        .map(|a| (a.asset_id, a.created_at))
+        .collect();
+    assets.sort_by_key(|(asset_id, created_at)| (*created_at, *asset_id));
+    for (asset_id, _) in assets {
+        ledger.generate_proof(asset_id, None)?;
+        report.processed += 1;
+    }
+    Ok(())
+}
+
+fn run_integrity_check(ledger: &IntelligenceCapitalLedger, report: &mut RunReport) -> IclResult<()> {
+    let integrity = IntegrityChecker::new(ledger).check_all_integrity();
+    report.processed = integrity.len();
+    report.messages.extend(integrity.findings.iter().map(|f| f.message.clone()));
+    if integrity.has_errors() {
+        return Err(IclError::IntegrityViolation(format!(
+            "{} integrity errors", integrity.errors().count()
+        )));
+    }
+    Ok(())
+}
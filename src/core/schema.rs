@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::Value;
+
+use crate::core::types::*;
+
+/// Standalone JSON Schema (draft 2020-12) for `T`, with referenced types under `$defs`
+pub fn json_schema_for<T: JsonSchema>() -> Value {
+    SchemaGenerator::new(SchemaSettings::draft2020_12()).into_root_schema_for::<T>().to_value()
+}
+
+/// JSON Schemas of the exported record types, keyed by type name: `IntelligenceAsset`,
+/// `CapitalEvent`, `LedgerEntry`, `JournalEntry`, and `CapitalProof`
+pub fn json_schemas() -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("IntelligenceAsset", json_schema_for::<IntelligenceAsset>()),
+        ("CapitalEvent", json_schema_for::<CapitalEvent>()),
+        ("LedgerEntry", json_schema_for::<LedgerEntry>()),
+        ("JournalEntry", json_schema_for::<JournalEntry>()),
+        ("CapitalProof", json_schema_for::<CapitalProof>()),
+    ])
+}
+
+/// OpenAPI 3.0 `components` object holding the record types and every type they reference, ready to
+/// merge into an API description
+pub fn openapi_components() -> Value {
+    let mut generator = SchemaGenerator::new(SchemaSettings::openapi3());
+    generator.subschema_for::<IntelligenceAsset>();
+    generator.subschema_for::<CapitalEvent>();
+    generator.subschema_for::<LedgerEntry>();
+    generator.subschema_for::<JournalEntry>();
+    generator.subschema_for::<CapitalProof>();
+    serde_json::json!({ "schemas": generator.take_definitions(true) })
+}
@@ -0,0 +1,525 @@
+use std::collections::BTreeMap;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::core::types::*;
+use crate::core::ledger::SharedLedger;
+use crate::core::lifecycle::{AssetSummary, IntelligenceCapitalLifecycle};
+use crate::core::integrity::{IntegrityChecker, IntegrityReport};
+use crate::core::impairment::{ImpairmentIndicator, ImpairmentReview};
+use crate::core::licensing::{License, LicenseTerms, RevenuePeriod};
+use crate::core::reports::*;
+use crate::core::error::*;
+
+impl IclError {
+    pub fn status_code(&self) -> StatusCode {
+        match self.category() {
+            ErrorCategory::NotFound => StatusCode::NOT_FOUND,
+            ErrorCategory::Conflict => StatusCode::CONFLICT,
+            ErrorCategory::Validation => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCategory::Unsupported => StatusCode::BAD_REQUEST,
+            ErrorCategory::Unauthorized => StatusCode::FORBIDDEN,
+            ErrorCategory::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for IclError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({ "error": self.to_string(), "code": self.code() });
+        (self.status_code(), Json(body)).into_response()
+    }
+}
+
+/// Body of `POST /assets`; a missing `asset_id` is generated
+#[derive(Debug, Clone, Deserialize)]
+pub struct CapitalizeRequest {
+    #[serde(default)]
+    pub asset_id: Option<AssetId>,
+    pub owner: String,
+    pub initial_value: f64,
+    pub depreciation_method: DepreciationMethod,
+    pub useful_life_months: i32,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub dimensions: BTreeMap<String, String>,
+    #[serde(default)]
+    pub org_id: Option<OrgId>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AllocateRequest {
+    pub target_owner: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UtilizeRequest {
+    #[serde(default)]
+    pub amount: f64,
+    #[serde(default, flatten)]
+    pub usage: Usage,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DepreciateRequest {
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    #[serde(default)]
+    pub salvage_value: f64,
+    #[serde(default = "default_rate_multiplier")]
+    pub rate_multiplier: f64,
+}
+
+/// Body of `POST /assets/{id}/indicators`, posted by monitoring systems
+#[derive(Debug, Clone, Deserialize)]
+pub struct IndicatorRequest {
+    pub metric: String,
+    pub value: f64,
+    #[serde(default)]
+    pub observed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// Body of `POST /assets/{id}/licenses`
+#[derive(Debug, Clone, Deserialize)]
+pub struct LicenseRequest {
+    pub licensee: String,
+    #[serde(flatten)]
+    pub terms: LicenseTerms,
+}
+
+/// Body of `POST /assets/{id}/revenue`
+#[derive(Debug, Clone, Deserialize)]
+pub struct RevenueRequest {
+    pub amount: f64,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+}
+
+fn default_rate_multiplier() -> f64 {
+    1.0
+}
+
+/// Body of `POST /assets/{id}/proofs`; without `event_id` an asset proof is generated
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProofRequest {
+    #[serde(default)]
+    pub event_id: Option<EventId>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct AssetFilter {
+    owner: Option<OwnerId>,
+    status: Option<AssetStatus>,
+    org_id: Option<OrgId>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct AsOfParams {
+    as_of: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PeriodParams {
+    from: DateTime<Utc>,
+    to: Option<DateTime<Utc>>,
+    group_by: Option<ReportGrouping>,
+    dimension: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ProofFilter {
+    asset_id: Option<AssetId>,
+}
+
+/// REST routes over the ledger
+pub fn router(ledger: SharedLedger) -> Router {
+    Router::new()
+        .route("/assets", get(list_assets).post(capitalize))
+        .route("/assets/{id}", get(get_asset))
+        .route("/assets/{id}/summary", get(asset_summary))
+        .route("/assets/{id}/events", get(asset_events))
+        .route("/assets/{id}/proofs", get(asset_proofs).post(generate_proof))
+        .route("/assets/{id}/allocate", post(allocate))
+        .route("/assets/{id}/utilize", post(utilize))
+        .route("/assets/{id}/depreciate", post(depreciate))
+        .route("/assets/{id}/retire", post(retire))
+        .route("/assets/{id}/indicators", post(record_indicator))
+        .route("/assets/{id}/licenses", get(asset_licenses).post(license))
+        .route("/assets/{id}/revenue", post(record_revenue))
+        .route("/impairment-reviews", get(list_impairment_reviews))
+        .route("/events", get(list_events))
+        .route("/journal-entries", get(list_journal_entries))
+        .route("/proofs", get(list_proofs))
+        .route("/integrity", get(integrity))
+        .route("/reports/register", get(asset_register))
+        .route("/reports/aging", get(asset_aging))
+        .route("/reports/depreciation", get(depreciation_by_period))
+        .route("/reports/rollforward", get(capital_rollforward))
+        .route("/reports/utilization", get(utilization_report))
+        .route("/reports/dimension", get(dimension_report))
+        .route("/reports/returns", get(returns_report))
+        .with_state(ledger)
+}
+
+/// Serve `router` on `addr` until the process exits
+pub async fn serve(ledger: SharedLedger, addr: impl tokio::net::ToSocketAddrs) -> IclResult<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router(ledger)).await?;
+    Ok(())
+}
+
+type ApiResult<T> = IclResult<Json<T>>;
+
+async fn list_assets(State(ledger): State<SharedLedger>, Query(filter): Query<AssetFilter>) -> Json<Vec<IntelligenceAsset>> {
+    let ledger = ledger.read();
+    let mut assets: Vec<IntelligenceAsset> = ledger.assets_iter()
+        .filter(|a| filter.owner.as_ref().is_none_or(|owner| &a.owner == owner))
+        .filter(|a| filter.status.is_none_or(|status| a.status == status))
+        .filter(|a| filter.org_id.as_ref().is_none_or(|org_id| &a.org_id == org_id))
+        .cloned()
+        .collect();
+    assets.sort_by_key(|a| (a.created_at, a.asset_id));
+    Json(assets)
+}
+
+async fn capitalize(
+    State(ledger): State<SharedLedger>,
+    Json(request): Json<CapitalizeRequest>
+) -> IclResult<(StatusCode, Json<IntelligenceAsset>)> {
+    let mut ledger = ledger.write();
+    let asset_id = request.asset_id.unwrap_or_else(|| ledger.next_id().into());
+    let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger).in_org(request.org_id.unwrap_or_default());
+    if let Some(category) = request.category {
+        lifecycle = lifecycle.in_category(category);
+    }
+    let mut asset = lifecycle.capitalize(
+        asset_id,
+        request.owner,
+        request.initial_value,
+        request.depreciation_method,
+        request.useful_life_months,
+    )?;
+    if !request.dimensions.is_empty() {
+        asset = ledger.update_asset_metadata(asset_id, |a| a.dimensions = request.dimensions)?;
+    }
+    Ok((StatusCode::CREATED, Json(asset)))
+}
+
+async fn get_asset(State(ledger): State<SharedLedger>, Path(id): Path<AssetId>) -> ApiResult<IntelligenceAsset> {
+    ledger.read().get_asset(id).cloned().map(Json).ok_or(IclError::AssetNotFound(id))
+}
+
+async fn asset_summary(State(ledger): State<SharedLedger>, Path(id): Path<AssetId>) -> ApiResult<AssetSummary> {
+    let mut ledger = ledger.write();
+    IntelligenceCapitalLifecycle::new(&mut ledger).get_asset_summary(id).map(Json)
+}
+
+async fn asset_events(State(ledger): State<SharedLedger>, Path(id): Path<AssetId>) -> ApiResult<Vec<CapitalEvent>> {
+    let ledger = ledger.read();
+    if ledger.get_asset(id).is_none() {
+        return Err(IclError::AssetNotFound(id));
+    }
+    Ok(Json(ledger.get_events_for_asset(id).into_iter().cloned().collect()))
+}
+
+async fn asset_proofs(State(ledger): State<SharedLedger>, Path(id): Path<AssetId>) -> ApiResult<Vec<CapitalProof>> {
+    let ledger = ledger.read();
+    if ledger.get_asset(id).is_none() {
+        return Err(IclError::AssetNotFound(id));
+    }
+    Ok(Json(ledger.get_proofs_for_asset(id).into_iter().cloned().collect()))
+}
+
+async fn generate_proof(
+    State(ledger): State<SharedLedger>,
+    Path(id): Path<AssetId>,
+    request: Option<Json<ProofRequest>>
+) -> IclResult<(StatusCode, Json<CapitalProof>)> {
+    let event_id = request.and_then(|Json(r)| r.event_id);
+    let proof = ledger.write().generate_proof(id, event_id)?;
+    Ok((StatusCode::CREATED, Json(proof)))
+}
+
+async fn allocate(
+    State(ledger): State<SharedLedger>,
+    Path(id): Path<AssetId>,
+    Json(request): Json<AllocateRequest>
+) -> ApiResult<CapitalEvent> {
+    IntelligenceCapitalLifecycle::new(&mut ledger.write()).allocate(id, request.target_owner).map(Json)
+}
+
+async fn utilize(
+    State(ledger): State<SharedLedger>,
+    Path(id): Path<AssetId>,
+    Json(request): Json<UtilizeRequest>
+) -> ApiResult<CapitalEvent> {
+    IntelligenceCapitalLifecycle::new(&mut ledger.write()).utilize(id, Utilization::new(request.usage).with_cost(request.amount)).map(Json)
+}
+
+async fn depreciate(
+    State(ledger): State<SharedLedger>,
+    Path(id): Path<AssetId>,
+    Json(request): Json<DepreciateRequest>
+) -> ApiResult<CapitalEvent> {
+    IntelligenceCapitalLifecycle::new(&mut ledger.write())
+        .depreciate(id, request.start_date, request.end_date, request.salvage_value, request.rate_multiplier)
+        .map(Json)
+}
+
+async fn retire(State(ledger): State<SharedLedger>, Path(id): Path<AssetId>) -> ApiResult<CapitalEvent> {
+    IntelligenceCapitalLifecycle::new(&mut ledger.write()).retire(id).map(Json)
+}
+
+async fn asset_licenses(State(ledger): State<SharedLedger>, Path(id): Path<AssetId>) -> ApiResult<Vec<License>> {
+    let ledger = ledger.read();
+    ledger.get_asset(id).ok_or(IclError::AssetNotFound(id))?;
+    Ok(Json(ledger.licenses_for_asset(id).into_iter().cloned().collect()))
+}
+
+async fn license(
+    State(ledger): State<SharedLedger>,
+    Path(id): Path<AssetId>,
+    Json(request): Json<LicenseRequest>
+) -> IclResult<(StatusCode, Json<License>)> {
+    let license = IntelligenceCapitalLifecycle::new(&mut ledger.write()).license(id, request.licensee, request.terms)?;
+    Ok((StatusCode::CREATED, Json(license)))
+}
+
+async fn record_revenue(
+    State(ledger): State<SharedLedger>,
+    Path(id): Path<AssetId>,
+    Json(request): Json<RevenueRequest>
+) -> ApiResult<CapitalEvent> {
+    IntelligenceCapitalLifecycle::new(&mut ledger.write())
+        .record_revenue(id, request.amount, RevenuePeriod::new(request.period_start, request.period_end))
+        .map(Json)
+}
+
+async fn record_indicator(
+    State(ledger): State<SharedLedger>,
+    Path(id): Path<AssetId>,
+    Json(request): Json<IndicatorRequest>
+) -> ApiResult<Option<ImpairmentReview>> {
+    let indicator = ImpairmentIndicator {
+        asset_id: id,
+        metric: request.metric,
+        value: request.value,
+        observed_at: request.observed_at,
+        source: request.source,
+    };
+    ledger.write().record_impairment_indicator(indicator).map(Json)
+}
+
+async fn list_impairment_reviews(State(ledger): State<SharedLedger>) -> Json<Vec<ImpairmentReview>> {
+    Json(ledger.read().open_impairment_reviews().cloned().collect())
+}
+
+async fn list_events(State(ledger): State<SharedLedger>) -> Json<Vec<CapitalEvent>> {
+    Json(ledger.read().events().to_vec())
+}
+
+async fn list_journal_entries(State(ledger): State<SharedLedger>) -> Json<Vec<JournalEntry>> {
+    let ledger = ledger.read();
+    Json(ledger.journal_entries().iter().filter(|j| !ledger.is_quarantined(j.entry_id)).cloned().collect())
+}
+
+async fn list_proofs(State(ledger): State<SharedLedger>, Query(filter): Query<ProofFilter>) -> Json<Vec<CapitalProof>> {
+    let ledger = ledger.read();
+    Json(ledger.proofs().iter()
+        .filter(|p| !ledger.is_quarantined(p.proof_id))
+        .filter(|p| filter.asset_id.is_none_or(|id| p.asset_id == id))
+        .cloned()
+        .collect())
+}
+
+async fn integrity(State(ledger): State<SharedLedger>) -> Json<IntegrityReport> {
+    Json(IntegrityChecker::new(&ledger.read()).check_all_integrity())
+}
+
+async fn asset_register(State(ledger): State<SharedLedger>, Query(params): Query<AsOfParams>) -> Json<AssetRegister> {
+    let ledger = ledger.read();
+    Json(ReportGenerator::new(&ledger).asset_register(params.as_of.unwrap_or_else(|| ledger.now())))
+}
+
+async fn asset_aging(State(ledger): State<SharedLedger>, Query(params): Query<AsOfParams>) -> Json<AssetAgingReport> {
+    let ledger = ledger.read();
+    Json(ReportGenerator::new(&ledger).asset_aging(params.as_of.unwrap_or_else(|| ledger.now())))
+}
+
+async fn depreciation_by_period(
+    State(ledger): State<SharedLedger>,
+    Query(params): Query<PeriodParams>
+) -> ApiResult<DepreciationByPeriod> {
+    let ledger = ledger.read();
+    let to = params.to.unwrap_or_else(|| ledger.now());
+    let group_by = match (params.group_by, params.dimension) {
+        (Some(group_by), _) => group_by,
+        (None, Some(dimension)) => ReportGrouping::Dimension(dimension),
+        (None, None) => ReportGrouping::Owner,
+    };
+    ReportGenerator::new(&ledger)
+        .depreciation_by_period(params.from, to, group_by)
+        .map(Json)
+}
+
+async fn capital_rollforward(
+    State(ledger): State<SharedLedger>,
+    Query(params): Query<PeriodParams>
+) -> ApiResult<CapitalRollforward> {
+    let ledger = ledger.read();
+    let to = params.to.unwrap_or_else(|| ledger.now());
+    ReportGenerator::new(&ledger).capital_rollforward(params.from, to).map(Json)
+}
+
+async fn utilization_report(
+    State(ledger): State<SharedLedger>,
+    Query(params): Query<PeriodParams>
+) -> ApiResult<UtilizationReport> {
+    let ledger = ledger.read();
+    let to = params.to.unwrap_or_else(|| ledger.now());
+    ReportGenerator::new(&ledger).utilization(params.from, to).map(Json)
+}
+
+async fn returns_report(
+    State(ledger): State<SharedLedger>,
+    Query(params): Query<PeriodParams>
+) -> ApiResult<AssetReturnReport> {
+    let ledger = ledger.read();
+    let to = params.to.unwrap_or_else(|| ledger.now());
+    ReportGenerator::new(&ledger).asset_returns(params.from, to).map(Json)
+}
+
+async fn dimension_report(
+    State(ledger): State<SharedLedger>,
+    Query(params): Query<PeriodParams>
+) -> ApiResult<DimensionReport> {
+    let ledger = ledger.read();
+    let to = params.to.unwrap_or_else(|| ledger.now());
+    let reports = ReportGenerator::new(&ledger);
+    match &params.dimension {
+        Some(dimension) => reports.by_dimension(dimension, params.from, to),
+        None => reports.by_owner(params.from, to),
+    }.map(Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use axum::body::Body;
+    use axum::http::{Method, Request};
+    use tower::ServiceExt;
+    use crate::core::ledger::IntelligenceCapitalLedger;
+
+    fn test_router() -> Router {
+        router(Arc::new(parking_lot::RwLock::new(IntelligenceCapitalLedger::new())))
+    }
+
+    async fn send(app: &Router, method: Method, uri: &str, body: Option<serde_json::Value>) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+    }
+
+    fn capitalize_request(asset_id: AssetId) -> serde_json::Value {
+        serde_json::json!({
+            "asset_id": asset_id,
+            "owner": "Team A",
+            "initial_value": 1200.0,
+            "depreciation_method": "Linear",
+            "useful_life_months": 12,
+            "dimensions": { "region": "EU" },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_asset_crud() {
+        let app = test_router();
+        let asset_id = AssetId::random();
+        let (status, asset) = send(&app, Method::POST, "/assets", Some(capitalize_request(asset_id))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(asset["dimensions"]["region"], "EU");
+
+        let (status, fetched) = send(&app, Method::GET, &format!("/assets/{}", asset_id), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(fetched, asset);
+
+        let (status, assets) = send(&app, Method::GET, "/assets?owner=Team%20A", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(assets.as_array().map(Vec::len), Some(1));
+        let (_, assets) = send(&app, Method::GET, "/assets?owner=Team%20B", None).await;
+        assert_eq!(assets.as_array().map(Vec::len), Some(0));
+
+        let (status, events) = send(&app, Method::GET, &format!("/assets/{}/events", asset_id), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(events.as_array().is_some_and(|events| !events.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_allocate_moves_asset_to_target_owner() {
+        let app = test_router();
+        let asset_id = AssetId::random();
+        send(&app, Method::POST, "/assets", Some(capitalize_request(asset_id))).await;
+
+        let uri = format!("/assets/{}/allocate", asset_id);
+        let (status, event) = send(&app, Method::POST, &uri, Some(serde_json::json!({ "target_owner": "Team B" }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(event["event_type"], "allocation");
+        let (_, asset) = send(&app, Method::GET, &format!("/assets/{}", asset_id), None).await;
+        assert_eq!(asset["owner"], "Team B");
+    }
+
+    #[tokio::test]
+    async fn test_errors_carry_http_status_and_code() {
+        let app = test_router();
+        let asset_id = AssetId::random();
+        let (status, body) = send(&app, Method::GET, &format!("/assets/{}", asset_id), None).await;
+        assert_eq!((status, body["code"].as_str()), (StatusCode::NOT_FOUND, Some("ICL-ASSET-404")));
+
+        send(&app, Method::POST, "/assets", Some(capitalize_request(asset_id))).await;
+        let (status, body) = send(&app, Method::POST, "/assets", Some(capitalize_request(asset_id))).await;
+        assert_eq!((status, body["code"].as_str()), (StatusCode::CONFLICT, Some("ICL-ASSET-409")));
+
+        let mut invalid = capitalize_request(AssetId::random());
+        invalid["initial_value"] = serde_json::json!(-1.0);
+        let (status, body) = send(&app, Method::POST, "/assets", Some(invalid)).await;
+        assert_eq!((status, body["code"].as_str()), (StatusCode::UNPROCESSABLE_ENTITY, Some("ICL-ASSET-422")));
+
+        let retire = format!("/assets/{}/retire", asset_id);
+        assert_eq!(send(&app, Method::POST, &retire, None).await.0, StatusCode::OK);
+        let (status, body) = send(&app, Method::POST, &retire, None).await;
+        assert_eq!((status, body["code"].as_str()), (StatusCode::CONFLICT, Some("ICL-ASSET-410")));
+    }
+
+    #[test]
+    fn test_status_code_follows_root_error_category() {
+        let cases = [
+            (IclError::EventNotFound(EventId::random()), StatusCode::NOT_FOUND),
+            (IclError::OverlappingDepreciation, StatusCode::CONFLICT),
+            (IclError::IntegrityViolation("broken".into()), StatusCode::UNPROCESSABLE_ENTITY),
+            (IclError::UnsupportedFormat("xml".into()), StatusCode::BAD_REQUEST),
+            (IclError::Unauthorized("denied".into()), StatusCode::FORBIDDEN),
+            (IclError::StorageError("disk".into()), StatusCode::INTERNAL_SERVER_ERROR),
+        ];
+        for (error, status) in cases {
+            assert_eq!(error.status_code(), status);
+            assert_eq!(error.context("step", None).status_code(), status);
+        }
+    }
+}
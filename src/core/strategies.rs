@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use proptest::prelude::*;
+use uuid::Uuid;
+
+use crate::core::types::*;
+use crate::core::ledger::{IntelligenceCapitalLedger, LEDGER_EVENT_TYPES};
+use crate::core::depreciation::{depreciate, DepreciationInput};
+use crate::core::testing::{AssetFactory, TestLedger};
+use crate::core::error::IclResult;
+
+/// Largest value generated assets are capitalized at
+pub const MAX_ASSET_VALUE: f64 = 1e9;
+
+/// Longest finite useful life generated assets are given, in months
+pub const MAX_USEFUL_LIFE_MONTHS: i32 = 600;
+
+/// Any asset ID, including the nil ID
+pub fn asset_id() -> impl Strategy<Value = AssetId> {
+    any::<u128>().prop_map(|n| AssetId::from_uuid(Uuid::from_u128(n)))
+}
+
+/// A timestamp between 2000 and 2100, to the second
+pub fn timestamp() -> impl Strategy<Value = DateTime<Utc>> {
+    (946_684_800i64..4_102_444_800i64).prop_map(|secs| Utc.timestamp_opt(secs, 0).single().expect("in range"))
+}
+
+/// A method with a finite useful life: linear, declining-balance, or units of production
+/// expecting at least one unit
+pub fn finite_depreciation_method() -> impl Strategy<Value = DepreciationMethod> {
+    prop_oneof![
+        Just(DepreciationMethod::Linear),
+        Just(DepreciationMethod::DecliningBalance),
+        (prop_oneof![Just(UsageUnit::Tokens), Just(UsageUnit::Requests), Just(UsageUnit::GpuHours)], 1u64..=1_000_000_000_000)
+            .prop_map(|(unit, expected_units)| DepreciationMethod::UnitsOfProduction { unit, expected_units }),
+    ]
+}
+
+/// An asset the ledger accepts for capitalization, at its initial value:
+///
+/// - `initial_value` is finite and in `0.01..=MAX_ASSET_VALUE`
+/// - `current_value` is `Some(initial_value)` and the asset is `Active`
+/// - finite methods have a useful life in `1..=MAX_USEFUL_LIFE_MONTHS`; impairment-only assets
+///   are datasets with a useful life of 0
+pub fn capitalized_asset() -> impl Strategy<Value = IntelligenceAsset> {
+    let finite = (
+        finite_depreciation_method(),
+        1..=MAX_USEFUL_LIFE_MONTHS,
+        prop_oneof![Just(AssetKind::Model), Just(AssetKind::Dataset), Just(AssetKind::PromptLibrary)],
+    );
+    let schedule = prop_oneof![
+        4 => finite,
+        1 => Just((DepreciationMethod::ImpairmentOnly, 0, AssetKind::Dataset)),
+    ];
+    (asset_id(), "[A-Za-z][A-Za-z ]{0,15}", 0.01..=MAX_ASSET_VALUE, schedule, timestamp()).prop_map(
+        |(asset_id, owner, value, (method, useful_life_months, kind), created_at)| {
+            let mut asset = IntelligenceAsset::new(asset_id, owner, value, method, useful_life_months, created_at);
+            asset.kind = kind;
+            asset
+        },
+    )
+}
+
+/// An asset somewhere along its life: as `capitalized_asset`, except that `current_value`
+/// may be anywhere in `0..=initial_value`
+pub fn asset() -> impl Strategy<Value = IntelligenceAsset> {
+    (capitalized_asset(), 0.0..=1.0f64).prop_map(|(mut asset, remaining)| {
+        asset.current_value = Some(asset.initial_value * remaining);
+        asset
+    })
+}
+
+/// An unrecorded event of a type the ledger defines for `asset`, timestamped no earlier than
+/// the asset's creation, with an `amount` detail in `0..=initial_value`
+pub fn capital_event_for(asset: &IntelligenceAsset) -> impl Strategy<Value = CapitalEvent> {
+    let (asset_id, created_at, initial_value) = (asset.asset_id, asset.created_at, asset.initial_value);
+    (prop::sample::select(LEDGER_EVENT_TYPES), 0i64..=10 * 365 * 86_400, 0.0..=initial_value, any::<u128>()).prop_map(
+        move |(event_type, offset, amount, event_id)| {
+            let details = HashMap::from([("amount".to_string(), serde_json::json!(amount))]);
+            CapitalEvent::new(asset_id, event_type, created_at + Duration::seconds(offset), details)
+                .with_id(Uuid::from_u128(event_id))
+        },
+    )
+}
+
+/// An unrecorded event for an arbitrary asset, as `capital_event_for`
+pub fn capital_event() -> impl Strategy<Value = CapitalEvent> {
+    asset().prop_flat_map(|asset| capital_event_for(&asset))
+}
+
+/// Inputs to one depreciation calculation
+#[derive(Debug, Clone)]
+pub struct DepreciationCase {
+    pub asset: IntelligenceAsset,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub salvage_value: f64,
+    pub rate_multiplier: f64,
+}
+
+impl DepreciationCase {
+    pub fn input(&self) -> DepreciationInput {
+        DepreciationInput::for_asset(&self.asset, self.start, self.end, self.salvage_value, self.rate_multiplier)
+    }
+
+    pub fn calculate(&self) -> IclResult<(f64, f64)> {
+        depreciate(&self.input())
+    }
+}
+
+/// A valid depreciation calculation: a linear or declining-balance asset, a period of up to ten
+/// years ending after it starts, a salvage value in `0..=initial_value`, and a rate multiplier in
+/// `0.5..=3.0`
+pub fn depreciation_case() -> impl Strategy<Value = DepreciationCase> {
+    let method = prop_oneof![Just(DepreciationMethod::Linear), Just(DepreciationMethod::DecliningBalance)];
+    (asset(), method, 1i64..=10 * 365 * 86_400, 0.0..=1.0f64, 0.5..=3.0f64).prop_map(
+        |(mut asset, method, length, salvage_share, rate_multiplier)| {
+            asset.depreciation_method = method;
+            asset.kind = AssetKind::Model;
+            asset.useful_life_months = asset.useful_life_months.max(1);
+            let current_value = asset.current_value.unwrap_or(asset.initial_value);
+            let start = asset.created_at;
+            DepreciationCase {
+                start,
+                end: start + Duration::seconds(length),
+                salvage_value: current_value * salvage_share,
+                rate_multiplier,
+                asset,
+            }
+        },
+    )
+}
+
+/// A ledger holding one asset and a chain of proofs over it
+#[derive(Debug)]
+pub struct ProofChain {
+    pub ledger: IntelligenceCapitalLedger,
+    pub asset_id: AssetId,
+}
+
+impl ProofChain {
+    pub fn proofs(&self) -> Vec<&CapitalProof> {
+        self.ledger.get_proofs_for_asset(self.asset_id)
+    }
+}
+
+/// A proof chain of `1..=max_len` proofs
+pub fn proof_chain(max_len: usize) -> impl Strategy<Value = ProofChain> {
+    let max_len = max_len.max(1);
+    (any::<u64>(), 1.0..=MAX_ASSET_VALUE, prop::collection::vec(prop::option::of(0.01..=1_000.0f64), 0..max_len)).prop_map(
+        |(seed, value, steps)| {
+            let mut test = crate::core::testing::LedgerBuilder::new().with_seed(seed).build();
+            let asset_id = test.capitalized(AssetFactory::model().valued_at(value).depreciated_by(DepreciationMethod::Linear, 60));
+            test.ledger.generate_proof(asset_id, None).expect("proof of a capitalized asset");
+            for step in steps {
+                let event = match step {
+                    Some(cost) => test.lifecycle().utilize(asset_id, cost).expect("utilize test asset"),
+                    None => test.depreciate_monthly(asset_id, 1).remove(0),
+                };
+                test.ledger.generate_proof(asset_id, Some(event.event_id)).expect("proof of a recorded event");
+            }
+            let TestLedger { ledger, .. } = test;
+            ProofChain { ledger, asset_id }
+        },
+    )
+}
+
+impl Arbitrary for AssetId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        asset_id().boxed()
+    }
+}
+
+impl Arbitrary for DepreciationMethod {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![4 => finite_depreciation_method(), 1 => Just(DepreciationMethod::ImpairmentOnly)].boxed()
+    }
+}
+
+impl Arbitrary for IntelligenceAsset {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        asset().boxed()
+    }
+}
+
+impl Arbitrary for CapitalEvent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        capital_event().boxed()
+    }
+}
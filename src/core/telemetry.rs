@@ -0,0 +1,18 @@
+use std::time::Instant;
+
+pub(crate) struct SpanTimer {
+    span: tracing::Span,
+    started: Instant,
+}
+
+impl SpanTimer {
+    pub(crate) fn start() -> Self {
+        Self { span: tracing::Span::current(), started: Instant::now() }
+    }
+}
+
+impl Drop for SpanTimer {
+    fn drop(&mut self) {
+        self.span.record("duration_us", self.started.elapsed().as_micros() as u64);
+    }
+}
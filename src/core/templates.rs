@@ -0,0 +1,83 @@
+use minijinja::Environment;
+use serde::Serialize;
+
+use crate::core::formatting::NumberFormat;
+use crate::core::error::*;
+
+/// Markdown asset register; render with an `AssetRegister`
+pub const ASSET_REGISTER_MARKDOWN: &str = "\
+# Asset Register as of {{ as_of[:10] }}
+
+| Asset | Owner | Status | Cost | Accumulated Depreciation | Net Book Value | Remaining Life (months) |
+|-------|-------|--------|-----:|-------------------------:|---------------:|------------------------:|
+{% for row in rows -%}
+| {{ row.asset_id[:8] }} | {{ row.owner_name }} | {{ row.status }} | {{ row.cost | money }} | {{ row.accumulated_depreciation | money }} | {{ row.net_book_value | money }} | {{ row.remaining_life_months }} |
+{% endfor -%}
+| **Total** | | | {{ total_cost | money }} | {{ total_accumulated_depreciation | money }} | {{ total_net_book_value | money }} | |
+";
+
+/// HTML depreciation schedule; render with a `DepreciationByPeriod`
+pub const DEPRECIATION_SCHEDULE_HTML: &str = "\
+<table class=\"depreciation-schedule\">
+  <thead>
+    <tr><th>{{ group_by }}</th>{% for period in periods %}<th>{{ period.label }}</th>{% endfor %}<th>Total</th></tr>
+  </thead>
+  <tbody>
+{% for row in rows %}    <tr><td>{{ row.group }}</td>{% for amount in row.amounts %}<td>{{ amount | money }}</td>{% endfor %}<td>{{ row.total | money }}</td></tr>
+{% endfor %}  </tbody>
+  <tfoot>
+    <tr><th>Total</th>{% for amount in period_totals %}<th>{{ amount | money }}</th>{% endfor %}<th>{{ total | money }}</th></tr>
+  </tfoot>
+</table>
+";
+
+fn template_error(e: minijinja::Error) -> IclError {
+    IclError::SerializationError(format!("Template: {}", e))
+}
+
+/// Renders report structs through Jinja-style templates
+#[derive(Debug)]
+pub struct ReportRenderer {
+    env: Environment<'static>,
+}
+
+impl Default for ReportRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReportRenderer {
+    /// Renderer with the built-in `asset_register.md` and `depreciation_schedule.html` templates
+    pub fn new() -> Self {
+        let mut env = Environment::new();
+        env.add_template("asset_register.md", ASSET_REGISTER_MARKDOWN)
+            .expect("built-in template parses");
+        env.add_template("depreciation_schedule.html", DEPRECIATION_SCHEDULE_HTML)
+            .expect("built-in template parses");
+        let mut renderer = Self { env };
+        renderer.set_number_format(NumberFormat::en_us().without_currency_symbol());
+        renderer
+    }
+
+    pub fn set_number_format(&mut self, number_format: NumberFormat) {
+        self.env.add_filter("money", move |value: f64| number_format.format(value));
+    }
+
+    /// Register a template, replacing any with the same name
+    pub fn add_template(&mut self, name: impl Into<String>, source: impl Into<String>) -> IclResult<()> {
+        self.env.add_template_owned(name.into(), source.into()).map_err(template_error)
+    }
+
+    /// Render a registered template with a report (or any serializable value) as its context
+    pub fn render<T: Serialize>(&self, template: &str, report: &T) -> IclResult<String> {
+        self.env.get_template(template)
+            .and_then(|t| t.render(report))
+            .map_err(template_error)
+    }
+
+    /// Render a one-off template source without registering it
+    pub fn render_str<T: Serialize>(&self, source: &str, report: &T) -> IclResult<String> {
+        self.env.render_str(source, report).map_err(template_error)
+    }
+}
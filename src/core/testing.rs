@@ -0,0 +1,230 @@
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Months, TimeZone, Utc};
+
+use crate::core::types::*;
+use crate::core::ledger::{IntelligenceCapitalLedger, ValidationConfig};
+use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+use crate::core::clock::ManualClock;
+use crate::core::ids::SeededIdGenerator;
+use crate::core::error::IclResult;
+
+/// Seed of the ID generator of ledgers built by `LedgerBuilder` unless told otherwise
+pub const DEFAULT_TEST_SEED: u64 = 42;
+
+/// Time test ledgers start at unless told otherwise: 2024-01-01T00:00:00Z
+pub fn test_epoch() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).single().expect("valid epoch")
+}
+
+/// Builds a `TestLedger` with a manual clock and seeded IDs, so every run records the same
+/// timestamps and identifiers
+#[derive(Debug, Clone)]
+pub struct LedgerBuilder {
+    start: DateTime<Utc>,
+    seed: u64,
+    validation: ValidationConfig,
+}
+
+impl Default for LedgerBuilder {
+    fn default() -> Self {
+        Self { start: test_epoch(), seed: DEFAULT_TEST_SEED, validation: ValidationConfig::default() }
+    }
+}
+
+impl LedgerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn starting_at(mut self, start: DateTime<Utc>) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_validation(mut self, validation: ValidationConfig) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    pub fn build(self) -> TestLedger {
+        let clock = Arc::new(ManualClock::new(self.start));
+        let mut ledger = IntelligenceCapitalLedger::new();
+        ledger.set_clock(clock.clone());
+        ledger.set_id_generator(Arc::new(SeededIdGenerator::new(self.seed)));
+        ledger.set_validation_config(self.validation);
+        TestLedger { ledger, clock }
+    }
+}
+
+/// Describes an asset to capitalize in a test: a linear 12-month model worth 12,000 owned by
+/// "Team A" unless changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetFactory {
+    pub asset_id: Option<AssetId>,
+    pub owner: OwnerId,
+    pub value: f64,
+    pub method: DepreciationMethod,
+    pub useful_life_months: i32,
+    pub kind: AssetKind,
+}
+
+impl Default for AssetFactory {
+    fn default() -> Self {
+        Self {
+            asset_id: None,
+            owner: OwnerId::from("Team A"),
+            value: 12_000.0,
+            method: DepreciationMethod::Linear,
+            useful_life_months: 12,
+            kind: AssetKind::Model,
+        }
+    }
+}
+
+impl AssetFactory {
+    pub fn model() -> Self {
+        Self::default()
+    }
+
+    /// A dataset with an indefinite life, only ever impaired
+    pub fn dataset() -> Self {
+        Self {
+            method: DepreciationMethod::ImpairmentOnly,
+            useful_life_months: 0,
+            kind: AssetKind::Dataset,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_id(mut self, asset_id: impl Into<AssetId>) -> Self {
+        self.asset_id = Some(asset_id.into());
+        self
+    }
+
+    pub fn owned_by(mut self, owner: impl Into<OwnerId>) -> Self {
+        self.owner = owner.into();
+        self
+    }
+
+    pub fn valued_at(mut self, value: f64) -> Self {
+        self.value = value;
+        self
+    }
+
+    pub fn depreciated_by(mut self, method: DepreciationMethod, useful_life_months: i32) -> Self {
+        self.method = method;
+        self.useful_life_months = useful_life_months;
+        self
+    }
+
+    /// Capitalize the asset, drawing its ID from the ledger's generator unless one was given
+    pub fn capitalize(&self, ledger: &mut IntelligenceCapitalLedger) -> IclResult<IntelligenceAsset> {
+        let asset_id = self.asset_id.unwrap_or_else(|| ledger.next_id().into());
+        IntelligenceCapitalLifecycle::new(ledger)
+            .of_kind(self.kind)
+            .capitalize(asset_id, self.owner.clone(), self.value, self.method, self.useful_life_months)
+    }
+}
+
+/// A ledger on a manual clock, with helpers that set up common scenarios
+#[derive(Debug)]
+pub struct TestLedger {
+    pub ledger: IntelligenceCapitalLedger,
+    pub clock: Arc<ManualClock>,
+}
+
+impl Default for TestLedger {
+    fn default() -> Self {
+        LedgerBuilder::new().build()
+    }
+}
+
+impl TestLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lifecycle(&mut self) -> IntelligenceCapitalLifecycle<'_> {
+        IntelligenceCapitalLifecycle::new(&mut self.ledger)
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.ledger.now()
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.clock.advance(by);
+    }
+
+    pub fn advance_months(&self, months: u32) {
+        self.clock.set(add_months(self.now(), months));
+    }
+
+    /// Capitalize an asset described by `factory`
+    ///
+    /// # Panics
+    /// If the ledger rejects the capitalization
+    pub fn capitalized(&mut self, factory: AssetFactory) -> AssetId {
+        factory.capitalize(&mut self.ledger).expect("capitalize test asset").asset_id
+    }
+
+    /// Capitalize the default model and depreciate it monthly for `months` months
+    ///
+    /// # Panics
+    /// If the ledger rejects any of the postings
+    pub fn asset_depreciated_for(&mut self, months: u32) -> AssetId {
+        let asset_id = self.capitalized(AssetFactory::model());
+        self.depreciate_monthly(asset_id, months);
+        asset_id
+    }
+
+    /// Depreciate an asset for `months` consecutive months from now, moving the clock to the
+    /// end of each month before posting it
+    ///
+    /// # Panics
+    /// If the ledger rejects any of the postings
+    pub fn depreciate_monthly(&mut self, asset_id: AssetId, months: u32) -> Vec<CapitalEvent> {
+        (0..months)
+            .map(|_| {
+                let start = self.now();
+                let end = add_months(start, 1);
+                self.clock.set(end);
+                self.lifecycle().depreciate(asset_id, start, end, 0.0, 1.0).expect("depreciate test asset")
+            })
+            .collect()
+    }
+
+    /// Capitalize the default model and retire it
+    ///
+    /// # Panics
+    /// If the ledger rejects either posting
+    pub fn retired_asset(&mut self) -> AssetId {
+        let asset_id = self.capitalized(AssetFactory::model());
+        self.lifecycle().retire(asset_id).expect("retire test asset");
+        asset_id
+    }
+
+    /// Check the ledger's accounting invariants
+    ///
+    /// # Panics
+    /// Listing the violations, if any invariant is broken
+    pub fn assert_invariants(&self) {
+        if let Err(violations) = self.ledger.assert_invariants() {
+            let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+            panic!("ledger invariants violated:\n{}", violations.join("\n"));
+        }
+    }
+
+    pub fn into_ledger(self) -> IntelligenceCapitalLedger {
+        self.ledger
+    }
+}
+
+fn add_months(at: DateTime<Utc>, months: u32) -> DateTime<Utc> {
+    at.checked_add_months(Months::new(months)).expect("test dates stay in range")
+}
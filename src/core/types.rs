@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+
+use crate::core::hashing::*;
+use crate::core::error::{IclError, IclResult};
+use crate::core::proofs::EVENT_DIGEST_CONTENT;
  
 /// Status of an intelligence asset in its lifecycle
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum AssetStatus {
     Active,
     Depreciated,
@@ -22,9 +27,16 @@ impl std::fmt::Display for AssetStatus {
 
 /// Method used to calculate depreciation over asset lifetime
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum DepreciationMethod {
     Linear,
     DecliningBalance,
+    /// Indefinite life: never depreciated, only written down by impairments. Only datasets may
+    /// use it, with a useful life of 0.
+    ImpairmentOnly,
+    /// Depreciated in proportion to recorded usage of `unit` against the `expected_units` the
+    /// asset will deliver over its life
+    UnitsOfProduction { unit: UsageUnit, expected_units: u64 },
 }
 
 impl std::fmt::Display for DepreciationMethod {
@@ -32,16 +44,260 @@ impl std::fmt::Display for DepreciationMethod {
         match self {
             DepreciationMethod::Linear => write!(f, "Linear"),
             DepreciationMethod::DecliningBalance => write!(f, "DecliningBalance"),
+            DepreciationMethod::ImpairmentOnly => write!(f, "ImpairmentOnly"),
+            DepreciationMethod::UnitsOfProduction { unit, expected_units } => {
+                write!(f, "UnitsOfProduction({} {})", expected_units, unit)
+            },
+        }
+    }
+}
+
+/// Unit of service an asset delivers
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum UsageUnit {
+    Tokens,
+    Requests,
+    GpuHours,
+}
+
+impl std::fmt::Display for UsageUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsageUnit::Tokens => write!(f, "tokens"),
+            UsageUnit::Requests => write!(f, "requests"),
+            UsageUnit::GpuHours => write!(f, "gpu_hours"),
         }
     }
 }
 
+/// Service delivered by an asset, recorded on utilization events and totalled per asset
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Usage {
+    #[serde(default)]
+    pub tokens: u64,
+    #[serde(default)]
+    pub requests: u64,
+    #[serde(default)]
+    pub gpu_hours: f64,
+}
+
+impl Usage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tokens(mut self, tokens: u64) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    pub fn with_requests(mut self, requests: u64) -> Self {
+        self.requests = requests;
+        self
+    }
+
+    pub fn with_gpu_hours(mut self, gpu_hours: f64) -> Self {
+        self.gpu_hours = gpu_hours;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens == 0 && self.requests == 0 && self.gpu_hours == 0.0
+    }
+
+    pub fn quantity(&self, unit: UsageUnit) -> f64 {
+        match unit {
+            UsageUnit::Tokens => self.tokens as f64,
+            UsageUnit::Requests => self.requests as f64,
+            UsageUnit::GpuHours => self.gpu_hours,
+        }
+    }
+
+    pub fn add(&mut self, other: &Usage) {
+        self.tokens += other.tokens;
+        self.requests += other.requests;
+        self.gpu_hours += other.gpu_hours;
+    }
+
+    /// Usage recorded in a utilization event's details
+    pub fn from_details(details: &HashMap<String, serde_json::Value>) -> Self {
+        Self {
+            tokens: details.get("tokens").and_then(|v| v.as_u64()).unwrap_or(0),
+            requests: details.get("requests").and_then(|v| v.as_u64()).unwrap_or(0),
+            gpu_hours: details.get("gpu_hours").and_then(|v| v.as_f64()).unwrap_or(0.0),
+        }
+    }
+
+    pub(crate) fn insert_into(&self, details: &mut HashMap<String, serde_json::Value>) {
+        if self.tokens > 0 {
+            details.insert("tokens".to_string(), serde_json::json!(self.tokens));
+        }
+        if self.requests > 0 {
+            details.insert("requests".to_string(), serde_json::json!(self.requests));
+        }
+        if self.gpu_hours > 0.0 {
+            details.insert("gpu_hours".to_string(), serde_json::json!(self.gpu_hours));
+        }
+    }
+}
+
+/// Argument of `IntelligenceCapitalLifecycle::utilize`: the inference cost incurred and the service
+/// delivered
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct Utilization {
+    pub cost: f64,
+    pub usage: Usage,
+}
+
+impl Utilization {
+    pub fn new(usage: Usage) -> Self {
+        Self { cost: 0.0, usage }
+    }
+
+    pub fn with_cost(mut self, cost: f64) -> Self {
+        self.cost = cost;
+        self
+    }
+}
+
+impl From<f64> for Utilization {
+    fn from(cost: f64) -> Self {
+        Self { cost, usage: Usage::default() }
+    }
+}
+
+impl From<Usage> for Utilization {
+    fn from(usage: Usage) -> Self {
+        Self::new(usage)
+    }
+}
+
+/// Class of intelligence asset, which decides the depreciation treatments it may use
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum AssetKind {
+    #[default]
+    Model,
+    /// Training or evaluation data; may have an indefinite life
+    Dataset,
+    /// Prompt or agent library
+    PromptLibrary,
+}
+
+impl AssetKind {
+    pub fn is_model(&self) -> bool {
+        *self == AssetKind::Model
+    }
+
+    pub fn allows_indefinite_life(&self) -> bool {
+        *self == AssetKind::Dataset
+    }
+}
+
+impl std::fmt::Display for AssetKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetKind::Model => write!(f, "Model"),
+            AssetKind::Dataset => write!(f, "Dataset"),
+            AssetKind::PromptLibrary => write!(f, "PromptLibrary"),
+        }
+    }
+}
+
+/// A span of fiscal time, `[start, end)`, such as a fiscal year
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FiscalPeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl FiscalPeriod {
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self { start, end }
+    }
+
+    /// January 1 to January 1 of the following year
+    ///
+    /// # Panics
+    /// If the year is outside the range chrono supports
+    pub fn calendar_year(year: i32) -> Self {
+        let new_year = |year| Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).single().expect("year in range");
+        Self { start: new_year(year), end: new_year(year + 1) }
+    }
+
+    pub fn validate(&self) -> IclResult<()> {
+        if self.start >= self.end {
+            return Err(IclError::InvalidDateRange { start: self.start.to_rfc3339(), end: self.end.to_rfc3339() });
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        at >= self.start && at < self.end
+    }
+
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        start < self.end && end > self.start
+    }
+
+    pub fn has_ended_by(&self, at: DateTime<Utc>) -> bool {
+        self.end - chrono::Duration::nanoseconds(1) <= at
+    }
+}
+
+impl std::fmt::Display for FiscalPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start.to_rfc3339(), self.end.to_rfc3339())
+    }
+}
+
 /// Account types for double-entry journal entries
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum AccountType {
     Asset,
     AccumulatedDepreciation,
     DepreciationExpense,
+    InferenceExpense,
+    AccountsPayable,
+    AccountsReceivable,
+    Revenue,
+    RoyaltyExpense,
+    DisposalGainLoss,
+    /// Equity that income statement accounts are closed into at year end
+    RetainedEarnings,
+}
+
+impl AccountType {
+    /// Every account type, in chart-of-accounts order
+    pub const ALL: [AccountType; 10] = [
+        AccountType::Asset,
+        AccountType::AccumulatedDepreciation,
+        AccountType::AccountsReceivable,
+        AccountType::AccountsPayable,
+        AccountType::RetainedEarnings,
+        AccountType::Revenue,
+        AccountType::DepreciationExpense,
+        AccountType::InferenceExpense,
+        AccountType::RoyaltyExpense,
+        AccountType::DisposalGainLoss,
+    ];
+
+    /// Whether the account is an income statement account, zeroed into retained earnings when
+    /// a fiscal year is closed
+    pub fn is_income_statement(&self) -> bool {
+        matches!(
+            self,
+            AccountType::Revenue
+                | AccountType::DepreciationExpense
+                | AccountType::InferenceExpense
+                | AccountType::RoyaltyExpense
+                | AccountType::DisposalGainLoss
+        )
+    }
 }
 
 impl std::fmt::Display for AccountType {
@@ -50,76 +306,677 @@ impl std::fmt::Display for AccountType {
             AccountType::Asset => write!(f, "Asset"),
             AccountType::AccumulatedDepreciation => write!(f, "AccumulatedDepreciation"),
             AccountType::DepreciationExpense => write!(f, "DepreciationExpense"),
+            AccountType::InferenceExpense => write!(f, "InferenceExpense"),
+            AccountType::AccountsPayable => write!(f, "AccountsPayable"),
+            AccountType::AccountsReceivable => write!(f, "AccountsReceivable"),
+            AccountType::Revenue => write!(f, "Revenue"),
+            AccountType::RoyaltyExpense => write!(f, "RoyaltyExpense"),
+            AccountType::DisposalGainLoss => write!(f, "DisposalGainLoss"),
+            AccountType::RetainedEarnings => write!(f, "RetainedEarnings"),
+        }
+    }
+}
+
+/// Side of a journal entry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum PostingSide {
+    Debit,
+    Credit,
+}
+
+/// Tenant namespace isolating assets, their records, and their proof chains within one ledger
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct OrgId(String);
+
+impl OrgId {
+    /// Namespace of records created without one, including everything written before namespaces existed
+    pub const DEFAULT: &'static str = "default";
+
+    pub fn new(org: impl Into<String>) -> Self {
+        Self(org.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == Self::DEFAULT
+    }
+}
+
+impl Default for OrgId {
+    fn default() -> Self {
+        Self(Self::DEFAULT.to_string())
+    }
+}
+
+impl std::fmt::Display for OrgId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Identifier of an owner; registered owners carry a display name and kind in the ledger's
+/// `OwnerRegistry`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct OwnerId(String);
+
+impl OwnerId {
+    pub fn new(owner: impl Into<String>) -> Self {
+        Self(owner.into())
+    }
+
+    /// Canonical ID for a free-text owner: lowercase words joined by hyphens, so "Team A",
+    /// "team-a", and "TeamA" all become `team-a`
+    pub fn normalize(raw: &str) -> Self {
+        let mut id = String::with_capacity(raw.len());
+        let mut previous: Option<char> = None;
+        for c in raw.trim().chars() {
+            if c.is_alphanumeric() {
+                let word_break = c.is_uppercase()
+                    && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit());
+                if word_break && !id.is_empty() && !id.ends_with('-') {
+                    id.push('-');
+                }
+                id.extend(c.to_lowercase());
+            } else if !id.is_empty() && !id.ends_with('-') {
+                id.push('-');
+            }
+            previous = Some(c);
+        }
+        Self(id.trim_end_matches('-').to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for OwnerId {
+    fn from(owner: String) -> Self {
+        Self(owner)
+    }
+}
+
+impl From<&str> for OwnerId {
+    fn from(owner: &str) -> Self {
+        Self(owner.to_string())
+    }
+}
+
+impl std::borrow::Borrow<str> for OwnerId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for OwnerId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for OwnerId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl std::fmt::Display for OwnerId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+macro_rules! uuid_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+        #[serde(transparent)]
+        pub struct $name(uuid::Uuid);
+
+        impl $name {
+            /// A random (v4) ID; ledgers assign IDs from their `IdGenerator` instead
+            pub fn random() -> Self {
+                Self(uuid::Uuid::new_v4())
+            }
+
+            pub const fn from_uuid(id: uuid::Uuid) -> Self {
+                Self(id)
+            }
+
+            pub const fn as_uuid(&self) -> uuid::Uuid {
+                self.0
+            }
+
+            pub fn is_nil(&self) -> bool {
+                self.0.is_nil()
+            }
+        }
+
+        impl From<uuid::Uuid> for $name {
+            fn from(id: uuid::Uuid) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for uuid::Uuid {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl PartialEq<uuid::Uuid> for $name {
+            fn eq(&self, other: &uuid::Uuid) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = uuid::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                uuid::Uuid::parse_str(s).map(Self)
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+uuid_id!(
+    AssetId
+);
+
+uuid_id!(
+    EventId
+);
+
+uuid_id!(
+    EntryId
+);
+
+/// The trained model an asset was capitalized for, as published to a model registry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ModelArtifact {
+    pub model_name: String,
+    pub version: String,
+    /// Digest of the model weights, e.g. `sha256:9f86d0...`, as deployed
+    pub weights_hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_uri: Option<String>,
+}
+
+impl ModelArtifact {
+    pub fn new(model_name: impl Into<String>, version: impl Into<String>, weights_hash: impl Into<String>) -> Self {
+        Self {
+            model_name: model_name.into(),
+            version: version.into(),
+            weights_hash: weights_hash.into(),
+            registry_uri: None,
         }
     }
+
+    pub fn with_registry_uri(mut self, registry_uri: impl Into<String>) -> Self {
+        self.registry_uri = Some(registry_uri.into());
+        self
+    }
 }
 
 /// A capitalized intelligence asset with ownership and depreciation rules
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct IntelligenceAsset {
-    pub asset_id: uuid::Uuid,
-    pub owner: String,
+    pub asset_id: AssetId,
+    pub owner: OwnerId,
     pub initial_value: f64,
     pub depreciation_method: DepreciationMethod,
     pub useful_life_months: i32,
     pub created_at: DateTime<Utc>,
     pub status: AssetStatus,
     pub current_value: Option<f64>,
+    /// Reporting category such as "foundation-model" or "dataset"; not committed to by proofs
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Reporting dimensions such as `cost_center`; not committed to by proofs
+    #[serde(default)]
+    pub dimensions: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "OrgId::is_default")]
+    pub org_id: OrgId,
+    /// Model artifact the asset stands for; committed to by proofs once linked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<ModelArtifact>,
+    /// Asset this one is a capitalized improvement of, e.g. the base model of a fine-tune;
+    /// committed to by proofs when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_asset_id: Option<AssetId>,
+    /// Asset class; committed to by proofs unless it is `Model`
+    #[serde(default, skip_serializing_if = "AssetKind::is_model")]
+    pub kind: AssetKind,
+}
+
+impl IntelligenceAsset {
+    /// An active `Model` asset at its initial value, in the default namespace
+    pub fn new(
+        asset_id: impl Into<AssetId>,
+        owner: impl Into<OwnerId>,
+        initial_value: f64,
+        depreciation_method: DepreciationMethod,
+        useful_life_months: i32,
+        created_at: DateTime<Utc>
+    ) -> Self {
+        Self {
+            asset_id: asset_id.into(),
+            owner: owner.into(),
+            initial_value,
+            depreciation_method,
+            useful_life_months,
+            created_at,
+            status: AssetStatus::Active,
+            current_value: Some(initial_value),
+            category: None,
+            dimensions: BTreeMap::new(),
+            org_id: OrgId::default(),
+            artifact: None,
+            parent_asset_id: None,
+            kind: AssetKind::default(),
+        }
+    }
+
+    pub fn proof_content(&self) -> BTreeMap<String, serde_json::Value> {
+        let mut content = BTreeMap::new();
+        content.insert("asset_id".to_string(), serde_json::Value::String(self.asset_id.to_string()));
+        content.insert("owner".to_string(), serde_json::Value::String(self.owner.to_string()));
+        content.insert("initial_value".to_string(), serde_json::json!(self.initial_value));
+        content.insert("depreciation_method".to_string(), serde_json::Value::String(self.depreciation_method.to_string()));
+        content.insert("useful_life_months".to_string(), serde_json::Value::Number(serde_json::Number::from(self.useful_life_months)));
+        content.insert("status".to_string(), serde_json::Value::String(self.status.to_string()));
+        content.insert("current_value".to_string(), serde_json::json!(self.current_value.unwrap_or_default()));
+        // Left out for the default namespace so proofs from before namespaces still verify
+        if !self.org_id.is_default() {
+            content.insert("org_id".to_string(), serde_json::Value::String(self.org_id.to_string()));
+        }
+        if let Some(artifact) = &self.artifact {
+            content.insert("artifact".to_string(), serde_json::json!(artifact));
+        }
+        if let Some(parent_asset_id) = self.parent_asset_id {
+            content.insert("parent_asset_id".to_string(), serde_json::Value::String(parent_asset_id.to_string()));
+        }
+        if !self.kind.is_model() {
+            content.insert("kind".to_string(), serde_json::Value::String(self.kind.to_string()));
+        }
+        content
+    }
+
+    pub(crate) fn validate_schedule(&self) -> Result<(), String> {
+        if self.depreciation_method == DepreciationMethod::ImpairmentOnly {
+            if !self.kind.allows_indefinite_life() {
+                return Err(format!("{} assets must be depreciated over a finite useful life", self.kind));
+            }
+            if self.useful_life_months != 0 {
+                return Err("Impairment-only assets have an indefinite useful life of 0 months".into());
+            }
+            return Ok(());
+        }
+        if self.useful_life_months <= 0 {
+            return Err("Useful life must be positive".into());
+        }
+        if let DepreciationMethod::UnitsOfProduction { expected_units: 0, .. } = self.depreciation_method {
+            return Err("Units-of-production assets must expect a positive number of units".into());
+        }
+        Ok(())
+    }
 }
 
 /// A discrete economic event affecting intelligence capital
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CapitalEvent {
-    pub event_id: uuid::Uuid,
-    pub asset_id: uuid::Uuid,
+    pub event_id: EventId,
+    pub asset_id: AssetId,
     pub event_type: String,
     pub timestamp: DateTime<Utc>,
     pub details: HashMap<String, serde_json::Value>,
+    /// Position in the ledger's write order, assigned when recorded
+    #[serde(default)]
+    pub sequence: u64,
+    /// Hash of the event recorded immediately before this one
+    #[serde(default)]
+    pub previous_hash: Option<String>,
+    /// Hash sealing this event, assigned by the ledger when recorded
+    #[serde(default)]
+    pub record_hash: Option<String>,
+    /// Who performed the operation that produced this event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Namespace of the event's asset, assigned by the ledger when recorded
+    #[serde(default, skip_serializing_if = "OrgId::is_default")]
+    pub org_id: OrgId,
+    /// Date the record takes effect in reports, when it differs from `timestamp`, e.g. a
+    /// depreciation posted today for last month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_date: Option<DateTime<Utc>>,
+    /// When the ledger wrote the record, assigned on write; write ordering is checked on it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recorded_at: Option<DateTime<Utc>>,
+    /// Posting dimensions such as `cost_center`, checked against the ledger's registered
+    /// dimensions on write; registered dimensions the event leaves out are taken from its asset
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dimensions: BTreeMap<String, String>,
+}
+
+impl CapitalEvent {
+    pub fn new(
+        asset_id: impl Into<AssetId>,
+        event_type: impl Into<String>,
+        timestamp: DateTime<Utc>,
+        details: HashMap<String, serde_json::Value>
+    ) -> Self {
+        Self {
+            event_id: EventId::random(),
+            asset_id: asset_id.into(),
+            event_type: event_type.into(),
+            timestamp,
+            details,
+            sequence: 0,
+            previous_hash: None,
+            record_hash: None,
+            org_id: OrgId::default(),
+            actor: None,
+            effective_date: None,
+            recorded_at: None,
+            dimensions: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_id(mut self, event_id: impl Into<EventId>) -> Self {
+        self.event_id = event_id.into();
+        self
+    }
+
+    pub fn with_effective_date(mut self, effective_date: DateTime<Utc>) -> Self {
+        self.effective_date = Some(effective_date);
+        self
+    }
+
+    pub fn with_dimension(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.dimensions.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn effective_date(&self) -> DateTime<Utc> {
+        self.effective_date.unwrap_or(self.timestamp)
+    }
+
+    /// When the event was written: its recording time, else (for records from before recording
+    /// times) its timestamp
+    pub fn recorded_at(&self) -> DateTime<Utc> {
+        self.recorded_at.unwrap_or(self.timestamp)
+    }
 }
 
 /// Immutable ledger entry derived from capital events
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LedgerEntry {
-    pub entry_id: uuid::Uuid,
-    pub event_id: uuid::Uuid,
-    pub asset_id: uuid::Uuid,
+    pub entry_id: EntryId,
+    pub event_id: EventId,
+    pub asset_id: AssetId,
     pub timestamp: DateTime<Utc>,
     pub amount: f64,
     pub description: String,
     pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub sequence: u64,
+    #[serde(default)]
+    pub previous_hash: Option<String>,
+    #[serde(default)]
+    pub record_hash: Option<String>,
+    /// Actor of the event this entry was derived from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Effective date of the event this entry was derived from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_date: Option<DateTime<Utc>>,
+    /// Recording time of the event this entry was derived from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recorded_at: Option<DateTime<Utc>>,
+}
+
+impl LedgerEntry {
+    pub fn effective_date(&self) -> DateTime<Utc> {
+        self.effective_date.unwrap_or(self.timestamp)
+    }
+
+    pub fn recorded_at(&self) -> DateTime<Utc> {
+        self.recorded_at.unwrap_or(self.timestamp)
+    }
 }
 
 /// Double-entry accounting journal entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct JournalEntry {
-    pub entry_id: uuid::Uuid,
-    pub event_id: uuid::Uuid,
+    pub entry_id: EntryId,
+    pub event_id: EventId,
     pub timestamp: DateTime<Utc>,
     pub debit_account: AccountType,
     pub credit_account: AccountType,
     pub amount: f64,
     pub description: String,
     pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub previous_hash: Option<String>,
+    #[serde(default)]
+    pub record_hash: Option<String>,
+    /// Namespace of the entry's event, assigned by the ledger when recorded
+    #[serde(default, skip_serializing_if = "OrgId::is_default")]
+    pub org_id: OrgId,
+    /// Who posted the entry; the actor of its event unless set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Date the entry posts to in reports and exports; the effective date of its event unless set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_date: Option<DateTime<Utc>>,
+    /// When the ledger wrote the entry, assigned on write
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recorded_at: Option<DateTime<Utc>>,
+    /// Posting dimensions such as `cost_center`; the dimensions of its event unless set
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dimensions: BTreeMap<String, String>,
+}
+
+impl JournalEntry {
+    pub fn new(
+        event_id: impl Into<EventId>,
+        timestamp: DateTime<Utc>,
+        debit_account: AccountType,
+        credit_account: AccountType,
+        amount: f64,
+        description: impl Into<String>,
+        metadata: HashMap<String, serde_json::Value>
+    ) -> Self {
+        Self {
+            entry_id: EntryId::random(),
+            event_id: event_id.into(),
+            timestamp,
+            debit_account,
+            credit_account,
+            amount,
+            description: description.into(),
+            metadata,
+            previous_hash: None,
+            record_hash: None,
+            org_id: OrgId::default(),
+            actor: None,
+            effective_date: None,
+            recorded_at: None,
+            dimensions: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_id(mut self, entry_id: impl Into<EntryId>) -> Self {
+        self.entry_id = entry_id.into();
+        self
+    }
+
+    pub fn with_effective_date(mut self, effective_date: DateTime<Utc>) -> Self {
+        self.effective_date = Some(effective_date);
+        self
+    }
+
+    pub fn with_dimension(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.dimensions.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn effective_date(&self) -> DateTime<Utc> {
+        self.effective_date.unwrap_or(self.timestamp)
+    }
+
+    pub fn recorded_at(&self) -> DateTime<Utc> {
+        self.recorded_at.unwrap_or(self.timestamp)
+    }
 }
 
 /// Machine-verifiable proof of capital state for audit purposes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CapitalProof {
     pub proof_id: uuid::Uuid,
-    pub asset_id: uuid::Uuid,
-    pub event_id: Option<uuid::Uuid>,
+    pub asset_id: AssetId,
+    pub event_id: Option<EventId>,
     pub timestamp: DateTime<Utc>,
     pub origin: String,
-    pub content: HashMap<String, serde_json::Value>,
+    pub content: BTreeMap<String, serde_json::Value>,
     pub previous_proof_hash: Option<String>,
     pub proof_hash: Option<String>,
+    /// Hash scheme the proof was sealed with; proofs serialized before versioning default to legacy
+    #[serde(default = "legacy_proof_hash_version")]
+    pub hash_version: u32,
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    /// Digests of content fields rewritten after sealing (e.g. by owner anonymization), standing
+    /// in for the rewritten values so the proof hash still verifies
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub redacted: BTreeMap<String, String>,
+    /// Order the content keys had when a legacy proof was sealed, which its hash depends on;
+    /// taken from the order they are read in unless recorded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub legacy_content_order: Vec<String>,
+}
+
+fn legacy_proof_hash_version() -> u32 {
+    PROOF_HASH_VERSION_LEGACY
+}
+
+struct OrderedContent(Vec<(String, serde_json::Value)>);
+
+impl<'de> Deserialize<'de> for OrderedContent {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OrderedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for OrderedVisitor {
+            type Value = OrderedContent;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "a map of proof content")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<OrderedContent, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(OrderedContent(entries))
+            }
+        }
+
+        deserializer.deserialize_map(OrderedVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+struct CapitalProofState {
+    proof_id: uuid::Uuid,
+    asset_id: AssetId,
+    event_id: Option<EventId>,
+    timestamp: DateTime<Utc>,
+    origin: String,
+    content: OrderedContent,
+    previous_proof_hash: Option<String>,
+    proof_hash: Option<String>,
+    #[serde(default = "legacy_proof_hash_version")]
+    hash_version: u32,
+    #[serde(default)]
+    hash_algorithm: HashAlgorithm,
+    #[serde(default)]
+    redacted: BTreeMap<String, String>,
+    #[serde(default)]
+    legacy_content_order: Vec<String>,
+}
+
+impl<'de> Deserialize<'de> for CapitalProof {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = CapitalProofState::deserialize(deserializer)?;
+        let legacy_content_order = match state.hash_version {
+            PROOF_HASH_VERSION_LEGACY if state.legacy_content_order.is_empty() => {
+                state.content.0.iter().map(|(key, _)| key.clone()).collect()
+            },
+            _ => state.legacy_content_order,
+        };
+        Ok(Self {
+            proof_id: state.proof_id,
+            asset_id: state.asset_id,
+            event_id: state.event_id,
+            timestamp: state.timestamp,
+            origin: state.origin,
+            content: state.content.0.into_iter().collect(),
+            previous_proof_hash: state.previous_proof_hash,
+            proof_hash: state.proof_hash,
+            hash_version: state.hash_version,
+            hash_algorithm: state.hash_algorithm,
+            redacted: state.redacted,
+            legacy_content_order,
+        })
+    }
 }
 
 impl CapitalProof {
-    pub fn compute_hash(&self) -> String {
-        use sha2::{Sha256, Digest};
-        let mut hasher = Sha256::new();
-        let content_str = serde_json::to_string(&self.content).unwrap_or_default();
+    pub fn compute_hash(&self) -> IclResult<String> {
+        match self.hash_version {
+            PROOF_HASH_VERSION_LEGACY => self.compute_legacy_hash(),
+            _ => Ok(self.compute_canonical_hash()),
+        }
+    }
+
+    fn compute_legacy_hash(&self) -> IclResult<String> {
+        let keys = self.legacy_content_order.iter()
+            .filter(|key| self.content.contains_key(*key))
+            .chain(self.content.keys().filter(|key| !self.legacy_content_order.contains(key)));
+        let mut fields = Vec::new();
+        for key in keys {
+            let value = serde_json::to_string(&self.content[key]).map_err(|e| IclError::HashingError(format!(
+                "Cannot serialize content of proof {}: {}", self.proof_id, e
+            )))?;
+            fields.push(format!("{}:{}", serde_json::Value::String(key.clone()), value));
+        }
+        let content_str = format!("{{{}}}", fields.join(","));
         let hash_input = format!(
             "{}{}{}{}",
             self.proof_id,
@@ -127,8 +984,147 @@ impl CapitalProof {
             content_str,
             self.previous_proof_hash.as_ref().unwrap_or(&String::new())
         );
-        hasher.update(hash_input.as_bytes());
-        let result = hasher.finalize();
-        format!("{:x}", result)
+        Ok(sha256_hex(hash_input.as_bytes()))
+    }
+
+    fn compute_canonical_hash(&self) -> String {
+        let envelope = serde_json::json!({
+            "hash_version": self.hash_version,
+            "hash_algorithm": self.hash_algorithm.to_string(),
+            "proof_id": self.proof_id.to_string(),
+            "asset_id": self.asset_id.to_string(),
+            "event_id": self.event_id.map(|id| id.to_string()),
+            "timestamp": self.timestamp.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            "origin": &self.origin,
+            "content": self.content_digests(),
+            "previous_proof_hash": &self.previous_proof_hash,
+        });
+        self.hash_algorithm.digest_hex(canonical_json(&envelope).as_bytes())
+    }
+
+    pub fn content_digests(&self) -> BTreeMap<String, String> {
+        self.content.iter()
+            .map(|(key, value)| {
+                let digest = self.redacted.get(key).cloned().unwrap_or_else(|| {
+                    let leaf = format!("{}:{}:{}", self.proof_id, key, canonical_json(value));
+                    self.hash_algorithm.digest_hex(leaf.as_bytes())
+                });
+                (key.clone(), digest)
+            })
+            .collect()
+    }
+
+    /// Replace a content field without changing the proof hash, keeping the digest of the original
+    /// value; never the event digest, which is what ties the proof to its event
+    pub fn redact_field(&mut self, key: &str, replacement: serde_json::Value) -> bool {
+        if self.hash_version == PROOF_HASH_VERSION_LEGACY || key == EVENT_DIGEST_CONTENT || !self.content.contains_key(key) {
+            return false;
+        }
+        if !self.redacted.contains_key(key) {
+            let digest = self.content_digests().remove(key).unwrap_or_default();
+            self.redacted.insert(key.to_string(), digest);
+        }
+        self.content.insert(key.to_string(), replacement);
+        true
+    }
+
+    pub fn verify_hash(&self) -> IclResult<bool> {
+        Ok(self.proof_hash.as_deref() == Some(self.compute_hash()?.as_str()))
+    }
+
+    /// Re-seal the proof under the current hash scheme
+    pub fn upgrade_hash_version(&mut self) -> IclResult<()> {
+        if !self.verify_hash()? {
+            return Err(IclError::IntegrityViolation(format!(
+                "Proof {} does not match its stored hash and cannot be re-sealed", self.proof_id
+            )));
+        }
+        self.reseal(self.previous_proof_hash.clone())
+    }
+
+    pub(crate) fn reseal(&mut self, previous_proof_hash: Option<String>) -> IclResult<()> {
+        self.previous_proof_hash = previous_proof_hash;
+        self.hash_version = PROOF_HASH_VERSION;
+        self.legacy_content_order.clear();
+        self.proof_hash = Some(self.compute_hash()?);
+        Ok(())
+    }
+}
+
+/// State commitment for one asset inside a portfolio proof
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioLeaf {
+    pub asset_id: AssetId,
+    pub state_hash: String,
+}
+
+/// Single Merkle-rooted proof committing to the state of many assets over a period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioProof {
+    pub proof_id: uuid::Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub origin: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub leaves: Vec<PortfolioLeaf>,
+    pub merkle_root: String,
+    pub previous_proof_hash: Option<String>,
+    pub proof_hash: Option<String>,
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    #[serde(default, skip_serializing_if = "OrgId::is_default")]
+    pub org_id: OrgId,
+}
+
+#[derive(Serialize)]
+struct PortfolioProofEnvelope<'a> {
+    hash_algorithm: String,
+    proof_id: uuid::Uuid,
+    timestamp: String,
+    origin: &'a str,
+    period_start: String,
+    period_end: String,
+    merkle_root: &'a str,
+    previous_proof_hash: &'a Option<String>,
+    #[serde(skip_serializing_if = "OrgId::is_default")]
+    org_id: &'a OrgId,
+}
+
+impl PortfolioProof {
+    pub fn compute_hash(&self) -> IclResult<String> {
+        let envelope = PortfolioProofEnvelope {
+            hash_algorithm: self.hash_algorithm.to_string(),
+            proof_id: self.proof_id,
+            timestamp: self.timestamp.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            origin: &self.origin,
+            period_start: self.period_start.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            period_end: self.period_end.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            merkle_root: &self.merkle_root,
+            previous_proof_hash: &self.previous_proof_hash,
+            org_id: &self.org_id,
+        };
+        let envelope = serde_json::to_value(&envelope).map_err(|e| IclError::HashingError(format!(
+            "Cannot serialize portfolio proof {}: {}", self.proof_id, e
+        )))?;
+        Ok(self.hash_algorithm.digest_hex(canonical_json(&envelope).as_bytes()))
     }
-}
\ No newline at end of file
+
+    fn leaf_values(&self) -> Vec<String> {
+        self.leaves.iter().map(|l| format!("{}:{}", l.asset_id, l.state_hash)).collect()
+    }
+
+    pub fn inclusion_path(&self, asset_id: AssetId) -> Option<Vec<MerkleStep>> {
+        let index = self.leaves.iter().position(|l| l.asset_id == asset_id)?;
+        merkle_path(self.hash_algorithm, &self.leaf_values(), index)
+    }
+
+    /// Check that an asset state hash is committed to by this proof's root
+    pub fn verify_inclusion(&self, asset_id: AssetId, state_hash: &str, path: &[MerkleStep]) -> bool {
+        verify_merkle_path(self.hash_algorithm, &format!("{}:{}", asset_id, state_hash), path, &self.merkle_root)
+    }
+
+    pub fn verify(&self) -> bool {
+        merkle_root(self.hash_algorithm, &self.leaf_values()).as_deref() == Some(self.merkle_root.as_str())
+            && self.compute_hash().is_ok_and(|hash| self.proof_hash.as_deref() == Some(hash.as_str()))
+    }
+}
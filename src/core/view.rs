@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+
+use crate::core::types::*;
+use crate::core::ledger::{AssetPosition, IntelligenceCapitalLedger, PortfolioSummary};
+
+/// Read-only view of the ledger as it stood at `as_of`: only records effective at or before
+/// that instant are visible, and asset state is reconstructed from those records
+#[derive(Debug, Clone, Copy)]
+pub struct LedgerView<'a> {
+    ledger: &'a IntelligenceCapitalLedger,
+    as_of: DateTime<Utc>,
+}
+
+impl<'a> LedgerView<'a> {
+    pub fn new(ledger: &'a IntelligenceCapitalLedger, as_of: DateTime<Utc>) -> Self {
+        Self { ledger, as_of }
+    }
+
+    pub fn as_of(&self) -> DateTime<Utc> {
+        self.as_of
+    }
+
+    pub fn asset_position(&self, asset_id: AssetId) -> Option<AssetPosition> {
+        self.ledger.asset_position(asset_id, self.as_of)
+    }
+
+    pub fn get_asset(&self, asset_id: AssetId) -> Option<IntelligenceAsset> {
+        let position = self.asset_position(asset_id)?;
+        let mut asset = self.ledger.get_asset(asset_id)?.clone();
+        asset.owner = position.owner;
+        asset.status = position.status;
+        asset.current_value = Some(position.book_value);
+        Some(asset)
+    }
+
+    pub fn assets(&self) -> Vec<IntelligenceAsset> {
+        let mut assets: Vec<IntelligenceAsset> = self.ledger.assets_iter()
+            .filter_map(|a| self.get_asset(a.asset_id))
+            .collect();
+        assets.sort_by_key(|a| (a.created_at, a.asset_id));
+        assets
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &'a CapitalEvent> + '_ {
+        self.ledger.events().iter().filter(move |e| e.effective_date() <= self.as_of)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &'a LedgerEntry> + '_ {
+        self.ledger.entries().iter().filter(move |e| e.effective_date() <= self.as_of)
+    }
+
+    pub fn journal_entries(&self) -> impl Iterator<Item = &'a JournalEntry> + '_ {
+        self.ledger.journal_entries().iter().filter(move |j| j.effective_date() <= self.as_of)
+    }
+
+    pub fn get_events_for_asset(&self, asset_id: AssetId) -> Vec<&'a CapitalEvent> {
+        self.ledger.get_events_for_asset(asset_id).into_iter()
+            .filter(|e| e.effective_date() <= self.as_of)
+            .collect()
+    }
+
+    /// Net balance per account (debits positive) over the journal entries visible at `as_of`
+    /// and those of periods archived by then, excluding quarantined entries
+    pub fn account_balances(&self) -> HashMap<AccountType, f64> {
+        let mut balances = HashMap::new();
+        for archive in self.ledger.archives().iter().filter(|a| a.period.has_ended_by(self.as_of)) {
+            for (account, balance) in &archive.account_balances {
+                *balances.entry(*account).or_default() += balance;
+            }
+        }
+        for entry in self.journal_entries().filter(|j| !self.ledger.is_quarantined(j.entry_id)) {
+            *balances.entry(entry.debit_account).or_default() += entry.amount;
+            *balances.entry(entry.credit_account).or_default() -= entry.amount;
+        }
+        balances
+    }
+
+    pub fn portfolio_summary(&self) -> PortfolioSummary {
+        self.ledger.portfolio_summary(self.as_of)
+    }
+}
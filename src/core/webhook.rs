@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::core::types::*;
+use crate::core::integration::EventSink;
+use crate::core::error::*;
+
+/// Header carrying the hex HMAC-SHA256 of the request body
+pub const SIGNATURE_HEADER: &str = "X-ICL-Signature";
+
+/// Posts each emitted record as JSON to the configured URLs, signed with a shared secret
+pub struct WebhookEmitter {
+    urls: Vec<String>,
+    secret: Vec<u8>,
+    max_retries: u32,
+    initial_backoff: Duration,
+    agent: ureq::Agent,
+}
+
+impl WebhookEmitter {
+    pub fn new(urls: Vec<String>, secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            urls,
+            secret: secret.into(),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(10)).build(),
+        }
+    }
+
+    /// Retry failed deliveries up to `max_retries` times, doubling the wait after each attempt
+    pub fn with_retry(mut self, max_retries: u32, initial_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn sign(&self, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret)
+            .expect("HMAC accepts keys of any length");
+        mac.update(body);
+        format!("sha256={:x}", mac.finalize().into_bytes())
+    }
+
+    fn post<T: Serialize>(&self, kind: &str, record: &T) -> IclResult<()> {
+        let body = serde_json::to_string(&serde_json::json!({
+            "kind": kind,
+            "data": record,
+        }))?;
+        let signature = self.sign(body.as_bytes());
+
+        for url in &self.urls {
+            self.post_with_retry(url, &body, &signature)?;
+        }
+        Ok(())
+    }
+
+    fn post_with_retry(&self, url: &str, body: &str, signature: &str) -> IclResult<()> {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            let result = self.agent.post(url)
+                .set("Content-Type", "application/json")
+                .set(SIGNATURE_HEADER, signature)
+                .send_string(body);
+
+            let retryable = match result {
+                Ok(_) => return Ok(()),
+                Err(ureq::Error::Status(code, _)) => code == 429 || code >= 500,
+                Err(ureq::Error::Transport(_)) => true,
+            };
+
+            if !retryable || attempt >= self.max_retries {
+                return Err(IclError::IntegrationError(format!(
+                    "Webhook delivery to {} failed after {} attempt(s)", url, attempt + 1
+                )));
+            }
+
+            std::thread::sleep(backoff);
+            backoff *= 2;
+            attempt += 1;
+        }
+    }
+}
+
+impl std::fmt::Debug for WebhookEmitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookEmitter")
+            .field("urls", &self.urls)
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .finish()
+    }
+}
+
+impl EventSink for WebhookEmitter {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn emit_event(&self, event: &CapitalEvent) -> IclResult<()> {
+        self.post("capital_event", event)
+    }
+
+    fn emit_journal_entry(&self, journal_entry: &JournalEntry) -> IclResult<()> {
+        self.post("journal_entry", journal_entry)
+    }
+}
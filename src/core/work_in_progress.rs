@@ -0,0 +1,108 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::types::*;
+
+/// Kinds of cost accumulated while an asset is under construction
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum CostCategory {
+    Compute,
+    DataLabeling,
+    Engineering,
+    Other,
+}
+
+impl std::fmt::Display for CostCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CostCategory::Compute => write!(f, "Compute"),
+            CostCategory::DataLabeling => write!(f, "DataLabeling"),
+            CostCategory::Engineering => write!(f, "Engineering"),
+            CostCategory::Other => write!(f, "Other"),
+        }
+    }
+}
+
+/// One cost accrued to a construction-in-progress account
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WipCost {
+    pub cost_id: Uuid,
+    pub category: CostCategory,
+    pub amount: f64,
+    pub description: String,
+    pub incurred_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+}
+
+impl WipCost {
+    pub fn new(category: CostCategory, amount: f64, description: impl Into<String>) -> Self {
+        Self {
+            cost_id: Uuid::nil(),
+            category,
+            amount,
+            description: description.into(),
+            incurred_at: None,
+            reference: None,
+        }
+    }
+
+    pub fn incurred_at(mut self, incurred_at: DateTime<Utc>) -> Self {
+        self.incurred_at = Some(incurred_at);
+        self
+    }
+
+    pub fn with_reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum WipStatus {
+    Open,
+    PlacedInService(AssetId),
+}
+
+impl std::fmt::Display for WipStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WipStatus::Open => write!(f, "Open"),
+            WipStatus::PlacedInService(asset_id) => write!(f, "PlacedInService({})", asset_id),
+        }
+    }
+}
+
+/// Costs accumulated for an asset that is not yet in service, such as a model still in training
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapitalWorkInProgress {
+    pub wip_id: Uuid,
+    pub name: String,
+    pub owner: OwnerId,
+    #[serde(default, skip_serializing_if = "OrgId::is_default")]
+    pub org_id: OrgId,
+    pub opened_at: DateTime<Utc>,
+    pub status: WipStatus,
+    pub costs: Vec<WipCost>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placed_in_service_at: Option<DateTime<Utc>>,
+}
+
+impl CapitalWorkInProgress {
+    pub fn total_cost(&self) -> f64 {
+        self.costs.iter().map(|c| c.amount).sum()
+    }
+
+    pub fn cost_by_category(&self) -> std::collections::BTreeMap<String, f64> {
+        let mut totals = std::collections::BTreeMap::new();
+        for cost in &self.costs {
+            *totals.entry(cost.category.to_string()).or_default() += cost.amount;
+        }
+        totals
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status == WipStatus::Open
+    }
+}
@@ -0,0 +1,278 @@
+use chrono::{DateTime, Utc};
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::reports::*;
+use crate::core::formatting::NumberFormat;
+use crate::core::error::*;
+
+const AMOUNT_FORMAT: &str = "#,##0.00";
+const DATETIME_FORMAT: &str = "yyyy-mm-dd hh:mm:ss";
+
+enum Cell {
+    Text(String),
+    Amount(f64),
+    Integer(i64),
+    DateTime(DateTime<Utc>),
+    Empty,
+}
+
+impl From<String> for Cell {
+    fn from(value: String) -> Self {
+        Cell::Text(value)
+    }
+}
+
+impl From<f64> for Cell {
+    fn from(value: f64) -> Self {
+        Cell::Amount(value)
+    }
+}
+
+impl From<DateTime<Utc>> for Cell {
+    fn from(value: DateTime<Utc>) -> Self {
+        Cell::DateTime(value)
+    }
+}
+
+impl<T: Into<Cell>> From<Option<T>> for Cell {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Cell::Empty, Into::into)
+    }
+}
+
+/// Report written as an XLSX workbook
+pub trait ReportXlsx {
+    fn write_xlsx(&self, amount_format: &str) -> IclResult<Vec<u8>>;
+
+    fn to_xlsx(&self) -> IclResult<Vec<u8>> {
+        self.write_xlsx(AMOUNT_FORMAT)
+    }
+
+    fn to_xlsx_with(&self, number_format: &NumberFormat) -> IclResult<Vec<u8>> {
+        self.write_xlsx(&number_format.xlsx_num_format())
+    }
+}
+
+fn xlsx_error(e: XlsxError) -> IclError {
+    IclError::SerializationError(format!("XLSX: {}", e))
+}
+
+fn write_sheet(
+    workbook: &mut Workbook,
+    name: &str,
+    headers: &[&str],
+    rows: Vec<Vec<Cell>>,
+    amount_format: &str,
+) -> IclResult<()> {
+    let header = Format::new().set_bold();
+    let amount = Format::new().set_num_format(amount_format);
+    let datetime = Format::new().set_num_format(DATETIME_FORMAT);
+
+    let sheet = workbook.add_worksheet();
+    sheet.set_name(name).map_err(xlsx_error)?;
+    for (col, title) in headers.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *title, &header).map_err(xlsx_error)?;
+    }
+    for (index, row) in rows.into_iter().enumerate() {
+        let row_num = index as u32 + 1;
+        for (col, cell) in row.into_iter().enumerate() {
+            let col = col as u16;
+            match cell {
+                Cell::Text(value) => sheet.write_string(row_num, col, value),
+                Cell::Amount(value) => sheet.write_number_with_format(row_num, col, value, &amount),
+                Cell::Integer(value) => sheet.write_number(row_num, col, value as f64),
+                Cell::DateTime(value) => sheet.write_datetime_with_format(row_num, col, value.naive_utc(), &datetime),
+                Cell::Empty => continue,
+            }.map_err(xlsx_error)?;
+        }
+    }
+    sheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+    sheet.autofit();
+    Ok(())
+}
+
+fn finish(mut workbook: Workbook) -> IclResult<Vec<u8>> {
+    workbook.save_to_buffer().map_err(xlsx_error)
+}
+
+impl IntelligenceCapitalLedger {
+    /// Audit trail as an XLSX workbook with one sheet per record type
+    pub fn export_audit_trail_xlsx(&self) -> IclResult<Vec<u8>> {
+        let mut workbook = Workbook::new();
+
+        let mut assets: Vec<_> = self.assets_iter().collect();
+        assets.sort_by_key(|a| (a.created_at, a.asset_id));
+        write_sheet(
+            &mut workbook,
+            "Assets",
+            &["asset_id", "owner", "category", "status", "depreciation_method", "useful_life_months", "initial_value", "current_value", "created_at"],
+            assets.into_iter().map(|a| vec![
+                a.asset_id.to_string().into(),
+                a.owner.to_string().into(),
+                a.category.clone().into(),
+                a.status.to_string().into(),
+                a.depreciation_method.to_string().into(),
+                Cell::Integer(a.useful_life_months.into()),
+                a.initial_value.into(),
+                a.current_value.into(),
+                a.created_at.into(),
+            ]).collect(),
+            AMOUNT_FORMAT,
+        )?;
+
+        write_sheet(
+            &mut workbook,
+            "Events",
+            &["sequence", "event_id", "asset_id", "event_type", "timestamp", "amount", "details", "actor", "record_hash"],
+            self.events().iter().map(|e| vec![
+                Cell::Integer(e.sequence as i64),
+                e.event_id.to_string().into(),
+                e.asset_id.to_string().into(),
+                e.event_type.clone().into(),
+                e.timestamp.into(),
+                e.details.get("amount").and_then(|v| v.as_f64()).into(),
+                serde_json::to_string(&e.details).unwrap_or_default().into(),
+                e.actor.clone().into(),
+                e.record_hash.clone().into(),
+            ]).collect(),
+            AMOUNT_FORMAT,
+        )?;
+
+        write_sheet(
+            &mut workbook,
+            "Entries",
+            &["sequence", "entry_id", "event_id", "asset_id", "timestamp", "amount", "description", "actor"],
+            self.entries().iter().filter(|e| !self.is_quarantined(e.entry_id)).map(|e| vec![
+                Cell::Integer(e.sequence as i64),
+                e.entry_id.to_string().into(),
+                e.event_id.to_string().into(),
+                e.asset_id.to_string().into(),
+                e.timestamp.into(),
+                e.amount.into(),
+                e.description.clone().into(),
+                e.actor.clone().into(),
+            ]).collect(),
+            AMOUNT_FORMAT,
+        )?;
+
+        write_sheet(
+            &mut workbook,
+            "Journal Entries",
+            &["entry_id", "event_id", "timestamp", "debit_account", "credit_account", "amount", "description", "actor"],
+            self.journal_entries().iter().filter(|j| !self.is_quarantined(j.entry_id)).map(|j| vec![
+                j.entry_id.to_string().into(),
+                j.event_id.to_string().into(),
+                j.timestamp.into(),
+                j.debit_account.to_string().into(),
+                j.credit_account.to_string().into(),
+                j.amount.into(),
+                j.description.clone().into(),
+                j.actor.clone().into(),
+            ]).collect(),
+            AMOUNT_FORMAT,
+        )?;
+
+        write_sheet(
+            &mut workbook,
+            "Proofs",
+            &["proof_id", "asset_id", "event_id", "timestamp", "hash_algorithm", "proof_hash", "previous_proof_hash"],
+            self.proofs().iter().filter(|p| !self.is_quarantined(p.proof_id)).map(|p| vec![
+                p.proof_id.to_string().into(),
+                p.asset_id.to_string().into(),
+                p.event_id.map(|id| id.to_string()).into(),
+                p.timestamp.into(),
+                p.hash_algorithm.to_string().into(),
+                p.proof_hash.clone().into(),
+                p.previous_proof_hash.clone().into(),
+            ]).collect(),
+            AMOUNT_FORMAT,
+        )?;
+
+        finish(workbook)
+    }
+}
+
+impl ReportXlsx for AssetRegister {
+    fn write_xlsx(&self, amount_format: &str) -> IclResult<Vec<u8>> {
+        let mut workbook = Workbook::new();
+        write_sheet(
+            &mut workbook,
+            "Asset Register",
+            &["asset_id", "owner", "owner_name", "category", "status", "depreciation_method", "created_at", "cost", "additions",
+              "accumulated_depreciation", "net_book_value", "useful_life_months", "remaining_life_months"],
+            self.rows.iter().map(|r| vec![
+                r.asset_id.to_string().into(),
+                r.owner.to_string().into(),
+                r.owner_name.clone().into(),
+                r.category.clone().into(),
+                r.status.to_string().into(),
+                r.depreciation_method.to_string().into(),
+                r.created_at.into(),
+                r.cost.into(),
+                r.additions.into(),
+                r.accumulated_depreciation.into(),
+                r.net_book_value.into(),
+                Cell::Integer(r.useful_life_months.into()),
+                Cell::Integer(r.remaining_life_months.into()),
+            ]).collect(),
+            amount_format,
+        )?;
+        finish(workbook)
+    }
+}
+
+impl ReportXlsx for DepreciationByPeriod {
+    fn write_xlsx(&self, amount_format: &str) -> IclResult<Vec<u8>> {
+        let mut workbook = Workbook::new();
+        let group = self.group_by.to_string().to_lowercase();
+        let mut headers: Vec<&str> = vec![&group];
+        headers.extend(self.periods.iter().map(|p| p.label.as_str()));
+        headers.push("total");
+        write_sheet(
+            &mut workbook,
+            "Depreciation",
+            &headers,
+            self.rows.iter().map(|r| {
+                let mut cells: Vec<Cell> = vec![r.group.clone().into()];
+                cells.extend(r.amounts.iter().map(|a| Cell::Amount(*a)));
+                cells.push(r.total.into());
+                cells
+            }).collect(),
+            amount_format,
+        )?;
+        finish(workbook)
+    }
+}
+
+impl ReportXlsx for CapitalRollforward {
+    fn write_xlsx(&self, amount_format: &str) -> IclResult<Vec<u8>> {
+        let mut workbook = Workbook::new();
+        let row = |id: String, m: &RollforwardMovements, recorded: f64, difference: f64| vec![
+            id.into(),
+            m.opening.into(),
+            m.additions.into(),
+            m.improvements.into(),
+            m.depreciation.into(),
+            m.impairments.into(),
+            m.disposals.into(),
+            m.closing.into(),
+            recorded.into(),
+            difference.into(),
+        ];
+        let mut rows: Vec<Vec<Cell>> = self.lines.iter()
+            .map(|l| row(l.asset_id.to_string(), &l.movements, l.recorded_closing, l.difference))
+            .collect();
+        let recorded: f64 = self.lines.iter().map(|l| l.recorded_closing).sum();
+        rows.push(row("total".to_string(), &self.total, recorded, recorded - self.total.closing));
+        write_sheet(
+            &mut workbook,
+            "Rollforward",
+            &["asset_id", "opening", "additions", "improvements", "depreciation", "impairments", "disposals",
+              "closing", "recorded_closing", "difference"],
+            rows,
+            amount_format,
+        )?;
+        finish(workbook)
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::types::*;
+use crate::core::ledger::IntelligenceCapitalLedger;
+use crate::core::hashing::{canonical_json, HashAlgorithm};
+use crate::core::integrity::VALUE_TOLERANCE;
+use crate::core::error::{IclError, IclResult, IclResultExt};
+
+/// Event type recorded by `close_year` for each asset with income statement balances to close,
+/// effective at the last instant of the fiscal year
+pub const YEAR_END_CLOSE_EVENT: &str = "year_end_close";
+
+/// A closed fiscal year: the closing postings, the balances carried into the next year, and a
+/// proof hash over them and the record chain heads at the close, chained to the previous close
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct YearEndClose {
+    pub close_id: Uuid,
+    pub fiscal_year: FiscalPeriod,
+    pub closed_at: DateTime<Utc>,
+    pub closing_event_ids: Vec<EventId>,
+    pub closing_entry_ids: Vec<EntryId>,
+    pub net_income: f64,
+    pub opening_balances: HashMap<AccountType, f64>,
+    pub event_chain_head: Option<String>,
+    pub entry_chain_head: Option<String>,
+    pub journal_chain_head: Option<String>,
+    pub previous_proof_hash: Option<String>,
+    pub hash_algorithm: HashAlgorithm,
+    pub proof_hash: String,
+}
+
+impl YearEndClose {
+    pub fn compute_hash(&self) -> IclResult<String> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("proof_hash");
+        }
+        Ok(self.hash_algorithm.digest_hex(canonical_json(&value).as_bytes()))
+    }
+
+    pub fn verify(&self) -> IclResult<bool> {
+        Ok(self.compute_hash()? == self.proof_hash)
+    }
+}
+
+impl IntelligenceCapitalLedger {
+    /// Close a fiscal year
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug", skip_all, fields(fiscal_year = %fiscal_year, duration_us = tracing::field::Empty), err
+    ))]
+    pub fn close_year(&mut self, fiscal_year: FiscalPeriod) -> IclResult<YearEndClose> {
+        #[cfg(feature = "tracing")]
+        let _timer = crate::core::telemetry::SpanTimer::start();
+        fiscal_year.validate()?;
+        if let Some(close) = self.year_end_closes().iter().find(|c| c.fiscal_year.overlaps(fiscal_year.start, fiscal_year.end)) {
+            return Err(IclError::IntegrityViolation(format!(
+                "Fiscal year {} overlaps {}, which is already closed", fiscal_year, close.fiscal_year
+            )));
+        }
+        // Years are closed back to back, so no activity falls between two closes or before the first
+        match self.year_end_closes().iter().map(|c| c.fiscal_year.end).max() {
+            Some(last_close) if fiscal_year.start != last_close => {
+                return Err(IclError::IntegrityViolation(format!(
+                    "Fiscal year {} must start at {}, where the last closed year ended", fiscal_year, last_close.to_rfc3339()
+                )));
+            },
+            Some(_) => {},
+            None => {
+                let first_record = self.journal_entries().iter().map(|j| j.effective_date())
+                    .chain(self.archives().iter().map(|a| a.period.start))
+                    .min();
+                if let Some(first_record) = first_record.filter(|first| *first < fiscal_year.start) {
+                    return Err(IclError::IntegrityViolation(format!(
+                        "Fiscal year {} starts after the first record at {}; close the earlier year first",
+                        fiscal_year, first_record.to_rfc3339()
+                    )));
+                }
+            },
+        }
+        if let Some(archive) = self.archives().iter().find(|a| a.period.overlaps(fiscal_year.start, fiscal_year.end)) {
+            return Err(IclError::IntegrityViolation(format!(
+                "Period {} is archived; load archive {} to close the year", archive.period, archive.archive_id
+            )));
+        }
+        if let Some(closed_until) = self.closed_until().filter(|closed_until| fiscal_year.end < *closed_until) {
+            return Err(IclError::IntegrityViolation(format!(
+                "Periods before {} are already closed and cannot be reopened", closed_until.to_rfc3339()
+            )));
+        }
+
+        // Nothing posted for the close is kept if a later posting fails
+        self.atomically(|ledger| ledger.post_year_end_close(fiscal_year))
+    }
+
+    fn post_year_end_close(&mut self, fiscal_year: FiscalPeriod) -> IclResult<YearEndClose> {
+        // Net income statement balances (debits positive) of each asset's entries up to year end
+        let mut to_close: BTreeMap<AssetId, Vec<(AccountType, f64)>> = BTreeMap::new();
+        for entry in self.journal_entries().iter().filter(|j| j.effective_date() < fiscal_year.end) {
+            let Some(event) = self.get_event(entry.event_id).filter(|_| !self.is_quarantined(entry.entry_id)) else {
+                continue;
+            };
+            let balances = to_close.entry(event.asset_id).or_default();
+            for (account, amount) in [(entry.debit_account, entry.amount), (entry.credit_account, -entry.amount)] {
+                if !account.is_income_statement() {
+                    continue;
+                }
+                match balances.iter_mut().find(|(a, _)| *a == account) {
+                    Some((_, balance)) => *balance += amount,
+                    None => balances.push((account, amount)),
+                }
+            }
+        }
+
+        let closing_date = fiscal_year.end - Duration::nanoseconds(1);
+        let (mut closing_event_ids, mut closing_entry_ids, mut net_income) = (Vec::new(), Vec::new(), 0.0);
+        for (asset_id, balances) in to_close {
+            let balances: Vec<(AccountType, f64)> = balances.into_iter()
+                .filter(|(_, balance)| balance.abs() > VALUE_TOLERANCE)
+                .collect();
+            if balances.is_empty() {
+                continue;
+            }
+            let asset_net_income = -balances.iter().map(|(_, balance)| balance).sum::<f64>();
+            let mut details = HashMap::new();
+            details.insert("fiscal_year_start".to_string(), serde_json::json!(fiscal_year.start.to_rfc3339()));
+            details.insert("fiscal_year_end".to_string(), serde_json::json!(fiscal_year.end.to_rfc3339()));
+            details.insert("net_income".to_string(), serde_json::json!(asset_net_income));
+            let event = CapitalEvent::new(asset_id, YEAR_END_CLOSE_EVENT, self.now(), details)
+                .with_id(self.next_id())
+                .with_effective_date(closing_date);
+            let event = self.record_ledger_event(event).with_context(|| "close year: record event", Some(asset_id))?;
+            closing_event_ids.push(event.event_id);
+
+            for (account, balance) in balances {
+                let (debit, credit) = if balance > 0.0 {
+                    (AccountType::RetainedEarnings, account)
+                } else {
+                    (account, AccountType::RetainedEarnings)
+                };
+                let journal_entry = JournalEntry::new(
+                    event.event_id,
+                    self.now(),
+                    debit,
+                    credit,
+                    balance.abs(),
+                    format!("Year-end close of {}", account),
+                    HashMap::from([("asset_id".to_string(), serde_json::json!(asset_id.to_string()))]),
+                ).with_id(self.next_id());
+                let journal_entry = self.record_ledger_journal_entry(journal_entry)
+                    .with_context(|| format!("close year: close {}", account), Some(asset_id))?;
+                closing_entry_ids.push(journal_entry.entry_id);
+            }
+            net_income += asset_net_income;
+        }
+        self.close_period(fiscal_year.end)?;
+
+        let mut opening_balances: HashMap<AccountType, f64> = HashMap::new();
+        for archive in self.archives().iter().filter(|a| a.period.end <= fiscal_year.end) {
+            for (account, balance) in &archive.account_balances {
+                *opening_balances.entry(*account).or_default() += balance;
+            }
+        }
+        for entry in self.journal_entries().iter().filter(|j| j.effective_date() < fiscal_year.end) {
+            if self.is_quarantined(entry.entry_id) {
+                continue;
+            }
+            *opening_balances.entry(entry.debit_account).or_default() += entry.amount;
+            *opening_balances.entry(entry.credit_account).or_default() -= entry.amount;
+        }
+        opening_balances.retain(|_, balance| balance.abs() > VALUE_TOLERANCE);
+
+        let mut close = YearEndClose {
+            close_id: self.next_id(),
+            fiscal_year,
+            closed_at: self.now(),
+            closing_event_ids,
+            closing_entry_ids,
+            net_income,
+            opening_balances,
+            event_chain_head: self.events().last().and_then(|e| e.record_hash.clone()),
+            entry_chain_head: self.entries().last().and_then(|e| e.record_hash.clone()),
+            journal_chain_head: self.journal_entries().last().and_then(|j| j.record_hash.clone()),
+            previous_proof_hash: self.year_end_closes().last().map(|c| c.proof_hash.clone()),
+            hash_algorithm: self.hash_algorithm(),
+            proof_hash: String::new(),
+        };
+        close.proof_hash = close.compute_hash()?;
+        self.push_year_end_close(close.clone());
+        Ok(close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::lifecycle::IntelligenceCapitalLifecycle;
+    use crate::core::integrity::{BackdatingPolicy, APPROVED_BY_DETAIL};
+    use crate::core::clock::ManualClock;
+
+    #[test]
+    fn test_close_year_zeroes_income_statement() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let asset_id = AssetId::random();
+        let mut lifecycle = IntelligenceCapitalLifecycle::new(&mut ledger);
+        lifecycle.capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12).unwrap();
+        let start = ledger.get_asset(asset_id).unwrap().created_at;
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .depreciate(asset_id, start, start + Duration::days(62), 0.0, 1.0)
+            .unwrap();
+        let year = FiscalPeriod::new(start, ledger.now() + Duration::days(1));
+
+        let close = ledger.close_year(year).unwrap();
+        assert!(close.verify().unwrap());
+        assert_eq!(close.closing_entry_ids.len(), 1);
+        assert!(close.net_income < 0.0);
+        assert!(!close.opening_balances.contains_key(&AccountType::DepreciationExpense));
+        assert!((close.opening_balances[&AccountType::RetainedEarnings] + close.net_income).abs() < 1e-9);
+        let next_year = FiscalPeriod::new(year.end, year.end + Duration::days(365));
+        assert_eq!(ledger.opening_balances(next_year), Some(&close.opening_balances));
+        assert_eq!(ledger.closed_until(), Some(year.end));
+
+        assert!(ledger.close_year(year).is_err());
+        let gap = FiscalPeriod::new(year.end + Duration::days(1), year.end + Duration::days(366));
+        assert!(ledger.close_year(gap).is_err());
+        assert_eq!(ledger.year_end_closes().len(), 1);
+        assert!(ledger.assert_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_close_year_needs_no_backdating_approval() {
+        let mut ledger = IntelligenceCapitalLedger::new();
+        let clock = std::sync::Arc::new(ManualClock::new(Utc::now()));
+        ledger.set_clock(clock.clone());
+        let asset_id = AssetId::random();
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .capitalize(asset_id, "Team A", 1200.0, DepreciationMethod::Linear, 12)
+            .unwrap();
+        let start = ledger.get_asset(asset_id).unwrap().created_at;
+        IntelligenceCapitalLifecycle::new(&mut ledger)
+            .depreciate(asset_id, start, start + Duration::days(31), 0.0, 1.0)
+            .unwrap();
+        let year = FiscalPeriod::new(start, ledger.now() + Duration::seconds(1));
+        clock.advance(Duration::seconds(2));
+        ledger.set_backdating_policy(BackdatingPolicy::allow_backdated_until(ledger.now()));
+
+        let close = ledger.close_year(year).unwrap();
+        assert_eq!(close.closing_entry_ids.len(), 1);
+        assert!(ledger.get_event(close.closing_event_ids[0]).is_some_and(|e| !e.details.contains_key(APPROVED_BY_DETAIL)));
+        // A caller still needs an approval to post at the same effective date
+        let late = CapitalEvent::new(asset_id, "utilization", ledger.now(), HashMap::new())
+            .with_effective_date(year.end + Duration::nanoseconds(1));
+        assert!(matches!(ledger.record_event(late), Err(IclError::Unauthorized(_))));
+    }
+}
@@ -9,6 +9,62 @@ pub use crate::core::integrity::*;
 pub use crate::core::proofs::*;
 pub use crate::core::error::*;
 pub use crate::core::integration::*;
+pub use crate::core::hashing::*;
+pub use crate::core::anchoring::*;
+pub use crate::core::outbox::*;
+pub use crate::core::messaging::*;
+pub use crate::core::exporters::*;
+pub use crate::core::connectors::*;
+pub use crate::core::reconciliation::*;
+pub use crate::core::import::*;
+pub use crate::core::clock::*;
+pub use crate::core::ids::*;
+pub use crate::core::query::*;
+pub use crate::core::reports::*;
+pub use crate::core::view::*;
+pub use crate::core::owners::*;
+pub use crate::core::authorization::*;
+pub use crate::core::metrics::*;
+pub use crate::core::compact::*;
+pub use crate::core::work_in_progress::*;
+pub use crate::core::impairment::*;
+pub use crate::core::licensing::*;
+pub use crate::core::obligations::*;
+pub use crate::core::intercompany::*;
+pub use crate::core::consolidation::*;
+pub use crate::core::invariants::*;
+pub use crate::core::migrations::*;
+pub use crate::core::archive::*;
+pub use crate::core::retention::*;
+pub use crate::core::formatting::*;
+pub use crate::core::dimensions::*;
+pub use crate::core::journal_templates::*;
+pub use crate::core::accruals::*;
+pub use crate::core::year_end::*;
+pub use crate::core::capitalization::*;
+pub use crate::core::pools::*;
+#[cfg(feature = "webhook")]
+pub use crate::core::webhook::*;
+#[cfg(feature = "xlsx")]
+pub use crate::core::xlsx::*;
+#[cfg(feature = "templates")]
+pub use crate::core::templates::*;
+#[cfg(feature = "server")]
+pub use crate::core::server::*;
+#[cfg(feature = "grpc")]
+pub use crate::core::grpc::*;
+#[cfg(feature = "ffi")]
+pub use crate::core::ffi::*;
+#[cfg(feature = "schema")]
+pub use crate::core::schema::*;
+#[cfg(feature = "encryption")]
+pub use crate::core::encryption::*;
+#[cfg(feature = "scheduler")]
+pub use crate::core::scheduler::*;
+#[cfg(feature = "testing")]
+pub use crate::core::testing;
+#[cfg(feature = "proptest")]
+pub use crate::core::strategies;
 
 // Core modules
 pub mod core {
@@ -20,4 +76,68 @@ pub mod core {
     pub mod error;
     pub mod proofs;
     pub mod integration;
+    pub mod hashing;
+    pub mod anchoring;
+    pub mod outbox;
+    pub mod messaging;
+    pub mod exporters;
+    pub mod connectors;
+    pub mod reconciliation;
+    pub mod import;
+    pub mod clock;
+    pub mod ids;
+    pub mod query;
+    pub mod reports;
+    pub mod view;
+    pub mod owners;
+    pub mod authorization;
+    pub mod metrics;
+    pub mod compact;
+    pub mod work_in_progress;
+    pub mod impairment;
+    pub mod licensing;
+    pub mod obligations;
+    pub mod intercompany;
+    pub mod consolidation;
+    pub mod invariants;
+    pub mod migrations;
+    pub mod archive;
+    pub mod retention;
+    pub mod formatting;
+    pub mod dimensions;
+    pub mod journal_templates;
+    pub mod accruals;
+    pub mod year_end;
+    pub mod capitalization;
+    pub mod pools;
+    #[cfg(feature = "webhook")]
+    pub mod webhook;
+    #[cfg(feature = "xlsx")]
+    pub mod xlsx;
+    #[cfg(feature = "parquet")]
+    pub mod columnar;
+    #[cfg(feature = "templates")]
+    pub mod templates;
+    #[cfg(feature = "server")]
+    pub mod server;
+    #[cfg(feature = "grpc")]
+    pub mod grpc;
+    #[cfg(feature = "grpc")]
+    pub mod grpc_proto;
+    #[cfg(feature = "ffi")]
+    pub mod ffi;
+    #[cfg(feature = "schema")]
+    pub mod schema;
+    #[cfg(feature = "encryption")]
+    pub mod encryption;
+    #[cfg(feature = "scheduler")]
+    pub mod scheduler;
+    #[cfg(feature = "tracing")]
+    pub(crate) mod telemetry;
+    #[cfg(feature = "parallel")]
+    pub mod parallel;
+    #[cfg(feature = "testing")]
+    pub mod testing;
+    #[cfg(feature = "proptest")]
+    pub mod strategies;
 }
\ No newline at end of file